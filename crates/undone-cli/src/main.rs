@@ -0,0 +1,438 @@
+//! Play the game in a terminal: prose and thoughts as text, numbered actions,
+//! save and load. No window or display needed.
+//!
+//! ```text
+//! undone-cli [--packs DIR] [--preset NAME] [--load SAVE] [--seed N] [--record FILE]
+//! undone-cli [--packs DIR] --replay FILE
+//! undone-cli [--packs DIR] [--preset NAME] [--seed N] --find TARGET [--max-steps N] [--beam N] [--record FILE]
+//! undone-cli [--packs DIR] [--preset NAME] [--seed N] --variants SCENE [--max-worlds N]
+//! ```
+//!
+//! `--record` writes the run to FILE as a replay when you quit; `--replay`
+//! plays one back and checks it still produces the same scenes, prose and
+//! final world. `--find` searches a new game for the shortest run of choices
+//! that reaches TARGET — a scene id, a game flag, or `ARC=STATE` — and prints
+//! it; with `--record` the path is saved as a replay. `--beam N` keeps only N
+//! states per step, for targets too deep for an exhaustive search.
+//! `--variants` renders every branch combination of each prose template in
+//! SCENE, starting from a new game's world, and prints each distinct variant
+//! with the conditions behind it as Markdown; `--max-worlds N` caps the worlds
+//! rendered per template.
+
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use undone_packs::char_creation::CharCreationConfig;
+use undone_scene::engine::{ActionView, EngineEvent};
+use undone_scene::scene_ctx::SceneCtx;
+use undone_scene::variants::{review_document, review_scene, VariantLimits};
+use undone_session::{
+    config_from_preset, find_path, load_runtime_content, preset_index, resolve_packs_dir,
+    run_replay, GameSession, LoadedRuntimeContent, PathLimits, PathSearch, PathTarget, Replay,
+    SessionCommand, SessionStep, NO_SCENE_AVAILABLE,
+};
+
+const HELP: &str = "\
+Commands:
+  <number>      choose that action
+  c, <Enter>    continue once a scene is over
+  w, wait       let time pass when nothing is happening
+  status        where and when you are
+  save <path>   write a save file
+  load <path>   load a save file and resume from it
+  help          this list
+  q, quit       leave";
+
+struct Args {
+    packs_dir: PathBuf,
+    preset: Option<String>,
+    load: Option<PathBuf>,
+    seed: Option<u64>,
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    find: Option<String>,
+    max_steps: Option<usize>,
+    beam: Option<usize>,
+    variants: Option<String>,
+    max_worlds: Option<usize>,
+}
+
+fn parse_args() -> Args {
+    let args: Vec<String> = std::env::args().collect();
+    let value_of = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|index| args.get(index + 1))
+            .cloned()
+    };
+    Args {
+        packs_dir: value_of("--packs")
+            .map(PathBuf::from)
+            .unwrap_or_else(resolve_packs_dir),
+        preset: value_of("--preset"),
+        load: value_of("--load").map(PathBuf::from),
+        seed: value_of("--seed").and_then(|value| value.parse().ok()),
+        record: value_of("--record").map(PathBuf::from),
+        replay: value_of("--replay").map(PathBuf::from),
+        find: value_of("--find"),
+        max_steps: value_of("--max-steps").and_then(|value| value.parse().ok()),
+        beam: value_of("--beam").and_then(|value| value.parse().ok()),
+        variants: value_of("--variants"),
+        max_worlds: value_of("--max-worlds").and_then(|value| value.parse().ok()),
+    }
+}
+
+fn main() {
+    let args = parse_args();
+    let content = load_runtime_content(&args.packs_dir).unwrap_or_else(|error| fail(&error));
+
+    if let Some(replay_path) = &args.replay {
+        let replay = Replay::read(replay_path, &content.registry)
+            .unwrap_or_else(|error| fail(&error.to_string()));
+        match run_replay(content, &replay) {
+            Ok(()) => println!("Replay matches: {} steps", replay.steps.len()),
+            Err(error) => fail(&format!("Replay diverged: {error}")),
+        }
+        return;
+    }
+
+    if let Some(target) = &args.find {
+        find(&args, content, target);
+        return;
+    }
+
+    if let Some(scene_id) = &args.variants {
+        variants(&args, content, scene_id);
+        return;
+    }
+
+    let rng = match args.seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_entropy(),
+    };
+    let (mut session, first) = match &args.load {
+        Some(_) if args.record.is_some() => fail("--record starts a new game; drop --load"),
+        Some(save_path) => {
+            let mut session =
+                GameSession::load(content, save_path, rng).unwrap_or_else(|error| fail(&error));
+            let step = session.resume();
+            (session, step)
+        }
+        None => {
+            let config = preset_config(&args, &content);
+            let session = match &args.record {
+                Some(_) => {
+                    let seed = args.seed.unwrap_or_else(rand::random);
+                    GameSession::new_recorded(content, config, seed)
+                }
+                None => GameSession::new_game(content, config, rng),
+            };
+            let mut session = session.unwrap_or_else(|error| fail(&error));
+            let step = session.apply(&SessionCommand::Continue);
+            (session, step)
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    show(&mut out, &session, first);
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        let _ = write!(out, "\n> ");
+        let _ = out.flush();
+        let Some(Ok(line)) = lines.next() else { break };
+        let step = match parse_input(&line, session.actions()) {
+            Input::Command(command) => session.apply(&command),
+            Input::Save(path) => {
+                let message = match session.save(&path) {
+                    Ok(()) => format!("Saved to {}", path.display()),
+                    Err(error) => error,
+                };
+                let _ = writeln!(out, "{message}");
+                continue;
+            }
+            Input::Load(path) => session.load_save(&path),
+            Input::Status => {
+                let _ = writeln!(out, "{}", status_line(&session));
+                continue;
+            }
+            Input::Help => {
+                let _ = writeln!(out, "{HELP}");
+                continue;
+            }
+            Input::Quit => break,
+            Input::Invalid(message) => {
+                let _ = writeln!(out, "{message}");
+                continue;
+            }
+        };
+        show(&mut out, &session, step);
+    }
+
+    if let Some(record_path) = &args.record {
+        let message = match session.replay() {
+            Some(replay) => match replay.write(record_path, &session.registry) {
+                Ok(()) => format!(
+                    "Recorded {} steps to {}",
+                    replay.steps.len(),
+                    record_path.display()
+                ),
+                Err(error) => format!("Recording failed: {error}"),
+            },
+            None => "Nothing recorded: a save was loaded mid-run".to_string(),
+        };
+        let _ = writeln!(out, "\n{message}");
+    }
+}
+
+fn preset_config(args: &Args, content: &LoadedRuntimeContent) -> CharCreationConfig {
+    let idx = match &args.preset {
+        Some(name) => preset_index(&content.registry, name)
+            .unwrap_or_else(|| fail(&format!("No preset named '{name}'"))),
+        None if content.registry.presets().is_empty() => fail("No presets loaded"),
+        None => 0,
+    };
+    config_from_preset(&content.registry, idx)
+}
+
+/// Search a new seeded game for `target` and print the path, or why there is none.
+fn find(args: &Args, content: LoadedRuntimeContent, target: &str) {
+    let config = preset_config(args, &content);
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut session =
+        GameSession::new_recorded(content, config, seed).unwrap_or_else(|error| fail(&error));
+    let target = PathTarget::parse(target, &session.engine).unwrap_or_else(|error| fail(&error));
+    let mut limits = PathLimits {
+        beam_width: args.beam,
+        ..PathLimits::default()
+    };
+    if let Some(max_steps) = args.max_steps {
+        limits.max_steps = max_steps;
+    }
+
+    match find_path(&mut session, &target, limits).unwrap_or_else(|error| fail(&error)) {
+        PathSearch::Found(found) => {
+            println!(
+                "Reached {target} in {} steps (seed {seed}, {} states explored):",
+                found.steps.len(),
+                found.states_explored
+            );
+            for (number, step) in found.steps.iter().enumerate() {
+                println!("  {:>3}. {step}", number + 1);
+            }
+            if let Some(record_path) = &args.record {
+                let replay = session.replay().expect("path finding records the session");
+                if let Err(error) = replay.write(record_path, &session.registry) {
+                    fail(&format!("Recording failed: {error}"));
+                }
+                println!("Recorded the path to {}", record_path.display());
+            }
+        }
+        PathSearch::NotFound(report) => fail(&format!("{target}: {report} (seed {seed})")),
+    }
+}
+
+/// Print every prose variant of `scene_id`, starting from a new game's world.
+fn variants(args: &Args, content: LoadedRuntimeContent, scene_id: &str) {
+    let config = preset_config(args, &content);
+    let rng = match args.seed {
+        Some(seed) => SmallRng::seed_from_u64(seed),
+        None => SmallRng::from_entropy(),
+    };
+    let session = GameSession::new_game(content, config, rng).unwrap_or_else(|error| fail(&error));
+    let Some(def) = session.engine.scenes().get(scene_id) else {
+        fail(&format!("No scene named '{scene_id}'"));
+    };
+    // Bind the first NPC of each sex, as a scene started without bindings does.
+    let mut ctx = SceneCtx::new();
+    ctx.active_male = session.world.male_npcs.iter().next().map(|(key, _)| key);
+    ctx.active_female = session.world.female_npcs.iter().next().map(|(key, _)| key);
+    let mut limits = VariantLimits::default();
+    if let Some(max_worlds) = args.max_worlds {
+        limits.max_worlds = max_worlds;
+    }
+    let reviews = review_scene(def, &session.world, &ctx, &session.registry, limits);
+    print!("{}", review_document(scene_id, &reviews));
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+fn show(out: &mut impl Write, session: &GameSession, step: Result<SessionStep, String>) {
+    let _ = match step {
+        Ok(step) => {
+            let mut text = render_events(&step.events);
+            if step.no_scene_available {
+                text.push_str(NO_SCENE_AVAILABLE);
+                text.push_str("\n(w to let time pass)\n");
+            }
+            if session.awaiting_continue() {
+                text.push_str("\n(c to continue)\n");
+            } else {
+                text.push_str(&render_actions(session.actions()));
+            }
+            write!(out, "{text}")
+        }
+        Err(error) => writeln!(out, "{error}"),
+    };
+}
+
+fn status_line(session: &GameSession) -> String {
+    let game_data = &session.world.game_data;
+    format!(
+        "{} — week {}, day {}, {:?}. ${}, stress {}. Scene: {}",
+        session.world.player.name_fem,
+        game_data.week,
+        game_data.day + 1,
+        game_data.time_slot,
+        session.world.player.money,
+        session.world.player.stress.get(),
+        session.current_scene_id().as_deref().unwrap_or("none"),
+    )
+}
+
+#[derive(Debug, PartialEq)]
+enum Input {
+    Command(SessionCommand),
+    Save(PathBuf),
+    Load(PathBuf),
+    Status,
+    Help,
+    Quit,
+    Invalid(String),
+}
+
+fn parse_input(line: &str, actions: &[ActionView]) -> Input {
+    let line = line.trim();
+    let (word, rest) = line
+        .split_once(char::is_whitespace)
+        .map(|(word, rest)| (word, rest.trim()))
+        .unwrap_or((line, ""));
+    match word {
+        "" | "c" | "continue" => Input::Command(SessionCommand::Continue),
+        "w" | "wait" => Input::Command(SessionCommand::Wait),
+        "status" => Input::Status,
+        "help" | "?" => Input::Help,
+        "q" | "quit" | "exit" => Input::Quit,
+        "save" | "load" if rest.is_empty() => Input::Invalid(format!("Usage: {word} <path>")),
+        "save" => Input::Save(Path::new(rest).to_path_buf()),
+        "load" => Input::Load(Path::new(rest).to_path_buf()),
+        _ => match word.parse::<usize>() {
+            Ok(number) if (1..=actions.len()).contains(&number) => {
+                Input::Command(SessionCommand::ChooseAction(actions[number - 1].id.clone()))
+            }
+            Ok(number) => Input::Invalid(format!("There is no action {number}.")),
+            Err(_) => Input::Invalid(format!("Unknown command '{word}'. Type help.")),
+        },
+    }
+}
+
+/// Prose as paragraphs, thoughts indented and italic, engine notes in brackets.
+fn render_events(events: &[EngineEvent]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            EngineEvent::ProseAdded(prose) => {
+                text.push('\n');
+                text.push_str(prose.trim());
+                text.push('\n');
+            }
+            EngineEvent::ThoughtAdded { text: thought, .. } => {
+                text.push('\n');
+                for line in thought.trim().lines() {
+                    if line.trim().is_empty() {
+                        text.push('\n');
+                    } else {
+                        text.push_str(&format!("    _{}_\n", line.trim()));
+                    }
+                }
+            }
+            EngineEvent::BodyChanged(message) => text.push_str(&format!("\n[{message}]\n")),
+            EngineEvent::ErrorOccurred(message) => {
+                text.push_str(&format!("\n[error: {message}]\n"))
+            }
+            EngineEvent::ActionsAvailable(_)
+            | EngineEvent::NpcActivated(_)
+            | EngineEvent::SceneFinished
+            | EngineEvent::SlotRequested(_) => {}
+        }
+    }
+    text
+}
+
+fn render_actions(actions: &[ActionView]) -> String {
+    let mut text = String::new();
+    if actions.is_empty() {
+        return text;
+    }
+    text.push('\n');
+    for (index, action) in actions.iter().enumerate() {
+        let check = match &action.check {
+            Some(check) if check.spent => " [spent]".to_string(),
+            Some(check) => match &check.phrase {
+                Some(phrase) => format!(" [{}: {phrase}]", check.skill),
+                None => format!(" [{}]", check.skill),
+            },
+            None => String::new(),
+        };
+        text.push_str(&format!("  {}. {}{check}\n", index + 1, action.label));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, label: &str) -> ActionView {
+        ActionView {
+            id: id.into(),
+            label: label.into(),
+            detail: String::new(),
+            check: None,
+        }
+    }
+
+    #[test]
+    fn numbers_choose_the_listed_action() {
+        let actions = vec![action("stay", "Stay"), action("leave", "Leave")];
+
+        assert_eq!(
+            parse_input(" 2 ", &actions),
+            Input::Command(SessionCommand::ChooseAction("leave".into()))
+        );
+        assert!(matches!(parse_input("3", &actions), Input::Invalid(_)));
+        assert_eq!(
+            parse_input("", &actions),
+            Input::Command(SessionCommand::Continue)
+        );
+        assert_eq!(
+            parse_input("save  run one.json", &actions),
+            Input::Save(PathBuf::from("run one.json"))
+        );
+        assert!(matches!(parse_input("load", &actions), Input::Invalid(_)));
+    }
+
+    #[test]
+    fn thoughts_render_apart_from_prose_and_actions_are_numbered() {
+        let text = render_events(&[
+            EngineEvent::ProseAdded("The rain keeps on.".into()),
+            EngineEvent::ThoughtAdded {
+                text: "Don't look.".into(),
+                style: "inner_voice".into(),
+            },
+            EngineEvent::SceneFinished,
+        ]);
+        assert_eq!(text, "\nThe rain keeps on.\n\n    _Don't look._\n");
+
+        let text = render_actions(&[action("stay", "Stay"), action("leave", "Leave")]);
+        assert_eq!(text, "\n  1. Stay\n  2. Leave\n");
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// A buff currently on the player. Pack data (`buffs.toml`) defines what each
+/// buff does; this is the live instance. `name` and `source` are copied in
+/// when the buff is applied so the sidebar can show them without the registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveBuff {
+    pub id: String,
+    pub name: String,
+    /// What put it there: a scene id, or the trait, item or need that grants it.
+    pub source: String,
+    pub stacks: u32,
+    /// Absolute slot at which the buff wears off. `None` lasts until removed.
+    pub expires_at: Option<u32>,
+    /// Granted buffs come and go with their grant condition rather than being
+    /// added and removed by scenes.
+    #[serde(default)]
+    pub granted: bool,
+}
@@ -62,6 +62,32 @@ pub enum RelationshipStatus {
     Ex,
 }
 
+/// How two NPCs know each other. Edges in the world's NPC relationship graph
+/// are undirected, so `Rival` covers one-sided jealousy as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NpcRelationKind {
+    Acquaintance,
+    Friend,
+    Coworker,
+    Housemate,
+    Family,
+    Partner,
+    Ex,
+    Rival,
+}
+
+/// A player body attribute that changes over time (pack body-change rules) or
+/// by deliberate scene writes (`w.setHairLength(...)` and friends).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyAttribute {
+    HairLength,
+    PubicHair,
+    Figure,
+    Complexion,
+    SmoothLegs,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayerFigure {
     Petite,
@@ -281,6 +307,21 @@ impl std::fmt::Display for RelationshipStatus {
     }
 }
 
+impl std::fmt::Display for NpcRelationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NpcRelationKind::Acquaintance => write!(f, "Acquaintance"),
+            NpcRelationKind::Friend => write!(f, "Friend"),
+            NpcRelationKind::Coworker => write!(f, "Coworker"),
+            NpcRelationKind::Housemate => write!(f, "Housemate"),
+            NpcRelationKind::Family => write!(f, "Family"),
+            NpcRelationKind::Partner => write!(f, "Partner"),
+            NpcRelationKind::Ex => write!(f, "Ex"),
+            NpcRelationKind::Rival => write!(f, "Rival"),
+        }
+    }
+}
+
 impl std::fmt::Display for PlayerFigure {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
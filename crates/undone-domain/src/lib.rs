@@ -1,12 +1,19 @@
 pub mod bounded_stat;
+pub mod buff;
 pub mod enums;
 pub mod ids;
+pub mod memory;
 pub mod npc;
 pub mod player;
+pub mod var;
 pub use bounded_stat::BoundedStat;
+pub use buff::ActiveBuff;
 pub use enums::*;
 pub use ids::*;
-pub use npc::{FemaleClothing, FemaleNpc, MaleClothing, MaleNpc, NpcCore};
+pub use memory::{Memory, MemoryLog, MemoryStamp, MemoryTally, MEMORY_LOG_CAP};
+pub use npc::{FemaleClothing, FemaleNpc, MaleClothing, MaleNpc, NpcAgenda, NpcCore};
 pub use player::{
-    BeforeIdentity, FemaleNpcKey, MaleNpcKey, NpcKey, Player, PregnancyState, SkillValue,
+    BeforeIdentity, FemaleNpcKey, MaleNpcKey, NpcKey, Player, PregnancyState, SkillPractice,
+    SkillValue,
 };
+pub use var::{VarType, VarValue};
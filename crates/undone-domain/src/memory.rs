@@ -0,0 +1,128 @@
+use crate::TimeSlot;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How many timeline entries a `MemoryLog` keeps before dropping the oldest.
+/// Per-event tallies are never dropped, so counts and "since" queries stay
+/// exact; only the ordered history is bounded.
+pub const MEMORY_LOG_CAP: usize = 32;
+
+/// A point in game time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryStamp {
+    pub week: u32,
+    pub day: u8,
+    pub slot: TimeSlot,
+}
+
+impl MemoryStamp {
+    /// Days since game start.
+    pub fn days(&self) -> u32 {
+        self.week * 7 + self.day as u32
+    }
+
+    /// Whole days from `self` to the later stamp `now` (0 if `now` is earlier).
+    pub fn days_until(&self, now: MemoryStamp) -> u32 {
+        now.days().saturating_sub(self.days())
+    }
+}
+
+/// One remembered event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Memory {
+    pub event: String,
+    pub at: MemoryStamp,
+}
+
+/// Running totals for one event name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryTally {
+    pub count: u32,
+    pub first: MemoryStamp,
+    pub last: MemoryStamp,
+}
+
+/// Append-only, timestamped memory of what happened — to the player or with an
+/// NPC. Written by `remember` effects and by the scene engine when a scene
+/// finishes (the scene id is the event).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryLog {
+    /// Most recent events, oldest first, capped at `MEMORY_LOG_CAP`.
+    #[serde(default)]
+    recent: Vec<Memory>,
+    #[serde(default)]
+    tallies: HashMap<String, MemoryTally>,
+}
+
+impl MemoryLog {
+    pub fn record(&mut self, event: impl Into<String>, at: MemoryStamp) {
+        let event = event.into();
+        self.tallies
+            .entry(event.clone())
+            .and_modify(|t| {
+                t.count += 1;
+                t.last = at;
+            })
+            .or_insert(MemoryTally {
+                count: 1,
+                first: at,
+                last: at,
+            });
+        if self.recent.len() >= MEMORY_LOG_CAP {
+            self.recent.remove(0);
+        }
+        self.recent.push(Memory { event, at });
+    }
+
+    /// How many times `event` has been recorded.
+    pub fn times(&self, event: &str) -> u32 {
+        self.tallies.get(event).map_or(0, |t| t.count)
+    }
+
+    pub fn tally(&self, event: &str) -> Option<&MemoryTally> {
+        self.tallies.get(event)
+    }
+
+    /// The newest memory of any event.
+    pub fn latest(&self) -> Option<&Memory> {
+        self.recent.last()
+    }
+
+    /// The retained timeline, oldest first.
+    pub fn recent(&self) -> &[Memory] {
+        &self.recent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(week: u32, day: u8) -> MemoryStamp {
+        MemoryStamp {
+            week,
+            day,
+            slot: TimeSlot::Evening,
+        }
+    }
+
+    #[test]
+    fn tallies_outlive_the_bounded_timeline() {
+        let mut log = MemoryLog::default();
+        log.record("kissed", at(0, 1));
+        for day in 0..MEMORY_LOG_CAP as u8 {
+            log.record("waved", at(1, day % 7));
+        }
+        log.record("kissed", at(5, 2));
+
+        assert_eq!(log.recent().len(), MEMORY_LOG_CAP);
+        assert_eq!(log.recent().first().unwrap().event, "waved");
+        assert_eq!(log.latest().unwrap().event, "kissed");
+        assert_eq!(log.times("kissed"), 2);
+        assert_eq!(log.times("waved"), MEMORY_LOG_CAP as u32);
+        assert_eq!(log.times("dated"), 0);
+        let kissed = log.tally("kissed").unwrap();
+        assert_eq!(kissed.first, at(0, 1));
+        assert_eq!(kissed.last.days_until(at(7, 2)), 14);
+    }
+}
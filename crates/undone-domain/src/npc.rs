@@ -1,7 +1,7 @@
 use crate::{
     Age, AlcoholLevel, ArousalLevel, AttractionLevel, Behaviour, BreastSize, CharTypeId,
-    LikingLevel, LoveLevel, MaleFigure, NpcTraitId, PersonalityId, PlayerFigure, PregnancyState,
-    RelationshipStatus,
+    LikingLevel, LoveLevel, MaleFigure, MemoryLog, NpcTraitId, PersonalityId, PlayerFigure,
+    PregnancyState, RelationshipStatus,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -15,6 +15,11 @@ pub struct NpcCore {
     /// `roles` so a scene can rename without assigning a role and vice versa.
     #[serde(default)]
     pub display_name: Option<String>,
+    /// Stable id of the authored story character this NPC was instantiated
+    /// from (`base::jake`), declared in a pack's `characters.toml`. `None` for
+    /// random spawns. Scripts address story NPCs by this id via `npc("base::jake")`.
+    #[serde(default)]
+    pub story_id: Option<String>,
     pub age: Age,
     pub race: String,
     pub eye_colour: String,
@@ -45,6 +50,26 @@ pub struct NpcCore {
 
     #[serde(default)]
     pub roles: HashSet<String>, // route role assignments e.g. "ROLE_LANDLORD"
+
+    /// Off-screen routine bookkeeping, maintained by the pack routine rules
+    /// (`routines.toml`) as time passes.
+    #[serde(default)]
+    pub agenda: NpcAgenda,
+
+    /// Timestamped history of what happened with this NPC.
+    #[serde(default)]
+    pub memory: MemoryLog,
+}
+
+/// When the player last dealt with an NPC, and when each routine rule last
+/// fired for them. Times are absolute slot counts (`GameData::absolute_slot`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NpcAgenda {
+    /// Slot of the most recent scene effect that touched this NPC. `None` until
+    /// the player has actually interacted with them; routines ignore such NPCs.
+    pub last_contact: Option<u32>,
+    /// Routine rule id → slot it last applied to this NPC.
+    pub rules_fired: HashMap<String, u32>,
 }
 
 impl NpcCore {
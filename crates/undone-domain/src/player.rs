@@ -1,8 +1,9 @@
 use crate::{
-    Age, AlcoholLevel, Appearance, ArousalLevel, BeforeSexuality, BeforeVoice, BoundedStat,
-    BreastSize, ButtSize, ClitSensitivity, Complexion, EyeColour, HairColour, HairLength, Height,
-    InnerLabiaSize, LipShape, MaleFigure, NaturalPubicHair, NippleSensitivity, PcOrigin, PenisSize,
-    PlayerFigure, PubicHairStyle, SkillId, SkinTone, StuffId, TraitId, WaistSize, WetnessBaseline,
+    ActiveBuff, Age, AlcoholLevel, Appearance, ArousalLevel, BeforeSexuality, BeforeVoice,
+    BoundedStat, BreastSize, ButtSize, ClitSensitivity, Complexion, EyeColour, HairColour,
+    HairLength, Height, InnerLabiaSize, LipShape, MaleFigure, MemoryLog, NaturalPubicHair,
+    NippleSensitivity, PcOrigin, PenisSize, PlayerFigure, PubicHairStyle, SkillId, SkinTone,
+    StuffId, TraitId, WaistSize, WetnessBaseline,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -52,6 +53,14 @@ impl SkillValue {
     }
 }
 
+/// XP banked toward a skill's next point, and when it was last practised
+/// (absolute slot). Written by `w.practice`; read by skill decay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillPractice {
+    pub xp: u32,
+    pub last_practiced: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PregnancyState {
     pub weeks: u32,
@@ -122,6 +131,18 @@ pub struct Player {
 
     // Before-transformation data (meaningful when origin.has_before_life())
     pub before: Option<BeforeIdentity>,
+
+    /// Timestamped history of what the player has done and been through.
+    #[serde(default)]
+    pub memory: MemoryLog,
+
+    /// Timed and granted skill modifiers, folded into `SkillValue::modifier`.
+    #[serde(default)]
+    pub buffs: Vec<ActiveBuff>,
+
+    /// Practice progress per skill, for progression curves and decay.
+    #[serde(default)]
+    pub practice: HashMap<SkillId, SkillPractice>,
 }
 
 impl Player {
@@ -219,6 +240,9 @@ mod tests {
             custom_flags: HashMap::new(),
             custom_ints: HashMap::new(),
             origin: crate::PcOrigin::CisMaleTransformed,
+            memory: Default::default(),
+            buffs: Vec::new(),
+            practice: HashMap::new(),
         }
     }
 
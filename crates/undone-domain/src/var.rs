@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The type of a scene param, scene variable or pack game variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VarType {
+    String,
+    Int,
+    Bool,
+}
+
+impl VarType {
+    /// What a variable of this type holds before anything sets it.
+    pub fn zero(self) -> VarValue {
+        match self {
+            VarType::String => VarValue::Str(String::new()),
+            VarType::Int => VarValue::Int(0),
+            VarType::Bool => VarValue::Bool(false),
+        }
+    }
+}
+
+impl fmt::Display for VarType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VarType::String => "string",
+            VarType::Int => "int",
+            VarType::Bool => "bool",
+        })
+    }
+}
+
+/// A typed variable value. Serialized bare (`3`, `"Jake"`, `true`) so it reads
+/// naturally in TOML and in saves.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VarValue {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+}
+
+impl VarValue {
+    pub fn var_type(&self) -> VarType {
+        match self {
+            VarValue::Str(_) => VarType::String,
+            VarValue::Int(_) => VarType::Int,
+            VarValue::Bool(_) => VarType::Bool,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_serialize_bare_and_keep_their_type() {
+        let values = vec![
+            VarValue::Int(3),
+            VarValue::Str("Jake".into()),
+            VarValue::Bool(true),
+        ];
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(json, r#"[3,"Jake",true]"#);
+        let back: Vec<VarValue> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, values);
+        assert_eq!(back[0].var_type(), VarType::Int);
+        assert_eq!(VarType::String.zero(), VarValue::Str(String::new()));
+    }
+}
@@ -0,0 +1,461 @@
+//! What the server says about one file's text: the scene load gate's first
+//! error as a diagnostic, completions, hover and go-to-definition.
+
+use std::path::Path;
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Documentation, Hover,
+    HoverContents, Location, MarkupContent, MarkupKind, Position, Range, Url,
+};
+use undone_scene::loader::{load_scene_source, validate_scene_references, SceneLoadError};
+use undone_scene::script::api::{lookup, table::REGISTRY, ArgShape, MethodDescriptor, Receiver};
+use undone_scene::script::known_ids;
+use undone_scene::script::validate::IdKind;
+use undone_scene::types::SceneDefinition;
+
+use crate::text::{
+    call_site, member_prefix, offset_at, quoted_at, range_of, receiver_before, span_range,
+    string_at, word_at,
+};
+use crate::workspace::Workspace;
+
+/// Keys whose string names a scene: branch targets and schedule events.
+const SCENE_KEYS: [&str; 3] = ["goto", "call", "scene"];
+
+/// Which script surface a TOML value is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptKind {
+    Condition,
+    Effect,
+    Prose,
+}
+
+impl ScriptKind {
+    fn for_key(key: &str) -> Option<Self> {
+        match key {
+            "condition" | "trigger" => Some(ScriptKind::Condition),
+            "effect" => Some(ScriptKind::Effect),
+            "prose" => Some(ScriptKind::Prose),
+            _ => None,
+        }
+    }
+
+    fn allows(self, method: &MethodDescriptor) -> bool {
+        match self {
+            ScriptKind::Condition => method.contexts.condition,
+            ScriptKind::Effect => method.contexts.effect,
+            ScriptKind::Prose => method.contexts.prose,
+        }
+    }
+}
+
+/// Whether `text` is a scene file rather than other pack data.
+pub fn is_scene_file(text: &str) -> bool {
+    text.lines().any(|line| line.trim() == "[scene]")
+}
+
+/// The load gate's verdict on a scene file: the first error
+/// `load_scene_source` or the cross-reference check reports, if any.
+pub fn diagnostics(ws: &Workspace, path: &Path, text: &str) -> Vec<Diagnostic> {
+    if !is_scene_file(text) {
+        return Vec::new();
+    }
+    let error = match load_scene_source(path, text, &ws.registry) {
+        Ok(def) => match reference_error(ws, path, def) {
+            Some(error) => error,
+            None => return Vec::new(),
+        },
+        Err(error) => error,
+    };
+    vec![Diagnostic {
+        range: error_range(path, text, &error),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("undone".into()),
+        message: error.to_string(),
+        ..Diagnostic::default()
+    }]
+}
+
+/// A duplicate scene id, or a `goto`/`call` the rest of the packs can't
+/// satisfy. A target whose file exists but doesn't load yet is not reported
+/// here; that file shows its own error.
+fn reference_error(ws: &Workspace, path: &Path, def: SceneDefinition) -> Option<SceneLoadError> {
+    if let Some(other) = ws.index.get(&def.id).filter(|loc| loc.path != path) {
+        return Some(SceneLoadError::DuplicateSceneId {
+            scene_id: def.id.clone(),
+            first_source: other.path.display().to_string(),
+            second_source: path.display().to_string(),
+        });
+    }
+    let mut scenes = ws.scenes.clone();
+    let id = def.id.clone();
+    scenes.insert(id.clone(), def.into());
+    let error = validate_scene_references(&id, &scenes[&id], &scenes).err()?;
+    let broken_target = match &error {
+        SceneLoadError::UnknownGotoTarget { target, .. }
+        | SceneLoadError::UnknownCallTarget { target, .. } => Some(target),
+        _ => None,
+    };
+    match broken_target {
+        Some(target) if ws.index.contains_key(target) => None,
+        _ => Some(error),
+    }
+}
+
+/// Where to underline `error`: its span when it has one in this file,
+/// otherwise the quoted id it names, otherwise the file start.
+fn error_range(path: &Path, text: &str, error: &SceneLoadError) -> Range {
+    if let Some(span) = error.span().filter(|span| span.path == path) {
+        return span_range(&span.line_text, span.line, span.column, span.width);
+    }
+    let (needle, last) = match error {
+        SceneLoadError::UnknownGotoTarget { target, .. }
+        | SceneLoadError::UnknownCallTarget { target, .. }
+        | SceneLoadError::BadCall { target, .. } => (target, false),
+        SceneLoadError::DuplicateActionId { action_id, .. }
+        | SceneLoadError::DuplicateNpcActionId { action_id, .. } => (action_id, true),
+        SceneLoadError::BadCheck { action_id, .. } => (action_id, false),
+        SceneLoadError::UnknownGameVar { id, .. } => (id, false),
+        SceneLoadError::DuplicateSceneId { scene_id, .. } => (scene_id, false),
+        _ => return Range::default(),
+    };
+    let quoted = format!("\"{needle}\"");
+    let found = if last {
+        text.rfind(&quoted)
+    } else {
+        text.find(&quoted)
+    };
+    match found {
+        Some(start) => range_of(text, start + 1..start + 1 + needle.len()),
+        None => Range::default(),
+    }
+}
+
+pub fn completions(ws: &Workspace, text: &str, position: Position) -> Vec<CompletionItem> {
+    let offset = offset_at(text, position);
+    let Some((key, contents_start)) = string_at(text, offset) else {
+        return Vec::new();
+    };
+    if SCENE_KEYS.contains(&key) {
+        return ws
+            .index
+            .iter()
+            .map(|(id, loc)| value_item(id, &loc.path.display().to_string()))
+            .collect();
+    }
+    if key == "skill" {
+        return id_items(ws, IdKind::Skill);
+    }
+    let Some(kind) = ScriptKind::for_key(key) else {
+        return Vec::new();
+    };
+    let Some(prefix) = script_prefix(text, contents_start, offset, kind) else {
+        return Vec::new();
+    };
+    if let Some(site) = call_site(prefix) {
+        if site.in_string {
+            return argument_items(ws, &site);
+        }
+    }
+    match member_prefix(prefix) {
+        Some((receiver, _)) => method_items(receiver, kind),
+        None => Vec::new(),
+    }
+}
+
+/// The script text on the cursor's line up to the cursor. In prose, only
+/// the text inside the last `{{`/`{%` counts; outside one there is no script.
+fn script_prefix(
+    text: &str,
+    contents_start: usize,
+    offset: usize,
+    kind: ScriptKind,
+) -> Option<&str> {
+    let line_start = text[..offset].rfind('\n').map_or(0, |nl| nl + 1);
+    let prefix = &text[line_start.max(contents_start)..offset];
+    if kind != ScriptKind::Prose {
+        return Some(prefix);
+    }
+    let open = prefix.rfind("{{").max(prefix.rfind("{%"))?;
+    let close = prefix.rfind("}}").max(prefix.rfind("%}"));
+    if close.is_some_and(|close| close > open) {
+        return None;
+    }
+    Some(&prefix[open + 2..])
+}
+
+fn method_items(receiver: Receiver, kind: ScriptKind) -> Vec<CompletionItem> {
+    REGISTRY
+        .iter()
+        .filter(|d| d.receiver == receiver && kind.allows(d))
+        .filter(|d| !(d.receiver == Receiver::Npc && d.name == "npc"))
+        .map(|d| CompletionItem {
+            label: d.name.to_string(),
+            kind: Some(CompletionItemKind::METHOD),
+            detail: Some(d.signature()),
+            documentation: Some(Documentation::String(format!(
+                "Usable in {}.",
+                d.usable_in()
+            ))),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// Ids for the string argument being typed, by the method's arg shape.
+fn argument_items(ws: &Workspace, site: &crate::text::CallSite) -> Vec<CompletionItem> {
+    let Some(method) = lookup(site.receiver, &site.method) else {
+        return Vec::new();
+    };
+    match (method.args, site.arg) {
+        (ArgShape::Id(IdKind::Arc), 1) => {
+            let arc = site.args_before.first().cloned().flatten();
+            match arc.and_then(|arc| ws.registry.get_arc(&arc)) {
+                Some(arc) => arc
+                    .states
+                    .iter()
+                    .map(|state| value_item(state, "arc state"))
+                    .collect(),
+                None => Vec::new(),
+            }
+        }
+        (ArgShape::Flag, 0) => {
+            let mut flags: Vec<&str> = ws.registry.flags().map(|f| f.id.as_str()).collect();
+            flags.sort_unstable();
+            flags
+                .into_iter()
+                .map(|flag| value_item(flag, "game flag"))
+                .collect()
+        }
+        (ArgShape::Body(attribute), 0) => undone_packs::body_scale(attribute)
+            .iter()
+            .map(|value| value_item(value, "body value"))
+            .collect(),
+        (shape, arg) => match shape.id_kind_at(arg) {
+            Some(kind) => id_items(ws, kind),
+            None => Vec::new(),
+        },
+    }
+}
+
+fn id_items(ws: &Workspace, kind: IdKind) -> Vec<CompletionItem> {
+    known_ids(&ws.registry, kind)
+        .iter()
+        .map(|id| value_item(id, kind.label()))
+        .collect()
+}
+
+fn value_item(label: &str, detail: &str) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(CompletionItemKind::VALUE),
+        detail: Some(detail.to_string()),
+        ..CompletionItem::default()
+    }
+}
+
+/// The signature and usable contexts of the `recv.method` under the cursor.
+pub fn hover(text: &str, position: Position) -> Option<Hover> {
+    let offset = offset_at(text, position);
+    let word = word_at(text, offset)?;
+    let dot = word.start.checked_sub(1)?;
+    if text.as_bytes()[dot] != b'.' {
+        return None;
+    }
+    let method = lookup(receiver_before(text, dot)?, &text[word.clone()])?;
+    let effect = if method.is_write() {
+        "Changes the world."
+    } else {
+        "Reads the world."
+    };
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!(
+                "```rhai\n{}\n```\n{effect} Usable in {}.",
+                method.signature(),
+                method.usable_in()
+            ),
+        }),
+        range: Some(range_of(text, word)),
+    })
+}
+
+/// The file defining the scene named by the `goto`/`call`/`scene` string
+/// under the cursor.
+pub fn definition(ws: &Workspace, text: &str, position: Position) -> Option<Location> {
+    let offset = offset_at(text, position);
+    let (key, _) = string_at(text, offset)?;
+    if !SCENE_KEYS.contains(&key) {
+        return None;
+    }
+    let scene_id = &text[quoted_at(text, offset)?];
+    let location = ws.index.get(scene_id)?;
+    Some(Location {
+        uri: Url::from_file_path(&location.path).ok()?,
+        range: Range::new(
+            Position::new(location.line, 0),
+            Position::new(location.line, 0),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn packs_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs")
+    }
+
+    fn workspace() -> Workspace {
+        Workspace::load(&packs_dir()).unwrap()
+    }
+
+    fn scene_path() -> PathBuf {
+        packs_dir().join("base/scenes/lsp_test_scene.toml")
+    }
+
+    /// The position just after the first occurrence of `marker` in `text`.
+    fn after(text: &str, marker: &str) -> Position {
+        crate::text::position_at(text, text.find(marker).unwrap() + marker.len())
+    }
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|item| item.label.as_str()).collect()
+    }
+
+    const SCENE: &str = r#"[scene]
+id = "base::lsp_test_scene"
+pack = "base"
+description = "x"
+
+[intro]
+prose = """
+Rain. {{ w.getSkill("FEMININITY") }}
+"""
+
+[[actions]]
+id = "leave"
+label = "Leave"
+condition = 'w.hasTrait("SHY")'
+effect = 'gd.advanceArc("base::campus_opening", "arrived")'
+prose = "You go."
+
+  [[actions.next]]
+  goto = "base::workplace_landlord"
+"#;
+
+    #[test]
+    fn a_clean_scene_has_no_diagnostics() {
+        let ws = workspace();
+        assert_eq!(diagnostics(&ws, &scene_path(), SCENE), vec![]);
+    }
+
+    #[test]
+    fn an_unknown_trait_is_underlined_where_it_is_used() {
+        let ws = workspace();
+        let text = SCENE.replace("\"SHY\"", "\"SHYY\"");
+        let diagnostics = diagnostics(&ws, &scene_path(), &text);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert!(diagnostics[0].message.contains("SHYY"));
+        let start = after(&text, "condition = 'w.");
+        assert_eq!(diagnostics[0].range.start.line, start.line);
+    }
+
+    #[test]
+    fn an_unknown_goto_target_points_at_the_target() {
+        let ws = workspace();
+        let text = SCENE.replace("base::workplace_landlord", "base::nowhere");
+        let diagnostics = diagnostics(&ws, &scene_path(), &text);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].range.start, after(&text, "goto = \""));
+    }
+
+    #[test]
+    fn an_unknown_call_target_points_at_the_target() {
+        let ws = workspace();
+        let text = SCENE.replace(
+            "goto = \"base::workplace_landlord\"",
+            "call = \"base::nowhere\"",
+        );
+        let diagnostics = diagnostics(&ws, &scene_path(), &text);
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert!(diagnostics[0].message.contains("unknown call target"));
+        assert_eq!(diagnostics[0].range.start, after(&text, "call = \""));
+    }
+
+    #[test]
+    fn a_reused_scene_id_is_a_duplicate() {
+        let ws = workspace();
+        let text = SCENE.replace("base::lsp_test_scene", "base::workplace_landlord");
+        let diagnostics = diagnostics(&ws, &scene_path(), &text);
+        assert!(diagnostics[0].message.contains("duplicate scene id"));
+    }
+
+    #[test]
+    fn methods_complete_by_receiver_and_context() {
+        let ws = workspace();
+        let text = SCENE.replace("'w.hasTrait(\"SHY\")'", "'w.'");
+        let items = completions(&ws, &text, after(&text, "condition = 'w."));
+        assert!(labels(&items).contains(&"hasTrait"));
+        assert!(
+            !labels(&items).contains(&"changeStress"),
+            "writes are effect-only"
+        );
+
+        let text = SCENE.replace(
+            "'gd.advanceArc(\"base::campus_opening\", \"arrived\")'",
+            "'w.'",
+        );
+        let items = completions(&ws, &text, after(&text, "effect = 'w."));
+        assert!(labels(&items).contains(&"changeStress"));
+    }
+
+    #[test]
+    fn ids_complete_inside_string_arguments() {
+        let ws = workspace();
+        let items = completions(&ws, SCENE, after(SCENE, "getSkill(\""));
+        assert!(labels(&items).contains(&"FEMININITY"));
+
+        let items = completions(&ws, SCENE, after(SCENE, "hasTrait(\""));
+        assert!(labels(&items).contains(&"SHY"));
+
+        let items = completions(&ws, SCENE, after(SCENE, "\"base::campus_opening\", \""));
+        let arc = ws.registry.get_arc("base::campus_opening").unwrap();
+        assert_eq!(
+            labels(&items),
+            arc.states.iter().map(String::as_str).collect::<Vec<_>>()
+        );
+
+        let items = completions(&ws, SCENE, after(SCENE, "goto = \""));
+        assert!(labels(&items).contains(&"base::workplace_landlord"));
+    }
+
+    #[test]
+    fn hover_shows_the_signature() {
+        let hover = hover(SCENE, after(SCENE, "gd.advance")).unwrap();
+        let HoverContents::Markup(markup) = hover.contents else {
+            panic!("expected markup");
+        };
+        assert!(markup.value.contains("gd.advanceArc(arc, state)"));
+        assert!(markup.value.contains("Changes the world."));
+    }
+
+    #[test]
+    fn goto_targets_jump_to_their_scene_file() {
+        let ws = workspace();
+        let location = definition(&ws, SCENE, after(SCENE, "goto = \"base::")).unwrap();
+        assert!(location
+            .uri
+            .path()
+            .ends_with("packs/base/scenes/workplace_landlord.toml"));
+    }
+}
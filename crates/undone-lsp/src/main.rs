@@ -0,0 +1,274 @@
+//! A language server for scene TOML files, over stdio.
+//!
+//! ```text
+//! undone-lsp
+//! ```
+//!
+//! Point an editor's LSP client at the binary for `packs/*/scenes/*.toml`
+//! (and `schedule.toml`). It finds the `packs` directory above the open file
+//! and loads it the way the game does, then offers:
+//!
+//! - diagnostics: the scene load gate's first error, on every edit
+//! - completion: methods after `w.` / `gd.` / `m.` / `f.` / `role.` /
+//!   `scene.` / `npc(ref).` for the context the cursor is in (condition,
+//!   effect or prose), content ids inside their string arguments, and scene
+//!   ids in `goto` / `call` / `scene` strings
+//! - hover: a method's signature and where it may be used
+//! - go-to-definition: from a `goto` / `call` / `scene` string to the scene
+//!   file
+//!
+//! Saving a file reloads its packs, so other files see its new scene ids.
+
+mod analysis;
+mod text;
+mod workspace;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, Diagnostic, DiagnosticSeverity, GotoDefinitionResponse,
+    HoverProviderCapability, OneOf, PublishDiagnosticsParams, SaveOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+
+use crate::workspace::{find_packs_dir, Workspace};
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("undone-lsp: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), BoxError> {
+    let (connection, io_threads) = Connection::stdio();
+    connection.initialize(serde_json::to_value(capabilities())?)?;
+    Server::default().serve(&connection)?;
+    // The writer thread ends once the connection's sender is gone.
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(false),
+                })),
+                ..TextDocumentSyncOptions::default()
+            },
+        )),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![".".into(), "\"".into()]),
+            ..CompletionOptions::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..ServerCapabilities::default()
+    }
+}
+
+#[derive(Default)]
+struct Server {
+    /// Open documents' current text.
+    documents: HashMap<Url, String>,
+    /// Loaded packs, by `packs` directory; `Err` holds a pack load error.
+    workspaces: HashMap<PathBuf, Result<Workspace, String>>,
+}
+
+impl Server {
+    fn serve(&mut self, connection: &Connection) -> Result<(), BoxError> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    // A request the server can't decode gets an error reply;
+                    // the session carries on.
+                    let id = request.id.clone();
+                    let response = self.respond(request).unwrap_or_else(|e| {
+                        Response::new_err(id, ErrorCode::InvalidParams as i32, e.to_string())
+                    });
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    let method = notification.method.clone();
+                    match self.notify(notification) {
+                        Ok(publishes) => {
+                            for publish in publishes {
+                                connection.sender.send(Message::Notification(publish))?;
+                            }
+                        }
+                        Err(e) => eprintln!("undone-lsp: skipped {method}: {e}"),
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn respond(&mut self, request: Request) -> Result<Response, BoxError> {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            Completion::METHOD => {
+                let (_, params) =
+                    request.extract::<lsp_types::CompletionParams>(Completion::METHOD)?;
+                let doc = params.text_document_position;
+                self.with_document(&doc.text_document.uri, |ws, text| {
+                    analysis::completions(ws, text, doc.position)
+                })
+                .map(serde_json::to_value)
+                .transpose()?
+            }
+            HoverRequest::METHOD => {
+                let (_, params) =
+                    request.extract::<lsp_types::HoverParams>(HoverRequest::METHOD)?;
+                let doc = params.text_document_position_params;
+                self.documents
+                    .get(&doc.text_document.uri)
+                    .and_then(|text| analysis::hover(text, doc.position))
+                    .map(serde_json::to_value)
+                    .transpose()?
+            }
+            GotoDefinition::METHOD => {
+                let (_, params) =
+                    request.extract::<lsp_types::GotoDefinitionParams>(GotoDefinition::METHOD)?;
+                let doc = params.text_document_position_params;
+                self.with_document(&doc.text_document.uri, |ws, text| {
+                    analysis::definition(ws, text, doc.position)
+                })
+                .flatten()
+                .map(|location| serde_json::to_value(GotoDefinitionResponse::Scalar(location)))
+                .transpose()?
+            }
+            _ => {
+                return Ok(Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request {}", request.method),
+                ))
+            }
+        };
+        Ok(Response::new_ok(id, result))
+    }
+
+    /// Track document text; return the diagnostics to publish.
+    fn notify(&mut self, notification: Notification) -> Result<Vec<Notification>, BoxError> {
+        let uris = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let doc = params.text_document;
+                self.documents.insert(doc.uri.clone(), doc.text);
+                vec![doc.uri]
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let Some(change) = params.content_changes.into_iter().last() else {
+                    return Ok(Vec::new());
+                };
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), change.text);
+                vec![uri]
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: lsp_types::DidSaveTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let Some(packs_dir) = packs_dir_of(&params.text_document.uri) else {
+                    return Ok(Vec::new());
+                };
+                self.workspaces.remove(&packs_dir);
+                self.documents
+                    .keys()
+                    .filter(|uri| packs_dir_of(uri).as_ref() == Some(&packs_dir))
+                    .cloned()
+                    .collect()
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                return Ok(vec![publish(params.text_document.uri, Vec::new())]);
+            }
+            _ => return Ok(Vec::new()),
+        };
+        Ok(uris
+            .into_iter()
+            .map(|uri| {
+                let diagnostics = self.diagnostics(&uri);
+                publish(uri, diagnostics)
+            })
+            .collect())
+    }
+
+    fn diagnostics(&mut self, uri: &Url) -> Vec<Diagnostic> {
+        let Some(path) = uri.to_file_path().ok() else {
+            return Vec::new();
+        };
+        let Some(packs_dir) = find_packs_dir(&path) else {
+            return Vec::new();
+        };
+        let Some(text) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        match self
+            .workspaces
+            .entry(packs_dir)
+            .or_insert_with_key(|dir| Workspace::load(dir))
+        {
+            Ok(ws) => analysis::diagnostics(ws, &path, text),
+            Err(message) => vec![Diagnostic {
+                severity: Some(DiagnosticSeverity::ERROR),
+                message: format!("could not load packs: {message}"),
+                source: Some("undone".into()),
+                ..Diagnostic::default()
+            }],
+        }
+    }
+
+    /// Run `f` over an open document and the packs it belongs to, if they load.
+    fn with_document<T>(&mut self, uri: &Url, f: impl FnOnce(&Workspace, &str) -> T) -> Option<T> {
+        let packs_dir = packs_dir_of(uri)?;
+        let text = self.documents.get(uri)?;
+        let ws = self
+            .workspaces
+            .entry(packs_dir)
+            .or_insert_with_key(|dir| Workspace::load(dir))
+            .as_ref()
+            .ok()?;
+        Some(f(ws, text))
+    }
+}
+
+fn packs_dir_of(uri: &Url) -> Option<PathBuf> {
+    find_packs_dir(&uri.to_file_path().ok()?)
+}
+
+fn publish(uri: Url, diagnostics: Vec<Diagnostic>) -> Notification {
+    Notification::new(
+        PublishDiagnostics::METHOD.to_string(),
+        PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        },
+    )
+}
@@ -0,0 +1,365 @@
+//! Reading a scene file as it is being typed, where it may not parse: LSP
+//! positions to byte offsets and back, the TOML key whose string the cursor
+//! is in, and the script call around the cursor.
+
+use lsp_types::{Position, Range};
+use undone_scene::script::api::{receiver_from_token, Receiver};
+
+/// Byte offset of an LSP (UTF-16) position, clamped to its line.
+pub fn offset_at(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(nl) => line_start += nl + 1,
+            None => return text.len(),
+        }
+    }
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |nl| line_start + nl);
+    let mut units = 0;
+    for (index, c) in text[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    line_end
+}
+
+/// LSP position of byte `offset`.
+pub fn position_at(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let line_start = text[..offset].rfind('\n').map_or(0, |nl| nl + 1);
+    Position::new(
+        text[..offset].matches('\n').count() as u32,
+        text[line_start..offset].encode_utf16().count() as u32,
+    )
+}
+
+pub fn range_of(text: &str, range: std::ops::Range<usize>) -> Range {
+    Range::new(position_at(text, range.start), position_at(text, range.end))
+}
+
+/// The range of a 1-based `line` and char `column`, `width` chars wide, as a
+/// `SourceSpan` reports it.
+pub fn span_range(line_text: &str, line: usize, column: usize, width: usize) -> Range {
+    let units = |chars: usize| -> u32 {
+        line_text
+            .chars()
+            .take(chars)
+            .map(|c| c.len_utf16() as u32)
+            .sum()
+    };
+    let line = line.saturating_sub(1) as u32;
+    let start = column.saturating_sub(1);
+    Range::new(
+        Position::new(line, units(start)),
+        Position::new(line, units(start + width)),
+    )
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The TOML string value that byte `offset` is inside: its key — `effect`,
+/// `prose`, `goto`, … — and the byte offset its contents start at. `None` when
+/// the offset is not in a string. Works on half-typed files: it only tracks
+/// strings, comments and `key =` pairs.
+pub fn string_at(text: &str, offset: usize) -> Option<(&str, usize)> {
+    let offset = offset.min(text.len());
+    let mut key: Option<&str> = None;
+    let mut open: Option<&'static str> = None;
+    let mut contents_start = 0;
+    let mut line_start = true;
+    let mut i = 0;
+    while i < offset {
+        let rest = &text[i..];
+        let c = rest.chars().next()?;
+        if let Some(delim) = open {
+            if delim.starts_with('"') && c == '\\' {
+                i += 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+            } else if rest.starts_with(delim) {
+                open = None;
+                i += delim.len();
+            } else {
+                if c == '\n' && delim.len() == 1 {
+                    open = None;
+                    line_start = true;
+                }
+                i += c.len_utf8();
+            }
+            continue;
+        }
+        if line_start && !c.is_whitespace() {
+            line_start = false;
+            if c == '[' {
+                key = None;
+            }
+        }
+        match c {
+            '\n' => {
+                line_start = true;
+                i += 1;
+            }
+            '#' => i += rest.find('\n').unwrap_or(rest.len()),
+            '\'' | '"' => {
+                let delim = match (c, rest.starts_with("'''"), rest.starts_with("\"\"\"")) {
+                    (_, true, _) => "'''",
+                    (_, _, true) => "\"\"\"",
+                    ('\'', ..) => "'",
+                    _ => "\"",
+                };
+                open = Some(delim);
+                i += delim.len();
+                contents_start = i;
+            }
+            c if is_key_char(c) => {
+                let len = rest.find(|c: char| !is_key_char(c)).unwrap_or(rest.len());
+                let after = rest[len..].trim_start_matches([' ', '\t']);
+                if after.starts_with('=') && !after.starts_with("==") {
+                    key = Some(&rest[..len]);
+                }
+                i += len;
+            }
+            _ => i += c.len_utf8(),
+        }
+    }
+    open.and(key).map(|key| (key, contents_start))
+}
+
+/// The string literal around byte `offset` on its line, as a byte range of
+/// its contents.
+pub fn quoted_at(text: &str, offset: usize) -> Option<std::ops::Range<usize>> {
+    let line_start = text[..offset].rfind('\n').map_or(0, |nl| nl + 1);
+    let line_end = text[offset..]
+        .find('\n')
+        .map_or(text.len(), |nl| offset + nl);
+    let start = line_start + text[line_start..offset].rfind('"')? + 1;
+    let end = offset + text[offset..line_end].find('"')?;
+    Some(start..end)
+}
+
+/// The identifier (`[A-Za-z0-9_]+`) touching byte `offset`, as a byte range.
+pub fn word_at(text: &str, offset: usize) -> Option<std::ops::Range<usize>> {
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()
+        .map_or(offset, |(index, _)| index);
+    let end = text[offset..]
+        .find(|c: char| !is_ident_char(c))
+        .map_or(text.len(), |len| offset + len);
+    (start < end).then_some(start..end)
+}
+
+/// The receiver of a method whose `.` is at byte `dot`: a token (`w`, `gd`, …)
+/// or an `npc(...)` call.
+pub fn receiver_before(text: &str, dot: usize) -> Option<Receiver> {
+    let before = &text[..dot];
+    if let Some(inner) = before.strip_suffix(')') {
+        let mut depth = 0;
+        let open = inner.char_indices().rev().find_map(|(index, c)| match c {
+            ')' => {
+                depth += 1;
+                None
+            }
+            '(' if depth == 0 => Some(index),
+            '(' => {
+                depth -= 1;
+                None
+            }
+            _ => None,
+        })?;
+        return inner[..open].ends_with("npc").then_some(Receiver::Npc);
+    }
+    let token_start = before
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()?
+        .0;
+    receiver_from_token(&before[token_start..])
+}
+
+/// A method call the cursor is inside the argument list of.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CallSite {
+    pub receiver: Receiver,
+    pub method: String,
+    /// Index of the argument being typed.
+    pub arg: usize,
+    /// The string literals of the arguments before it (`None` for others).
+    pub args_before: Vec<Option<String>>,
+    /// Whether the cursor is inside a string literal.
+    pub in_string: bool,
+}
+
+/// The innermost `receiver.method(` call left open in `prefix`, the script
+/// text up to the cursor.
+pub fn call_site(prefix: &str) -> Option<CallSite> {
+    let mut opens: Vec<usize> = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (index, c) in prefix.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' => opens.push(index),
+                ')' => {
+                    opens.pop();
+                }
+                _ => {}
+            },
+        }
+    }
+    let open = *opens.last()?;
+    let name_start = prefix[..open]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()?
+        .0;
+    let dot = name_start.checked_sub(1)?;
+    if prefix.as_bytes()[dot] != b'.' {
+        return None;
+    }
+    let receiver = receiver_before(prefix, dot)?;
+
+    let mut args: Vec<String> = vec![String::new()];
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for c in prefix[open + 1..].chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    args.push(String::new());
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        if let Some(current) = args.last_mut() {
+            current.push(c);
+        }
+    }
+    args.pop();
+    let args_before = args
+        .iter()
+        .map(|arg| {
+            let arg = arg.trim();
+            arg.strip_prefix('"')
+                .and_then(|arg| arg.strip_suffix('"'))
+                .map(str::to_string)
+        })
+        .collect::<Vec<_>>();
+    Some(CallSite {
+        receiver,
+        method: prefix[name_start..open].to_string(),
+        arg: args_before.len(),
+        args_before,
+        in_string: quote.is_some(),
+    })
+}
+
+/// `recv.partial` at the end of `prefix`: the receiver and the partial
+/// method name.
+pub fn member_prefix(prefix: &str) -> Option<(Receiver, &str)> {
+    let partial_start = prefix
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()
+        .map_or(prefix.len(), |(index, _)| index);
+    let dot = partial_start.checked_sub(1)?;
+    if prefix.as_bytes()[dot] != b'.' {
+        return None;
+    }
+    Some((receiver_before(prefix, dot)?, &prefix[partial_start..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENE: &str = r#"[scene]
+id = "base::demo"
+
+[[actions]]
+id = "leave"   # a comment with "quotes"
+condition = 'w.hasTrait("SHY")'
+prose = """
+She's {{ w.getName() }}.
+"""
+next = [{ goto = "base::other" }]
+"#;
+
+    fn offset_of(needle: &str) -> usize {
+        SCENE.find(needle).unwrap()
+    }
+
+    #[test]
+    fn string_at_names_the_value_the_cursor_is_in() {
+        let key_at = |needle| string_at(SCENE, offset_of(needle)).map(|(key, _)| key);
+        assert_eq!(key_at("base::demo"), Some("id"));
+        assert_eq!(key_at("SHY"), Some("condition"));
+        assert_eq!(key_at("w.getName"), Some("prose"));
+        assert_eq!(key_at("base::other"), Some("goto"));
+        assert_eq!(key_at("a comment"), None);
+        assert_eq!(key_at("next"), None);
+        assert_eq!(
+            string_at(SCENE, offset_of("SHY")),
+            Some(("condition", offset_of("w.hasTrait")))
+        );
+    }
+
+    #[test]
+    fn positions_round_trip_through_utf16() {
+        let text = "a = \"é😀\"\nb = 1";
+        let offset = text.find('b').unwrap();
+        assert_eq!(position_at(text, offset), Position::new(1, 0));
+        assert_eq!(offset_at(text, Position::new(1, 0)), offset);
+        let after_emoji = text.find('"').unwrap() + 1 + "é😀".len();
+        assert_eq!(position_at(text, after_emoji), Position::new(0, 8));
+        assert_eq!(offset_at(text, Position::new(0, 8)), after_emoji);
+    }
+
+    #[test]
+    fn call_site_finds_the_argument_being_typed() {
+        let site = call_site(r#"w.getSkill("FEMININITY") > 3 && gd.advanceArc("base::jake", "DAT"#)
+            .unwrap();
+        assert_eq!(site.receiver, Receiver::Gd);
+        assert_eq!(site.method, "advanceArc");
+        assert_eq!(site.arg, 1);
+        assert_eq!(site.args_before, vec![Some("base::jake".to_string())]);
+        assert!(site.in_string);
+
+        let site = call_site(r#"npc("base::jake").hasTrait(""#).unwrap();
+        assert_eq!(site.receiver, Receiver::Npc);
+        assert_eq!(site.arg, 0);
+        assert!(call_site(r#"w.hasTrait("SHY")"#).is_none());
+    }
+
+    #[test]
+    fn member_prefix_reads_the_receiver() {
+        assert_eq!(member_prefix("x && w.has"), Some((Receiver::W, "has")));
+        assert_eq!(member_prefix("npc(\"a\")."), Some((Receiver::Npc, "")));
+        assert_eq!(member_prefix("foo.bar"), None);
+        assert_eq!(member_prefix("w"), None);
+    }
+}
@@ -0,0 +1,93 @@
+//! The packs an edited scene file belongs to: the registry its scripts are
+//! checked against, the other scenes its `goto`/`call` targets must name, and
+//! where each scene id is defined.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use undone_packs::{load_packs, PackRegistry};
+use undone_scene::loader::load_scene_source;
+use undone_scene::types::SceneDefinition;
+
+/// Where a scene id is defined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SceneLocation {
+    pub path: PathBuf,
+    /// 0-based line of the `id = "..."` entry.
+    pub line: u32,
+}
+
+pub struct Workspace {
+    pub registry: PackRegistry,
+    /// Every scene that loads cleanly, by id.
+    pub scenes: HashMap<String, Arc<SceneDefinition>>,
+    /// Every scene file with a readable `[scene] id`, loading or not.
+    pub index: BTreeMap<String, SceneLocation>,
+}
+
+impl Workspace {
+    /// Load the registry and every scene under `packs_dir`. A scene that fails
+    /// to load is still indexed, so references to it resolve while it is being
+    /// fixed; its own errors show when it is opened.
+    pub fn load(packs_dir: &Path) -> Result<Self, String> {
+        let (registry, metas) = load_packs(packs_dir).map_err(|e| e.to_string())?;
+        let mut scenes: HashMap<String, Arc<SceneDefinition>> = HashMap::new();
+        let mut index = BTreeMap::new();
+        for meta in &metas {
+            let scene_dir = meta.pack_dir.join(&meta.manifest.content.scenes_dir);
+            let Ok(entries) = std::fs::read_dir(&scene_dir) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .collect();
+            paths.sort();
+            for path in paths {
+                let Ok(text) = std::fs::read_to_string(&path) else {
+                    continue;
+                };
+                let Some((id, line)) = scene_id_line(&text) else {
+                    continue;
+                };
+                if let Ok(def) = load_scene_source(&path, &text, &registry) {
+                    scenes.insert(id.clone(), def.into());
+                }
+                index.insert(id, SceneLocation { path, line });
+            }
+        }
+        Ok(Workspace {
+            registry,
+            scenes,
+            index,
+        })
+    }
+}
+
+/// The `packs` directory a file lives under: the nearest ancestor named
+/// `packs`, or the nearest one with a `packs` child.
+pub fn find_packs_dir(file: &Path) -> Option<PathBuf> {
+    file.ancestors().skip(1).find_map(|dir| {
+        if dir.file_name().is_some_and(|name| name == "packs") {
+            Some(dir.to_path_buf())
+        } else {
+            let child = dir.join("packs");
+            child.is_dir().then_some(child)
+        }
+    })
+}
+
+/// The `[scene] id` of a scene file and the 0-based line it is on.
+pub fn scene_id_line(text: &str) -> Option<(String, u32)> {
+    let table: toml::Table = toml::from_str(text).ok()?;
+    let id = table.get("scene")?.get("id")?.as_str()?.to_string();
+    let line = text
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            line.starts_with("id") && line.contains(&format!("\"{id}\""))
+        })
+        .unwrap_or(0);
+    Some((id, line as u32))
+}
@@ -0,0 +1,328 @@
+//! Player body change over time: pack body-change rules (`body.toml`) evaluated
+//! at every week rollover, plus the setter scene writes use for deliberate
+//! changes. Both stamp `GameData::body_changed`, which rules measure from.
+
+use undone_domain::{BodyAttribute, Complexion, HairLength, Player, PlayerFigure, PubicHairStyle};
+use undone_world::World;
+
+use crate::data::BodyChangeRule;
+use crate::PackRegistry;
+
+const HAIR_LENGTHS: [HairLength; 5] = [
+    HairLength::Buzzed,
+    HairLength::Short,
+    HairLength::Shoulder,
+    HairLength::Long,
+    HairLength::VeryLong,
+];
+
+/// Growing back is a step *down* this scale.
+const PUBIC_HAIR: [PubicHairStyle; 5] = [
+    PubicHairStyle::Natural,
+    PubicHairStyle::Trimmed,
+    PubicHairStyle::Landing,
+    PubicHairStyle::Brazilian,
+    PubicHairStyle::Bare,
+];
+
+const FIGURES: [PlayerFigure; 7] = [
+    PlayerFigure::Petite,
+    PlayerFigure::Slim,
+    PlayerFigure::Athletic,
+    PlayerFigure::Hourglass,
+    PlayerFigure::Curvy,
+    PlayerFigure::Thick,
+    PlayerFigure::Plus,
+];
+
+const COMPLEXIONS: [Complexion; 5] = [
+    Complexion::Clear,
+    Complexion::Glowing,
+    Complexion::Normal,
+    Complexion::Rosy,
+    Complexion::Acne,
+];
+
+/// The values `attribute` can take, in scale order. These are the strings body
+/// rules and `w.set*` writes use.
+pub fn body_scale(attribute: BodyAttribute) -> &'static [&'static str] {
+    match attribute {
+        BodyAttribute::HairLength => &["Buzzed", "Short", "Shoulder", "Long", "VeryLong"],
+        BodyAttribute::PubicHair => &["Natural", "Trimmed", "Landing", "Brazilian", "Bare"],
+        BodyAttribute::Figure => &[
+            "Petite",
+            "Slim",
+            "Athletic",
+            "Hourglass",
+            "Curvy",
+            "Thick",
+            "Plus",
+        ],
+        BodyAttribute::Complexion => &["Clear", "Glowing", "Normal", "Rosy", "Acne"],
+        BodyAttribute::SmoothLegs => &["false", "true"],
+    }
+}
+
+fn scale_index(attribute: BodyAttribute, value: &str) -> Option<usize> {
+    body_scale(attribute).iter().position(|&v| v == value)
+}
+
+/// The player's current position on `attribute`'s scale.
+fn current_index(player: &Player, registry: &PackRegistry, attribute: BodyAttribute) -> usize {
+    let found = match attribute {
+        BodyAttribute::HairLength => HAIR_LENGTHS.iter().position(|&v| v == player.hair_length),
+        BodyAttribute::PubicHair => PUBIC_HAIR.iter().position(|&v| v == player.pubic_hair),
+        BodyAttribute::Figure => FIGURES.iter().position(|&v| v == player.figure),
+        BodyAttribute::Complexion => COMPLEXIONS.iter().position(|&v| v == player.complexion),
+        BodyAttribute::SmoothLegs => Some(
+            registry
+                .smooth_legs_trait()
+                .is_ok_and(|id| player.has_trait(id)) as usize,
+        ),
+    };
+    found.unwrap_or(0)
+}
+
+fn set_index(player: &mut Player, registry: &PackRegistry, attribute: BodyAttribute, idx: usize) {
+    match attribute {
+        BodyAttribute::HairLength => player.hair_length = HAIR_LENGTHS[idx],
+        BodyAttribute::PubicHair => player.pubic_hair = PUBIC_HAIR[idx],
+        BodyAttribute::Figure => player.figure = FIGURES[idx],
+        BodyAttribute::Complexion => player.complexion = COMPLEXIONS[idx],
+        BodyAttribute::SmoothLegs => {
+            if let Ok(id) = registry.smooth_legs_trait() {
+                if idx == 1 {
+                    player.traits.insert(id);
+                } else {
+                    player.traits.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+/// The player's current value for `attribute`, as a scale string.
+pub fn body_value(
+    player: &Player,
+    registry: &PackRegistry,
+    attribute: BodyAttribute,
+) -> &'static str {
+    body_scale(attribute)[current_index(player, registry, attribute)]
+}
+
+/// Deliberately set `attribute` (a scene write). Restarts the attribute's
+/// body-change clock even if the value is unchanged — a fresh shave of already
+/// smooth legs still buys another week. Returns `false` for an unknown value.
+pub fn set_body_attribute(
+    world: &mut World,
+    registry: &PackRegistry,
+    attribute: BodyAttribute,
+    value: &str,
+) -> bool {
+    let Some(idx) = scale_index(attribute, value) else {
+        return false;
+    };
+    set_index(&mut world.player, registry, attribute, idx);
+    let week = world.game_data.week;
+    world.game_data.body_changed.insert(attribute, week);
+    true
+}
+
+/// Apply every due body-change rule. Called on week rollover by
+/// `advance_time_slot`; each change queues its message on
+/// `GameData::body_notices` for the scene engine to report.
+pub fn run_body_changes(world: &mut World, registry: &PackRegistry) {
+    for rule in registry.body_change_rules() {
+        let attribute = rule.attribute;
+        if world.game_data.weeks_since_body_change(attribute) < rule.every_weeks
+            || !skill_gate_passes(rule, &world.player, registry)
+        {
+            continue;
+        }
+        let current = current_index(&world.player, registry, attribute);
+        if !rule.from.is_empty()
+            && !rule
+                .from
+                .iter()
+                .any(|v| scale_index(attribute, v) == Some(current))
+        {
+            continue;
+        }
+        let Some(target) = target_index(rule, current) else {
+            continue;
+        };
+        if target == current {
+            continue;
+        }
+        set_index(&mut world.player, registry, attribute, target);
+        let week = world.game_data.week;
+        world.game_data.body_changed.insert(attribute, week);
+        world.game_data.body_notices.push(rule.message.clone());
+    }
+}
+
+fn skill_gate_passes(rule: &BodyChangeRule, player: &Player, registry: &PackRegistry) -> bool {
+    let Some(skill) = rule.skill.as_deref() else {
+        return true;
+    };
+    let Ok(id) = registry.resolve_skill(skill) else {
+        return false;
+    };
+    let value = player.skill(id);
+    rule.skill_min.is_none_or(|min| value >= min) && rule.skill_max.is_none_or(|max| value <= max)
+}
+
+/// Where `rule` moves the attribute from `current`. `step` stops at `until`
+/// (or the end of the scale) and never steps back past it.
+fn target_index(rule: &BodyChangeRule, current: usize) -> Option<usize> {
+    let scale_len = body_scale(rule.attribute).len() as i32;
+    if let Some(to) = &rule.to {
+        return scale_index(rule.attribute, to);
+    }
+    let step = rule.step? as i32;
+    let stepped = (current as i32 + step).clamp(0, scale_len - 1);
+    let target = match rule
+        .until
+        .as_deref()
+        .and_then(|u| scale_index(rule.attribute, u))
+    {
+        Some(until) if step > 0 => stepped.min(until as i32).max(current as i32),
+        Some(until) => stepped.max(until as i32).min(current as i32),
+        None => stepped,
+    };
+    Some(target as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{BodyChangesFile, SkillDef, TraitDef};
+    use undone_domain::SkillValue;
+    use undone_world::test_helpers::make_test_world;
+
+    fn registry_with(rules: &str) -> PackRegistry {
+        let mut registry = PackRegistry::new();
+        registry.register_skills(vec![SkillDef {
+            id: "FITNESS".into(),
+            name: "Fitness".into(),
+            description: String::new(),
+            min: 0,
+            max: 100,
+            progression: None,
+        }]);
+        registry.register_traits(vec![TraitDef {
+            id: "SMOOTH_LEGS".into(),
+            name: "Smooth Legs".into(),
+            description: String::new(),
+            hidden: false,
+            group: None,
+            conflicts: vec![],
+            composure_penalty: 0,
+        }]);
+        registry.register_body_changes(toml::from_str::<BodyChangesFile>(rules).unwrap());
+        registry
+    }
+
+    fn roll_weeks(world: &mut World, registry: &PackRegistry, weeks: u32) {
+        for _ in 0..weeks * undone_world::SLOTS_PER_WEEK {
+            crate::advance_time_slot(world, registry);
+        }
+    }
+
+    #[test]
+    fn hair_grows_until_the_cap_and_a_haircut_restarts_the_clock() {
+        let registry = registry_with(
+            r#"
+            [[change]]
+            id          = "test::hair_grows"
+            attribute   = "hair_length"
+            every_weeks = 2
+            step        = 1
+            until       = "Long"
+            message     = "Your hair has grown."
+            "#,
+        );
+        let mut world = make_test_world();
+        world.player.hair_length = HairLength::Short;
+
+        roll_weeks(&mut world, &registry, 1);
+        assert_eq!(world.player.hair_length, HairLength::Short);
+        roll_weeks(&mut world, &registry, 1);
+        assert_eq!(world.player.hair_length, HairLength::Shoulder);
+        assert_eq!(world.game_data.body_notices, vec!["Your hair has grown."]);
+
+        assert!(set_body_attribute(
+            &mut world,
+            &registry,
+            BodyAttribute::HairLength,
+            "Short"
+        ));
+        roll_weeks(&mut world, &registry, 1);
+        assert_eq!(world.player.hair_length, HairLength::Short);
+
+        roll_weeks(&mut world, &registry, 5);
+        assert_eq!(world.player.hair_length, HairLength::Long);
+    }
+
+    #[test]
+    fn figure_shift_is_gated_by_fitness_and_current_figure() {
+        let registry = registry_with(
+            r#"
+            [[change]]
+            id          = "test::tones_up"
+            attribute   = "figure"
+            every_weeks = 1
+            to          = "Athletic"
+            from        = ["Curvy"]
+            skill       = "FITNESS"
+            skill_min   = 70
+            message     = "You're leaner."
+            "#,
+        );
+        let mut world = make_test_world();
+        world.player.figure = PlayerFigure::Curvy;
+        roll_weeks(&mut world, &registry, 2);
+        assert_eq!(world.player.figure, PlayerFigure::Curvy);
+
+        let fitness = registry.resolve_skill("FITNESS").unwrap();
+        world.player.skills.insert(
+            fitness,
+            SkillValue {
+                value: 80,
+                modifier: 0,
+            },
+        );
+        roll_weeks(&mut world, &registry, 1);
+        assert_eq!(world.player.figure, PlayerFigure::Athletic);
+    }
+
+    #[test]
+    fn smooth_legs_decay_removes_the_trait() {
+        let registry = registry_with(
+            r#"
+            [[change]]
+            id          = "test::stubble"
+            attribute   = "smooth_legs"
+            every_weeks = 1
+            to          = "false"
+            message     = "Stubble."
+            "#,
+        );
+        let mut world = make_test_world();
+        assert!(set_body_attribute(
+            &mut world,
+            &registry,
+            BodyAttribute::SmoothLegs,
+            "true"
+        ));
+        assert_eq!(
+            body_value(&world.player, &registry, BodyAttribute::SmoothLegs),
+            "true"
+        );
+        roll_weeks(&mut world, &registry, 1);
+        assert_eq!(
+            body_value(&world.player, &registry, BodyAttribute::SmoothLegs),
+            "false"
+        );
+    }
+}
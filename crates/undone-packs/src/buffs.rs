@@ -0,0 +1,273 @@
+//! Timed skill modifiers: pack buff definitions (`buffs.toml`) applied by scene
+//! writes or granted by traits, items and need-state thresholds. Buffs own
+//! `SkillValue::modifier` — `refresh_buffs` recomputes it from scratch.
+
+use undone_domain::{ActiveBuff, SkillValue};
+use undone_world::{World, SLOTS_PER_DAY, SLOTS_PER_WEEK};
+
+use crate::data::{BuffGrant, BuffNeed};
+use crate::PackRegistry;
+
+/// Parse a buff duration — `"<n>s"` slots, `"<n>d"` days, `"<n>w"` weeks — into
+/// time slots. `None` for anything else, including zero.
+pub fn parse_duration(duration: &str) -> Option<u32> {
+    let duration = duration.trim();
+    let unit = duration.chars().last()?;
+    let count: u32 = duration[..duration.len() - unit.len_utf8()].parse().ok()?;
+    let per = match unit {
+        's' => 1,
+        'd' => SLOTS_PER_DAY,
+        'w' => SLOTS_PER_WEEK,
+        _ => return None,
+    };
+    count.checked_mul(per).filter(|&slots| slots > 0)
+}
+
+/// Apply buff `id` from `source` (a scene id, for scene writes). Re-applying a
+/// buff the player already has adds a stack, up to `max_stacks`, and restarts
+/// its clock. Returns `false` for an unknown buff.
+pub fn add_buff(world: &mut World, registry: &PackRegistry, id: &str, source: &str) -> bool {
+    let Some(def) = registry.get_buff(id) else {
+        return false;
+    };
+    let expires_at = def
+        .duration
+        .as_deref()
+        .and_then(parse_duration)
+        .map(|slots| world.game_data.absolute_slot() + slots);
+    let buffs = &mut world.player.buffs;
+    match buffs.iter_mut().find(|buff| buff.id == id && !buff.granted) {
+        Some(buff) => {
+            buff.stacks = (buff.stacks + 1).min(def.max_stacks);
+            buff.expires_at = expires_at;
+            buff.source = source.to_string();
+        }
+        None => buffs.push(ActiveBuff {
+            id: id.to_string(),
+            name: def.name.clone(),
+            source: source.to_string(),
+            stacks: 1,
+            expires_at,
+            granted: false,
+        }),
+    }
+    refresh_buffs(world, registry);
+    true
+}
+
+/// Remove every stack of scene-applied buff `id`. Granted buffs stay while
+/// their grant holds. Returns whether anything was removed.
+pub fn remove_buff(world: &mut World, registry: &PackRegistry, id: &str) -> bool {
+    let before = world.player.buffs.len();
+    world
+        .player
+        .buffs
+        .retain(|buff| buff.id != id || buff.granted);
+    let removed = world.player.buffs.len() != before;
+    if removed {
+        refresh_buffs(world, registry);
+    }
+    removed
+}
+
+/// Drop expired buffs, add or drop granted ones to match their grants, and
+/// recompute every skill modifier. Runs every time slot via
+/// `advance_time_slot`.
+pub fn refresh_buffs(world: &mut World, registry: &PackRegistry) {
+    let now = world.game_data.absolute_slot();
+    world
+        .player
+        .buffs
+        .retain(|buff| buff.expires_at.is_none_or(|at| now < at));
+
+    for def in registry.buffs() {
+        let Some(grant) = &def.granted_by else {
+            continue;
+        };
+        let source = grant_source(world, registry, grant);
+        let buffs = &mut world.player.buffs;
+        let held = buffs
+            .iter()
+            .position(|buff| buff.id == def.id && buff.granted);
+        match (source, held) {
+            (Some(source), None) => buffs.push(ActiveBuff {
+                id: def.id.clone(),
+                name: def.name.clone(),
+                source,
+                stacks: 1,
+                expires_at: None,
+                granted: true,
+            }),
+            (None, Some(idx)) => {
+                buffs.remove(idx);
+            }
+            _ => {}
+        }
+    }
+
+    let player = &mut world.player;
+    for skill in player.skills.values_mut() {
+        skill.modifier = 0;
+    }
+    for buff in &player.buffs {
+        let Some(def) = registry.get_buff(&buff.id) else {
+            continue;
+        };
+        for (skill_id, delta) in &def.skills {
+            let Ok(skill) = registry.resolve_skill(skill_id) else {
+                continue;
+            };
+            player
+                .skills
+                .entry(skill)
+                .or_insert(SkillValue {
+                    value: 0,
+                    modifier: 0,
+                })
+                .modifier += delta * buff.stacks as i32;
+        }
+    }
+}
+
+/// The display source of a granted buff whose grant currently holds.
+fn grant_source(world: &World, registry: &PackRegistry, grant: &BuffGrant) -> Option<String> {
+    let player = &world.player;
+    if let Some(trait_id) = &grant.trait_id {
+        let id = registry.resolve_trait(trait_id).ok()?;
+        return player.has_trait(id).then(|| {
+            registry
+                .get_trait_def(id)
+                .map_or(trait_id.clone(), |def| def.name.clone())
+        });
+    }
+    if let Some(stuff) = &grant.stuff {
+        let id = registry.resolve_stuff(stuff)?;
+        return player.stuff.contains(&id).then(|| stuff.clone());
+    }
+    let (need, min) = (grant.need?, grant.min?);
+    let (label, level) = match need {
+        BuffNeed::Desire => ("Desire", world.game_data.desire()),
+        BuffNeed::Stress => ("Stress", player.stress.get()),
+        BuffNeed::Anxiety => ("Anxiety", player.anxiety.get()),
+    };
+    (level >= min).then(|| format!("{label} {min}+"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{BuffsFile, SkillDef, TraitDef};
+    use undone_world::test_helpers::make_test_world;
+
+    fn registry_with(buffs: &str) -> PackRegistry {
+        let mut registry = PackRegistry::new();
+        registry.register_skills(vec![SkillDef {
+            id: "COMPOSURE".into(),
+            name: "Composure".into(),
+            description: String::new(),
+            min: 0,
+            max: 100,
+            progression: None,
+        }]);
+        registry.register_traits(vec![TraitDef {
+            id: "CONFIDENT".into(),
+            name: "Confident".into(),
+            description: String::new(),
+            hidden: false,
+            group: None,
+            conflicts: vec![],
+            composure_penalty: 0,
+        }]);
+        registry.register_buffs(toml::from_str::<BuffsFile>(buffs).unwrap());
+        registry
+    }
+
+    fn composure(world: &World, registry: &PackRegistry) -> i32 {
+        world.player.skill(registry.composure_skill().unwrap())
+    }
+
+    #[test]
+    fn durations_parse_to_slots() {
+        assert_eq!(parse_duration("3s"), Some(3));
+        assert_eq!(parse_duration("1d"), Some(4));
+        assert_eq!(parse_duration("2w"), Some(56));
+        assert_eq!(parse_duration("0d"), None);
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("1y"), None);
+    }
+
+    #[test]
+    fn scene_buffs_stack_up_to_the_cap_and_wear_off() {
+        let registry = registry_with(
+            r#"
+            [[buff]]
+            id         = "HUNGOVER"
+            name       = "Hungover"
+            skills     = { COMPOSURE = -10 }
+            max_stacks = 2
+            duration   = "1d"
+            "#,
+        );
+        let mut world = make_test_world();
+        let base = composure(&world, &registry);
+
+        for _ in 0..3 {
+            assert!(add_buff(&mut world, &registry, "HUNGOVER", "test::bar"));
+        }
+        assert_eq!(world.player.buffs[0].stacks, 2);
+        assert_eq!(composure(&world, &registry), base - 20);
+
+        for _ in 0..3 {
+            crate::advance_time_slot(&mut world, &registry);
+        }
+        assert_eq!(composure(&world, &registry), base - 20);
+        crate::advance_time_slot(&mut world, &registry);
+        assert!(world.player.buffs.is_empty());
+        assert_eq!(composure(&world, &registry), base);
+
+        assert!(!add_buff(&mut world, &registry, "TYPO", "test::bar"));
+    }
+
+    #[test]
+    fn granted_buffs_follow_their_grant() {
+        let registry = registry_with(
+            r#"
+            [[buff]]
+            id         = "POISED"
+            name       = "Poised"
+            skills     = { COMPOSURE = 5 }
+            granted_by = { trait = "CONFIDENT" }
+
+            [[buff]]
+            id         = "RESTLESS"
+            name       = "Restless"
+            skills     = { COMPOSURE = -15 }
+            granted_by = { need = "desire", min = 80 }
+            "#,
+        );
+        let mut world = make_test_world();
+        let base = composure(&world, &registry);
+        let confident = registry.resolve_trait("CONFIDENT").unwrap();
+
+        world.player.traits.insert(confident);
+        world.game_data.set_desire(90);
+        refresh_buffs(&mut world, &registry);
+        assert_eq!(composure(&world, &registry), base - 10);
+        let sources: Vec<_> = world
+            .player
+            .buffs
+            .iter()
+            .map(|b| b.source.as_str())
+            .collect();
+        assert!(sources.contains(&"Confident"));
+        assert!(sources.contains(&"Desire 80+"));
+
+        // A scene can't strip a granted buff; losing the grant does.
+        assert!(!remove_buff(&mut world, &registry, "POISED"));
+        world.player.traits.remove(&confident);
+        world.game_data.set_desire(10);
+        refresh_buffs(&mut world, &registry);
+        assert!(world.player.buffs.is_empty());
+        assert_eq!(composure(&world, &registry), base);
+    }
+}
@@ -1,4 +1,5 @@
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use undone_domain::{
     Age, AlcoholLevel, Appearance, ArousalLevel, BeforeIdentity, BoundedStat, BreastSize, ButtSize,
@@ -9,10 +10,11 @@ use undone_domain::{
 use undone_world::{GameData, World};
 
 use crate::{
-    spawner::{spawn_npcs, NpcSpawnConfig},
+    spawner::{seed_social_clusters, spawn_npcs, spawn_story_npcs, NpcSpawnConfig},
     PackRegistry,
 };
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharCreationConfig {
     /// Feminine display name (femininity 50+)
     pub name_fem: String,
@@ -120,6 +122,9 @@ pub fn new_game<R: Rng>(
         custom_ints: HashMap::new(),
         origin: config.origin,
         before: config.before,
+        memory: Default::default(),
+        buffs: Vec::new(),
+        practice: HashMap::new(),
     };
 
     // Seed FEMININITY skill in the skills map.
@@ -180,22 +185,28 @@ pub fn new_game<R: Rng>(
         male_count: config.male_count,
         female_count: config.female_count,
     };
-    let (male_npcs, female_npcs) = spawn_npcs(&spawn_config, registry, rng);
+    let (mut male_npcs, mut female_npcs) = spawn_npcs(&spawn_config, registry, rng);
+    spawn_story_npcs(registry, &mut male_npcs, &mut female_npcs);
+    let npc_relations = seed_social_clusters(&male_npcs, &female_npcs);
 
     let mut game_data = GameData::default();
     for flag in config.starting_flags {
-        game_data.set_flag(flag);
+        game_data.set_flag_from(flag, "new game");
     }
     for (arc_id, state) in config.starting_arc_states {
         game_data.advance_arc(arc_id, state);
     }
 
-    World {
+    let mut world = World {
         player,
         male_npcs,
         female_npcs,
         game_data,
-    }
+        npc_relations,
+    };
+    // Starting traits and items grant their buffs from the first slot.
+    crate::buffs::refresh_buffs(&mut world, registry);
+    world
 }
 
 #[cfg(test)]
@@ -288,8 +299,39 @@ mod tests {
         let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
         let world = new_game(config, &mut registry, &mut rng);
 
-        assert_eq!(world.male_npcs.len(), 7);
-        assert_eq!(world.female_npcs.len(), 2);
+        let random_males = world
+            .male_npcs
+            .values()
+            .filter(|npc| npc.core.story_id.is_none())
+            .count();
+        let random_females = world
+            .female_npcs
+            .values()
+            .filter(|npc| npc.core.story_id.is_none())
+            .count();
+        assert_eq!(random_males, 7);
+        assert_eq!(random_females, 2);
+    }
+
+    #[test]
+    fn new_game_instantiates_story_characters() {
+        let (mut registry, _) = load_packs(&packs_dir()).unwrap();
+        let config = base_config();
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(6);
+        let world = new_game(config, &mut registry, &mut rng);
+
+        let jake = world
+            .male_npcs
+            .values()
+            .find(|npc| npc.core.story_id.as_deref() == Some("base::jake"))
+            .expect("base::jake should be instantiated at new_game");
+        assert_eq!(jake.core.effective_name(), "Jake");
+        assert!(jake.core.roles.contains("ROLE_JAKE"));
+        assert_eq!(
+            world.male_npcs.values().next().unwrap().core.story_id,
+            None,
+            "story NPCs must not displace the random pool's first slot"
+        );
     }
 
     #[test]
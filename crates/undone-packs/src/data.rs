@@ -1,4 +1,10 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
+use undone_domain::{
+    Age, AttractionLevel, Behaviour, BodyAttribute, BreastSize, LikingLevel, LoveLevel, MaleFigure,
+    PlayerFigure, RelationshipStatus, VarType, VarValue,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct TraitFile {
@@ -17,6 +23,10 @@ pub struct TraitDef {
     pub group: Option<String>,
     #[serde(default)]
     pub conflicts: Vec<String>,
+    /// Added to the DC of COMPOSURE checks while the player has this trait —
+    /// sexual-response traits make holding it together harder.
+    #[serde(default)]
+    pub composure_penalty: i32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,6 +56,37 @@ pub struct SkillDef {
     pub description: String,
     pub min: i32,
     pub max: i32,
+    /// How `w.practice` XP turns into points. Unset uses a flat
+    /// `DEFAULT_XP_PER_POINT` with no soft cap or decay.
+    #[serde(default)]
+    pub progression: Option<SkillProgression>,
+}
+
+/// A skill's progression curve: XP cost per point by tier, a soft cap past
+/// which points cost double, and decay when the skill goes unpractised.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SkillProgression {
+    pub tiers: Vec<XpTier>,
+    #[serde(default)]
+    pub soft_cap: Option<i32>,
+    #[serde(default)]
+    pub decay: Option<SkillDecay>,
+}
+
+/// XP for one point while the skill's value is at least `from`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct XpTier {
+    pub from: i32,
+    pub xp: u32,
+}
+
+/// Lose `amount` points at each week rollover once the skill has gone
+/// `after_weeks` without practice, never below `floor`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SkillDecay {
+    pub after_weeks: u32,
+    pub amount: i32,
+    pub floor: i32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -116,3 +157,325 @@ pub struct ArcsFile {
     #[serde(default)]
     pub arc: Vec<ArcDef>,
 }
+
+// ---------------------------------------------------------------------------
+// Story characters
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CharacterSex {
+    Male,
+    Female,
+}
+
+/// An authored story NPC with a stable identity. Instantiated once per
+/// playthrough by `new_game`, alongside the random spawn pool, so the prose can
+/// rely on the same age, looks and personality every run.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharacterDef {
+    /// Stable, pack-namespaced id (`base::jake`). Scripts address the NPC by it.
+    pub id: String,
+    pub sex: CharacterSex,
+    pub name: String,
+    pub age: Age,
+    pub race: String,
+    pub eye_colour: String,
+    pub hair_colour: String,
+    /// Personality id (`ROMANTIC`, `JERK`, …). Custom personalities are allowed.
+    pub personality: String,
+    /// NPC trait ids; validated against the registry after all packs load.
+    #[serde(default)]
+    pub traits: Vec<String>,
+    /// Male figure. Ignored for female characters; defaults to `Average`.
+    #[serde(default)]
+    pub figure: Option<MaleFigure>,
+    /// Female figure. Ignored for male characters; defaults to `Slim`.
+    #[serde(default)]
+    pub female_figure: Option<PlayerFigure>,
+    /// Ignored for male characters; defaults to `Average`.
+    #[serde(default)]
+    pub breasts: Option<BreastSize>,
+    /// Route role tags held from game start (e.g. `ROLE_JAKE`).
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub contactable: bool,
+    #[serde(default)]
+    pub start: CharacterStart,
+}
+
+/// Starting relationship values for a story character. Every field defaults to
+/// the same value a random spawn starts with.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CharacterStart {
+    pub relationship: RelationshipStatus,
+    pub pc_liking: LikingLevel,
+    pub npc_liking: LikingLevel,
+    pub pc_love: LoveLevel,
+    pub npc_love: LoveLevel,
+    pub pc_attraction: AttractionLevel,
+    pub npc_attraction: AttractionLevel,
+    pub behaviour: Behaviour,
+}
+
+impl Default for CharacterStart {
+    fn default() -> Self {
+        Self {
+            relationship: RelationshipStatus::Stranger,
+            pc_liking: LikingLevel::Neutral,
+            npc_liking: LikingLevel::Neutral,
+            pc_love: LoveLevel::None,
+            npc_love: LoveLevel::None,
+            pc_attraction: AttractionLevel::Unattracted,
+            npc_attraction: AttractionLevel::Unattracted,
+            behaviour: Behaviour::Neutral,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CharactersFile {
+    #[serde(default)]
+    pub character: Vec<CharacterDef>,
+}
+
+// ---------------------------------------------------------------------------
+// NPC routines
+// ---------------------------------------------------------------------------
+
+/// Which side of the contact gap a drift rule applies on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftWhen {
+    /// At least `slots` have passed since the player last dealt with the NPC.
+    Neglected,
+    /// The player dealt with the NPC within the last `slots`.
+    Attended,
+}
+
+/// The relationship value a drift rule moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStat {
+    NpcLiking,
+    NpcLove,
+}
+
+/// NPC filters shared by every routine rule. An unset filter matches every NPC;
+/// set filters must all match.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RoutineFilter {
+    /// Personality id (`ROMANTIC`, `JERK`, …).
+    #[serde(default)]
+    pub personality: Option<String>,
+    /// NPC trait id; validated against the registry after all packs load.
+    #[serde(default)]
+    pub npc_trait: Option<String>,
+    /// Story character id (`base::jake`).
+    #[serde(default)]
+    pub character: Option<String>,
+    /// Route role tag (`ROLE_JAKE`).
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// Off-screen relationship drift: while the contact condition holds, move `stat`
+/// by `delta` steps at most once every `every` slots.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DriftRule {
+    pub id: String,
+    pub when: DriftWhen,
+    pub slots: u32,
+    pub every: u32,
+    pub stat: DriftStat,
+    pub delta: i8,
+    #[serde(flatten)]
+    pub filter: RoutineFilter,
+}
+
+/// An NPC reaching out: once `after_slots` have passed since the last contact,
+/// queue `scene` with the NPC bound. Fires at most once every `every` slots.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutreachRule {
+    pub id: String,
+    pub scene: String,
+    pub after_slots: u32,
+    pub every: u32,
+    /// Minimum NPC-to-PC liking required to reach out.
+    #[serde(default)]
+    pub min_npc_liking: Option<LikingLevel>,
+    #[serde(flatten)]
+    pub filter: RoutineFilter,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RoutinesFile {
+    #[serde(default)]
+    pub drift: Vec<DriftRule>,
+    #[serde(default)]
+    pub outreach: Vec<OutreachRule>,
+}
+
+// ---------------------------------------------------------------------------
+// Body changes
+// ---------------------------------------------------------------------------
+
+/// A player body change over time, checked at every week rollover. Fires once
+/// `every_weeks` have passed since `attribute` last changed, then either moves
+/// it `step` places along its scale (stopping at `until`) or sets it `to` a
+/// value. Values are the attribute's variant names (`"VeryLong"`, `"Athletic"`;
+/// `"true"`/`"false"` for `smooth_legs`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct BodyChangeRule {
+    pub id: String,
+    pub attribute: BodyAttribute,
+    pub every_weeks: u32,
+    #[serde(default)]
+    pub step: Option<i8>,
+    #[serde(default)]
+    pub to: Option<String>,
+    #[serde(default)]
+    pub until: Option<String>,
+    /// Only fire while the attribute currently has one of these values.
+    #[serde(default)]
+    pub from: Vec<String>,
+    /// Skill gate: `skill` must be within `skill_min..=skill_max` (either bound
+    /// optional).
+    #[serde(default)]
+    pub skill: Option<String>,
+    #[serde(default)]
+    pub skill_min: Option<i32>,
+    #[serde(default)]
+    pub skill_max: Option<i32>,
+    /// Shown to the player when the change happens.
+    pub message: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BodyChangesFile {
+    #[serde(default)]
+    pub change: Vec<BodyChangeRule>,
+}
+
+/// A narrator-voice phrase for check odds: used when the pass chance, in
+/// percent, is at least `min` (and below the next band up).
+#[derive(Debug, Clone, Deserialize)]
+pub struct OddsBand {
+    pub min: i32,
+    pub phrase: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OddsFile {
+    #[serde(default)]
+    pub band: Vec<OddsBand>,
+}
+
+// ---------------------------------------------------------------------------
+// Buffs
+// ---------------------------------------------------------------------------
+
+/// A named, stackable modifier on one or more skills. Scenes apply it with
+/// `w.addBuff`, or `granted_by` keeps it on the player while a trait, item or
+/// need-state threshold holds. Each stack adds `skills` once.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuffDef {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Skill id → modifier per stack.
+    pub skills: HashMap<String, i32>,
+    #[serde(default = "default_max_stacks")]
+    pub max_stacks: u32,
+    /// How long an application lasts: `"<n>s"` slots, `"<n>d"` days or `"<n>w"`
+    /// weeks. Unset lasts until a scene removes it.
+    #[serde(default)]
+    pub duration: Option<String>,
+    #[serde(default)]
+    pub granted_by: Option<BuffGrant>,
+}
+
+fn default_max_stacks() -> u32 {
+    1
+}
+
+/// What keeps a granted buff on the player. Set exactly one of `trait`,
+/// `stuff` or `need`; `need` also takes `min`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuffGrant {
+    #[serde(default, rename = "trait")]
+    pub trait_id: Option<String>,
+    #[serde(default)]
+    pub stuff: Option<String>,
+    #[serde(default)]
+    pub need: Option<BuffNeed>,
+    #[serde(default)]
+    pub min: Option<i32>,
+}
+
+/// Need-states a buff can be granted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuffNeed {
+    Desire,
+    Stress,
+    Anxiety,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BuffsFile {
+    #[serde(default)]
+    pub buff: Vec<BuffDef>,
+}
+
+// ---------------------------------------------------------------------------
+// Game flags and variables
+// ---------------------------------------------------------------------------
+
+/// A declared persistent game flag (`flags.toml`). Once any pack declares its
+/// flags, `gd.hasGameFlag` / `setGameFlag` / `removeGameFlag` on an undeclared
+/// literal fail the load.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FlagDef {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    /// The story thread (`roadmap.toml` name) that owns the flag.
+    #[serde(default)]
+    pub thread: Option<String>,
+}
+
+/// A persistent typed variable (`flags.toml`). Scenes promote a scene variable
+/// to it with `[scene.vars] name = { persist = "ID" }`; `gd.getVar` reads it
+/// anywhere.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VarDef {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub var_type: VarType,
+    /// The value before any scene sets it; the type's zero when unset.
+    #[serde(default)]
+    pub default: Option<VarValue>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub thread: Option<String>,
+}
+
+impl VarDef {
+    pub fn default_value(&self) -> VarValue {
+        self.default.clone().unwrap_or_else(|| self.var_type.zero())
+    }
+}
+
+/// A pack's persistent-state schema: its game flags and variables.
+#[derive(Debug, Default, Deserialize)]
+pub struct FlagsFile {
+    #[serde(default)]
+    pub flag: Vec<FlagDef>,
+    #[serde(default)]
+    pub var: Vec<VarDef>,
+}
@@ -1,17 +1,30 @@
+pub mod body;
+pub mod buffs;
 pub mod char_creation;
 pub mod data;
 pub mod loader;
 pub mod manifest;
 pub mod preset;
+pub mod progression;
 pub mod registry;
+pub mod routines;
+pub mod scripts;
 pub mod spawner;
 
+pub use body::{body_scale, body_value, run_body_changes, set_body_attribute};
+pub use buffs::{add_buff, refresh_buffs, remove_buff};
 pub use char_creation::{new_game, CharCreationConfig};
 pub use data::{
-    CategoriesFile, CategoryDef, CategoryType, NamesFile, NpcTraitDef, SkillDef, TraitDef,
+    BodyChangeRule, BodyChangesFile, BuffDef, BuffGrant, BuffNeed, BuffsFile, CategoriesFile,
+    CategoryDef, CategoryType, CharacterDef, CharacterSex, CharacterStart, DriftRule, DriftStat,
+    DriftWhen, FlagDef, FlagsFile, NamesFile, NpcTraitDef, OddsBand, OddsFile, OutreachRule,
+    RoutineFilter, SkillDecay, SkillDef, SkillProgression, TraitDef, VarDef, XpTier,
 };
 pub use loader::{load_packs, LoadedPackMeta, PackLoadError};
 pub use manifest::{PackContent, PackManifest, PackMeta};
 pub use preset::{DiscoveryBeat, PresetData, RevealGroup};
-pub use registry::{PackRegistry, RegistryError};
-pub use spawner::{spawn_npcs, NpcSpawnConfig};
+pub use progression::{practice, run_skill_decay, DEFAULT_XP_PER_POINT};
+pub use registry::{PackRegistry, RegistryError, ONCE_FLAG_PREFIX};
+pub use routines::{advance_time_slot, run_npc_routines};
+pub use scripts::ScriptFile;
+pub use spawner::{backfill_story_npcs, spawn_npcs, spawn_story_npcs, NpcSpawnConfig};
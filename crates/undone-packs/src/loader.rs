@@ -3,7 +3,10 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use crate::{
-    data::{ArcsFile, CategoriesFile, NpcTraitFile, SkillFile, TraitFile},
+    data::{
+        ArcsFile, BodyChangesFile, BuffsFile, CategoriesFile, CharactersFile, FlagsFile,
+        NpcTraitFile, OddsFile, RoutineFilter, RoutinesFile, SkillFile, TraitFile,
+    },
     manifest::PackManifest,
     registry::PackRegistry,
 };
@@ -27,6 +30,22 @@ pub enum PackLoadError {
     },
     #[error("required pack data is missing: {kind}")]
     MissingRequiredData { kind: &'static str },
+    #[error("story character '{id}': {message}")]
+    InvalidCharacter { id: String, message: String },
+    #[error("npc routine '{id}': {message}")]
+    InvalidRoutine { id: String, message: String },
+    #[error("body change '{id}': {message}")]
+    InvalidBodyChange { id: String, message: String },
+    #[error("odds bands: {0}")]
+    InvalidOddsBands(String),
+    #[error("skill '{id}': {message}")]
+    InvalidSkill { id: String, message: String },
+    #[error("buff '{id}': {message}")]
+    InvalidBuff { id: String, message: String },
+    #[error("var '{id}': {message}")]
+    InvalidVar { id: String, message: String },
+    #[error("preset '{preset}' starts with undeclared game flag '{flag}'")]
+    UndeclaredStartingFlag { preset: String, flag: String },
 }
 
 pub struct LoadedPackMeta {
@@ -65,6 +84,14 @@ pub fn load_packs(packs_dir: &Path) -> Result<(PackRegistry, Vec<LoadedPackMeta>
     }
 
     validate_required_ids(&registry)?;
+    validate_skill_progression(&registry)?;
+    validate_characters(&registry)?;
+    validate_routines(&registry)?;
+    validate_body_changes(&registry)?;
+    validate_odds_bands(&registry)?;
+    validate_buffs(&registry)?;
+    validate_vars(&registry)?;
+    validate_starting_flags(&registry)?;
     registry.seal_pack_id_prefix();
 
     Ok((registry, metas))
@@ -131,6 +158,302 @@ fn validate_required_ids(registry: &PackRegistry) -> Result<(), PackLoadError> {
     Ok(())
 }
 
+/// Story characters are instantiated unconditionally by `new_game`, so a bad
+/// declaration must fail at load rather than surface mid-playthrough. Runs after
+/// every pack is registered so a character may use another pack's NPC traits.
+fn validate_characters(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    let invalid = |id: &str, message: String| PackLoadError::InvalidCharacter {
+        id: id.to_string(),
+        message,
+    };
+    let mut seen = std::collections::HashSet::new();
+    for def in registry.characters() {
+        if !def.id.contains("::") {
+            return Err(invalid(
+                &def.id,
+                "id must be namespaced as '<pack>::<name>'".to_string(),
+            ));
+        }
+        if !seen.insert(def.id.as_str()) {
+            return Err(invalid(&def.id, "declared more than once".to_string()));
+        }
+        for trait_id in &def.traits {
+            if registry.resolve_npc_trait(trait_id).is_err() {
+                return Err(invalid(&def.id, format!("unknown npc trait '{trait_id}'")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Routine rules run silently on every time advance, so a typo'd trait or
+/// character id would make a rule never fire. Catch those at load instead.
+fn validate_routines(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    let invalid = |id: &str, message: String| PackLoadError::InvalidRoutine {
+        id: id.to_string(),
+        message,
+    };
+    let check_filter = |id: &str, filter: &RoutineFilter| -> Result<(), PackLoadError> {
+        if let Some(trait_id) = &filter.npc_trait {
+            if registry.resolve_npc_trait(trait_id).is_err() {
+                return Err(invalid(id, format!("unknown npc trait '{trait_id}'")));
+            }
+        }
+        if let Some(character) = &filter.character {
+            if registry.get_character(character).is_none() {
+                return Err(invalid(
+                    id,
+                    format!("unknown story character '{character}'"),
+                ));
+            }
+        }
+        Ok(())
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    let rules = registry
+        .drift_rules()
+        .iter()
+        .map(|r| (&r.id, r.every, &r.filter))
+        .chain(
+            registry
+                .outreach_rules()
+                .iter()
+                .map(|r| (&r.id, r.every, &r.filter)),
+        );
+    for (id, every, filter) in rules {
+        if !id.contains("::") {
+            return Err(invalid(
+                id,
+                "id must be namespaced as '<pack>::<name>'".to_string(),
+            ));
+        }
+        if !seen.insert(id.as_str()) {
+            return Err(invalid(id, "declared more than once".to_string()));
+        }
+        if every == 0 {
+            return Err(invalid(id, "'every' must be at least 1 slot".to_string()));
+        }
+        check_filter(id, filter)?;
+    }
+    Ok(())
+}
+
+/// Body-change rules are checked against the attribute scales and the skill
+/// registry so a misspelt value fails the load instead of never firing.
+fn validate_body_changes(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    let mut seen = std::collections::HashSet::new();
+    for rule in registry.body_change_rules() {
+        let invalid = |message: String| PackLoadError::InvalidBodyChange {
+            id: rule.id.clone(),
+            message,
+        };
+        if !rule.id.contains("::") {
+            return Err(invalid(
+                "id must be namespaced as '<pack>::<name>'".to_string(),
+            ));
+        }
+        if !seen.insert(rule.id.as_str()) {
+            return Err(invalid("declared more than once".to_string()));
+        }
+        if rule.every_weeks == 0 {
+            return Err(invalid("'every_weeks' must be at least 1".to_string()));
+        }
+        if rule.step.is_some() == rule.to.is_some() {
+            return Err(invalid("set exactly one of 'step' or 'to'".to_string()));
+        }
+        let scale = crate::body_scale(rule.attribute);
+        let values = rule
+            .to
+            .iter()
+            .chain(rule.until.iter())
+            .chain(rule.from.iter());
+        for value in values {
+            if !scale.contains(&value.as_str()) {
+                return Err(invalid(format!(
+                    "'{value}' is not one of {}",
+                    scale.join(", ")
+                )));
+            }
+        }
+        if let Some(skill) = &rule.skill {
+            if registry.resolve_skill(skill).is_err() {
+                return Err(invalid(format!("unknown skill '{skill}'")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bands must cover every pass chance: mins within 0..=100, one at 0, no two
+/// alike.
+fn validate_odds_bands(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    let bands = registry.odds_bands();
+    if bands.is_empty() {
+        return Ok(());
+    }
+    if let Some(band) = bands.iter().find(|band| !(0..=100).contains(&band.min)) {
+        return Err(PackLoadError::InvalidOddsBands(format!(
+            "min {} is outside 0..=100",
+            band.min
+        )));
+    }
+    if bands.windows(2).any(|pair| pair[0].min == pair[1].min) {
+        return Err(PackLoadError::InvalidOddsBands(
+            "two bands share a min".to_string(),
+        ));
+    }
+    if bands.last().is_some_and(|band| band.min != 0) {
+        return Err(PackLoadError::InvalidOddsBands(
+            "no band starts at 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Progression curves must price every value from the skill's min, with
+/// positive costs, and decay must stay within the skill's range.
+fn validate_skill_progression(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    for skill in registry.skill_defs() {
+        let Some(progression) = &skill.progression else {
+            continue;
+        };
+        let invalid = |message: String| PackLoadError::InvalidSkill {
+            id: skill.id.clone(),
+            message,
+        };
+        if !progression.tiers.iter().any(|tier| tier.from <= skill.min) {
+            return Err(invalid(format!(
+                "no progression tier starts at or below min {}",
+                skill.min
+            )));
+        }
+        if progression.tiers.iter().any(|tier| tier.xp == 0) {
+            return Err(invalid("tier 'xp' must be at least 1".to_string()));
+        }
+        let mut froms: Vec<i32> = progression.tiers.iter().map(|tier| tier.from).collect();
+        froms.sort_unstable();
+        if froms.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(invalid("two tiers share a 'from'".to_string()));
+        }
+        if let Some(decay) = &progression.decay {
+            if decay.after_weeks == 0 || decay.amount < 1 {
+                return Err(invalid(
+                    "decay needs 'after_weeks' and 'amount' of at least 1".to_string(),
+                ));
+            }
+            if !(skill.min..=skill.max).contains(&decay.floor) {
+                return Err(invalid(format!(
+                    "decay floor {} is outside {}..={}",
+                    decay.floor, skill.min, skill.max
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Buffs must name real skills and traits, carry a readable duration, and
+/// declare exactly one grant source when granted.
+fn validate_buffs(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    for buff in registry.buffs() {
+        let invalid = |message: String| PackLoadError::InvalidBuff {
+            id: buff.id.clone(),
+            message,
+        };
+        if buff.skills.is_empty() {
+            return Err(invalid("modifies no skills".to_string()));
+        }
+        if let Some(skill) = buff
+            .skills
+            .keys()
+            .find(|skill| registry.resolve_skill(skill).is_err())
+        {
+            return Err(invalid(format!("unknown skill '{skill}'")));
+        }
+        if buff.max_stacks == 0 {
+            return Err(invalid("'max_stacks' must be at least 1".to_string()));
+        }
+        if let Some(duration) = &buff.duration {
+            if crate::buffs::parse_duration(duration).is_none() {
+                return Err(invalid(format!(
+                    "duration '{duration}' is not '<n>s', '<n>d' or '<n>w'"
+                )));
+            }
+        }
+        let Some(grant) = &buff.granted_by else {
+            continue;
+        };
+        if buff.duration.is_some() {
+            return Err(invalid(
+                "granted buffs last while the grant holds; drop 'duration'".to_string(),
+            ));
+        }
+        let sources = [
+            grant.trait_id.is_some(),
+            grant.stuff.is_some(),
+            grant.need.is_some(),
+        ];
+        if sources.iter().filter(|&&set| set).count() != 1 {
+            return Err(invalid(
+                "'granted_by' sets exactly one of 'trait', 'stuff' or 'need'".to_string(),
+            ));
+        }
+        if grant.need.is_some() != grant.min.is_some() {
+            return Err(invalid(
+                "'min' goes with 'need', and 'need' requires it".to_string(),
+            ));
+        }
+        if let Some(trait_id) = &grant.trait_id {
+            if registry.resolve_trait(trait_id).is_err() {
+                return Err(invalid(format!("unknown trait '{trait_id}'")));
+            }
+        }
+        if let Some(stuff) = &grant.stuff {
+            if registry.resolve_stuff(stuff).is_none() {
+                return Err(invalid(format!("unknown stuff '{stuff}'")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Preset starting flags must be declared, like the flags scripts use.
+fn validate_starting_flags(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    for preset in registry.presets() {
+        if let Some(flag) = preset
+            .starting_flags
+            .iter()
+            .find(|flag| !registry.is_known_flag(flag))
+        {
+            return Err(PackLoadError::UndeclaredStartingFlag {
+                preset: preset.name_fem.clone(),
+                flag: flag.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A game variable's declared default must be of its declared type.
+fn validate_vars(registry: &PackRegistry) -> Result<(), PackLoadError> {
+    for var in registry.vars() {
+        if let Some(default) = &var.default {
+            if default.var_type() != var.var_type {
+                return Err(PackLoadError::InvalidVar {
+                    id: var.id.clone(),
+                    message: format!(
+                        "default is {}, but the var is declared {}",
+                        default.var_type(),
+                        var.var_type
+                    ),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 fn load_one_pack(
     registry: &mut PackRegistry,
     pack_dir: &Path,
@@ -227,16 +550,122 @@ fn load_one_pack(
         registry.register_arcs(arcs_file.arc);
     }
 
+    if let Some(ref characters_rel) = manifest.content.characters_file {
+        let characters_path = pack_dir.join(characters_rel);
+        let src = read_file(&characters_path)?;
+        let characters_file: CharactersFile =
+            toml::from_str(&src).map_err(|e| PackLoadError::Toml {
+                path: characters_path.clone(),
+                message: e.to_string(),
+            })?;
+        registry.register_characters(characters_file.character);
+    }
+
+    if let Some(ref routines_rel) = manifest.content.routines_file {
+        let routines_path = pack_dir.join(routines_rel);
+        let src = read_file(&routines_path)?;
+        let routines_file: RoutinesFile =
+            toml::from_str(&src).map_err(|e| PackLoadError::Toml {
+                path: routines_path.clone(),
+                message: e.to_string(),
+            })?;
+        registry.register_routines(routines_file);
+    }
+
+    if let Some(ref body_rel) = manifest.content.body_file {
+        let body_path = pack_dir.join(body_rel);
+        let src = read_file(&body_path)?;
+        let body_file: BodyChangesFile = toml::from_str(&src).map_err(|e| PackLoadError::Toml {
+            path: body_path.clone(),
+            message: e.to_string(),
+        })?;
+        registry.register_body_changes(body_file);
+    }
+
+    if let Some(ref odds_rel) = manifest.content.odds_file {
+        let odds_path = pack_dir.join(odds_rel);
+        let src = read_file(&odds_path)?;
+        let odds_file: OddsFile = toml::from_str(&src).map_err(|e| PackLoadError::Toml {
+            path: odds_path.clone(),
+            message: e.to_string(),
+        })?;
+        registry.register_odds(odds_file);
+    }
+
+    if let Some(ref buffs_rel) = manifest.content.buffs_file {
+        let buffs_path = pack_dir.join(buffs_rel);
+        let src = read_file(&buffs_path)?;
+        let buffs_file: BuffsFile = toml::from_str(&src).map_err(|e| PackLoadError::Toml {
+            path: buffs_path.clone(),
+            message: e.to_string(),
+        })?;
+        registry.register_buffs(buffs_file);
+    }
+
+    if let Some(ref flags_rel) = manifest.content.flags_file {
+        let flags_path = pack_dir.join(flags_rel);
+        let src = read_file(&flags_path)?;
+        let flags_file: FlagsFile = toml::from_str(&src).map_err(|e| PackLoadError::Toml {
+            path: flags_path.clone(),
+            message: e.to_string(),
+        })?;
+        registry.register_flags(flags_file);
+    }
+
     // Load character presets (optional — directory may not exist)
     let presets = crate::preset::load_presets(pack_dir)?;
     registry.register_presets(presets);
 
+    // Load the pack's script helpers (optional — directory may not exist)
+    let scripts = crate::scripts::load_scripts(pack_dir)?;
+    registry.register_scripts(scripts);
+
+    for id in scene_ids(&pack_dir.join(&manifest.content.scenes_dir))? {
+        registry.register_scene_id(id);
+    }
+
     Ok(LoadedPackMeta {
         manifest,
         pack_dir: pack_dir.to_path_buf(),
     })
 }
 
+/// The `[scene] id` of every scene file in `scenes_dir`, so flags can name
+/// scenes before the scene loader runs. Files that don't parse are skipped;
+/// the scene loader reports them.
+fn scene_ids(scenes_dir: &Path) -> Result<Vec<String>, PackLoadError> {
+    #[derive(serde::Deserialize)]
+    struct SceneHeader {
+        scene: SceneId,
+    }
+    #[derive(serde::Deserialize)]
+    struct SceneId {
+        id: String,
+    }
+
+    if !scenes_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(scenes_dir).map_err(|e| PackLoadError::Io {
+        path: scenes_dir.to_path_buf(),
+        source: e,
+    })?;
+    let mut ids = Vec::new();
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+        let src = read_file(&path)?;
+        if let Ok(header) = toml::from_str::<SceneHeader>(&src) {
+            ids.push(header.scene.id);
+        }
+    }
+    Ok(ids)
+}
+
 fn read_file(path: &Path) -> Result<String, PackLoadError> {
     std::fs::read_to_string(path).map_err(|e| PackLoadError::Io {
         path: path.to_path_buf(),
@@ -332,6 +761,13 @@ mod tests {
         assert_eq!(registry.opening_scene(), Some("base::rain_shelter"));
     }
 
+    #[test]
+    fn base_pack_once_flags_name_its_scenes() {
+        let (registry, _) = load_packs(&packs_dir()).unwrap();
+        assert!(registry.is_known_flag("ONCE_base::coffee_shop"));
+        assert!(!registry.is_known_flag("ONCE_base::no_such_scene"));
+    }
+
     #[test]
     fn base_pack_has_transformation_scene() {
         let (registry, _) = load_packs(&packs_dir()).unwrap();
@@ -375,6 +811,7 @@ mod tests {
                 description: "".into(),
                 min: 0,
                 max: 100,
+                progression: None,
             },
             crate::data::SkillDef {
                 id: "COMPOSURE".into(),
@@ -382,6 +819,7 @@ mod tests {
                 description: "".into(),
                 min: 0,
                 max: 100,
+                progression: None,
             },
         ]);
         registry.register_traits(vec![
@@ -392,6 +830,7 @@ mod tests {
                 hidden: true,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             crate::data::TraitDef {
                 id: "ALWAYS_FEMALE".into(),
@@ -400,6 +839,7 @@ mod tests {
                 hidden: true,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             crate::data::TraitDef {
                 id: "NOT_TRANSFORMED".into(),
@@ -408,6 +848,7 @@ mod tests {
                 hidden: true,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             crate::data::TraitDef {
                 id: "NATURALLY_SMOOTH".into(),
@@ -416,6 +857,7 @@ mod tests {
                 hidden: true,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             crate::data::TraitDef {
                 id: "SMOOTH_LEGS".into(),
@@ -424,6 +866,7 @@ mod tests {
                 hidden: true,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
         ]);
 
@@ -434,4 +877,270 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn loads_base_pack_characters() {
+        let (registry, _) = load_packs(&packs_dir()).unwrap();
+        let jake = registry
+            .get_character("base::jake")
+            .expect("base::jake should be declared");
+        assert_eq!(jake.name, "Jake");
+        assert!(jake.roles.contains(&"ROLE_JAKE".to_string()));
+    }
+
+    #[test]
+    fn character_validation_rejects_unknown_npc_trait() {
+        let mut registry = PackRegistry::new();
+        let file: CharactersFile = toml::from_str(
+            r#"
+            [[character]]
+            id          = "test::sam"
+            sex         = "male"
+            name        = "Sam"
+            age         = "Thirties"
+            race        = "White"
+            eye_colour  = "brown"
+            hair_colour = "dark"
+            personality = "FRIEND"
+            traits      = ["NOT_A_TRAIT"]
+            "#,
+        )
+        .unwrap();
+        registry.register_characters(file.character);
+        let result = validate_characters(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidCharacter { .. })),
+            "expected invalid character error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn routine_validation_rejects_unknown_character() {
+        let mut registry = PackRegistry::new();
+        let file: RoutinesFile = toml::from_str(
+            r#"
+            [[outreach]]
+            id          = "test::calls"
+            scene       = "test::phone"
+            after_slots = 8
+            every       = 28
+            character   = "test::nobody"
+            "#,
+        )
+        .unwrap();
+        registry.register_routines(file);
+        let result = validate_routines(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidRoutine { .. })),
+            "expected invalid routine error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn body_change_validation_rejects_values_off_the_scale() {
+        let mut registry = PackRegistry::new();
+        let file: BodyChangesFile = toml::from_str(
+            r#"
+            [[change]]
+            id          = "test::hair"
+            attribute   = "hair_length"
+            every_weeks = 4
+            step        = 1
+            until       = "Waist"
+            message     = "Longer."
+            "#,
+        )
+        .unwrap();
+        registry.register_body_changes(file);
+        let result = validate_body_changes(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidBodyChange { .. })),
+            "expected invalid body change error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn odds_bands_must_start_at_zero() {
+        let mut registry = PackRegistry::new();
+        registry.register_odds(
+            toml::from_str(
+                r#"
+                [[band]]
+                min    = 40
+                phrase = "Maybe."
+                "#,
+            )
+            .unwrap(),
+        );
+        let result = validate_odds_bands(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidOddsBands(_))),
+            "expected invalid odds bands error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn buff_validation_rejects_bad_durations_and_double_grants() {
+        let mut registry = PackRegistry::new();
+        registry.register_skills(vec![crate::data::SkillDef {
+            id: "COMPOSURE".into(),
+            name: "Composure".into(),
+            description: String::new(),
+            min: 0,
+            max: 100,
+            progression: None,
+        }]);
+        registry.register_buffs(
+            toml::from_str(
+                r#"
+                [[buff]]
+                id       = "HUNGOVER"
+                name     = "Hungover"
+                skills   = { COMPOSURE = -10 }
+                duration = "tomorrow"
+                "#,
+            )
+            .unwrap(),
+        );
+        let result = validate_buffs(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidBuff { .. })),
+            "expected invalid buff error, got: {:?}",
+            result
+        );
+
+        registry.register_buffs(
+            toml::from_str(
+                r#"
+                [[buff]]
+                id         = "HUNGOVER"
+                name       = "Hungover"
+                skills     = { COMPOSURE = -10 }
+                granted_by = { stuff = "FLASK", need = "desire", min = 50 }
+                "#,
+            )
+            .unwrap(),
+        );
+        let result = validate_buffs(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidBuff { .. })),
+            "expected invalid buff error, got: {:?}",
+            result
+        );
+
+        registry.register_buffs(
+            toml::from_str(
+                r#"
+                [[buff]]
+                id         = "HUNGOVER"
+                name       = "Hungover"
+                skills     = { COMPOSURE = -10 }
+                granted_by = { stuff = "FLASK" }
+                "#,
+            )
+            .unwrap(),
+        );
+        let result = validate_buffs(&registry);
+        assert!(
+            matches!(&result, Err(PackLoadError::InvalidBuff { message, .. }) if message.contains("FLASK")),
+            "expected unknown stuff error, got: {:?}",
+            result
+        );
+        registry.intern_stuff("FLASK");
+        assert!(validate_buffs(&registry).is_ok());
+    }
+
+    #[test]
+    fn var_defaults_must_match_their_declared_type() {
+        let mut registry = PackRegistry::new();
+        registry.register_flags(
+            toml::from_str(
+                r#"
+                [[var]]
+                id      = "BAR_TENSION"
+                type    = "int"
+                default = 2
+                "#,
+            )
+            .unwrap(),
+        );
+        assert!(validate_vars(&registry).is_ok());
+        assert_eq!(
+            registry.get_var("BAR_TENSION").unwrap().default_value(),
+            undone_domain::VarValue::Int(2)
+        );
+
+        registry.register_flags(
+            toml::from_str(
+                r#"
+                [[var]]
+                id      = "BAR_TENSION"
+                type    = "int"
+                default = "high"
+                "#,
+            )
+            .unwrap(),
+        );
+        let result = validate_vars(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidVar { .. })),
+            "expected invalid var error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn base_pack_declares_its_starting_flags() {
+        let (registry, _) = load_packs(&packs_dir()).unwrap();
+        assert!(registry.declares_flags());
+        assert!(registry.get_flag("ROUTE_WORKPLACE").is_some());
+        assert!(registry.get_flag("ROUTE_CAMPUS").is_some());
+    }
+
+    #[test]
+    fn undeclared_starting_flags_fail_the_load() {
+        let (mut registry, _) = load_packs(&packs_dir()).unwrap();
+        let mut preset = registry.presets()[0].clone();
+        preset.starting_flags = vec!["ROUTE_NOWHERE".into()];
+        registry.register_presets(vec![preset]);
+        let result = validate_starting_flags(&registry);
+        assert!(
+            matches!(
+                result,
+                Err(PackLoadError::UndeclaredStartingFlag { ref flag, .. }) if flag == "ROUTE_NOWHERE"
+            ),
+            "expected undeclared starting flag error, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn skill_progression_must_price_the_skill_minimum() {
+        let mut registry = PackRegistry::new();
+        let file: SkillFile = toml::from_str(
+            r#"
+            [[skill]]
+            id          = "DANCE"
+            name        = "Dance"
+            description = ""
+            min         = 0
+            max         = 100
+
+            [skill.progression]
+            tiers = [{ from = 10, xp = 10 }]
+            "#,
+        )
+        .unwrap();
+        registry.register_skills(file.skill);
+        let result = validate_skill_progression(&registry);
+        assert!(
+            matches!(result, Err(PackLoadError::InvalidSkill { .. })),
+            "expected invalid skill error, got: {:?}",
+            result
+        );
+    }
 }
@@ -38,6 +38,21 @@ pub struct PackContent {
     pub categories_file: Option<String>,
     #[serde(default)]
     pub arcs_file: Option<String>,
+    #[serde(default)]
+    pub characters_file: Option<String>,
+    #[serde(default)]
+    pub routines_file: Option<String>,
+    #[serde(default)]
+    pub body_file: Option<String>,
+    #[serde(default)]
+    pub odds_file: Option<String>,
+    #[serde(default)]
+    pub buffs_file: Option<String>,
+    #[serde(default)]
+    pub flags_file: Option<String>,
+    /// Prose-lint rules validate-pack runs over every scene's prose.
+    #[serde(default)]
+    pub prose_lint_file: Option<String>,
 }
 
 #[cfg(test)]
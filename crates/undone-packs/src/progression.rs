@@ -0,0 +1,163 @@
+//! Skill progression: `w.practice` banks XP that pack progression curves
+//! (`[skill.progression]` in `skills.toml`) convert into points, and unused
+//! skills decay at week rollover.
+
+use undone_domain::{SkillId, SkillValue};
+use undone_world::{World, SLOTS_PER_WEEK};
+
+use crate::data::SkillDef;
+use crate::PackRegistry;
+
+/// XP per point for skills that declare no progression curve.
+pub const DEFAULT_XP_PER_POINT: u32 = 10;
+
+/// XP the next point costs while the skill sits at `value`: the highest tier
+/// `value` has reached, doubled at or past the soft cap.
+pub fn point_cost(def: &SkillDef, value: i32) -> u32 {
+    let Some(progression) = &def.progression else {
+        return DEFAULT_XP_PER_POINT;
+    };
+    let base = progression
+        .tiers
+        .iter()
+        .filter(|tier| value >= tier.from)
+        .max_by_key(|tier| tier.from)
+        .map_or(DEFAULT_XP_PER_POINT, |tier| tier.xp);
+    if progression.soft_cap.is_some_and(|cap| value >= cap) {
+        base * 2
+    } else {
+        base
+    }
+}
+
+/// Bank `xp` toward `skill` and spend it on as many points as the curve
+/// allows, up to the skill's max. Restarts the skill's decay clock. Returns the
+/// points gained.
+pub fn practice(world: &mut World, registry: &PackRegistry, skill: SkillId, xp: u32) -> i32 {
+    let Some(def) = registry.get_skill_def(&skill) else {
+        return 0;
+    };
+    let now = world.game_data.absolute_slot();
+    let player = &mut world.player;
+    let entry = player.practice.entry(skill).or_default();
+    entry.xp = entry.xp.saturating_add(xp);
+    entry.last_practiced = now;
+    let value = player.skills.entry(skill).or_insert(SkillValue {
+        value: def.min,
+        modifier: 0,
+    });
+
+    let mut gained = 0;
+    while value.value < def.max {
+        let cost = point_cost(def, value.value);
+        if entry.xp < cost {
+            break;
+        }
+        entry.xp -= cost;
+        value.value += 1;
+        gained += 1;
+    }
+    if value.value >= def.max {
+        entry.xp = 0;
+    }
+    gained
+}
+
+/// Apply decay to every skill that has gone unpractised for its curve's
+/// `after_weeks` (counted from game start if never practised). Runs at each
+/// week rollover via `advance_time_slot`.
+pub fn run_skill_decay(world: &mut World, registry: &PackRegistry) {
+    let now = world.game_data.absolute_slot();
+    let player = &mut world.player;
+    for (skill, value) in player.skills.iter_mut() {
+        let Some(decay) = registry
+            .get_skill_def(skill)
+            .and_then(|def| def.progression.as_ref())
+            .and_then(|progression| progression.decay.as_ref())
+        else {
+            continue;
+        };
+        let last = player
+            .practice
+            .get(skill)
+            .map_or(0, |practice| practice.last_practiced);
+        let idle_weeks = now.saturating_sub(last) / SLOTS_PER_WEEK;
+        if idle_weeks < decay.after_weeks || value.value <= decay.floor {
+            continue;
+        }
+        value.value = (value.value - decay.amount).max(decay.floor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::SkillFile;
+    use undone_world::test_helpers::make_test_world;
+
+    fn registry() -> PackRegistry {
+        let mut registry = PackRegistry::new();
+        let file: SkillFile = toml::from_str(
+            r#"
+            [[skill]]
+            id          = "DANCE"
+            name        = "Dance"
+            description = ""
+            min         = 0
+            max         = 100
+
+            [skill.progression]
+            tiers    = [{ from = 0, xp = 10 }, { from = 20, xp = 20 }]
+            soft_cap = 22
+            decay    = { after_weeks = 2, amount = 1, floor = 19 }
+            "#,
+        )
+        .unwrap();
+        registry.register_skills(file.skill);
+        registry
+    }
+
+    #[test]
+    fn practice_gains_diminish_by_tier_and_past_the_soft_cap() {
+        let registry = registry();
+        let dance = registry.resolve_skill("DANCE").unwrap();
+        let mut world = make_test_world();
+        world.player.skills.insert(
+            dance,
+            SkillValue {
+                value: 18,
+                modifier: 0,
+            },
+        );
+
+        // 18→19→20 at 10 each, 20→21→22 at 20 each, then 40 past the cap.
+        assert_eq!(practice(&mut world, &registry, dance, 60), 4);
+        assert_eq!(world.player.skill(dance), 22);
+        assert_eq!(practice(&mut world, &registry, dance, 39), 0);
+        assert_eq!(practice(&mut world, &registry, dance, 1), 1);
+        assert_eq!(world.player.practice[&dance].xp, 0);
+    }
+
+    #[test]
+    fn unpractised_skills_decay_to_their_floor() {
+        let registry = registry();
+        let dance = registry.resolve_skill("DANCE").unwrap();
+        let mut world = make_test_world();
+        world.player.skills.insert(
+            dance,
+            SkillValue {
+                value: 20,
+                modifier: 0,
+            },
+        );
+        practice(&mut world, &registry, dance, 0);
+
+        let mut weekly = Vec::new();
+        for _ in 0..4 * SLOTS_PER_WEEK {
+            if crate::advance_time_slot(&mut world, &registry) {
+                weekly.push(world.player.skill(dance));
+            }
+        }
+        assert_eq!(weekly, vec![20, 19, 19, 19]);
+    }
+}
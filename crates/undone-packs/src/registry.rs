@@ -4,8 +4,16 @@ use lasso::{Key, Rodeo, Spur};
 use thiserror::Error;
 use undone_domain::{NpcTraitId, PersonalityId, Player, SkillId, StatId, StuffId, TraitId};
 
-use crate::data::{ArcDef, CategoryDef, NpcTraitDef, SkillDef, StatDef, TraitDef};
+use crate::data::{
+    ArcDef, BodyChangeRule, BodyChangesFile, BuffDef, BuffsFile, CategoryDef, CharacterDef,
+    DriftRule, FlagDef, FlagsFile, NpcTraitDef, OddsBand, OddsFile, OutreachRule, RoutinesFile,
+    SkillDef, StatDef, TraitDef, VarDef,
+};
 use crate::preset::PresetData;
+use crate::scripts::ScriptFile;
+
+/// Prefix of the flags the scheduler sets when a `once_only` event fires.
+pub const ONCE_FLAG_PREFIX: &str = "ONCE_";
 
 #[derive(Debug, Error)]
 pub enum RegistryError {
@@ -32,10 +40,20 @@ pub struct PackRegistry {
     races: Vec<String>,
     categories: HashMap<String, CategoryDef>,
     arcs: HashMap<String, ArcDef>,
+    characters: Vec<CharacterDef>,
+    drift_rules: Vec<DriftRule>,
+    outreach_rules: Vec<OutreachRule>,
+    body_change_rules: Vec<BodyChangeRule>,
+    odds_bands: Vec<OddsBand>,
+    buffs: HashMap<String, BuffDef>,
+    flags: HashMap<String, FlagDef>,
+    vars: HashMap<String, VarDef>,
     registered_stats: HashSet<StatId>,
+    scene_ids: HashSet<String>,
     opening_scene: Option<String>,
     transformation_scene: Option<String>,
     presets: Vec<PresetData>,
+    scripts: Vec<ScriptFile>,
 }
 
 impl PackRegistry {
@@ -61,10 +79,20 @@ impl PackRegistry {
             races: Vec::new(),
             categories: HashMap::new(),
             arcs: HashMap::new(),
+            characters: Vec::new(),
+            drift_rules: Vec::new(),
+            outreach_rules: Vec::new(),
+            body_change_rules: Vec::new(),
+            odds_bands: Vec::new(),
+            buffs: HashMap::new(),
+            flags: HashMap::new(),
+            vars: HashMap::new(),
             registered_stats: HashSet::new(),
+            scene_ids: HashSet::new(),
             opening_scene: None,
             transformation_scene: None,
             presets: Vec::new(),
+            scripts: Vec::new(),
         }
     }
 
@@ -132,6 +160,13 @@ impl PackRegistry {
             .unwrap_or(false)
     }
 
+    /// Ids of every stat declared in a pack's stats file, in no particular order.
+    pub fn registered_stats(&self) -> impl Iterator<Item = &str> {
+        self.registered_stats
+            .iter()
+            .map(|id| self.rodeo.resolve(&id.inner()))
+    }
+
     /// Resolve a string to a TraitId. Errors if the id is unknown.
     /// Call this at scene load time to validate condition expressions.
     pub fn resolve_trait(&self, id: &str) -> Result<TraitId, RegistryError> {
@@ -155,9 +190,27 @@ impl PackRegistry {
             .ok_or_else(|| RegistryError::UnknownNpcTrait(id.to_string()))
     }
 
-    /// Return all registered NPC trait IDs.
+    /// Return all registered NPC trait IDs, in registration order so seeded
+    /// NPC spawning picks the same traits every run.
     pub fn npc_trait_ids(&self) -> Vec<NpcTraitId> {
-        self.npc_trait_defs.keys().copied().collect()
+        let mut ids: Vec<NpcTraitId> = self.npc_trait_defs.keys().copied().collect();
+        ids.sort_by_key(|id| id.inner());
+        ids
+    }
+
+    /// Look up a TraitDef by TraitId.
+    pub fn get_trait_def(&self, id: TraitId) -> Option<&TraitDef> {
+        self.trait_defs.get(&id)
+    }
+
+    /// All trait definitions, in no particular order.
+    pub fn trait_defs(&self) -> impl Iterator<Item = &TraitDef> {
+        self.trait_defs.values()
+    }
+
+    /// All NPC trait definitions, in no particular order.
+    pub fn npc_trait_defs(&self) -> impl Iterator<Item = &NpcTraitDef> {
+        self.npc_trait_defs.values()
     }
 
     /// Look up a SkillDef by SkillId.
@@ -165,6 +218,11 @@ impl PackRegistry {
         self.skill_defs.get(id)
     }
 
+    /// All skill definitions, in no particular order.
+    pub fn skill_defs(&self) -> impl Iterator<Item = &SkillDef> {
+        self.skill_defs.values()
+    }
+
     /// Resolve a string to a SkillId.
     pub fn resolve_skill(&self, id: &str) -> Result<SkillId, RegistryError> {
         self.rodeo
@@ -220,6 +278,16 @@ impl PackRegistry {
             || player.has_trait(self.smooth_legs_trait()?))
     }
 
+    /// Sum of `composure_penalty` over the traits the player has.
+    pub fn composure_penalty(&self, player: &Player) -> i32 {
+        player
+            .traits
+            .iter()
+            .filter_map(|id| self.trait_defs.get(id))
+            .map(|def| def.composure_penalty)
+            .sum()
+    }
+
     /// Intern a stat name (stat names don't need definitions, just interning).
     pub fn intern_stat(&mut self, id: &str) -> StatId {
         StatId::from_spur(self.intern(id))
@@ -326,6 +394,11 @@ impl PackRegistry {
         self.categories.get(id)
     }
 
+    /// All category definitions, in no particular order.
+    pub fn categories(&self) -> impl Iterator<Item = &CategoryDef> {
+        self.categories.values()
+    }
+
     /// Register arc definitions from a pack data file.
     pub fn register_arcs(&mut self, arcs: Vec<ArcDef>) {
         for arc in arcs {
@@ -338,6 +411,142 @@ impl PackRegistry {
         self.arcs.get(id)
     }
 
+    /// All arc definitions, in no particular order.
+    pub fn arcs(&self) -> impl Iterator<Item = &ArcDef> {
+        self.arcs.values()
+    }
+
+    /// Register story character definitions from a pack data file. Declaration
+    /// order is kept so `new_game` instantiates them deterministically.
+    pub fn register_characters(&mut self, defs: Vec<CharacterDef>) {
+        self.characters.extend(defs);
+    }
+
+    /// Look up a story character definition by its stable id.
+    pub fn get_character(&self, id: &str) -> Option<&CharacterDef> {
+        self.characters.iter().find(|c| c.id == id)
+    }
+
+    /// All declared story characters, in pack load + declaration order.
+    pub fn characters(&self) -> &[CharacterDef] {
+        &self.characters
+    }
+
+    /// Register NPC routine rules from a pack data file. Rules from every pack
+    /// apply together, in pack load + declaration order.
+    pub fn register_routines(&mut self, file: RoutinesFile) {
+        self.drift_rules.extend(file.drift);
+        self.outreach_rules.extend(file.outreach);
+    }
+
+    pub fn drift_rules(&self) -> &[DriftRule] {
+        &self.drift_rules
+    }
+
+    pub fn outreach_rules(&self) -> &[OutreachRule] {
+        &self.outreach_rules
+    }
+
+    /// Register player body-change rules from a pack data file. Rules from
+    /// every pack apply together, in pack load + declaration order.
+    pub fn register_body_changes(&mut self, file: BodyChangesFile) {
+        self.body_change_rules.extend(file.change);
+    }
+
+    pub fn body_change_rules(&self) -> &[BodyChangeRule] {
+        &self.body_change_rules
+    }
+
+    /// Register check-odds phrasing. A later pack's bands replace earlier ones
+    /// wholesale, so a pack can re-voice the choices bar.
+    pub fn register_odds(&mut self, file: OddsFile) {
+        let mut bands = file.band;
+        bands.sort_by_key(|band| std::cmp::Reverse(band.min));
+        self.odds_bands = bands;
+    }
+
+    /// Bands, highest `min` first.
+    pub fn odds_bands(&self) -> &[OddsBand] {
+        &self.odds_bands
+    }
+
+    /// The phrase for a pass chance of `percent`, if any pack declares bands.
+    pub fn odds_phrase(&self, percent: i32) -> Option<&str> {
+        self.odds_bands
+            .iter()
+            .find(|band| percent >= band.min)
+            .map(|band| band.phrase.as_str())
+    }
+
+    /// Register buff definitions. A later pack may redefine an earlier pack's
+    /// buff by reusing its id.
+    pub fn register_buffs(&mut self, file: BuffsFile) {
+        for def in file.buff {
+            self.buffs.insert(def.id.clone(), def);
+        }
+    }
+
+    pub fn get_buff(&self, id: &str) -> Option<&BuffDef> {
+        self.buffs.get(id)
+    }
+
+    /// All buff definitions, in no particular order.
+    pub fn buffs(&self) -> impl Iterator<Item = &BuffDef> {
+        self.buffs.values()
+    }
+
+    /// Register a pack's game flag and variable schema. A later pack may
+    /// redefine an earlier pack's entry by reusing its id.
+    pub fn register_flags(&mut self, file: FlagsFile) {
+        for def in file.flag {
+            self.flags.insert(def.id.clone(), def);
+        }
+        for def in file.var {
+            self.vars.insert(def.id.clone(), def);
+        }
+    }
+
+    pub fn get_flag(&self, id: &str) -> Option<&FlagDef> {
+        self.flags.get(id)
+    }
+
+    /// All declared game flags, in no particular order.
+    pub fn flags(&self) -> impl Iterator<Item = &FlagDef> {
+        self.flags.values()
+    }
+
+    /// Whether any pack declares its game flags. Until one does, flag names
+    /// are free-form.
+    pub fn declares_flags(&self) -> bool {
+        !self.flags.is_empty()
+    }
+
+    /// Whether scripts may use game flag `id`: it is declared, flags are
+    /// free-form, or it is the scheduler's `ONCE_<scene>` marker for a scene
+    /// some pack ships.
+    pub fn is_known_flag(&self, id: &str) -> bool {
+        !self.declares_flags()
+            || self.flags.contains_key(id)
+            || id
+                .strip_prefix(ONCE_FLAG_PREFIX)
+                .is_some_and(|scene| self.scene_ids.contains(scene))
+    }
+
+    /// Record the id of a scene a pack ships, ahead of the scene loader
+    /// compiling it.
+    pub fn register_scene_id(&mut self, id: String) {
+        self.scene_ids.insert(id);
+    }
+
+    pub fn get_var(&self, id: &str) -> Option<&VarDef> {
+        self.vars.get(id)
+    }
+
+    /// All game variable schemas, in no particular order.
+    pub fn vars(&self) -> impl Iterator<Item = &VarDef> {
+        self.vars.values()
+    }
+
     /// Set the opening scene ID for the first pack that declares one.
     /// Subsequent packs cannot override it (first-writer wins).
     pub fn set_opening_scene(&mut self, id: String) {
@@ -374,6 +583,15 @@ impl PackRegistry {
         &self.presets
     }
 
+    pub fn register_scripts(&mut self, scripts: Vec<ScriptFile>) {
+        self.scripts.extend(scripts);
+    }
+
+    /// Return the packs' `scripts/*.rhai` helper libraries, in load order.
+    pub fn scripts(&self) -> &[ScriptFile] {
+        &self.scripts
+    }
+
     /// Return all interned strings in Spur-index order (index 0 first).
     /// The save system records these so it can detect if the pack load order changed
     /// between saving and loading.
@@ -463,6 +681,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             TraitDef {
                 id: "POSH".into(),
@@ -471,6 +690,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
         ]);
         reg
@@ -533,6 +753,28 @@ mod tests {
         assert_eq!(reg.female_names(), &["Emma"]);
     }
 
+    #[test]
+    fn flags_are_free_form_until_a_pack_declares_them() {
+        let mut reg = PackRegistry::new();
+        assert!(reg.is_known_flag("ANYTHING"));
+        reg.register_flags(
+            toml::from_str(
+                r#"
+                [[flag]]
+                id          = "MET_JAKE"
+                description = "She met Jake."
+                "#,
+            )
+            .unwrap(),
+        );
+        assert!(reg.is_known_flag("MET_JAKE"));
+        assert!(!reg.is_known_flag("MET_JACK"));
+        assert!(!reg.is_known_flag("ONCE_base::coffee_shop"));
+        reg.register_scene_id("base::coffee_shop".into());
+        assert!(reg.is_known_flag("ONCE_base::coffee_shop"));
+        assert!(!reg.is_known_flag("ONCE_base::cofee_shop"));
+    }
+
     #[test]
     fn validate_trait_conflicts_no_errors_when_all_valid() {
         let mut reg = PackRegistry::new();
@@ -544,6 +786,7 @@ mod tests {
                 hidden: false,
                 group: Some("personality".into()),
                 conflicts: vec!["OUTGOING".into()],
+                composure_penalty: 0,
             },
             TraitDef {
                 id: "OUTGOING".into(),
@@ -552,6 +795,7 @@ mod tests {
                 hidden: false,
                 group: Some("personality".into()),
                 conflicts: vec!["SHY".into()],
+                composure_penalty: 0,
             },
         ]);
         let errors = reg.validate_trait_conflicts();
@@ -568,6 +812,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec!["NONEXISTENT".into()],
+            composure_penalty: 0,
         }]);
         let errors = reg.validate_trait_conflicts();
         assert_eq!(errors.len(), 1);
@@ -585,6 +830,7 @@ mod tests {
                 hidden: false,
                 group: Some("personality".into()),
                 conflicts: vec!["OUTGOING".into()],
+                composure_penalty: 0,
             },
             TraitDef {
                 id: "OUTGOING".into(),
@@ -593,6 +839,7 @@ mod tests {
                 hidden: false,
                 group: Some("personality".into()),
                 conflicts: vec!["SHY".into()],
+                composure_penalty: 0,
             },
         ]);
         let shy_id = reg.resolve_trait("SHY").unwrap();
@@ -615,6 +862,7 @@ mod tests {
                 hidden: false,
                 group: Some("personality".into()),
                 conflicts: vec!["OUTGOING".into()],
+                composure_penalty: 0,
             },
             TraitDef {
                 id: "POSH".into(),
@@ -623,6 +871,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             TraitDef {
                 id: "OUTGOING".into(),
@@ -631,6 +880,7 @@ mod tests {
                 hidden: false,
                 group: Some("personality".into()),
                 conflicts: vec!["SHY".into()],
+                composure_penalty: 0,
             },
         ]);
         let shy_id = reg.resolve_trait("SHY").unwrap();
@@ -666,6 +916,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             TraitDef {
                 id: "SMOOTH_LEGS".into(),
@@ -674,6 +925,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
         ]);
         let naturally_smooth = reg.naturally_smooth_trait().unwrap();
@@ -0,0 +1,289 @@
+//! Off-screen NPC routines: relationship drift and outreach, evaluated each time
+//! a time slot passes. Rules are pack data (`routines.toml`); this module only
+//! interprets them.
+
+use undone_domain::{LikingLevel, LoveLevel, NpcCore, NpcKey};
+use undone_world::{NpcOutreach, World};
+
+use crate::body::run_body_changes;
+use crate::buffs::refresh_buffs;
+use crate::data::{DriftRule, DriftStat, DriftWhen, OutreachRule, RoutineFilter};
+use crate::progression::run_skill_decay;
+use crate::PackRegistry;
+
+/// Advance the world one time slot, run NPC routines for the new slot and
+/// refresh buffs, plus body-change rules and skill decay when the week rolls
+/// over. Returns true if it did. Runtime code should advance time through this
+/// rather than `GameData::advance_time_slot` directly.
+pub fn advance_time_slot(world: &mut World, registry: &PackRegistry) -> bool {
+    let rolled_over = world.game_data.advance_time_slot();
+    run_npc_routines(world, registry);
+    if rolled_over {
+        run_body_changes(world, registry);
+        run_skill_decay(world, registry);
+    }
+    refresh_buffs(world, registry);
+    rolled_over
+}
+
+/// Apply every drift rule and queue every due outreach for the current slot.
+/// Only NPCs the player has interacted with (`agenda.last_contact` set) are
+/// considered — strangers have no relationship to drift.
+pub fn run_npc_routines(world: &mut World, registry: &PackRegistry) {
+    let now = world.game_data.absolute_slot();
+    let World {
+        male_npcs,
+        female_npcs,
+        game_data,
+        ..
+    } = world;
+
+    let npcs = male_npcs
+        .iter_mut()
+        .map(|(key, npc)| (NpcKey::Male(key), &mut npc.core))
+        .chain(
+            female_npcs
+                .iter_mut()
+                .map(|(key, npc)| (NpcKey::Female(key), &mut npc.core)),
+        );
+    for (key, core) in npcs {
+        let Some(last_contact) = core.agenda.last_contact else {
+            continue;
+        };
+        let since_contact = now.saturating_sub(last_contact);
+
+        for rule in registry.drift_rules() {
+            if drift_applies(rule, since_contact)
+                && matches_filter(&rule.filter, core, registry)
+                && rule_due(core, &rule.id, rule.every, now)
+            {
+                apply_drift(core, rule);
+                core.agenda.rules_fired.insert(rule.id.clone(), now);
+            }
+        }
+
+        for rule in registry.outreach_rules() {
+            if outreach_applies(rule, core, since_contact)
+                && matches_filter(&rule.filter, core, registry)
+                && rule_due(core, &rule.id, rule.every, now)
+            {
+                game_data.queue_outreach(NpcOutreach {
+                    rule: rule.id.clone(),
+                    scene: rule.scene.clone(),
+                    npc: key,
+                    queued_at: now,
+                });
+                core.agenda.rules_fired.insert(rule.id.clone(), now);
+            }
+        }
+    }
+}
+
+fn drift_applies(rule: &DriftRule, since_contact: u32) -> bool {
+    match rule.when {
+        DriftWhen::Neglected => since_contact >= rule.slots,
+        DriftWhen::Attended => since_contact < rule.slots,
+    }
+}
+
+fn outreach_applies(rule: &OutreachRule, core: &NpcCore, since_contact: u32) -> bool {
+    core.contactable
+        && since_contact >= rule.after_slots
+        && rule.min_npc_liking.is_none_or(|min| core.npc_liking >= min)
+}
+
+fn matches_filter(filter: &RoutineFilter, core: &NpcCore, registry: &PackRegistry) -> bool {
+    filter
+        .personality
+        .as_deref()
+        .is_none_or(|p| registry.personality_name(core.personality) == p)
+        && filter.npc_trait.as_deref().is_none_or(|t| {
+            registry
+                .resolve_npc_trait(t)
+                .is_ok_and(|id| core.has_trait(id))
+        })
+        && filter
+            .character
+            .as_deref()
+            .is_none_or(|c| core.story_id.as_deref() == Some(c))
+        && filter
+            .role
+            .as_deref()
+            .is_none_or(|r| core.roles.contains(r))
+}
+
+fn rule_due(core: &NpcCore, rule_id: &str, every: u32, now: u32) -> bool {
+    core.agenda
+        .rules_fired
+        .get(rule_id)
+        .is_none_or(|&last| now.saturating_sub(last) >= every)
+}
+
+fn apply_drift(core: &mut NpcCore, rule: &DriftRule) {
+    match rule.stat {
+        DriftStat::NpcLiking => core.npc_liking = step_liking(core.npc_liking, rule.delta),
+        DriftStat::NpcLove => core.npc_love = step_love(core.npc_love, rule.delta),
+    }
+}
+
+fn step_liking(current: LikingLevel, delta: i8) -> LikingLevel {
+    const LEVELS: [LikingLevel; 4] = [
+        LikingLevel::Neutral,
+        LikingLevel::Ok,
+        LikingLevel::Like,
+        LikingLevel::Close,
+    ];
+    let idx = LEVELS.iter().position(|&l| l == current).unwrap_or(0) as i32;
+    LEVELS[(idx + delta as i32).clamp(0, 3) as usize]
+}
+
+fn step_love(current: LoveLevel, delta: i8) -> LoveLevel {
+    const LEVELS: [LoveLevel; 5] = [
+        LoveLevel::None,
+        LoveLevel::Some,
+        LoveLevel::Confused,
+        LoveLevel::Crush,
+        LoveLevel::Love,
+    ];
+    let idx = LEVELS.iter().position(|&l| l == current).unwrap_or(0) as i32;
+    LEVELS[(idx + delta as i32).clamp(0, 4) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{CharactersFile, NpcTraitDef, RoutinesFile};
+    use crate::spawn_story_npcs;
+    use undone_domain::MaleNpcKey;
+    use undone_world::test_helpers::make_test_world;
+
+    fn registry_with(routines: &str) -> PackRegistry {
+        let mut registry = PackRegistry::new();
+        registry.register_routines(toml::from_str::<RoutinesFile>(routines).unwrap());
+        registry
+    }
+
+    /// A world holding one story NPC the player met at slot 0.
+    fn world_with_sam(registry: &mut PackRegistry) -> (World, MaleNpcKey) {
+        let file: CharactersFile = toml::from_str(
+            r#"
+            [[character]]
+            id          = "test::sam"
+            sex         = "male"
+            name        = "Sam"
+            age         = "Thirties"
+            race        = "White"
+            eye_colour  = "brown"
+            hair_colour = "dark"
+            personality = "ROMANTIC"
+            contactable = true
+            start       = { npc_liking = "Like" }
+            "#,
+        )
+        .unwrap();
+        registry.register_characters(file.character);
+        let mut world = make_test_world();
+        spawn_story_npcs(registry, &mut world.male_npcs, &mut world.female_npcs);
+        let key = world.male_npcs.keys().next().unwrap();
+        world.male_npcs[key].core.agenda.last_contact = Some(0);
+        (world, key)
+    }
+
+    #[test]
+    fn neglect_cools_liking_once_per_interval() {
+        let mut registry = registry_with(
+            r#"
+            [[drift]]
+            id    = "test::neglect"
+            when  = "neglected"
+            slots = 4
+            every = 4
+            stat  = "npc_liking"
+            delta = -1
+            "#,
+        );
+        let (mut world, key) = world_with_sam(&mut registry);
+
+        for _ in 0..3 {
+            advance_time_slot(&mut world, &registry);
+        }
+        assert_eq!(world.male_npcs[key].core.npc_liking, LikingLevel::Like);
+
+        advance_time_slot(&mut world, &registry);
+        assert_eq!(world.male_npcs[key].core.npc_liking, LikingLevel::Ok);
+
+        for _ in 0..3 {
+            advance_time_slot(&mut world, &registry);
+        }
+        assert_eq!(world.male_npcs[key].core.npc_liking, LikingLevel::Ok);
+        advance_time_slot(&mut world, &registry);
+        assert_eq!(world.male_npcs[key].core.npc_liking, LikingLevel::Neutral);
+    }
+
+    #[test]
+    fn uncontacted_npcs_do_not_drift() {
+        let mut registry = registry_with(
+            r#"
+            [[drift]]
+            id    = "test::neglect"
+            when  = "neglected"
+            slots = 1
+            every = 1
+            stat  = "npc_liking"
+            delta = -1
+            "#,
+        );
+        let (mut world, key) = world_with_sam(&mut registry);
+        world.male_npcs[key].core.agenda.last_contact = None;
+        for _ in 0..8 {
+            advance_time_slot(&mut world, &registry);
+        }
+        assert_eq!(world.male_npcs[key].core.npc_liking, LikingLevel::Like);
+    }
+
+    #[test]
+    fn outreach_queues_once_and_respects_filters() {
+        let mut registry = registry_with(
+            r#"
+            [[outreach]]
+            id             = "test::texts"
+            scene          = "test::phone"
+            after_slots    = 3
+            every          = 28
+            min_npc_liking = "Like"
+            personality    = "ROMANTIC"
+
+            [[outreach]]
+            id          = "test::never"
+            scene       = "test::never"
+            after_slots = 0
+            every       = 1
+            npc_trait   = "CLINGY"
+            "#,
+        );
+        registry.register_npc_traits(vec![NpcTraitDef {
+            id: "CLINGY".into(),
+            name: "Clingy".into(),
+            description: String::new(),
+            hidden: false,
+        }]);
+        let (mut world, key) = world_with_sam(&mut registry);
+
+        advance_time_slot(&mut world, &registry);
+        advance_time_slot(&mut world, &registry);
+        assert!(world.game_data.npc_outreach.is_empty());
+
+        for _ in 0..4 {
+            advance_time_slot(&mut world, &registry);
+        }
+        assert_eq!(
+            world.game_data.npc_outreach,
+            vec![NpcOutreach {
+                rule: "test::texts".into(),
+                scene: "test::phone".into(),
+                npc: NpcKey::Male(key),
+                queued_at: 3,
+            }]
+        );
+    }
+}
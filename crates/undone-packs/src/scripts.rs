@@ -0,0 +1,45 @@
+//! Pack script libraries: the `scripts/*.rhai` helper functions a pack shares
+//! across its conditions, effects and schedule gates. Loaded as source here;
+//! `undone-scene` compiles and validates them against the script API.
+
+use std::path::{Path, PathBuf};
+
+use crate::loader::PackLoadError;
+
+/// One `scripts/*.rhai` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptFile {
+    pub path: PathBuf,
+    pub source: String,
+}
+
+/// Read every `.rhai` file in the pack's `scripts/` directory, sorted by file
+/// name. The directory is optional.
+pub fn load_scripts(pack_dir: &Path) -> Result<Vec<ScriptFile>, PackLoadError> {
+    let scripts_dir = pack_dir.join("scripts");
+    if !scripts_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&scripts_dir)
+        .map_err(|e| PackLoadError::Io {
+            path: scripts_dir.clone(),
+            source: e,
+        })?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let source = std::fs::read_to_string(&path).map_err(|e| PackLoadError::Io {
+                path: path.clone(),
+                source: e,
+            })?;
+            Ok(ScriptFile { path, source })
+        })
+        .collect()
+}
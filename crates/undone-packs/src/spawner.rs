@@ -5,9 +5,12 @@ use std::collections::{HashMap, HashSet};
 use undone_domain::{
     Age, AlcoholLevel, ArousalLevel, AttractionLevel, Behaviour, BreastSize, CharTypeId,
     FemaleClothing, FemaleNpc, FemaleNpcKey, LikingLevel, LoveLevel, MaleClothing, MaleFigure,
-    MaleNpc, MaleNpcKey, NpcCore, NpcTraitId, PersonalityId, PlayerFigure, RelationshipStatus,
+    MaleNpc, MaleNpcKey, NpcCore, NpcKey, NpcRelationKind, NpcTraitId, PersonalityId, PlayerFigure,
+    RelationshipStatus,
 };
+use undone_world::NpcRelations;
 
+use crate::data::{CharacterDef, CharacterSex};
 use crate::PackRegistry;
 
 const MALE_FIGURES: &[MaleFigure] = &[
@@ -182,6 +185,167 @@ pub fn spawn_npcs<R: Rng>(
     (males, females)
 }
 
+/// Instantiate every declared story character (`characters.toml`) into the NPC
+/// maps. Story NPCs are appended after the random pool so the pool's slot order,
+/// which scenes without an `npc_role` fall back on, is unchanged.
+///
+/// Trait ids are validated at pack load; an unresolvable one is skipped here.
+pub fn spawn_story_npcs(
+    registry: &mut PackRegistry,
+    males: &mut SlotMap<MaleNpcKey, MaleNpc>,
+    females: &mut SlotMap<FemaleNpcKey, FemaleNpc>,
+) {
+    let defs = registry.characters().to_vec();
+    for def in &defs {
+        spawn_story_npc(registry, def, males, females);
+    }
+}
+
+/// Give a save written before a story character existed that character. An
+/// NPC of the same sex already holding one of its roles (the save's Jake, say)
+/// is adopted as it; otherwise the character is spawned as at new game. New
+/// games spawn every story character and never need this.
+pub fn backfill_story_npcs(
+    registry: &mut PackRegistry,
+    males: &mut SlotMap<MaleNpcKey, MaleNpc>,
+    females: &mut SlotMap<FemaleNpcKey, FemaleNpc>,
+) {
+    let defs = registry.characters().to_vec();
+    for def in &defs {
+        let has_story_id = |core: &NpcCore| core.story_id.as_deref() == Some(def.id.as_str());
+        if males.values().any(|npc| has_story_id(&npc.core))
+            || females.values().any(|npc| has_story_id(&npc.core))
+        {
+            continue;
+        }
+        let role_holder = |core: &NpcCore| {
+            core.story_id.is_none() && def.roles.iter().any(|role| core.roles.contains(role))
+        };
+        let adopted = match def.sex {
+            CharacterSex::Male => males
+                .values_mut()
+                .map(|npc| &mut npc.core)
+                .find(|core| role_holder(core)),
+            CharacterSex::Female => females
+                .values_mut()
+                .map(|npc| &mut npc.core)
+                .find(|core| role_holder(core)),
+        };
+        match adopted {
+            Some(core) => core.story_id = Some(def.id.clone()),
+            None => spawn_story_npc(registry, def, males, females),
+        }
+    }
+}
+
+fn spawn_story_npc(
+    registry: &mut PackRegistry,
+    def: &CharacterDef,
+    males: &mut SlotMap<MaleNpcKey, MaleNpc>,
+    females: &mut SlotMap<FemaleNpcKey, FemaleNpc>,
+) {
+    let personality = registry.intern_personality(&def.personality);
+    let traits: HashSet<NpcTraitId> = def
+        .traits
+        .iter()
+        .filter_map(|t| registry.resolve_npc_trait(t).ok())
+        .collect();
+    let mut core = make_core(
+        def.name.clone(),
+        def.age,
+        def.race.clone(),
+        def.eye_colour.clone(),
+        def.hair_colour.clone(),
+        personality,
+        traits,
+    );
+    core.display_name = Some(def.name.clone());
+    core.story_id = Some(def.id.clone());
+    core.contactable = def.contactable;
+    core.roles = def.roles.iter().cloned().collect();
+    core.relationship = def.start.relationship.clone();
+    core.pc_liking = def.start.pc_liking;
+    core.npc_liking = def.start.npc_liking;
+    core.pc_love = def.start.pc_love;
+    core.npc_love = def.start.npc_love;
+    core.pc_attraction = def.start.pc_attraction;
+    core.npc_attraction = def.start.npc_attraction;
+    core.behaviour = def.start.behaviour;
+
+    match def.sex {
+        CharacterSex::Male => {
+            males.insert(MaleNpc {
+                core,
+                figure: def.figure.unwrap_or(MaleFigure::Average),
+                clothing: MaleClothing::default(),
+                had_orgasm: false,
+                has_baby_with_pc: false,
+            });
+        }
+        CharacterSex::Female => {
+            let char_type = CharTypeId::from_spur(personality.inner());
+            females.insert(FemaleNpc {
+                core,
+                char_type,
+                figure: def.female_figure.unwrap_or(PlayerFigure::Slim),
+                breasts: def.breasts.unwrap_or(BreastSize::Average),
+                clothing: FemaleClothing::default(),
+                pregnancy: None,
+                virgin: true,
+            });
+        }
+    }
+}
+
+/// Seed the social clusters among the random pool: a few coworkers, a pair of
+/// housemates and a circle of friends, so NPCs know each other before the
+/// player meets them. Members are taken by spawn order within each sex — the
+/// pool itself is already randomised, so this needs no extra rolls. Story
+/// characters are left out; packs wire them up with `setRelation` effects.
+pub fn seed_social_clusters(
+    males: &SlotMap<MaleNpcKey, MaleNpc>,
+    females: &SlotMap<FemaleNpcKey, FemaleNpc>,
+) -> NpcRelations {
+    let m: Vec<NpcKey> = males
+        .iter()
+        .filter(|(_, npc)| npc.core.story_id.is_none())
+        .map(|(key, _)| NpcKey::Male(key))
+        .collect();
+    let f: Vec<NpcKey> = females
+        .iter()
+        .filter(|(_, npc)| npc.core.story_id.is_none())
+        .map(|(key, _)| NpcKey::Female(key))
+        .collect();
+
+    let mut relations = NpcRelations::default();
+    let clusters = [
+        (
+            [m.first(), m.get(1), f.first()],
+            NpcRelationKind::Coworker,
+            LikingLevel::Ok,
+        ),
+        (
+            [m.get(2), m.get(3), None],
+            NpcRelationKind::Housemate,
+            LikingLevel::Like,
+        ),
+        (
+            [f.first(), f.get(1), m.get(4)],
+            NpcRelationKind::Friend,
+            LikingLevel::Like,
+        ),
+    ];
+    for (members, kind, closeness) in clusters {
+        let members: Vec<NpcKey> = members.into_iter().flatten().copied().collect();
+        for (i, &a) in members.iter().enumerate() {
+            for &b in &members[i + 1..] {
+                relations.set(a, b, kind, closeness);
+            }
+        }
+    }
+    relations
+}
+
 fn pick_traits<R: Rng>(pool: &[NpcTraitId], count: usize, rng: &mut R) -> HashSet<NpcTraitId> {
     pool.choose_multiple(rng, count.min(pool.len()))
         .copied()
@@ -200,6 +364,9 @@ fn make_core(
     NpcCore {
         name,
         display_name: None,
+        story_id: None,
+        agenda: Default::default(),
+        memory: Default::default(),
         age,
         race,
         eye_colour,
@@ -277,6 +444,35 @@ mod tests {
         assert_eq!(females.len(), 2);
     }
 
+    #[test]
+    fn social_clusters_seed_coworkers_housemates_and_friends() {
+        let mut reg = make_registry();
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(42);
+        let (males, females) = spawn_npcs(&NpcSpawnConfig::default(), &mut reg, &mut rng);
+        let relations = seed_social_clusters(&males, &females);
+
+        let count = |kind| relations.iter().filter(|e| e.kind == kind).count();
+        assert_eq!(count(NpcRelationKind::Coworker), 3);
+        assert_eq!(count(NpcRelationKind::Housemate), 1);
+        assert_eq!(count(NpcRelationKind::Friend), 3);
+
+        // The first woman sits in both the office and the friend circle.
+        let (first_female, _) = females.iter().next().unwrap();
+        assert_eq!(relations.of(NpcKey::Female(first_female)).count(), 4);
+    }
+
+    #[test]
+    fn social_clusters_skip_missing_members() {
+        let mut reg = make_registry();
+        let config = NpcSpawnConfig {
+            male_count: 1,
+            female_count: 0,
+        };
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+        let (males, females) = spawn_npcs(&config, &mut reg, &mut rng);
+        assert!(seed_social_clusters(&males, &females).is_empty());
+    }
+
     #[test]
     fn spawn_guarantees_required_personalities() {
         let mut reg = make_registry();
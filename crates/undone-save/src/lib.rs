@@ -183,6 +183,26 @@ pub fn load_game(path: &Path, registry: &mut PackRegistry) -> Result<World, Save
     Ok(file.world)
 }
 
+/// Check that a freshly loaded `registry` still maps every ID `previous`
+/// interned to the same string, replaying `previous`'s runtime-only IDs into
+/// it. This is the check `load_game` makes against a save file, for swapping
+/// reloaded packs in under a live world.
+///
+/// # Errors
+///
+/// Returns `SaveError::IdMismatch` or `SaveError::TooManyIds` if the pack
+/// content changed in a way that shifts IDs the live world already holds.
+pub fn carry_over_ids(
+    previous: &PackRegistry,
+    registry: &mut PackRegistry,
+) -> Result<(), SaveError> {
+    validate_ids(
+        &previous.all_interned_strings(),
+        Some(previous.pack_id_prefix_len()),
+        registry,
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Migration helpers
 // ---------------------------------------------------------------------------
@@ -680,6 +700,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bare_red_check_keys_load_as_skill_checks() {
+        let (mut registry, _) = undone_packs::load_packs(&packs_dir()).unwrap();
+        let world = make_world(&registry);
+
+        let dir = tempfile_dir();
+        let path = dir.join("bare_red_check.json");
+        save_game(&world, &registry, &path).unwrap();
+
+        // Saves from before red-check keys named their kind.
+        let content = std::fs::read_to_string(&path).unwrap();
+        let mut parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        parsed["world"]["game_data"]["red_check_failures"] =
+            serde_json::json!(["base::some_scene::CHARM"]);
+        std::fs::write(&path, serde_json::to_string_pretty(&parsed).unwrap()).unwrap();
+
+        let loaded = load_game(&path, &mut registry).expect("load should succeed");
+        assert!(loaded
+            .game_data
+            .has_failed_red_check("base::some_scene", "CHARM"));
+        assert!(!loaded.game_data.has_failed_check("base::some_scene::CHARM"));
+    }
+
     #[test]
     fn load_replays_runtime_only_interned_tail() {
         let (mut save_registry, _) = undone_packs::load_packs(&packs_dir()).unwrap();
@@ -699,6 +742,26 @@ mod tests {
             .contains(&"RUNTIME_ONLY".to_string()));
     }
 
+    #[test]
+    fn carry_over_ids_replays_runtime_ids_into_a_reloaded_registry() {
+        let (mut previous, _) = undone_packs::load_packs(&packs_dir()).unwrap();
+        previous.intern_personality("RUNTIME_ONLY");
+        let (mut reloaded, _) = undone_packs::load_packs(&packs_dir()).unwrap();
+
+        carry_over_ids(&previous, &mut reloaded).expect("same packs should carry over");
+        assert_eq!(
+            reloaded.all_interned_strings(),
+            previous.all_interned_strings()
+        );
+
+        let (mut shifted, _) = undone_packs::load_packs(&packs_dir()).unwrap();
+        shifted.intern_personality("PACK_ADDED_LATER");
+        assert!(matches!(
+            carry_over_ids(&previous, &mut shifted),
+            Err(SaveError::IdMismatch { .. })
+        ));
+    }
+
     #[test]
     fn validate_ids_detects_mismatch() {
         let mut registry = PackRegistry::new();
@@ -709,6 +772,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
 
         // "ALPHA" → index 0; save claims index 0 = "BETA"
@@ -740,6 +804,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             undone_packs::TraitDef {
                 id: "POSH".into(),
@@ -748,6 +813,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
         ]);
 
@@ -768,6 +834,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
             undone_packs::TraitDef {
                 id: "PACK_B".into(),
@@ -776,6 +843,7 @@ mod tests {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             },
         ]);
         let saved = vec!["PACK_A".to_string(), "RUNTIME_ONLY".to_string()];
@@ -797,6 +865,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         registry.ensure_interned_string("RUNTIME_ONLY");
         let saved = vec![
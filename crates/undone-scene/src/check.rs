@@ -0,0 +1,123 @@
+//! Check actions (`kind = "check"`): how hard a check is and how it resolves.
+//!
+//! The target math is the one `checkSkill` uses — `skill + (50 - dc)`, clamped
+//! to 5..=95, rolled against the scene's cached d100 for the skill. COMPOSURE
+//! checks are harder for a player with `composure_penalty` traits and harder
+//! the more aroused she is.
+
+use undone_domain::ArousalLevel;
+use undone_packs::PackRegistry;
+use undone_world::World;
+
+use crate::scene_ctx::SceneCtx;
+use crate::types::{Check, CheckType};
+
+/// DC added to COMPOSURE checks at each arousal tier.
+pub fn arousal_penalty(level: ArousalLevel) -> i32 {
+    match level {
+        ArousalLevel::Discomfort | ArousalLevel::Comfort => 0,
+        ArousalLevel::Enjoy => 10,
+        ArousalLevel::Close => 20,
+        ArousalLevel::Orgasm => 30,
+    }
+}
+
+/// `base_dc`, plus trait and arousal penalties when the check is on COMPOSURE.
+pub fn effective_dc(check: &Check, world: &World, registry: &PackRegistry) -> i32 {
+    let on_composure = match (
+        registry.resolve_skill(&check.skill),
+        registry.composure_skill(),
+    ) {
+        (Ok(skill), Ok(composure)) => skill == composure,
+        _ => false,
+    };
+    if !on_composure {
+        return check.base_dc;
+    }
+    check.base_dc
+        + registry.composure_penalty(&world.player)
+        + arousal_penalty(world.player.arousal)
+}
+
+/// The d100 roll at or under which the check passes (5..=95).
+pub fn pass_target(check: &Check, world: &World, registry: &PackRegistry) -> i32 {
+    let skill_value = registry
+        .resolve_skill(&check.skill)
+        .map_or(0, |id| world.player.skill(id));
+    (skill_value + (50 - effective_dc(check, world, registry))).clamp(5, 95)
+}
+
+/// A red check that has already been failed. It can't pass again.
+pub fn is_spent(check: &Check, world: &World) -> bool {
+    check.check_type == CheckType::Red && world.game_data.has_failed_check(&check.id)
+}
+
+/// Roll the check. Spent red checks fail without rolling.
+pub fn roll_check(check: &Check, world: &World, ctx: &SceneCtx, registry: &PackRegistry) -> bool {
+    if is_spent(check, world) {
+        return false;
+    }
+    ctx.get_or_roll_skill(&check.skill) <= pass_target(check, world, registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CheckOutcome;
+    use undone_packs::data::{SkillDef, TraitDef};
+    use undone_world::test_helpers::make_test_world;
+
+    fn skill(id: &str) -> SkillDef {
+        SkillDef {
+            id: id.into(),
+            name: id.into(),
+            description: String::new(),
+            min: 0,
+            max: 100,
+            progression: None,
+        }
+    }
+
+    fn check_on(skill: &str) -> Check {
+        let outcome = || CheckOutcome {
+            prose: String::new(),
+            effect: None,
+            next: vec![],
+            thoughts: vec![],
+        };
+        Check {
+            id: "test::scene::check".into(),
+            skill: skill.into(),
+            base_dc: 40,
+            check_type: CheckType::White,
+            pass: outcome(),
+            fail: outcome(),
+        }
+    }
+
+    #[test]
+    fn traits_and_arousal_raise_only_composure_dcs() {
+        let mut registry = PackRegistry::new();
+        registry.register_skills(vec![skill("COMPOSURE"), skill("CHARM")]);
+        registry.register_traits(vec![TraitDef {
+            id: "HAIR_TRIGGER".into(),
+            name: "Hair Trigger".into(),
+            description: String::new(),
+            hidden: false,
+            group: None,
+            conflicts: vec![],
+            composure_penalty: 20,
+        }]);
+        let mut world = make_test_world();
+        world
+            .player
+            .traits
+            .insert(registry.resolve_trait("HAIR_TRIGGER").unwrap());
+        world.player.arousal = ArousalLevel::Close;
+
+        assert_eq!(effective_dc(&check_on("COMPOSURE"), &world, &registry), 80);
+        assert_eq!(effective_dc(&check_on("CHARM"), &world, &registry), 40);
+        // Skill 0 against DC 80 bottoms out at the floor.
+        assert_eq!(pass_target(&check_on("COMPOSURE"), &world, &registry), 5);
+    }
+}
@@ -0,0 +1,649 @@
+//! Branch and prose coverage.
+//!
+//! A [`SceneEngine`](crate::engine::SceneEngine) with coverage enabled keeps a
+//! [`Coverage`] log: per scene, how often it started and how often each
+//! [`CoveragePoint`] was hit — actions offered and chosen, check outcomes,
+//! intro variants, thoughts, NPC actions, and the `{% if %}` / `{% elif %}` /
+//! `{% else %}` branches of every prose template. [`coverage_report`] sets the
+//! hits against everything each scene could do, so a simulated playthrough can
+//! list the branches realistic play never reaches.
+//!
+//! Prose branches are traced by rendering an instrumented copy of the template
+//! ([`instrument_prose`]) with a marker call at the top of each branch body.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::types::{
+    CheckOutcome, CheckOutcomeDef, SceneDefinition, SceneToml, SpannedText, Thought, ThoughtDef,
+};
+
+/// The function the instrumented template calls to record a branch.
+pub(crate) const BRANCH_MARKER_FN: &str = "coverage_branch";
+
+/// One run of prose, thoughts and effect in a scene.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Beat {
+    Intro,
+    Action(String),
+    CheckPass(String),
+    CheckFail(String),
+    NpcAction(String),
+}
+
+/// Where a prose template lives in its scene.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProseSite {
+    Beat(Beat),
+    IntroVariant(usize),
+    Thought(Beat, usize),
+}
+
+/// Something a scene can do that a playthrough may or may not reach.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoveragePoint {
+    /// The action was in the offered list.
+    Offered(String),
+    /// The beat played. `Beat::Intro` means the base intro, used when no
+    /// intro variant matched.
+    Beat(Beat),
+    IntroVariant(usize),
+    /// The thought's condition passed and it rendered.
+    Thought(Beat, usize),
+    /// Branch `n` (in template order) of the prose at a site rendered.
+    Branch(ProseSite, usize),
+}
+
+impl fmt::Display for Beat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Beat::Intro => write!(f, "intro"),
+            Beat::Action(id) => write!(f, "action '{id}'"),
+            Beat::CheckPass(id) => write!(f, "action '{id}' check pass"),
+            Beat::CheckFail(id) => write!(f, "action '{id}' check fail"),
+            Beat::NpcAction(id) => write!(f, "npc_action '{id}'"),
+        }
+    }
+}
+
+impl fmt::Display for ProseSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProseSite::Beat(beat) => write!(f, "{beat} prose"),
+            ProseSite::IntroVariant(index) => write!(f, "intro_variant #{}", index + 1),
+            ProseSite::Thought(beat, index) => write!(f, "{beat} thought #{}", index + 1),
+        }
+    }
+}
+
+impl fmt::Display for CoveragePoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoveragePoint::Offered(id) => write!(f, "action '{id}' offered"),
+            CoveragePoint::Beat(Beat::Intro) => write!(f, "base intro"),
+            CoveragePoint::Beat(Beat::Action(id)) => write!(f, "action '{id}' chosen"),
+            CoveragePoint::Beat(beat) => write!(f, "{beat}"),
+            CoveragePoint::IntroVariant(index) => write!(f, "intro_variant #{}", index + 1),
+            CoveragePoint::Thought(beat, index) => write!(f, "{beat} thought #{}", index + 1),
+            CoveragePoint::Branch(site, index) => write!(f, "{site} branch #{}", index + 1),
+        }
+    }
+}
+
+/// Hits per scene, as the engine records them.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    scenes: HashMap<String, SceneHits>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SceneHits {
+    pub starts: u64,
+    pub hits: HashMap<CoveragePoint, u64>,
+}
+
+impl Coverage {
+    pub fn started(&mut self, scene_id: &str) {
+        self.scenes.entry(scene_id.to_string()).or_default().starts += 1;
+    }
+
+    pub fn hit(&mut self, scene_id: &str, point: CoveragePoint) {
+        *self
+            .scenes
+            .entry(scene_id.to_string())
+            .or_default()
+            .hits
+            .entry(point)
+            .or_insert(0) += 1;
+    }
+
+    pub fn merge(&mut self, other: Coverage) {
+        for (scene_id, other) in other.scenes {
+            let hits = self.scenes.entry(scene_id).or_default();
+            hits.starts += other.starts;
+            for (point, count) in other.hits {
+                *hits.hits.entry(point).or_insert(0) += count;
+            }
+        }
+    }
+
+    pub fn scene(&self, scene_id: &str) -> Option<&SceneHits> {
+        self.scenes.get(scene_id)
+    }
+}
+
+/// One scene's coverage: every point it has, with hit counts.
+#[derive(Debug, Clone, Serialize)]
+pub struct SceneCoverage {
+    pub scene_id: String,
+    pub starts: u64,
+    pub covered: usize,
+    pub total: usize,
+    pub points: Vec<PointCoverage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PointCoverage {
+    pub point: String,
+    pub hits: u64,
+}
+
+impl SceneCoverage {
+    /// Points no playthrough reached.
+    pub fn dead(&self) -> impl Iterator<Item = &PointCoverage> {
+        self.points.iter().filter(|point| point.hits == 0)
+    }
+}
+
+/// Coverage of every scene in `scenes`, sorted by scene id. Scenes the log
+/// never saw are included with zero starts.
+pub fn coverage_report(
+    coverage: &Coverage,
+    scenes: &HashMap<String, Arc<SceneDefinition>>,
+) -> Vec<SceneCoverage> {
+    let mut report: Vec<SceneCoverage> = scenes
+        .values()
+        .map(|def| {
+            let hits = coverage.scene(&def.id);
+            let points: Vec<PointCoverage> = coverage_points(def)
+                .into_iter()
+                .map(|(point, label)| PointCoverage {
+                    hits: hits
+                        .and_then(|hits| hits.hits.get(&point))
+                        .copied()
+                        .unwrap_or(0),
+                    point: label,
+                })
+                .collect();
+            SceneCoverage {
+                scene_id: def.id.clone(),
+                starts: hits.map_or(0, |hits| hits.starts),
+                covered: points.iter().filter(|point| point.hits > 0).count(),
+                total: points.len(),
+                points,
+            }
+        })
+        .collect();
+    report.sort_by(|left, right| left.scene_id.cmp(&right.scene_id));
+    report
+}
+
+/// Everything `def` can do, in authoring order, each with its report label.
+pub fn coverage_points(def: &SceneDefinition) -> Vec<(CoveragePoint, String)> {
+    let mut points = Vec::new();
+
+    if !def.intro_variants.is_empty() {
+        points.push((CoveragePoint::Beat(Beat::Intro), "base intro".to_string()));
+    }
+    push_branches(&mut points, ProseSite::Beat(Beat::Intro), &def.intro_prose);
+    for (index, variant) in def.intro_variants.iter().enumerate() {
+        let point = CoveragePoint::IntroVariant(index);
+        points.push((point.clone(), point.to_string()));
+        push_branches(&mut points, ProseSite::IntroVariant(index), &variant.prose);
+    }
+    push_thoughts(&mut points, Beat::Intro, &def.intro_thoughts);
+
+    for action in &def.actions {
+        let offered = CoveragePoint::Offered(action.id.clone());
+        points.push((offered.clone(), offered.to_string()));
+        let beat = Beat::Action(action.id.clone());
+        push_beat(&mut points, beat.clone(), &action.prose);
+        push_thoughts(&mut points, beat, &action.thoughts);
+        if let Some(check) = &action.check {
+            for (beat, outcome) in [
+                (Beat::CheckPass(action.id.clone()), &check.pass),
+                (Beat::CheckFail(action.id.clone()), &check.fail),
+            ] {
+                push_beat(&mut points, beat.clone(), &outcome.prose);
+                push_thoughts(&mut points, beat, &outcome.thoughts);
+            }
+        }
+    }
+
+    for npc_action in &def.npc_actions {
+        push_beat(
+            &mut points,
+            Beat::NpcAction(npc_action.id.clone()),
+            &npc_action.prose,
+        );
+    }
+    points
+}
+
+fn push_beat(points: &mut Vec<(CoveragePoint, String)>, beat: Beat, prose: &str) {
+    let point = CoveragePoint::Beat(beat.clone());
+    points.push((point.clone(), point.to_string()));
+    push_branches(points, ProseSite::Beat(beat), prose);
+}
+
+fn push_thoughts(points: &mut Vec<(CoveragePoint, String)>, beat: Beat, thoughts: &[Thought]) {
+    for (index, thought) in thoughts.iter().enumerate() {
+        let point = CoveragePoint::Thought(beat.clone(), index);
+        points.push((point.clone(), point.to_string()));
+        push_branches(
+            points,
+            ProseSite::Thought(beat.clone(), index),
+            &thought.prose,
+        );
+    }
+}
+
+fn push_branches(points: &mut Vec<(CoveragePoint, String)>, site: ProseSite, prose: &str) {
+    for (index, branch) in prose_branches(prose).into_iter().enumerate() {
+        let point = CoveragePoint::Branch(site.clone(), index);
+        let label = format!("{site} `{{% {} %}}` (line {})", branch.tag, branch.line);
+        points.push((point, label));
+    }
+}
+
+/// A scene's prose templates, as [`prose_sites`] walks them: a loaded
+/// [`SceneDefinition`], or a scene file as parsed, whose prose keeps its
+/// source spans.
+pub trait SceneProse {
+    type Prose: ?Sized;
+
+    fn intro(&self) -> &Self::Prose;
+    fn intro_variants(&self) -> Vec<&Self::Prose>;
+    fn intro_thoughts(&self) -> Vec<&Self::Prose>;
+    fn actions(&self) -> Vec<ActionProse<'_, Self::Prose>>;
+    fn npc_actions(&self) -> Vec<(&str, &Self::Prose)>;
+}
+
+/// One action's prose and thoughts, then its check outcomes'.
+pub struct ActionProse<'a, P: ?Sized> {
+    pub id: &'a str,
+    pub prose: &'a P,
+    pub thoughts: Vec<&'a P>,
+    pub pass: Option<(&'a P, Vec<&'a P>)>,
+    pub fail: Option<(&'a P, Vec<&'a P>)>,
+}
+
+/// Every prose template in `scene`, in authoring order, with where it plays.
+pub fn prose_sites<S: SceneProse + ?Sized>(scene: &S) -> Vec<(ProseSite, &S::Prose)> {
+    let mut sites = vec![(ProseSite::Beat(Beat::Intro), scene.intro())];
+    for (index, prose) in scene.intro_variants().into_iter().enumerate() {
+        sites.push((ProseSite::IntroVariant(index), prose));
+    }
+    push_thought_sites(&mut sites, Beat::Intro, scene.intro_thoughts());
+
+    for action in scene.actions() {
+        let beat = Beat::Action(action.id.to_string());
+        sites.push((ProseSite::Beat(beat.clone()), action.prose));
+        push_thought_sites(&mut sites, beat, action.thoughts);
+        for (beat, outcome) in [
+            (Beat::CheckPass(action.id.to_string()), action.pass),
+            (Beat::CheckFail(action.id.to_string()), action.fail),
+        ] {
+            if let Some((prose, thoughts)) = outcome {
+                sites.push((ProseSite::Beat(beat.clone()), prose));
+                push_thought_sites(&mut sites, beat, thoughts);
+            }
+        }
+    }
+
+    for (id, prose) in scene.npc_actions() {
+        sites.push((ProseSite::Beat(Beat::NpcAction(id.to_string())), prose));
+    }
+    sites
+}
+
+fn push_thought_sites<'a, P: ?Sized>(
+    sites: &mut Vec<(ProseSite, &'a P)>,
+    beat: Beat,
+    thoughts: Vec<&'a P>,
+) {
+    for (index, prose) in thoughts.into_iter().enumerate() {
+        sites.push((ProseSite::Thought(beat.clone(), index), prose));
+    }
+}
+
+impl SceneProse for SceneDefinition {
+    type Prose = str;
+
+    fn intro(&self) -> &str {
+        &self.intro_prose
+    }
+
+    fn intro_variants(&self) -> Vec<&str> {
+        self.intro_variants
+            .iter()
+            .map(|v| v.prose.as_str())
+            .collect()
+    }
+
+    fn intro_thoughts(&self) -> Vec<&str> {
+        thought_prose(&self.intro_thoughts)
+    }
+
+    fn actions(&self) -> Vec<ActionProse<'_, str>> {
+        self.actions
+            .iter()
+            .map(|action| ActionProse {
+                id: &action.id,
+                prose: action.prose.as_str(),
+                thoughts: thought_prose(&action.thoughts),
+                pass: action
+                    .check
+                    .as_ref()
+                    .map(|check| outcome_prose(&check.pass)),
+                fail: action
+                    .check
+                    .as_ref()
+                    .map(|check| outcome_prose(&check.fail)),
+            })
+            .collect()
+    }
+
+    fn npc_actions(&self) -> Vec<(&str, &str)> {
+        self.npc_actions
+            .iter()
+            .map(|action| (action.id.as_str(), action.prose.as_str()))
+            .collect()
+    }
+}
+
+fn outcome_prose(outcome: &CheckOutcome) -> (&str, Vec<&str>) {
+    (&outcome.prose, thought_prose(&outcome.thoughts))
+}
+
+fn thought_prose(thoughts: &[Thought]) -> Vec<&str> {
+    thoughts
+        .iter()
+        .map(|thought| thought.prose.as_str())
+        .collect()
+}
+
+impl SceneProse for SceneToml {
+    type Prose = SpannedText;
+
+    fn intro(&self) -> &SpannedText {
+        &self.intro.prose
+    }
+
+    fn intro_variants(&self) -> Vec<&SpannedText> {
+        self.intro_variants.iter().map(|v| &v.prose).collect()
+    }
+
+    fn intro_thoughts(&self) -> Vec<&SpannedText> {
+        thought_def_prose(&self.thoughts)
+    }
+
+    fn actions(&self) -> Vec<ActionProse<'_, SpannedText>> {
+        self.actions
+            .iter()
+            .map(|action| ActionProse {
+                id: &action.id,
+                prose: &action.prose,
+                thoughts: thought_def_prose(&action.thoughts),
+                pass: action.pass.as_ref().map(outcome_def_prose),
+                fail: action.fail.as_ref().map(outcome_def_prose),
+            })
+            .collect()
+    }
+
+    fn npc_actions(&self) -> Vec<(&str, &SpannedText)> {
+        self.npc_actions
+            .iter()
+            .map(|action| (action.id.as_str(), &action.prose))
+            .collect()
+    }
+}
+
+fn outcome_def_prose(outcome: &CheckOutcomeDef) -> (&SpannedText, Vec<&SpannedText>) {
+    (&outcome.prose, thought_def_prose(&outcome.thoughts))
+}
+
+fn thought_def_prose(thoughts: &[ThoughtDef]) -> Vec<&SpannedText> {
+    thoughts.iter().map(|thought| &thought.prose).collect()
+}
+
+/// One `{% if %}`, `{% elif %}` or `{% else %}` branch of a prose template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProseBranch {
+    /// The opening tag's contents, e.g. `if w.hasTrait("SHY")`.
+    pub tag: String,
+    /// 1-based line of the tag in the template.
+    pub line: usize,
+}
+
+/// The branches of `template`, in order. `{% else %}` of a `{% for %}` is not
+/// a branch; comments and `{% raw %}` blocks are skipped.
+pub fn prose_branches(template: &str) -> Vec<ProseBranch> {
+    scan_branches(template)
+        .into_iter()
+        .map(|found| found.branch)
+        .collect()
+}
+
+/// `template` with a `{{ coverage_branch(n) }}` call at the start of branch
+/// `n`'s body. Whitespace control on the branch tag carries over to the
+/// marker, so the rendered text is unchanged.
+pub fn instrument_prose(template: &str) -> String {
+    let mut out = String::with_capacity(template.len() + 64);
+    let mut copied = 0;
+    for (index, found) in scan_branches(template).into_iter().enumerate() {
+        out.push_str(&template[copied..found.body.start]);
+        let close = if found.trim_after { "-}}" } else { "}}" };
+        out.push_str(&format!("{{{{ {BRANCH_MARKER_FN}({index}) {close}"));
+        copied = found.body.start;
+    }
+    out.push_str(&template[copied..]);
+    out
+}
+
+pub(crate) struct FoundBranch {
+    pub branch: ProseBranch,
+    /// Byte range of the branch tag.
+    pub tag: Range<usize>,
+    /// Byte range of the branch body: from just past its tag to the chain's
+    /// next `{% elif %}`, `{% else %}` or `{% endif %}`.
+    pub body: Range<usize>,
+    /// The `{% if %}` chain the branch belongs to, numbered in template order.
+    pub chain: usize,
+    /// The tag ends in `-%}`, trimming the whitespace after it.
+    pub trim_after: bool,
+}
+
+/// The branches of `template`, in order, with where each chain and body
+/// lies. An unclosed chain's last body runs to the end of the template.
+pub(crate) fn scan_branches(template: &str) -> Vec<FoundBranch> {
+    let mut found: Vec<FoundBranch> = Vec::new();
+    let mut blocks = Vec::new();
+    // The current branch of each open chain, innermost last.
+    let mut open: Vec<usize> = Vec::new();
+    let mut chains = 0;
+    for tag in template_tags(template) {
+        let Some(statement) = tag.statement else {
+            continue;
+        };
+        let chain = match branching(statement, &mut blocks) {
+            Branching::Continues if !open.is_empty() => {
+                let current = open.pop().expect("checked non-empty");
+                found[current].body.end = tag.range.start;
+                found[current].chain
+            }
+            // A stray `{% elif %}` or `{% else %}` still counts as a branch.
+            Branching::Opens | Branching::Continues => {
+                chains += 1;
+                chains - 1
+            }
+            Branching::Closes => {
+                if let Some(current) = open.pop() {
+                    found[current].body.end = tag.range.start;
+                }
+                continue;
+            }
+            Branching::Neither => continue,
+        };
+        open.push(found.len());
+        found.push(FoundBranch {
+            branch: ProseBranch {
+                tag: statement.split_whitespace().collect::<Vec<_>>().join(" "),
+                line: template[..tag.range.start].matches('\n').count() + 1,
+            },
+            tag: tag.range.clone(),
+            body: tag.range.end..template.len(),
+            chain,
+            trim_after: tag.trim_after,
+        });
+    }
+    found
+}
+
+/// A `{% %}`, `{{ }}` or `{# #}` tag of a template. Tags inside a
+/// `{% raw %}` block are text, not tags.
+pub(crate) struct TemplateTag<'a> {
+    /// Byte range of the tag, delimiters included.
+    pub range: Range<usize>,
+    /// A `{% %}` tag's statement without whitespace control, e.g. `if a`.
+    /// `None` for expressions and comments.
+    pub statement: Option<&'a str>,
+    /// The tag ends in `-%}`, trimming the whitespace after it.
+    pub trim_after: bool,
+}
+
+/// The tags of `template`, in order.
+pub(crate) fn template_tags(template: &str) -> Vec<TemplateTag<'_>> {
+    let mut tags = Vec::new();
+    let mut in_raw = false;
+    let mut rest_start = 0;
+    while let Some(open) = template[rest_start..].find('{').map(|i| rest_start + i) {
+        let kind = template[open + 1..].chars().next();
+        let close_delim = match kind {
+            Some('%') => "%}",
+            Some('{') => "}}",
+            Some('#') => "#}",
+            _ => {
+                rest_start = open + 1;
+                continue;
+            }
+        };
+        let Some(close) = template[open + 2..].find(close_delim).map(|i| open + 2 + i) else {
+            break;
+        };
+        rest_start = close + 2;
+        let statement = (kind == Some('%')).then(|| {
+            template[open + 2..close]
+                .trim_start_matches(['-', '+'])
+                .trim_end_matches(['-', '+'])
+                .trim()
+        });
+        let head = statement.and_then(|s| s.split_whitespace().next());
+        if in_raw {
+            if head != Some("endraw") {
+                continue;
+            }
+            in_raw = false;
+        } else if head == Some("raw") {
+            in_raw = true;
+        }
+        tags.push(TemplateTag {
+            range: open..close + 2,
+            statement,
+            trim_after: template[open + 2..close].ends_with('-'),
+        });
+    }
+    tags
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Block {
+    If,
+    For,
+}
+
+/// What a statement does to the `{% if %}` chains of a template.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Branching {
+    /// `{% if %}`: starts a chain with its first branch.
+    Opens,
+    /// `{% elif %}` or the `{% else %}` of an `{% if %}`: the chain's next branch.
+    Continues,
+    /// `{% endif %}`.
+    Closes,
+    Neither,
+}
+
+/// Classify `statement`, tracking the open `{% if %}` / `{% for %}` blocks
+/// in `blocks`. `{% else %}` of a `{% for %}` is not a branch.
+pub(crate) fn branching(statement: &str, blocks: &mut Vec<Block>) -> Branching {
+    match statement.split_whitespace().next().unwrap_or("") {
+        "if" => {
+            blocks.push(Block::If);
+            Branching::Opens
+        }
+        "for" => {
+            blocks.push(Block::For);
+            Branching::Neither
+        }
+        "endif" => {
+            blocks.pop();
+            Branching::Closes
+        }
+        "endfor" => {
+            blocks.pop();
+            Branching::Neither
+        }
+        "elif" => Branching::Continues,
+        "else" if blocks.last() == Some(&Block::If) => Branching::Continues,
+        _ => Branching::Neither,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn branches_are_if_elif_and_else_but_not_for_else() {
+        let template = "{% if a %}A{% elif b %}B{% else %}C{% endif %}\n\
+                        {% for x in xs %}{{ x }}{% else %}none{% endfor %}\n\
+                        {# {% if hidden %} #}{% raw %}{% if raw %}{% endraw %}";
+        let branches = prose_branches(template);
+        let tags: Vec<&str> = branches.iter().map(|b| b.tag.as_str()).collect();
+        assert_eq!(tags, ["if a", "elif b", "else"]);
+        assert!(branches.iter().all(|branch| branch.line == 1));
+    }
+
+    #[test]
+    fn instrumented_prose_renders_the_same_text() {
+        let template = "Start.\n{% if a -%}\n  A.\n{%- else %}\n  B.\n{% endif %}End.";
+        let instrumented = instrument_prose(template);
+        assert!(instrumented.contains("{{ coverage_branch(0) -}}"));
+        assert!(instrumented.contains("{{ coverage_branch(1) }}"));
+
+        for a in [true, false] {
+            let render = |source: &str| {
+                let mut env = minijinja::Environment::new();
+                env.add_function(BRANCH_MARKER_FN, |_: usize| String::new());
+                env.render_str(source, minijinja::context! { a => a })
+                    .unwrap()
+            };
+            assert_eq!(render(template), render(&instrumented));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! File locations for scene load errors, and a codespan-style renderer for them.
+//!
+//! Scene TOML values are deserialized as `toml::Spanned`, so the loader knows the
+//! byte range of every condition, effect and prose template. A `SourceSpan`
+//! resolves such a range — narrowed to a script position when the error has one —
+//! to a file line/column plus the line's text, so an error can show the offending
+//! snippet underlined.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// A located snippet of an authored file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+    pub path: PathBuf,
+    /// 1-based line of the span start.
+    pub line: usize,
+    /// 1-based column (in chars) of the span start.
+    pub column: usize,
+    /// Underline width in chars, at least 1.
+    pub width: usize,
+    /// The text of `line`, for rendering.
+    pub line_text: String,
+}
+
+impl SourceSpan {
+    /// The span of byte `range` in the file `text`. Underlines the range up to
+    /// the end of its first line.
+    pub fn new(path: &Path, text: &str, range: Range<usize>) -> Self {
+        let start = range.start.min(text.len());
+        let line_start = text[..start].rfind('\n').map_or(0, |nl| nl + 1);
+        let line_end = text[start..].find('\n').map_or(text.len(), |nl| start + nl);
+        let line_text = text[line_start..line_end].trim_end_matches('\r');
+        let end = range.end.clamp(start, line_start + line_text.len());
+        SourceSpan {
+            path: path.to_path_buf(),
+            line: text[..start].matches('\n').count() + 1,
+            column: text[line_start..start].chars().count() + 1,
+            width: text[start..end].chars().count().max(1),
+            line_text: line_text.to_string(),
+        }
+    }
+
+    /// The span of a position inside the TOML string value at `range`:
+    /// `position` is the script/template line and column within the decoded
+    /// value. Points at the identifier there. Falls back to the whole value when
+    /// the position is unknown or escapes make decoded offsets differ from the
+    /// file's.
+    pub fn in_value(
+        path: &Path,
+        text: &str,
+        range: Range<usize>,
+        position: rhai::Position,
+    ) -> Self {
+        let offset = match (position.line(), position.position()) {
+            (Some(line), Some(column)) => value_offset(&text[range.clone()], line, column),
+            _ => None,
+        };
+        let Some(offset) = offset else {
+            return SourceSpan::new(path, text, range);
+        };
+        identifier_at(path, text, range.start + offset, range.end)
+    }
+
+    /// The span of a script `position` in a plain source file (a pack
+    /// `scripts/*.rhai` library). Points at the identifier there, or at the
+    /// file start when the position is unknown.
+    pub fn at(path: &Path, text: &str, position: rhai::Position) -> Self {
+        let offset = match (position.line(), position.position()) {
+            (Some(line), Some(column)) => line_offset(text, line, column),
+            _ => None,
+        };
+        identifier_at(path, text, offset.unwrap_or(0), text.len())
+    }
+
+    /// Render `message` codespan-style: a header, the `path:line:column`
+    /// pointer and the source line with the span underlined.
+    pub fn render(&self, message: &str) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let indent: String = self
+            .line_text
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        format!(
+            "error: {message}\n{gutter}--> {self}\n{gutter} |\n{number} | {}\n{gutter} | {indent}{}",
+            self.line_text,
+            "^".repeat(self.width)
+        )
+    }
+}
+
+impl fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// The span of the identifier starting at byte `start` (at least one char).
+fn identifier_at(path: &Path, text: &str, start: usize, end: usize) -> SourceSpan {
+    let width = text[start..end]
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .count()
+        .max(1);
+    SourceSpan::new(path, text, start..start + width)
+}
+
+/// Byte offset of 1-based `line`/`column` (in chars) in `text`.
+fn line_offset(text: &str, line: usize, column: usize) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 1..line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+    text[line_start..]
+        .char_indices()
+        .nth(column - 1)
+        .map(|(idx, _)| line_start + idx)
+}
+
+/// Byte offset, within the raw TOML string `raw` (delimiters included), of
+/// 1-based `line`/`column` in its decoded value. `None` for non-strings and for
+/// basic strings with escapes.
+fn value_offset(raw: &str, line: usize, column: usize) -> Option<usize> {
+    let (delimiter, escapes) = if raw.starts_with("'''") {
+        ("'''", false)
+    } else if raw.starts_with("\"\"\"") {
+        ("\"\"\"", true)
+    } else if raw.starts_with('\'') {
+        ("'", false)
+    } else if raw.starts_with('"') {
+        ("\"", true)
+    } else {
+        return None;
+    };
+    if escapes && raw.contains('\\') {
+        return None;
+    }
+    let mut start = delimiter.len();
+    // A newline right after a multi-line opener is not part of the value.
+    if delimiter.len() == 3 {
+        if raw[start..].starts_with("\r\n") {
+            start += 2;
+        } else if raw[start..].starts_with('\n') {
+            start += 1;
+        }
+    }
+    let body = &raw[start..raw.len() - delimiter.len()];
+    line_offset(body, line, column).map(|offset| start + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "[[actions]]\nid = \"go\"\ncondition = 'w.hasTrait(\"SHY\")'\nprose = '''\nYou go.\n{{ w.notAReal() }}\n'''\n";
+
+    fn range_of(needle: &str) -> Range<usize> {
+        let start = FILE.find(needle).unwrap();
+        start..start + needle.len()
+    }
+
+    #[test]
+    fn positions_map_into_literal_strings() {
+        let path = Path::new("scenes/x.toml");
+        let span = SourceSpan::in_value(
+            path,
+            FILE,
+            range_of("'w.hasTrait(\"SHY\")'"),
+            rhai::Position::new(1, 3),
+        );
+        assert_eq!((span.line, span.column, span.width), (3, 16, 8));
+
+        let prose = "'''\nYou go.\n{{ w.notAReal() }}\n'''";
+        let span = SourceSpan::in_value(path, FILE, range_of(prose), rhai::Position::new(2, 1));
+        assert_eq!((span.line, span.column), (6, 1));
+    }
+
+    #[test]
+    fn escaped_strings_fall_back_to_the_whole_value() {
+        let text = "effect = \"gd.setGameFlag(\\\"X\\\")\"\n";
+        let span = SourceSpan::in_value(
+            Path::new("x.toml"),
+            text,
+            9..text.len() - 1,
+            rhai::Position::new(1, 4),
+        );
+        assert_eq!((span.line, span.column, span.width), (1, 10, 23));
+    }
+
+    #[test]
+    fn render_underlines_the_span() {
+        let span = SourceSpan::in_value(
+            Path::new("scenes/x.toml"),
+            FILE,
+            range_of("'w.hasTrait(\"SHY\")'"),
+            rhai::Position::new(1, 3),
+        );
+        assert_eq!(
+            span.render("unknown trait"),
+            "error: unknown trait\n --> scenes/x.toml:3:16\n  |\n3 | condition = 'w.hasTrait(\"SHY\")'\n  |                ^^^^^^^^"
+        );
+    }
+}
@@ -2,12 +2,13 @@ use crate::scene_ctx::{SceneCtx, SceneNpcRef};
 use thiserror::Error;
 use undone_domain::{
     AlcoholLevel, ArousalLevel, AttractionLevel, Behaviour, FemaleNpcKey, LikingLevel, LoveLevel,
-    MaleNpcKey, RelationshipStatus,
+    MaleNpcKey, NpcKey, NpcRelationKind, RelationshipStatus, VarType,
 };
+use undone_world::World;
 
 #[derive(Debug, Error)]
 pub enum EffectError {
-    #[error("effect npc ref '{0}' is not 'm', 'f', or a bound scene role")]
+    #[error("effect npc ref '{0}' is not 'm', 'f', a bound scene role, or a story character")]
     BadNpcRef(String),
     #[error("effect requires active male NPC but none is set")]
     NoActiveMale,
@@ -27,8 +28,22 @@ pub enum EffectError {
     UnknownStuff(String),
     #[error("unknown relationship status '{0}'")]
     UnknownRelationshipStatus(String),
+    #[error("unknown npc relation '{0}'")]
+    UnknownNpcRelation(String),
     #[error("unknown behaviour '{0}'")]
     UnknownBehaviour(String),
+    #[error("unknown body value '{0}'")]
+    UnknownBodyValue(String),
+    #[error("unknown buff '{0}'")]
+    UnknownBuff(String),
+    #[error("unknown scene var '{0}'")]
+    UnknownSceneVar(String),
+    #[error("scene var '{name}' is {declared}, got {got}")]
+    SceneVarType {
+        name: String,
+        declared: VarType,
+        got: VarType,
+    },
     #[error("unknown virgin_type '{0}'")]
     UnknownVirginType(String),
     #[error("trait conflict: {0}")]
@@ -112,6 +127,20 @@ pub(crate) fn parse_relationship_status(s: &str) -> Option<RelationshipStatus> {
     }
 }
 
+pub(crate) fn parse_npc_relation_kind(s: &str) -> Option<NpcRelationKind> {
+    match s {
+        "Acquaintance" => Some(NpcRelationKind::Acquaintance),
+        "Friend" => Some(NpcRelationKind::Friend),
+        "Coworker" => Some(NpcRelationKind::Coworker),
+        "Housemate" => Some(NpcRelationKind::Housemate),
+        "Family" => Some(NpcRelationKind::Family),
+        "Partner" => Some(NpcRelationKind::Partner),
+        "Ex" => Some(NpcRelationKind::Ex),
+        "Rival" => Some(NpcRelationKind::Rival),
+        _ => None,
+    }
+}
+
 pub(crate) fn parse_behaviour(s: &str) -> Option<Behaviour> {
     match s {
         "Neutral" => Some(Behaviour::Neutral),
@@ -128,7 +157,23 @@ pub(crate) enum NpcRef {
     Female(FemaleNpcKey),
 }
 
-pub(crate) fn resolve_npc_ref(npc: &str, ctx: &SceneCtx) -> Result<NpcRef, EffectError> {
+impl From<NpcRef> for NpcKey {
+    fn from(r: NpcRef) -> Self {
+        match r {
+            NpcRef::Male(key) => NpcKey::Male(key),
+            NpcRef::Female(key) => NpcKey::Female(key),
+        }
+    }
+}
+
+/// Resolve an effect npc ref: `"m"`/`"f"` (active NPCs), a bound scene role, or a
+/// namespaced story character id (`"base::jake"`). Story ids are looked up in the
+/// world, so they work from any scene without a binding.
+pub(crate) fn resolve_npc_ref(
+    npc: &str,
+    ctx: &SceneCtx,
+    world: &World,
+) -> Result<NpcRef, EffectError> {
     match npc {
         "m" => ctx
             .active_male
@@ -138,6 +183,11 @@ pub(crate) fn resolve_npc_ref(npc: &str, ctx: &SceneCtx) -> Result<NpcRef, Effec
             .active_female
             .map(NpcRef::Female)
             .ok_or(EffectError::NoActiveFemale),
+        story_id if story_id.contains("::") => match world.story_npc(story_id) {
+            Some(NpcKey::Male(key)) => Ok(NpcRef::Male(key)),
+            Some(NpcKey::Female(key)) => Ok(NpcRef::Female(key)),
+            None => Err(EffectError::BadNpcRef(story_id.to_string())),
+        },
         role => match ctx.role_binding(role) {
             Some(SceneNpcRef::Male(key)) => Ok(NpcRef::Male(key)),
             Some(SceneNpcRef::Female(key)) => Ok(NpcRef::Female(key)),
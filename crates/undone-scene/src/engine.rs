@@ -1,4 +1,5 @@
 use std::{
+    cell::RefCell,
     collections::{HashMap, VecDeque},
     sync::Arc,
 };
@@ -10,15 +11,23 @@ use undone_packs::PackRegistry;
 use undone_world::World;
 
 use crate::{
-    script::{apply_effect_script, eval_bool, CompiledScript},
-    template_ctx::render_prose,
-    types::{Action, NarratorVariant, NextBranch, SceneDefinition, Thought},
+    check,
+    coverage::{Beat, Coverage, CoveragePoint, ProseSite},
+    script::{api::ApiValue, apply_effect_script, eval_bool, CompiledScript},
+    template_ctx::{render_prose, render_prose_traced},
+    types::{
+        Action, Check, CheckType, NarratorVariant, NextBranch, SceneCall, SceneDefinition, Thought,
+    },
 };
 
 /// Maximum scene transitions per command. Prevents both deep sub-scene stacks
 /// and flat goto cycles (where the stack stays at depth 1 but transitions loop).
 const MAX_TRANSITIONS_PER_COMMAND: usize = 32;
 
+/// Maximum depth of the scene stack — a scene plus the sub-scenes it has
+/// `call`ed. Guards against a call cycle that grows one frame per command.
+const MAX_CALL_DEPTH: usize = 8;
+
 const _: () = {
     assert!(
         MAX_TRANSITIONS_PER_COMMAND >= 8,
@@ -34,6 +43,7 @@ const _: () = {
 // Public types
 // ---------------------------------------------------------------------------
 
+#[derive(Clone)]
 pub struct SceneEngine {
     scenes: HashMap<String, Arc<SceneDefinition>>,
     stack: Vec<SceneFrame>,
@@ -42,11 +52,17 @@ pub struct SceneEngine {
     /// Counts scene transitions within a single `send()` call.
     /// Reset at the start of each command. Guards against goto cycles.
     transition_count: usize,
+    /// What scenes did, when coverage is on (simulation only).
+    coverage: Option<Coverage>,
 }
 
+#[derive(Clone)]
 struct SceneFrame {
     def: Arc<SceneDefinition>,
     ctx: SceneCtx,
+    /// While a sub-scene this scene called is running: the branches to
+    /// evaluate here when it returns.
+    resume: Vec<NextBranch>,
 }
 
 #[derive(Debug)]
@@ -57,7 +73,9 @@ pub enum EngineCommand {
     SetActiveFemale(FemaleNpcKey),
 }
 
-#[derive(Debug, Clone)]
+/// Serialized for the dev IPC event stream as `{"event": "prose_added", "data": ...}`.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
 pub enum EngineEvent {
     ProseAdded(String),
     /// An inner thought or emotional aside. The UI renders this in a distinct style.
@@ -71,10 +89,13 @@ pub enum EngineEvent {
     SceneFinished,
     /// Hub scene chose a scheduler slot — UI should run the scheduler for this slot.
     SlotRequested(String),
+    /// A body-change rule fired at week rollover. Carries the rule's message,
+    /// reported ahead of whatever the command itself produces.
+    BodyChanged(String),
     ErrorOccurred(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct NpcActivatedData {
     pub name: String,
     pub age: undone_domain::Age,
@@ -103,11 +124,54 @@ pub struct BoundNpcData {
     pub npc: NpcActivatedData,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct ActionView {
     pub id: String,
     pub label: String,
     pub detail: String,
+    /// Set for check actions.
+    pub check: Option<CheckView>,
+}
+
+impl ActionView {
+    /// A spent red check: shown, but can't be chosen.
+    pub fn is_locked(&self) -> bool {
+        self.check.as_ref().is_some_and(|check| check.spent)
+    }
+}
+
+/// What the UI may show about a check action. `odds_pass` is internal — the
+/// player sees `phrase`, never the number.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CheckView {
+    pub skill: String,
+    /// Pass chance, 0.0–1.0 (0.0 once spent).
+    pub odds_pass: f32,
+    pub check_type: CheckType,
+    pub spent: bool,
+    /// The pack's odds phrase for `odds_pass`; `None` when spent or when no
+    /// pack declares bands.
+    pub phrase: Option<String>,
+}
+
+impl CheckView {
+    fn new(check: &Check, world: &World, registry: &PackRegistry) -> Self {
+        let spent = check::is_spent(check, world);
+        let percent = if spent {
+            0
+        } else {
+            check::pass_target(check, world, registry)
+        };
+        Self {
+            skill: check.skill.clone(),
+            odds_pass: percent as f32 / 100.0,
+            check_type: check.check_type,
+            spent,
+            phrase: (!spent)
+                .then(|| registry.odds_phrase(percent).map(str::to_owned))
+                .flatten(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -149,15 +213,23 @@ impl SceneEngine {
             events: VecDeque::new(),
             rng: SmallRng::from_entropy(),
             transition_count: 0,
+            coverage: None,
         }
     }
 
+    /// Reseed the engine's rng — NPC action picks and skill-check rolls — so a
+    /// run can be reproduced. Engines start from entropy.
+    pub fn seed_rng(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
     /// Dispatch a command. The engine may push zero or more events.
     pub fn send(&mut self, cmd: EngineCommand, world: &mut World, registry: &PackRegistry) {
         self.transition_count = 0;
+        self.report_body_notices(world);
         match cmd {
             EngineCommand::StartScene(id) => {
-                self.start_scene(id, world, registry, None, None, HashMap::new());
+                self.start_scene(id, world, registry, SceneCtx::new());
             }
             EngineCommand::ChooseAction(action_id) => {
                 self.choose_action(action_id, world, registry);
@@ -185,6 +257,25 @@ impl SceneEngine {
         }
     }
 
+    /// Queue a `BodyChanged` event for each body-change notice the world is
+    /// holding. Scene starts outside [`send`](Self::send) call this first so
+    /// the notices come before the new scene's prose.
+    pub fn report_body_notices(&mut self, world: &mut World) {
+        for text in world.game_data.body_notices.drain(..) {
+            self.events.push_back(EngineEvent::BodyChanged(text));
+        }
+    }
+
+    /// Start keeping a [`Coverage`] log. Prose renders slower while it is on.
+    pub fn enable_coverage(&mut self) {
+        self.coverage.get_or_insert_with(Coverage::default);
+    }
+
+    /// Hand over the coverage log, turning coverage off.
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        self.coverage.take()
+    }
+
     /// Drain all pending events, returning them in order.
     pub fn drain(&mut self) -> Vec<EngineEvent> {
         self.events.drain(..).collect()
@@ -200,6 +291,11 @@ impl SceneEngine {
         self.scenes.contains_key(scene_id)
     }
 
+    /// The loaded scene definitions, keyed by id.
+    pub fn scenes(&self) -> &HashMap<String, Arc<SceneDefinition>> {
+        &self.scenes
+    }
+
     /// Return a summary of every loaded scene (id, pack, description, action count).
     pub fn all_scene_summaries(&self) -> Vec<SceneSummary> {
         let mut summaries: Vec<SceneSummary> = self
@@ -235,7 +331,7 @@ impl SceneEngine {
                     label: a.label.clone(),
                     detail: a.detail.clone(),
                     has_condition: a.condition.is_some(),
-                    has_next: !a.next.is_empty(),
+                    has_next: a.next_branches().next().is_some(),
                 })
                 .collect(),
             npc_action_count: def.npc_actions.len(),
@@ -248,6 +344,47 @@ impl SceneEngine {
         self.stack.last().map(|frame| frame.def.id.clone())
     }
 
+    /// The running scene's context — active NPCs, role bindings, scene flags
+    /// and variables — for scripts run against the live game from outside it.
+    pub fn current_ctx(&self) -> Option<&SceneCtx> {
+        self.stack.last().map(|frame| &frame.ctx)
+    }
+
+    pub fn current_ctx_mut(&mut self) -> Option<&mut SceneCtx> {
+        self.stack.last_mut().map(|frame| &mut frame.ctx)
+    }
+
+    /// A stable text key for the scene-local state of the running stack: each
+    /// frame's scene id, flags, variables and cached rolls. Two engines with
+    /// the same key (and the same world) play the same from here on.
+    pub fn runtime_key(&self) -> String {
+        let mut key = String::new();
+        for frame in &self.stack {
+            let mut flags: Vec<&String> = frame.ctx.scene_flags.iter().collect();
+            flags.sort();
+            let mut vars: Vec<String> = frame
+                .ctx
+                .vars
+                .iter()
+                .map(|(name, var)| format!("{name}={:?}", var.value))
+                .collect();
+            vars.sort();
+            let mut rolls: Vec<String> = frame
+                .ctx
+                .skill_rolls
+                .borrow()
+                .iter()
+                .map(|(skill, roll)| format!("{skill}={roll}"))
+                .collect();
+            rolls.sort();
+            key.push_str(&format!(
+                "{}|{:?}|{:?}|{:?};",
+                frame.def.id, flags, vars, rolls
+            ));
+        }
+        key
+    }
+
     pub fn current_bound_npcs(&self, world: &World, registry: &PackRegistry) -> Vec<BoundNpcData> {
         let Some(frame) = self.stack.last() else {
             return Vec::new();
@@ -306,14 +443,10 @@ impl SceneEngine {
         registry: &PackRegistry,
     ) {
         self.transition_count = 0;
-        self.start_scene(
-            scene_id,
-            world,
-            registry,
-            active_male,
-            active_female,
-            HashMap::new(),
-        );
+        let mut ctx = SceneCtx::new();
+        ctx.active_male = active_male;
+        ctx.active_female = active_female;
+        self.start_scene(scene_id, world, registry, ctx);
     }
 
     pub fn start_scene_with_role_bindings(
@@ -326,14 +459,11 @@ impl SceneEngine {
         registry: &PackRegistry,
     ) {
         self.transition_count = 0;
-        self.start_scene(
-            scene_id,
-            world,
-            registry,
-            active_male,
-            active_female,
-            role_bindings,
-        );
+        let mut ctx = SceneCtx::new();
+        ctx.active_male = active_male;
+        ctx.active_female = active_female;
+        ctx.role_bindings = role_bindings;
+        self.start_scene(scene_id, world, registry, ctx);
     }
 
     /// Convenience: send a ChooseAction command and immediately drain events.
@@ -360,6 +490,36 @@ impl SceneEngine {
         self.transition_count = 0;
     }
 
+    /// Swap in a fresh set of scene definitions (dev-mode hot reload). Running
+    /// frames keep the definitions they started with until they restart.
+    pub fn replace_scenes(&mut self, scenes: HashMap<String, Arc<SceneDefinition>>) {
+        self.scenes = scenes;
+    }
+
+    /// Restart the running scene from its current definition with the
+    /// bindings it was started with: active NPCs, role bindings and call
+    /// params. Flags, vars and rolls start fresh and the intro plays again.
+    /// Returns false, changing nothing, when no scene is running or its
+    /// definition is no longer loaded.
+    pub fn restart_current_scene(&mut self, world: &World, registry: &PackRegistry) -> bool {
+        let restartable = self
+            .stack
+            .last()
+            .is_some_and(|frame| self.scenes.contains_key(&frame.def.id));
+        if !restartable {
+            return false;
+        }
+        let frame = self.stack.pop().expect("checked a frame is running");
+        self.transition_count = 0;
+        let mut ctx = SceneCtx::new();
+        ctx.active_male = frame.ctx.active_male;
+        ctx.active_female = frame.ctx.active_female;
+        ctx.role_bindings = frame.ctx.role_bindings;
+        ctx.params = frame.ctx.params;
+        self.start_scene(frame.def.id.clone(), world, registry, ctx);
+        true
+    }
+
     // -----------------------------------------------------------------------
     // Private: condition evaluation helper
     // -----------------------------------------------------------------------
@@ -404,18 +564,44 @@ impl SceneEngine {
         events.push_back(EngineEvent::ErrorOccurred(msg));
     }
 
+    /// Render prose, logging which of its branches rendered when coverage is on.
+    fn render(
+        template: &str,
+        site: ProseSite,
+        scene_id: &str,
+        world: &World,
+        ctx: &SceneCtx,
+        registry: &PackRegistry,
+        coverage: &mut Option<Coverage>,
+    ) -> Result<String, minijinja::Error> {
+        let Some(coverage) = coverage else {
+            return render_prose(template, world, ctx, registry);
+        };
+        let (prose, branches) = render_prose_traced(template, world, ctx, registry)?;
+        for index in branches {
+            coverage.hit(scene_id, CoveragePoint::Branch(site.clone(), index));
+        }
+        Ok(prose)
+    }
+
+    fn cover(coverage: &mut Option<Coverage>, scene_id: &str, point: CoveragePoint) {
+        if let Some(coverage) = coverage {
+            coverage.hit(scene_id, point);
+        }
+    }
+
     // -----------------------------------------------------------------------
     // Private: scene lifecycle
     // -----------------------------------------------------------------------
 
+    /// Push scene `id` with `ctx` (its NPC bindings and params) and play its
+    /// intro. Its vars start fresh from the declarations.
     fn start_scene(
         &mut self,
         id: String,
         world: &World,
         registry: &PackRegistry,
-        active_male: Option<MaleNpcKey>,
-        active_female: Option<FemaleNpcKey>,
-        role_bindings: HashMap<String, SceneNpcRef>,
+        mut ctx: SceneCtx,
     ) {
         self.transition_count += 1;
         if self.transition_count > MAX_TRANSITIONS_PER_COMMAND {
@@ -445,13 +631,15 @@ impl SceneEngine {
             }
         };
 
-        let mut ctx = SceneCtx::new();
         ctx.scene_id = Some(def.id.clone());
-        ctx.active_male = active_male;
-        ctx.active_female = active_female;
-        ctx.role_bindings = role_bindings;
+        ctx.vars = def.vars.clone().into_iter().collect();
+        ctx.roll_rng = RefCell::new(Some(SmallRng::seed_from_u64(self.rng.gen())));
+        let (active_male, active_female) = (ctx.active_male, ctx.active_female);
+        if let Some(coverage) = &mut self.coverage {
+            coverage.started(&def.id);
+        }
 
-        let intro_prose = Self::select_intro_prose(
+        let (intro_site, intro_prose) = Self::select_intro_prose(
             &def.intro_variants,
             &def.intro_prose,
             world,
@@ -461,21 +649,40 @@ impl SceneEngine {
             &mut self.events,
         );
 
-        match render_prose(intro_prose, world, &ctx, registry) {
+        let intro_point = match &intro_site {
+            ProseSite::IntroVariant(index) => CoveragePoint::IntroVariant(*index),
+            _ => CoveragePoint::Beat(Beat::Intro),
+        };
+        Self::cover(&mut self.coverage, &def.id, intro_point);
+        match Self::render(
+            intro_prose,
+            intro_site,
+            &def.id,
+            world,
+            &ctx,
+            registry,
+            &mut self.coverage,
+        ) {
             Ok(prose) => self.events.push_back(EngineEvent::ProseAdded(prose)),
             Err(e) => Self::emit_template_error(&mut self.events, &def.id, "intro prose", &e),
         }
 
         Self::render_thoughts(
             &def.intro_thoughts,
+            &Beat::Intro,
             world,
             &ctx,
             registry,
             &mut self.events,
             &def.id,
+            &mut self.coverage,
         );
 
-        self.stack.push(SceneFrame { def, ctx });
+        self.stack.push(SceneFrame {
+            def,
+            ctx,
+            resume: Vec::new(),
+        });
         if let Some(key) = active_male {
             if let Some(npc) = world.male_npc(key) {
                 self.events
@@ -525,32 +732,113 @@ impl SceneEngine {
             }
         }
 
-        let allow_npc = action.allow_npc_actions;
+        // A spent red check is shown locked; a click on it is likewise ignored.
+        if action
+            .check
+            .as_ref()
+            .is_some_and(|check| check::is_spent(check, world))
+        {
+            self.emit_actions(world, registry);
+            return;
+        }
 
-        if !action.prose.is_empty() {
-            let frame = self.stack.last().expect("engine stack must not be empty");
-            match render_prose(&action.prose, world, &frame.ctx, registry) {
-                Ok(prose) => self.events.push_back(EngineEvent::ProseAdded(prose)),
-                Err(e) => Self::emit_template_error(
-                    &mut self.events,
-                    &frame.def.id,
-                    &format!("action '{}'", action.id),
-                    &e,
-                ),
+        let context = format!("action '{}'", action.id);
+        self.play_beat(
+            Beat::Action(action.id.clone()),
+            &action.prose,
+            &action.thoughts,
+            action.effect.as_ref(),
+            &context,
+            world,
+            registry,
+        );
+
+        let next_branches = match &action.check {
+            Some(check) => {
+                let passed = {
+                    let frame = self.stack.last().expect("engine stack must not be empty");
+                    check::roll_check(check, world, &frame.ctx, registry)
+                };
+                if !passed && check.check_type == CheckType::Red {
+                    world.game_data.fail_check(&check.id);
+                }
+                let (outcome, beat) = if passed {
+                    (&check.pass, Beat::CheckPass(action.id.clone()))
+                } else {
+                    (&check.fail, Beat::CheckFail(action.id.clone()))
+                };
+                let context = format!(
+                    "action '{}' {}",
+                    action.id,
+                    if passed { "pass" } else { "fail" }
+                );
+                self.play_beat(
+                    beat,
+                    &outcome.prose,
+                    &outcome.thoughts,
+                    outcome.effect.as_ref(),
+                    &context,
+                    world,
+                    registry,
+                );
+                outcome.next.clone()
             }
+            None => action.next.clone(),
+        };
+
+        if action.allow_npc_actions {
+            self.run_npc_actions(world, registry);
         }
 
+        self.evaluate_next(next_branches, world, registry);
+    }
+
+    /// Render prose and thoughts, then apply the effect — the body of an action
+    /// or of one check outcome.
+    #[allow(clippy::too_many_arguments)]
+    fn play_beat(
+        &mut self,
+        beat: Beat,
+        prose: &str,
+        thoughts: &[Thought],
+        effect: Option<&CompiledScript>,
+        context: &str,
+        world: &mut World,
+        registry: &PackRegistry,
+    ) {
         {
             let frame = self.stack.last().expect("engine stack must not be empty");
-            let thoughts = action.thoughts.clone();
+            Self::cover(
+                &mut self.coverage,
+                &frame.def.id,
+                CoveragePoint::Beat(beat.clone()),
+            );
+            if !prose.is_empty() {
+                match Self::render(
+                    prose,
+                    ProseSite::Beat(beat.clone()),
+                    &frame.def.id,
+                    world,
+                    &frame.ctx,
+                    registry,
+                    &mut self.coverage,
+                ) {
+                    Ok(prose) => self.events.push_back(EngineEvent::ProseAdded(prose)),
+                    Err(e) => {
+                        Self::emit_template_error(&mut self.events, &frame.def.id, context, &e)
+                    }
+                }
+            }
             let scene_id = frame.def.id.clone();
             Self::render_thoughts(
-                &thoughts,
+                thoughts,
+                &beat,
                 world,
                 &frame.ctx,
                 registry,
                 &mut self.events,
                 &scene_id,
+                &mut self.coverage,
             );
         }
 
@@ -559,7 +847,7 @@ impl SceneEngine {
                 .stack
                 .last_mut()
                 .expect("engine stack must not be empty");
-            match &action.effect {
+            match effect {
                 Some(script) => apply_effect_script(script, world, &mut frame.ctx, registry),
                 None => Vec::new(),
             }
@@ -570,20 +858,14 @@ impl SceneEngine {
         for msg in effect_errors {
             self.events.push_back(EngineEvent::ErrorOccurred(msg));
         }
-
-        if allow_npc {
-            self.run_npc_actions(world, registry);
-        }
-
-        let next_branches = action.next.clone();
-        self.evaluate_next(next_branches, world, registry);
     }
 
     // -----------------------------------------------------------------------
     // Private: thought and narrator variant helpers
     // -----------------------------------------------------------------------
 
-    /// Select the first narrator variant whose condition passes, or fall back to `base`.
+    /// Select the first narrator variant whose condition passes, or fall back to
+    /// `base`, with the site of the prose picked.
     fn select_intro_prose<'a>(
         variants: &'a [NarratorVariant],
         base: &'a str,
@@ -592,8 +874,8 @@ impl SceneEngine {
         registry: &PackRegistry,
         scene_id: &str,
         events: &mut VecDeque<EngineEvent>,
-    ) -> &'a str {
-        for variant in variants {
+    ) -> (ProseSite, &'a str) {
+        for (index, variant) in variants.iter().enumerate() {
             if Self::eval_condition(
                 &variant.condition,
                 world,
@@ -603,22 +885,25 @@ impl SceneEngine {
                 "intro_variant",
                 Some(events),
             ) {
-                return &variant.prose;
+                return (ProseSite::IntroVariant(index), &variant.prose);
             }
         }
-        base
+        (ProseSite::Beat(Beat::Intro), base)
     }
 
     /// Evaluate and emit thought events for all thoughts whose conditions pass.
+    #[allow(clippy::too_many_arguments)]
     fn render_thoughts(
         thoughts: &[Thought],
+        beat: &Beat,
         world: &World,
         ctx: &SceneCtx,
         registry: &PackRegistry,
         events: &mut VecDeque<EngineEvent>,
         scene_id: &str,
+        coverage: &mut Option<Coverage>,
     ) {
-        for thought in thoughts {
+        for (index, thought) in thoughts.iter().enumerate() {
             let passes = match &thought.condition {
                 None => true,
                 Some(expr) => Self::eval_condition(
@@ -632,8 +917,22 @@ impl SceneEngine {
                 ),
             };
             if passes {
-                match render_prose(&thought.prose, world, ctx, registry) {
+                let site = ProseSite::Thought(beat.clone(), index);
+                match Self::render(
+                    &thought.prose,
+                    site,
+                    scene_id,
+                    world,
+                    ctx,
+                    registry,
+                    coverage,
+                ) {
                     Ok(text) if !text.trim().is_empty() => {
+                        Self::cover(
+                            coverage,
+                            scene_id,
+                            CoveragePoint::Thought(beat.clone(), index),
+                        );
                         events.push_back(EngineEvent::ThoughtAdded {
                             text,
                             style: thought.style.clone(),
@@ -667,10 +966,19 @@ impl SceneEngine {
                 None => true,
             };
             if visible {
+                Self::cover(
+                    &mut self.coverage,
+                    &frame.def.id,
+                    CoveragePoint::Offered(action.id.clone()),
+                );
                 views.push(ActionView {
                     id: action.id.clone(),
                     label: action.label.clone(),
                     detail: action.detail.clone(),
+                    check: action
+                        .check
+                        .as_ref()
+                        .map(|check| CheckView::new(check, world, registry)),
                 });
             }
         }
@@ -731,7 +1039,7 @@ impl SceneEngine {
 
         let Some(idx) = selected_idx else { return };
 
-        let (prose, effect, next_branches): (String, Option<_>, Vec<_>) = {
+        let (npc_action_id, prose, effect, next_branches): (String, String, Option<_>, Vec<_>) = {
             let frame = self.stack.last().expect("engine stack must not be empty");
             let Some(na) = frame.def.npc_actions.get(idx) else {
                 log::error!(
@@ -742,12 +1050,33 @@ impl SceneEngine {
                 );
                 return;
             };
-            (na.prose.clone(), na.effect.clone(), na.next.clone())
+            (
+                na.id.clone(),
+                na.prose.clone(),
+                na.effect.clone(),
+                na.next.clone(),
+            )
         };
 
+        let beat = Beat::NpcAction(npc_action_id);
+        if let Some(frame) = self.stack.last() {
+            Self::cover(
+                &mut self.coverage,
+                &frame.def.id,
+                CoveragePoint::Beat(beat.clone()),
+            );
+        }
         if !prose.is_empty() {
             let frame = self.stack.last().expect("engine stack must not be empty");
-            match render_prose(&prose, world, &frame.ctx, registry) {
+            match Self::render(
+                &prose,
+                ProseSite::Beat(beat),
+                &frame.def.id,
+                world,
+                &frame.ctx,
+                registry,
+                &mut self.coverage,
+            ) {
                 Ok(rendered) => self.events.push_back(EngineEvent::ProseAdded(rendered)),
                 Err(e) => Self::emit_template_error(
                     &mut self.events,
@@ -780,7 +1109,77 @@ impl SceneEngine {
         }
     }
 
-    fn evaluate_next(&mut self, branches: Vec<NextBranch>, world: &World, registry: &PackRegistry) {
+    /// Pop the current scene. Leaving is remembered: the scene id goes into the
+    /// player's memory log and that of every NPC the scene had bound.
+    fn leave_scene(&mut self, world: &mut World) {
+        let Some(frame) = self.stack.pop() else {
+            return;
+        };
+        let at = world.game_data.memory_stamp();
+        world.player.memory.record(frame.def.id.as_str(), at);
+        for key in frame.ctx.participants() {
+            if let Some(core) = world.npc_core_mut(key) {
+                core.memory.record(frame.def.id.as_str(), at);
+            }
+        }
+    }
+
+    /// Pop every frame — a `finish` or `slot` inside a called sub-scene ends its
+    /// callers too.
+    fn leave_all_scenes(&mut self, world: &mut World) {
+        while !self.stack.is_empty() {
+            self.leave_scene(world);
+        }
+    }
+
+    /// Start `call.scene` on top of the current frame. The callee shares the
+    /// caller's NPC bindings and gets the call's params.
+    fn call_scene(&mut self, call: &SceneCall, world: &World, registry: &PackRegistry) {
+        if self.stack.len() >= MAX_CALL_DEPTH {
+            let msg = format!(
+                "[scene-engine] call depth limit: can't call '{}' {MAX_CALL_DEPTH} scenes deep",
+                call.scene
+            );
+            log::error!("{msg}");
+            self.events.push_back(EngineEvent::ErrorOccurred(msg));
+            self.emit_actions(world, registry);
+            return;
+        }
+        let Some(frame) = self.stack.last_mut() else {
+            return;
+        };
+        frame.resume = call.then.clone();
+        let mut ctx = SceneCtx::new();
+        ctx.active_male = frame.ctx.active_male;
+        ctx.active_female = frame.ctx.active_female;
+        ctx.role_bindings = frame.ctx.role_bindings.clone();
+        ctx.params = call.params.clone().into_iter().collect();
+        self.start_scene(call.scene.clone(), world, registry, ctx);
+    }
+
+    /// End the current (called) scene and resume its caller with `value` as
+    /// `scene.result()`. A scene started directly has no caller; its `return`
+    /// finishes it.
+    fn return_to_caller(&mut self, value: ApiValue, world: &mut World, registry: &PackRegistry) {
+        if self.stack.len() < 2 {
+            self.leave_all_scenes(world);
+            self.events.push_back(EngineEvent::NpcActivated(None));
+            self.events.push_back(EngineEvent::SceneFinished);
+            return;
+        }
+        self.leave_scene(world);
+        let frame = self.stack.last_mut().expect("caller frame checked above");
+        frame.ctx.returned = Some(value);
+        let resume = std::mem::take(&mut frame.resume);
+        self.evaluate_next(resume, world, registry);
+    }
+
+    fn evaluate_next(
+        &mut self,
+        branches: Vec<NextBranch>,
+        world: &mut World,
+        registry: &PackRegistry,
+    ) {
         if branches.is_empty() {
             self.emit_actions(world, registry);
             return;
@@ -808,7 +1207,7 @@ impl SceneEngine {
             }
 
             if branch.finish {
-                self.stack.pop();
+                self.leave_all_scenes(world);
                 self.events.push_back(EngineEvent::NpcActivated(None));
                 self.events.push_back(EngineEvent::SceneFinished);
                 return;
@@ -816,14 +1215,24 @@ impl SceneEngine {
 
             if let Some(goto) = &branch.goto {
                 let target = goto.clone();
-                self.stack.pop();
-                self.start_scene(target, world, registry, None, None, HashMap::new());
+                self.leave_scene(world);
+                self.start_scene(target, world, registry, SceneCtx::new());
+                return;
+            }
+
+            if let Some(call) = &branch.call {
+                self.call_scene(call, world, registry);
+                return;
+            }
+
+            if let Some(value) = &branch.returns {
+                self.return_to_caller(value.clone(), world, registry);
                 return;
             }
 
             if let Some(slot_name) = &branch.slot {
                 let slot = slot_name.clone();
-                self.stack.pop();
+                self.leave_all_scenes(world);
                 self.events.push_back(EngineEvent::NpcActivated(None));
                 self.events.push_back(EngineEvent::SlotRequested(slot));
                 return;
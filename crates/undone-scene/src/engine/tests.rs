@@ -17,6 +17,8 @@ fn make_simple_scene() -> SceneDefinition {
         id: "test::simple".into(),
         pack: "test".into(),
         intro_prose: "It begins.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![
@@ -30,6 +32,7 @@ fn make_simple_scene() -> SceneDefinition {
                 effect: None,
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             },
             Action {
                 id: "leave".into(),
@@ -44,8 +47,11 @@ fn make_simple_scene() -> SceneDefinition {
                     goto: None,
                     slot: None,
                     finish: true,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             },
         ],
         npc_actions: vec![],
@@ -96,6 +102,9 @@ fn start_scene_with_role_bindings_exposes_bound_npcs() {
         core: NpcCore {
             name: "Dan".into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: Age::MidLateTwenties,
             race: "white".into(),
             eye_colour: "blue".into(),
@@ -129,6 +138,9 @@ fn start_scene_with_role_bindings_exposes_bound_npcs() {
         core: NpcCore {
             name: "Mia".into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: Age::MidLateTwenties,
             race: "white".into(),
             eye_colour: "green".into(),
@@ -279,6 +291,8 @@ fn condition_filters_actions() {
         id: "test::conditional".into(),
         pack: "test".into(),
         intro_prose: "Conditional test.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![
@@ -292,6 +306,7 @@ fn condition_filters_actions() {
                 effect: None,
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             },
             Action {
                 id: "special".into(),
@@ -303,6 +318,7 @@ fn condition_filters_actions() {
                 effect: None,
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             },
         ],
         npc_actions: vec![],
@@ -347,6 +363,9 @@ fn set_active_male_emits_npc_activated() {
         core: NpcCore {
             name: "Jake".into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: Age::MidLateTwenties,
             race: "white".into(),
             eye_colour: "blue".into(),
@@ -417,6 +436,9 @@ fn npc_activated_uses_display_name_when_set() {
         core: NpcCore {
             name: "Brian".into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: Age::MidLateTwenties,
             race: "white".into(),
             eye_colour: "blue".into(),
@@ -488,6 +510,36 @@ fn npc_activated_uses_display_name_when_set() {
     );
 }
 
+#[test]
+fn finishing_a_scene_is_remembered_by_player_and_npcs() {
+    let mut engine = make_engine_with(make_simple_scene());
+    let mut world = make_world();
+    let mut registry = undone_packs::PackRegistry::new();
+    let personality = registry.intern_personality("ROMANTIC");
+    let key = world
+        .male_npcs
+        .insert(undone_world::test_helpers::make_test_male_npc(personality));
+
+    for _ in 0..2 {
+        engine.start_scene_with_bindings("test::simple".into(), Some(key), None, &world, &registry);
+        engine.drain();
+        engine.send(
+            EngineCommand::ChooseAction("leave".into()),
+            &mut world,
+            &registry,
+        );
+        engine.drain();
+    }
+
+    assert_eq!(world.player.memory.times("test::simple"), 2);
+    let npc_memory = &world.male_npcs[key].core.memory;
+    assert_eq!(npc_memory.times("test::simple"), 2);
+    assert_eq!(
+        npc_memory.latest().unwrap().at,
+        world.game_data.memory_stamp()
+    );
+}
+
 #[test]
 fn scene_finished_clears_npc_activated() {
     let mut engine = make_engine_with(make_simple_scene());
@@ -523,6 +575,8 @@ fn goto_transition_works_normally() {
         id: "test::a".into(),
         pack: "test".into(),
         intro_prose: "A".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![Action {
@@ -538,8 +592,11 @@ fn goto_transition_works_normally() {
                 goto: Some("test::b".into()),
                 slot: None,
                 finish: false,
+                call: None,
+                returns: None,
             }],
             thoughts: vec![],
+            check: None,
         }],
         npc_actions: vec![],
     };
@@ -547,6 +604,8 @@ fn goto_transition_works_normally() {
         id: "test::b".into(),
         pack: "test".into(),
         intro_prose: "B".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![Action {
@@ -559,6 +618,7 @@ fn goto_transition_works_normally() {
             effect: None,
             next: vec![],
             thoughts: vec![],
+            check: None,
         }],
         npc_actions: vec![],
     };
@@ -658,6 +718,41 @@ fn reset_runtime_clears_stack_and_pending_events() {
     assert!(engine.drain().is_empty());
 }
 
+#[test]
+fn restart_current_scene_replays_the_reloaded_definition_with_its_bindings() {
+    let mut engine = make_engine_with(make_simple_scene());
+    let mut world = make_world();
+    let registry = PackRegistry::new();
+    assert!(!engine.restart_current_scene(&world, &registry));
+
+    engine.send(
+        EngineCommand::StartScene("test::simple".into()),
+        &mut world,
+        &registry,
+    );
+    engine.drain();
+    let ctx = engine.current_ctx_mut().unwrap();
+    ctx.active_male = Some(MaleNpcKey::default());
+    ctx.params.insert("mood".into(), ApiValue::Int(3));
+    ctx.set_flag("SEEN");
+
+    let mut edited = make_simple_scene();
+    edited.intro_prose = "It begins again.".into();
+    engine.replace_scenes(HashMap::from([(edited.id.clone(), edited.into())]));
+    assert!(engine.restart_current_scene(&world, &registry));
+
+    let events = engine.drain();
+    assert!(matches!(&events[0], EngineEvent::ProseAdded(p) if p == "It begins again."));
+    let ctx = engine.current_ctx().unwrap();
+    assert_eq!(ctx.active_male, Some(MaleNpcKey::default()));
+    assert_eq!(ctx.params.get("mood"), Some(&ApiValue::Int(3)));
+    assert!(!ctx.has_flag("SEEN"));
+
+    engine.replace_scenes(HashMap::new());
+    assert!(!engine.restart_current_scene(&world, &registry));
+    assert_eq!(engine.current_scene_id().as_deref(), Some("test::simple"));
+}
+
 #[test]
 fn start_unknown_scene_emits_error_and_finishes() {
     let mut engine = SceneEngine::new(HashMap::new());
@@ -692,6 +787,8 @@ fn slot_branch_emits_slot_requested() {
         id: "test::hub".into(),
         pack: "test".into(),
         intro_prose: "Hub scene.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![Action {
@@ -707,8 +804,11 @@ fn slot_branch_emits_slot_requested() {
                 goto: None,
                 slot: Some("free_time".into()),
                 finish: false,
+                call: None,
+                returns: None,
             }],
             thoughts: vec![],
+            check: None,
         }],
         npc_actions: vec![],
     };
@@ -756,6 +856,8 @@ fn intro_thought_emits_thought_added() {
         id: "test::thought".into(),
         pack: "test".into(),
         intro_prose: "The rain hammers the shelter roof.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![thought],
         actions: vec![],
@@ -794,6 +896,8 @@ fn action_thought_emits_after_action_prose() {
         id: "test::action_thought".into(),
         pack: "test".into(),
         intro_prose: "Shelter.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![Action {
@@ -809,8 +913,11 @@ fn action_thought_emits_after_action_prose() {
                 goto: None,
                 slot: None,
                 finish: true,
+                call: None,
+                returns: None,
             }],
             thoughts: vec![thought],
+            check: None,
         }],
         npc_actions: vec![],
     };
@@ -852,6 +959,8 @@ fn effect_error_emits_error_occurred_event() {
         id: "test::effect_error".into(),
         pack: "test".into(),
         intro_prose: "Something happens.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![Action {
@@ -867,8 +976,11 @@ fn effect_error_emits_error_occurred_event() {
                 goto: None,
                 slot: None,
                 finish: true,
+                call: None,
+                returns: None,
             }],
             thoughts: vec![],
+            check: None,
         }],
         npc_actions: vec![],
     };
@@ -909,6 +1021,8 @@ fn action_condition_error_emits_error_occurred_and_hides_action() {
         id: "test::condition_error".into(),
         pack: "test".into(),
         intro_prose: "Condition test.".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![
@@ -922,6 +1036,7 @@ fn action_condition_error_emits_error_occurred_and_hides_action() {
                 effect: None,
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             },
             Action {
                 id: "broken".into(),
@@ -933,6 +1048,7 @@ fn action_condition_error_emits_error_occurred_and_hides_action() {
                 effect: None,
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             },
         ],
         npc_actions: vec![],
@@ -982,6 +1098,8 @@ fn intro_template_error_emits_error_occurred() {
         id: "test::template_error".into(),
         pack: "test".into(),
         intro_prose: "{{ m.getLiking() }}".into(),
+        params: Default::default(),
+        vars: Default::default(),
         intro_variants: vec![],
         intro_thoughts: vec![],
         actions: vec![],
@@ -1015,3 +1133,444 @@ fn intro_template_error_emits_error_occurred() {
             events
         );
 }
+
+#[test]
+fn body_notices_are_reported_before_the_next_scene() {
+    let mut engine = make_engine_with(make_simple_scene());
+    let mut world = make_world();
+    let registry = undone_packs::PackRegistry::new();
+    world
+        .game_data
+        .body_notices
+        .push("Your hair brushes your collar now.".into());
+
+    engine.send(
+        EngineCommand::StartScene("test::simple".into()),
+        &mut world,
+        &registry,
+    );
+    let events = engine.drain();
+    assert!(
+        matches!(events.first(), Some(EngineEvent::BodyChanged(text)) if text.contains("collar")),
+        "expected BodyChanged first, got {events:?}"
+    );
+    assert!(world.game_data.body_notices.is_empty());
+}
+
+fn make_check_scene(check_type: crate::types::CheckType) -> SceneDefinition {
+    let outcome = |prose: &str| crate::types::CheckOutcome {
+        prose: prose.into(),
+        effect: None,
+        next: vec![],
+        thoughts: vec![],
+    };
+    SceneDefinition {
+        id: "test::check".into(),
+        pack: "test".into(),
+        intro_prose: "It begins.".into(),
+        params: Default::default(),
+        vars: Default::default(),
+        intro_variants: vec![],
+        intro_thoughts: vec![],
+        actions: vec![Action {
+            id: "hold".into(),
+            label: "Hold still".into(),
+            detail: String::new(),
+            condition: None,
+            prose: "You try.".into(),
+            allow_npc_actions: false,
+            effect: None,
+            next: vec![],
+            thoughts: vec![],
+            check: Some(crate::types::Check {
+                id: "test::check::hold".into(),
+                skill: "COMPOSURE".into(),
+                base_dc: 50,
+                check_type,
+                pass: outcome("You hold."),
+                fail: outcome("You don't."),
+            }),
+        }],
+        npc_actions: vec![],
+    }
+}
+
+fn composure_registry() -> PackRegistry {
+    let mut registry = PackRegistry::new();
+    registry.register_skills(vec![undone_packs::data::SkillDef {
+        id: "COMPOSURE".into(),
+        name: "Composure".into(),
+        description: String::new(),
+        min: 0,
+        max: 100,
+        progression: None,
+    }]);
+    registry
+}
+
+fn prose_of(events: &[EngineEvent]) -> Vec<&str> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            EngineEvent::ProseAdded(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn check_action_routes_to_the_outcome_the_roll_picks() {
+    let registry = composure_registry();
+    let composure = registry.resolve_skill("COMPOSURE").unwrap();
+    let mut engine = make_engine_with(make_check_scene(crate::types::CheckType::White));
+    let mut world = make_world();
+    world.player.skills.insert(
+        composure,
+        SkillValue {
+            value: 50,
+            modifier: 0,
+        },
+    );
+
+    for (roll, expected) in [(40, "You hold."), (60, "You don't.")] {
+        engine.send(
+            EngineCommand::StartScene("test::check".into()),
+            &mut world,
+            &registry,
+        );
+        engine.drain();
+        engine
+            .stack
+            .last()
+            .unwrap()
+            .ctx
+            .set_skill_roll("COMPOSURE", roll);
+        let events = engine.advance_with_action("hold", &mut world, &registry);
+        assert_eq!(prose_of(&events), vec!["You try.", expected]);
+    }
+    assert!(!world.game_data.has_failed_check("test::check::hold"));
+}
+
+#[test]
+fn failed_red_check_is_recorded_and_stays_failed() {
+    let registry = composure_registry();
+    let mut engine = make_engine_with(make_check_scene(crate::types::CheckType::Red));
+    let mut world = make_world();
+
+    engine.send(
+        EngineCommand::StartScene("test::check".into()),
+        &mut world,
+        &registry,
+    );
+    engine.drain();
+    engine
+        .stack
+        .last()
+        .unwrap()
+        .ctx
+        .set_skill_roll("COMPOSURE", 95);
+    engine.advance_with_action("hold", &mut world, &registry);
+    assert!(world.game_data.has_failed_check("test::check::hold"));
+
+    engine
+        .stack
+        .last()
+        .unwrap()
+        .ctx
+        .set_skill_roll("COMPOSURE", 1);
+    let events = engine.advance_with_action("hold", &mut world, &registry);
+    assert!(
+        prose_of(&events).is_empty(),
+        "a spent check can't be chosen"
+    );
+    let Some(EngineEvent::ActionsAvailable(views)) = events.last() else {
+        panic!("expected refreshed actions, got {events:?}");
+    };
+    let view = views[0].check.as_ref().expect("check metadata");
+    assert!(views[0].is_locked());
+    assert_eq!(view.odds_pass, 0.0);
+    assert_eq!(view.phrase, None);
+}
+
+#[test]
+fn check_actions_carry_odds_and_the_pack_phrase() {
+    let mut registry = composure_registry();
+    registry.register_odds(
+        toml::from_str(
+            r#"
+            [[band]]
+            min = 0
+            phrase = "No."
+            [[band]]
+            min = 60
+            phrase = "Likely."
+            "#,
+        )
+        .unwrap(),
+    );
+    let composure = registry.resolve_skill("COMPOSURE").unwrap();
+    let mut engine = make_engine_with(make_check_scene(crate::types::CheckType::White));
+    let mut world = make_world();
+    world.player.skills.insert(
+        composure,
+        SkillValue {
+            value: 70,
+            modifier: 0,
+        },
+    );
+
+    engine.send(
+        EngineCommand::StartScene("test::check".into()),
+        &mut world,
+        &registry,
+    );
+    let events = engine.drain();
+    let views = events
+        .iter()
+        .find_map(|e| match e {
+            EngineEvent::ActionsAvailable(views) => Some(views),
+            _ => None,
+        })
+        .expect("actions");
+    let view = views[0].check.as_ref().expect("check metadata");
+    assert_eq!(view.skill, "COMPOSURE");
+    assert_eq!(view.odds_pass, 0.7);
+    assert_eq!(view.check_type, crate::types::CheckType::White);
+    assert!(!view.spent);
+    assert_eq!(view.phrase.as_deref(), Some("Likely."));
+}
+
+/// Load scene files written as TOML, cross-referenced like a pack load.
+fn load_toml_scenes(
+    name: &str,
+    registry: &PackRegistry,
+    files: &[&str],
+) -> HashMap<String, Arc<SceneDefinition>> {
+    let dir = std::env::temp_dir().join(format!(
+        "undone_engine_{name}_{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    for (i, text) in files.iter().enumerate() {
+        std::fs::write(dir.join(format!("{i}.toml")), text).unwrap();
+    }
+    let scenes = crate::load_scenes(&dir, registry).unwrap();
+    crate::loader::validate_cross_references(&scenes).unwrap();
+    std::fs::remove_dir_all(dir).unwrap();
+    scenes
+}
+
+const COUNTER_SCENE: &str = r#"
+[scene]
+id = "test::counter"
+pack = "test"
+description = "caller"
+
+[intro]
+prose = "At the counter."
+
+[[actions]]
+id = "order"
+label = "Order"
+  [[actions.next]]
+  call = "test::buy"
+  params = { item = "coffee", price = 4 }
+    [[actions.next.then]]
+    if = 'scene.result() == "broke"'
+    finish = true
+
+[[actions]]
+id = "drink"
+label = "Drink"
+condition = 'scene.result() == "paid"'
+"#;
+
+const BUY_SCENE: &str = r#"
+[scene]
+id = "test::buy"
+pack = "test"
+description = "callee"
+
+[scene.params]
+item = "string"
+price = "int"
+
+[intro]
+prose = "You buy a {{ scene.param('item') }}."
+
+[[actions]]
+id = "pay"
+label = "Pay"
+  [[actions.next]]
+  if = 'scene.param("price") < 10'
+  return = "paid"
+  [[actions.next]]
+  return = "broke"
+
+[[actions]]
+id = "walk_out"
+label = "Walk out"
+  [[actions.next]]
+  finish = true
+"#;
+
+fn action_ids(events: &[EngineEvent]) -> Vec<String> {
+    events
+        .iter()
+        .rev()
+        .find_map(|e| match e {
+            EngineEvent::ActionsAvailable(views) => {
+                Some(views.iter().map(|v| v.id.clone()).collect())
+            }
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+#[test]
+fn call_runs_sub_scene_with_params_and_resumes_caller_with_result() {
+    let registry = PackRegistry::new();
+    let mut engine = SceneEngine::new(load_toml_scenes(
+        "call",
+        &registry,
+        &[COUNTER_SCENE, BUY_SCENE],
+    ));
+    let mut world = make_world();
+
+    engine.send(
+        EngineCommand::StartScene("test::counter".into()),
+        &mut world,
+        &registry,
+    );
+    assert_eq!(action_ids(&engine.drain()), ["order"]);
+
+    let events = engine.advance_with_action("order", &mut world, &registry);
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, EngineEvent::ProseAdded(s) if s == "You buy a coffee.")),
+        "callee reads its params in prose, got: {events:?}"
+    );
+    assert_eq!(action_ids(&events), ["pay", "walk_out"]);
+    assert_eq!(engine.current_scene_id().as_deref(), Some("test::buy"));
+
+    // The callee returns "paid"; no `then` branch matches, so the caller's
+    // actions come back — including the one gated on the result.
+    let events = engine.advance_with_action("pay", &mut world, &registry);
+    assert_eq!(action_ids(&events), ["order", "drink"]);
+    assert_eq!(engine.current_scene_id().as_deref(), Some("test::counter"));
+    assert!(!events.iter().any(|e| matches!(
+        e,
+        EngineEvent::SceneFinished | EngineEvent::ErrorOccurred(_)
+    )));
+    assert!(world.player.memory.times("test::buy") >= 1);
+}
+
+#[test]
+fn finish_inside_a_sub_scene_ends_its_callers_too() {
+    let registry = PackRegistry::new();
+    let mut engine = SceneEngine::new(load_toml_scenes(
+        "unwind",
+        &registry,
+        &[COUNTER_SCENE, BUY_SCENE],
+    ));
+    let mut world = make_world();
+
+    engine.send(
+        EngineCommand::StartScene("test::counter".into()),
+        &mut world,
+        &registry,
+    );
+    engine.advance_with_action("order", &mut world, &registry);
+    let events = engine.advance_with_action("walk_out", &mut world, &registry);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, EngineEvent::SceneFinished)));
+    assert_eq!(engine.current_scene_id(), None);
+}
+
+const BAR_SCENE: &str = r#"
+[scene]
+id = "test::bar"
+pack = "test"
+description = "counts drinks"
+
+[scene.vars]
+drinks = 0
+tension = { persist = "BAR_TENSION" }
+
+[intro]
+prose = "The bar."
+
+[[actions]]
+id = "drink"
+label = "Drink"
+condition = 'scene.get("drinks") < 2'
+prose = "Drinks so far: {{ scene.get('drinks') }}."
+effect = 'scene.add("drinks", 1); scene.add("tension", 3);'
+"#;
+
+#[test]
+fn scene_vars_count_within_a_run_and_persistent_ones_carry_over() {
+    let mut registry = PackRegistry::new();
+    registry.register_flags(
+        toml::from_str(
+            r#"
+            [[var]]
+            id      = "BAR_TENSION"
+            type    = "int"
+            default = 1
+            "#,
+        )
+        .unwrap(),
+    );
+    let mut engine = SceneEngine::new(load_toml_scenes("vars", &registry, &[BAR_SCENE]));
+    let mut world = make_world();
+
+    engine.send(
+        EngineCommand::StartScene("test::bar".into()),
+        &mut world,
+        &registry,
+    );
+    assert_eq!(action_ids(&engine.drain()), ["drink"]);
+    let events = engine.advance_with_action("drink", &mut world, &registry);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, EngineEvent::ProseAdded(s) if s == "Drinks so far: 0.")));
+    let events = engine.advance_with_action("drink", &mut world, &registry);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, EngineEvent::ProseAdded(s) if s == "Drinks so far: 1.")));
+    assert!(action_ids(&events).is_empty(), "two drinks is the limit");
+    assert_eq!(
+        world.game_data.var("BAR_TENSION"),
+        Some(&undone_domain::VarValue::Int(7))
+    );
+
+    // A new run starts the local counter over; the persistent var keeps its value.
+    engine.send(
+        EngineCommand::StartScene("test::bar".into()),
+        &mut world,
+        &registry,
+    );
+    assert_eq!(action_ids(&engine.drain()), ["drink"]);
+    engine.advance_with_action("drink", &mut world, &registry);
+    assert_eq!(
+        world.game_data.var("BAR_TENSION"),
+        Some(&undone_domain::VarValue::Int(10))
+    );
+}
+
+#[test]
+fn engine_events_serialize_with_a_snake_case_tag() {
+    let event = serde_json::to_value(EngineEvent::ProseAdded("Hi.".into())).unwrap();
+    assert_eq!(
+        event,
+        serde_json::json!({"event": "prose_added", "data": "Hi."})
+    );
+    let event = serde_json::to_value(EngineEvent::SceneFinished).unwrap();
+    assert_eq!(event, serde_json::json!({"event": "scene_finished"}));
+}
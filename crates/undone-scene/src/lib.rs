@@ -1,6 +1,10 @@
+pub mod check;
+pub mod coverage;
+pub mod diagnostic;
 pub mod effects;
 pub mod engine;
 pub mod loader;
+pub mod prose_lint;
 pub mod reachability;
 pub mod scene_ctx;
 pub mod scheduler;
@@ -8,13 +12,20 @@ pub mod script;
 pub mod simulator;
 pub mod template_ctx;
 pub mod types;
+pub mod variants;
 
 #[cfg(test)]
 mod set_npc_name_tests;
 
+pub use diagnostic::SourceSpan;
 pub use effects::EffectError;
-pub use engine::{ActionView, EngineCommand, EngineEvent, NpcActivatedData, SceneEngine};
-pub use loader::{load_scenes, validate_cross_references, SceneLoadError};
+pub use engine::{
+    ActionView, CheckView, EngineCommand, EngineEvent, NpcActivatedData, SceneEngine,
+};
+pub use loader::{
+    load_scene_source, load_scenes, validate_cross_references, validate_scene_references,
+    SceneLoadError,
+};
 pub use scene_ctx::{SceneCtx, SceneNpcRef};
 pub use scheduler::{
     load_schedule, validate_entry_scene_references, PickResult, Scheduler, SchedulerError,
@@ -22,7 +33,10 @@ pub use scheduler::{
 pub use script::{
     apply_effect_script, compile_condition, compile_effect, eval_bool, CompiledScript,
 };
-pub use types::{Action, NextBranch, NpcAction, SceneDefinition, SceneMeta, SceneToml};
+pub use types::{
+    Action, Check, CheckOutcome, CheckType, NextBranch, NpcAction, SceneDefinition, SceneMeta,
+    SceneToml,
+};
 
 #[cfg(test)]
 mod integration_tests {
@@ -60,6 +74,9 @@ mod integration_tests {
             core: NpcCore {
                 name: "Stranger".into(),
                 display_name: None,
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: Age::Thirties,
                 race: "white".into(),
                 eye_colour: "grey".into(),
@@ -262,9 +279,7 @@ mod integration_tests {
             let scene_id = pick.scene_id.clone();
 
             // Game loop responsibility: mark once-only scenes as played
-            if pick.once_only {
-                world.game_data.set_flag(format!("ONCE_{}", scene_id));
-            }
+            pick.mark_started(&mut world);
 
             visited.push(scene_id.clone());
 
@@ -380,9 +395,7 @@ mod integration_tests {
                 break;
             };
             let scene_id = pick.scene_id.clone();
-            if pick.once_only {
-                world.game_data.set_flag(format!("ONCE_{}", scene_id));
-            }
+            pick.mark_started(&mut world);
 
             engine.send(
                 EngineCommand::StartScene(scene_id.clone()),
@@ -554,6 +567,44 @@ mod integration_tests {
         assert!(eval_bool(&script, &world, &ctx, &registry).unwrap());
     }
 
+    #[test]
+    fn story_character_id_resolves_in_effects_and_role_reads() {
+        use crate::script::{apply_effect_script, compile_condition, compile_effect, eval_bool};
+        use crate::SceneCtx;
+        let (registry, _metas) = undone_packs::load_packs(&packs_dir()).unwrap();
+        let mut world = make_world_with_shy(&registry);
+        let mut ctx = SceneCtx::new();
+
+        // No scene binding: the story id alone must find the NPC.
+        let key = world.male_npcs.insert(make_male_npc());
+        world.male_npcs[key].core.story_id = Some("base::jake".to_string());
+
+        let effect =
+            compile_effect(r#"npc("base::jake").addLiking(1);"#, &registry, "test").unwrap();
+        let errors = apply_effect_script(&effect, &mut world, &mut ctx, &registry);
+        assert!(errors.is_empty(), "unexpected effect errors: {errors:?}");
+        assert_eq!(world.male_npcs[key].core.pc_liking, LikingLevel::Ok);
+
+        let cond = compile_condition(r#"role.getLiking("base::jake") == "Ok""#, &registry, "test")
+            .unwrap();
+        assert!(eval_bool(&cond, &world, &ctx, &registry).unwrap());
+    }
+
+    #[test]
+    fn undeclared_story_character_is_rejected_at_load() {
+        use crate::script::{compile_effect, ScriptError};
+        let (registry, _metas) = undone_packs::load_packs(&packs_dir()).unwrap();
+
+        let err = compile_effect(r#"npc("base::nobody").addLiking(1);"#, &registry, "test")
+            .expect_err("undeclared character id must fail the load gate");
+        assert!(
+            matches!(&err, ScriptError::UnknownId { kind, .. } if kind == "character"),
+            "expected unknown character, got: {err}"
+        );
+        // Plain refs are unaffected: "m", "f" and role ids are bound at runtime.
+        assert!(compile_effect(r#"npc("ROLE_JAKE").addLiking(1);"#, &registry, "test").is_ok());
+    }
+
     #[test]
     fn workplace_opening_branch_contract_exposes_memory_flags_and_callbacks() {
         use crate::scheduler::load_schedule;
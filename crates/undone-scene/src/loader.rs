@@ -1,15 +1,23 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use thiserror::Error;
+use undone_domain::VarType;
 use undone_packs::PackRegistry;
 
+use crate::diagnostic::SourceSpan;
+use crate::script::api::ApiValue;
+use crate::script::{
+    prose_scene_refs, script_library, source_scene_refs, CompiledScript, SceneNameRef,
+    ScriptLibraryError,
+};
 use crate::types::{
-    Action, ActionDef, NarratorVariant, NarratorVariantDef, NextBranch, NextBranchDef, NpcAction,
-    NpcActionDef, SceneDefinition, SceneToml, Thought, ThoughtDef,
+    Action, ActionDef, ActionKind, Check, CheckOutcome, CheckOutcomeDef, NarratorVariant,
+    NarratorVariantDef, NextBranch, NextBranchDef, NpcAction, NpcActionDef, SceneCall,
+    SceneDefinition, SceneToml, SceneVar, SceneVarDef, SpannedText, Thought, ThoughtDef,
 };
 
 #[derive(Debug, Error)]
@@ -21,17 +29,30 @@ pub enum SceneLoadError {
         source: std::io::Error,
     },
     #[error("toml parse error in {path}: {message}")]
-    Toml { path: PathBuf, message: String },
+    Toml {
+        path: PathBuf,
+        message: String,
+        span: Option<Box<SourceSpan>>,
+    },
     #[error("parse error in condition '{expr}' in scene {scene_id}: {message}")]
     BadCondition {
         scene_id: String,
         expr: String,
         message: String,
+        span: Option<Box<SourceSpan>>,
     },
     #[error("unknown trait '{id}' in scene {scene_id}")]
-    UnknownTrait { scene_id: String, id: String },
+    UnknownTrait {
+        scene_id: String,
+        id: String,
+        span: Option<Box<SourceSpan>>,
+    },
     #[error("unknown skill '{id}' in scene {scene_id}")]
-    UnknownSkill { scene_id: String, id: String },
+    UnknownSkill {
+        scene_id: String,
+        id: String,
+        span: Option<Box<SourceSpan>>,
+    },
     #[error("scenes directory not found: {0}")]
     DirNotFound(PathBuf),
     #[error("unknown goto target '{target}' in scene {scene_id}, action {action_id}")]
@@ -40,18 +61,37 @@ pub enum SceneLoadError {
         action_id: String,
         target: String,
     },
+    #[error("unknown call target '{target}' in scene {scene_id}, action {action_id}")]
+    UnknownCallTarget {
+        scene_id: String,
+        action_id: String,
+        target: String,
+    },
     #[error("unknown arc '{id}' in scene {scene_id}")]
-    UnknownArc { scene_id: String, id: String },
+    UnknownArc {
+        scene_id: String,
+        id: String,
+        span: Option<Box<SourceSpan>>,
+    },
     #[error("unknown arc state '{state}' for arc '{arc}' in scene {scene_id}")]
     UnknownArcState {
         scene_id: String,
         arc: String,
         state: String,
+        span: Option<Box<SourceSpan>>,
     },
     #[error("unknown stat '{id}' in scene {scene_id}")]
-    UnknownStat { scene_id: String, id: String },
+    UnknownStat {
+        scene_id: String,
+        id: String,
+        span: Option<Box<SourceSpan>>,
+    },
     #[error("unknown category '{id}' in scene {scene_id}")]
-    UnknownCategory { scene_id: String, id: String },
+    UnknownCategory {
+        scene_id: String,
+        id: String,
+        span: Option<Box<SourceSpan>>,
+    },
     #[error(
         "duplicate scene id '{scene_id}': '{second_source}' conflicts with already-loaded '{first_source}'"
     )]
@@ -64,6 +104,173 @@ pub enum SceneLoadError {
     DuplicateActionId { scene_id: String, action_id: String },
     #[error("duplicate npc_action id '{action_id}' in scene {scene_id}")]
     DuplicateNpcActionId { scene_id: String, action_id: String },
+    #[error("check action '{action_id}' in scene {scene_id}: {message}")]
+    BadCheck {
+        scene_id: String,
+        action_id: String,
+        message: String,
+    },
+    #[error("pack script error at {0}")]
+    ScriptLibrary(#[from] ScriptLibraryError),
+    #[error("scene {scene_id} reads param '{name}', which it doesn't declare in [scene.params]")]
+    UndeclaredParam {
+        scene_id: String,
+        name: String,
+        span: Option<Box<SourceSpan>>,
+    },
+    #[error("scene {scene_id} uses var '{name}', which it doesn't declare in [scene.vars]")]
+    UndeclaredVar {
+        scene_id: String,
+        name: String,
+        span: Option<Box<SourceSpan>>,
+    },
+    #[error("scene var '{name}' in scene {scene_id}: {message}")]
+    SceneVarType {
+        scene_id: String,
+        name: String,
+        message: String,
+        span: Option<Box<SourceSpan>>,
+    },
+    #[error("scene {scene_id} var '{name}' persists to unknown var '{id}'")]
+    UnknownGameVar {
+        scene_id: String,
+        name: String,
+        id: String,
+    },
+    #[error("bad next branch in scene {scene_id}: {message}")]
+    BadBranch { scene_id: String, message: String },
+    #[error("bad call of '{target}' in scene {scene_id}, action {action_id}: {message}")]
+    BadCall {
+        scene_id: String,
+        action_id: String,
+        target: String,
+        message: String,
+    },
+}
+
+impl SceneLoadError {
+    /// Where in a scene (or pack script) file the error is, when known.
+    pub fn span(&self) -> Option<&SourceSpan> {
+        match self {
+            SceneLoadError::ScriptLibrary(e) => e.span.as_ref(),
+            SceneLoadError::Toml { span, .. }
+            | SceneLoadError::BadCondition { span, .. }
+            | SceneLoadError::UnknownTrait { span, .. }
+            | SceneLoadError::UnknownSkill { span, .. }
+            | SceneLoadError::UnknownArc { span, .. }
+            | SceneLoadError::UnknownArcState { span, .. }
+            | SceneLoadError::UnknownStat { span, .. }
+            | SceneLoadError::UnknownCategory { span, .. }
+            | SceneLoadError::UndeclaredParam { span, .. }
+            | SceneLoadError::UndeclaredVar { span, .. }
+            | SceneLoadError::SceneVarType { span, .. } => span.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The error message, followed by the offending snippet underlined when the
+    /// error has a span.
+    pub fn render(&self) -> String {
+        match self.span() {
+            Some(span) => span.render(&self.to_string()),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// The scene file being resolved: its id, path and text, for locating errors,
+/// and the params and vars it declares.
+struct SceneFile<'a> {
+    scene_id: &'a str,
+    path: &'a Path,
+    text: &'a str,
+    params: &'a BTreeMap<String, VarType>,
+    vars: &'a BTreeMap<String, SceneVar>,
+}
+
+impl SceneFile<'_> {
+    fn span(&self, value: &SpannedText, position: rhai::Position) -> SourceSpan {
+        SourceSpan::in_value(self.path, self.text, value.span(), position)
+    }
+
+    /// Fail on the first `scene.param` of a param the scene doesn't declare,
+    /// or `scene.get` / `set` / `add` of a var it doesn't declare or with a
+    /// literal of the wrong type.
+    fn check_scene_refs(
+        &self,
+        refs: Vec<SceneNameRef>,
+        value: &SpannedText,
+    ) -> Result<(), SceneLoadError> {
+        for r in refs {
+            let span = Some(Box::new(self.span(value, r.position)));
+            if r.method == "param" {
+                if !self.params.contains_key(&r.name) {
+                    return Err(SceneLoadError::UndeclaredParam {
+                        scene_id: self.scene_id.to_string(),
+                        name: r.name,
+                        span,
+                    });
+                }
+                continue;
+            }
+            let Some(declared) = self.vars.get(&r.name).map(SceneVar::var_type) else {
+                return Err(SceneLoadError::UndeclaredVar {
+                    scene_id: self.scene_id.to_string(),
+                    name: r.name,
+                    span,
+                });
+            };
+            let message = match (r.method.as_str(), r.value) {
+                ("add", _) if declared != VarType::Int => {
+                    format!("'scene.add' needs an int var, and it is {declared}")
+                }
+                ("set", Some(got)) if got != declared => {
+                    format!("it is {declared}, but is set to a {got}")
+                }
+                _ => continue,
+            };
+            return Err(SceneLoadError::SceneVarType {
+                scene_id: self.scene_id.to_string(),
+                name: r.name,
+                message,
+                span,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Resolve `[scene.vars]`: a local var starts at its literal, a persistent one
+/// reads as its pack default until set.
+fn resolve_scene_vars(
+    raw: BTreeMap<String, SceneVarDef>,
+    registry: &PackRegistry,
+    scene_id: &str,
+) -> Result<BTreeMap<String, SceneVar>, SceneLoadError> {
+    raw.into_iter()
+        .map(|(name, def)| {
+            let var = match def {
+                SceneVarDef::Local(value) => SceneVar {
+                    value,
+                    persist: None,
+                },
+                SceneVarDef::Persistent { persist } => {
+                    let Some(game_var) = registry.get_var(&persist) else {
+                        return Err(SceneLoadError::UnknownGameVar {
+                            scene_id: scene_id.to_string(),
+                            name,
+                            id: persist,
+                        });
+                    };
+                    SceneVar {
+                        value: game_var.default_value(),
+                        persist: Some(persist),
+                    }
+                }
+            };
+            Ok((name, var))
+        })
+        .collect()
 }
 
 /// Load all `.toml` scene files from `scenes_dir`.
@@ -75,6 +282,8 @@ pub fn load_scenes(
     if !scenes_dir.exists() {
         return Err(SceneLoadError::DirNotFound(scenes_dir.to_path_buf()));
     }
+    // The helpers every scene script compiles against; report them first.
+    script_library(registry)?;
 
     let mut map: HashMap<String, Arc<SceneDefinition>> = HashMap::new();
     let mut scene_sources: HashMap<String, String> = HashMap::new();
@@ -98,14 +307,8 @@ pub fn load_scenes(
             path: path.clone(),
             source: e,
         })?;
-
-        let raw: SceneToml = toml::from_str(&src).map_err(|e| SceneLoadError::Toml {
-            path: path.clone(),
-            message: e.to_string(),
-        })?;
-
-        let scene_id = raw.scene.id.clone();
-        let def = resolve_scene(raw, registry, &scene_id)?;
+        let def = load_scene_source(&path, &src, registry)?;
+        let scene_id = def.id.clone();
         let source = path.display().to_string();
         if let Some(first_source) = scene_sources.insert(scene_id.clone(), source.clone()) {
             return Err(SceneLoadError::DuplicateSceneId {
@@ -120,47 +323,143 @@ pub fn load_scenes(
     Ok(map)
 }
 
-/// Validate that all `goto` targets in all scenes reference existing scene IDs.
-/// Call this after all packs' scenes have been loaded into the combined map.
+/// Parse, validate and resolve one scene file's text against the pack
+/// registry. `path` only labels errors; nothing is read from disk.
+pub fn load_scene_source(
+    path: &Path,
+    src: &str,
+    registry: &PackRegistry,
+) -> Result<SceneDefinition, SceneLoadError> {
+    let mut raw: SceneToml = toml::from_str(src).map_err(|e| SceneLoadError::Toml {
+        path: path.to_path_buf(),
+        message: e.message().to_string(),
+        span: e
+            .span()
+            .map(|range| Box::new(SourceSpan::new(path, src, range))),
+    })?;
+
+    let scene_id = raw.scene.id.clone();
+    let params = std::mem::take(&mut raw.scene.params);
+    let vars = resolve_scene_vars(std::mem::take(&mut raw.scene.vars), registry, &scene_id)?;
+    let file = SceneFile {
+        scene_id: &scene_id,
+        path,
+        text: src,
+        params: &params,
+        vars: &vars,
+    };
+    resolve_scene(raw, registry, &file)
+}
+
+/// Validate that all `goto` targets in all scenes reference existing scene IDs,
+/// and that every `call` names an existing scene and passes exactly the params
+/// it declares. Call this after all packs' scenes have been loaded into the
+/// combined map.
 pub fn validate_cross_references(
     scenes: &HashMap<String, Arc<SceneDefinition>>,
 ) -> Result<(), SceneLoadError> {
     for (scene_id, def) in scenes {
-        for action in &def.actions {
-            for branch in &action.next {
-                if let Some(ref target) = branch.goto {
-                    if !scenes.contains_key(target) {
-                        return Err(SceneLoadError::UnknownGotoTarget {
-                            scene_id: scene_id.clone(),
-                            action_id: action.id.clone(),
-                            target: target.clone(),
-                        });
-                    }
-                }
+        validate_scene_references(scene_id, def, scenes)?;
+    }
+    Ok(())
+}
+
+/// [`validate_cross_references`] for a single scene: its `goto` and `call`
+/// targets must be in `scenes`, which need not contain the scene itself.
+pub fn validate_scene_references(
+    scene_id: &str,
+    def: &SceneDefinition,
+    scenes: &HashMap<String, Arc<SceneDefinition>>,
+) -> Result<(), SceneLoadError> {
+    let branches = def
+        .actions
+        .iter()
+        .flat_map(|action| action.next_branches().map(move |b| (&action.id, b)))
+        .chain(def.npc_actions.iter().flat_map(|action| {
+            action
+                .next
+                .iter()
+                .flat_map(NextBranch::flatten)
+                .map(move |b| (&action.id, b))
+        }));
+    for (action_id, branch) in branches {
+        let bad_call = |target: &str, message: String| SceneLoadError::BadCall {
+            scene_id: scene_id.to_string(),
+            action_id: action_id.clone(),
+            target: target.to_string(),
+            message,
+        };
+        if let Some(ref target) = branch.goto {
+            let Some(callee) = scenes.get(target) else {
+                return Err(SceneLoadError::UnknownGotoTarget {
+                    scene_id: scene_id.to_string(),
+                    action_id: action_id.clone(),
+                    target: target.clone(),
+                });
+            };
+            if !callee.params.is_empty() {
+                return Err(bad_call(
+                    target,
+                    "the scene takes params; reach it with 'call', not 'goto'".into(),
+                ));
             }
         }
+        if let Some(ref call) = branch.call {
+            let Some(callee) = scenes.get(&call.scene) else {
+                return Err(SceneLoadError::UnknownCallTarget {
+                    scene_id: scene_id.to_string(),
+                    action_id: action_id.clone(),
+                    target: call.scene.clone(),
+                });
+            };
+            check_call_params(call, &callee.params)
+                .map_err(|message| bad_call(&call.scene, message))?;
+        }
     }
     Ok(())
 }
 
+/// A call must pass every declared param, with its declared type, and nothing
+/// else.
+fn check_call_params(call: &SceneCall, declared: &BTreeMap<String, VarType>) -> Result<(), String> {
+    for (name, ty) in declared {
+        match call.params.get(name).map(ApiValue::var_type) {
+            None => return Err(format!("missing param '{name}' ({ty})")),
+            Some(passed) if passed != *ty => {
+                return Err(format!("param '{name}' is {ty}, got {passed}"))
+            }
+            Some(_) => {}
+        }
+    }
+    match call
+        .params
+        .keys()
+        .find(|name| !declared.contains_key(*name))
+    {
+        Some(name) => Err(format!("unknown param '{name}'")),
+        None => Ok(()),
+    }
+}
+
 fn resolve_scene(
     raw: SceneToml,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
 ) -> Result<SceneDefinition, SceneLoadError> {
+    let scene_id = file.scene_id;
     let mut intro_variants = Vec::with_capacity(raw.intro_variants.len());
     for v in raw.intro_variants {
-        intro_variants.push(resolve_narrator_variant(v, registry, scene_id)?);
+        intro_variants.push(resolve_narrator_variant(v, registry, file)?);
     }
 
     let mut intro_thoughts = Vec::with_capacity(raw.thoughts.len());
     for t in raw.thoughts {
-        intro_thoughts.push(resolve_thought(t, registry, scene_id)?);
+        intro_thoughts.push(resolve_thought(t, registry, file)?);
     }
 
     let mut actions = Vec::with_capacity(raw.actions.len());
     for a in raw.actions {
-        actions.push(resolve_action(a, registry, scene_id)?);
+        actions.push(resolve_action(a, registry, file)?);
     }
     validate_unique_ids(
         actions.iter().map(|action| action.id.as_str()),
@@ -173,7 +472,7 @@ fn resolve_scene(
 
     let mut npc_actions = Vec::with_capacity(raw.npc_actions.len());
     for na in raw.npc_actions {
-        npc_actions.push(resolve_npc_action(na, registry, scene_id)?);
+        npc_actions.push(resolve_npc_action(na, registry, file)?);
     }
     validate_unique_ids(
         npc_actions.iter().map(|action| action.id.as_str()),
@@ -184,12 +483,14 @@ fn resolve_scene(
         },
     )?;
 
-    validate_prose_checked(&raw.intro.prose, registry, scene_id)?;
+    prose_in_file(&raw.intro.prose, registry, file)?;
 
     Ok(SceneDefinition {
         id: raw.scene.id,
         pack: raw.scene.pack,
-        intro_prose: raw.intro.prose,
+        intro_prose: raw.intro.prose.into_inner(),
+        params: file.params.clone(),
+        vars: file.vars.clone(),
         intro_variants,
         intro_thoughts,
         actions,
@@ -218,19 +519,19 @@ where
 fn resolve_thought(
     raw: ThoughtDef,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
 ) -> Result<Thought, SceneLoadError> {
     let condition = raw
         .condition
-        .as_deref()
-        .map(|s| parse_condition_checked(s, registry, scene_id))
+        .as_ref()
+        .map(|s| condition_in_file(s, registry, file))
         .transpose()?;
 
-    validate_prose_checked(&raw.prose, registry, scene_id)?;
+    prose_in_file(&raw.prose, registry, file)?;
 
     Ok(Thought {
         condition,
-        prose: raw.prose,
+        prose: raw.prose.into_inner(),
         style: raw.style,
     })
 }
@@ -238,13 +539,13 @@ fn resolve_thought(
 fn resolve_narrator_variant(
     raw: NarratorVariantDef,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
 ) -> Result<NarratorVariant, SceneLoadError> {
-    let condition = parse_condition_checked(&raw.condition, registry, scene_id)?;
-    validate_prose_checked(&raw.prose, registry, scene_id)?;
+    let condition = condition_in_file(&raw.condition, registry, file)?;
+    prose_in_file(&raw.prose, registry, file)?;
     Ok(NarratorVariant {
         condition,
-        prose: raw.prose,
+        prose: raw.prose.into_inner(),
     })
 }
 
@@ -254,19 +555,61 @@ pub(crate) fn parse_condition_checked(
     scene_id: &str,
 ) -> Result<crate::script::CompiledScript, SceneLoadError> {
     crate::script::compile_condition(expr_str, registry, scene_id)
-        .map_err(|e| map_script_error(e, scene_id, expr_str))
+        .map_err(|e| map_script_error(e, scene_id, expr_str, None))
 }
 
-/// Validate a prose template's method surface through the load-time prose gate —
-/// the fail-fast guarantee for prose (an unknown / mis-contexted method or bad
-/// content id fails at load, not when a player reaches that scene branch).
-pub(crate) fn validate_prose_checked(
-    prose: &str,
+/// Compile a scene-file condition, locating any error in the file and tagging
+/// the script with its origin.
+fn condition_in_file(
+    value: &SpannedText,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
+) -> Result<CompiledScript, SceneLoadError> {
+    let script = crate::script::compile_condition(value.get_ref(), registry, file.scene_id)
+        .map(|script| script.with_origin(file.span(value, rhai::Position::NONE)))
+        .map_err(|e| script_error_in_file(e, value, file))?;
+    file.check_scene_refs(source_scene_refs(&script), value)?;
+    Ok(script)
+}
+
+/// Compile a scene-file effect call-list, like [`condition_in_file`].
+fn effect_in_file(
+    value: Option<&SpannedText>,
+    registry: &PackRegistry,
+    file: &SceneFile,
+) -> Result<Option<CompiledScript>, SceneLoadError> {
+    value
+        .map(|value| {
+            let script = crate::script::compile_effect(value.get_ref(), registry, file.scene_id)
+                .map(|script| script.with_origin(file.span(value, rhai::Position::NONE)))
+                .map_err(|e| script_error_in_file(e, value, file))?;
+            file.check_scene_refs(source_scene_refs(&script), value)?;
+            Ok(script)
+        })
+        .transpose()
+}
+
+/// Validate a scene-file prose template's method surface through the load-time
+/// prose gate — the fail-fast guarantee for prose (an unknown / mis-contexted
+/// method or bad content id fails at load, not when a player reaches that scene
+/// branch).
+fn prose_in_file(
+    value: &SpannedText,
+    registry: &PackRegistry,
+    file: &SceneFile,
 ) -> Result<(), SceneLoadError> {
-    crate::script::api::prose_validate::validate_prose(prose, registry, scene_id)
-        .map_err(|e| map_script_error(e, scene_id, prose))
+    crate::script::api::prose_validate::validate_prose(value.get_ref(), registry, file.scene_id)
+        .map_err(|e| script_error_in_file(e, value, file))?;
+    file.check_scene_refs(prose_scene_refs(value.get_ref()), value)
+}
+
+fn script_error_in_file(
+    err: crate::script::ScriptError,
+    value: &SpannedText,
+    file: &SceneFile,
+) -> SceneLoadError {
+    let span = file.span(value, err.position());
+    map_script_error(err, file.scene_id, value.get_ref(), Some(span))
 }
 
 /// Map a `ScriptError` from the load-time gate onto the loader's error taxonomy,
@@ -276,29 +619,37 @@ pub(crate) fn map_script_error(
     err: crate::script::ScriptError,
     scene_id: &str,
     expr_str: &str,
+    span: Option<SourceSpan>,
 ) -> SceneLoadError {
     use crate::script::ScriptError;
+    let position = err.position();
+    let span = span.map(Box::new);
     match err {
         ScriptError::UnknownId { kind, id, .. } => match kind.as_str() {
             "trait" | "npc_trait" => SceneLoadError::UnknownTrait {
                 scene_id: scene_id.to_string(),
                 id,
+                span,
             },
             "skill" => SceneLoadError::UnknownSkill {
                 scene_id: scene_id.to_string(),
                 id,
+                span,
             },
             "category" => SceneLoadError::UnknownCategory {
                 scene_id: scene_id.to_string(),
                 id,
+                span,
             },
             "stat" => SceneLoadError::UnknownStat {
                 scene_id: scene_id.to_string(),
                 id,
+                span,
             },
             "arc" => SceneLoadError::UnknownArc {
                 scene_id: scene_id.to_string(),
                 id,
+                span,
             },
             "arc_state" => {
                 // id is "arc -> state"; split for a precise message.
@@ -307,19 +658,27 @@ pub(crate) fn map_script_error(
                     scene_id: scene_id.to_string(),
                     arc: arc.to_string(),
                     state: state.to_string(),
+                    span,
                 }
             }
             _ => SceneLoadError::BadCondition {
                 scene_id: scene_id.to_string(),
                 expr: expr_str.to_string(),
                 message: format!("unknown {kind} id '{id}'"),
+                span,
             },
         },
         ScriptError::Compile { message, .. } | ScriptError::Runtime { message, .. } => {
+            let message = if position.is_none() {
+                message
+            } else {
+                format!("{message} ({position})")
+            };
             SceneLoadError::BadCondition {
                 scene_id: scene_id.to_string(),
                 expr: expr_str.to_string(),
                 message,
+                span,
             }
         }
     }
@@ -328,79 +687,158 @@ pub(crate) fn map_script_error(
 fn resolve_action(
     raw: ActionDef,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
 ) -> Result<Action, SceneLoadError> {
+    let scene_id = file.scene_id;
     let condition = raw
         .condition
-        .as_deref()
-        .map(|s| parse_condition_checked(s, registry, scene_id))
+        .as_ref()
+        .map(|s| condition_in_file(s, registry, file))
         .transpose()?;
+    let check = resolve_check(&raw, registry, file)?;
 
     let mut next = Vec::with_capacity(raw.next.len());
     for nb in raw.next {
-        next.push(resolve_next_branch(nb, registry, scene_id)?);
+        next.push(resolve_next_branch(nb, registry, file)?);
     }
 
-    let effect = compile_effect_checked(raw.effect.as_deref(), registry, scene_id)?;
+    let effect = effect_in_file(raw.effect.as_ref(), registry, file)?;
 
     let mut thoughts = Vec::with_capacity(raw.thoughts.len());
     for t in raw.thoughts {
-        thoughts.push(resolve_thought(t, registry, scene_id)?);
+        thoughts.push(resolve_thought(t, registry, file)?);
     }
 
-    validate_prose_checked(&raw.prose, registry, scene_id)?;
+    prose_in_file(&raw.prose, registry, file)?;
+
+    if check.is_some() && !next.is_empty() {
+        return Err(bad_check(
+            scene_id,
+            &raw.id,
+            "put 'next' on [actions.pass] / [actions.fail], not the action",
+        ));
+    }
 
     Ok(Action {
         id: raw.id,
         label: raw.label,
         detail: raw.detail,
         condition,
-        prose: raw.prose,
+        prose: raw.prose.into_inner(),
         allow_npc_actions: raw.allow_npc_actions,
         effect,
         next,
         thoughts,
+        check,
     })
 }
 
-/// Compile an effect call-list string through the load-time gate.
-fn compile_effect_checked(
-    src: Option<&str>,
+fn bad_check(scene_id: &str, action_id: &str, message: &str) -> SceneLoadError {
+    SceneLoadError::BadCheck {
+        scene_id: scene_id.to_string(),
+        action_id: action_id.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Resolve the check half of an action. Check fields on a plain choice are an
+/// error rather than silently ignored.
+fn resolve_check(
+    raw: &ActionDef,
     registry: &PackRegistry,
-    scene_id: &str,
-) -> Result<Option<crate::script::CompiledScript>, SceneLoadError> {
-    src.map(|s| {
-        crate::script::compile_effect(s, registry, scene_id)
-            .map_err(|e| map_script_error(e, scene_id, s))
+    file: &SceneFile,
+) -> Result<Option<Check>, SceneLoadError> {
+    let scene_id = file.scene_id;
+    if raw.kind == ActionKind::Choice {
+        let has_check_fields = raw.skill.is_some()
+            || raw.base_dc.is_some()
+            || raw.pass.is_some()
+            || raw.fail.is_some();
+        if has_check_fields {
+            return Err(bad_check(
+                scene_id,
+                &raw.id,
+                "check fields need kind = \"check\"",
+            ));
+        }
+        return Ok(None);
+    }
+    let (Some(skill), Some(base_dc), Some(pass), Some(fail)) =
+        (&raw.skill, raw.base_dc, &raw.pass, &raw.fail)
+    else {
+        return Err(bad_check(
+            scene_id,
+            &raw.id,
+            "needs skill, base_dc, [actions.pass] and [actions.fail]",
+        ));
+    };
+    if registry.resolve_skill(skill).is_err() {
+        return Err(SceneLoadError::UnknownSkill {
+            scene_id: scene_id.to_string(),
+            id: skill.clone(),
+            span: None,
+        });
+    }
+    Ok(Some(Check {
+        id: format!("{scene_id}::{}", raw.id),
+        skill: skill.clone(),
+        base_dc,
+        check_type: raw.check_type,
+        pass: resolve_check_outcome(pass, registry, file)?,
+        fail: resolve_check_outcome(fail, registry, file)?,
+    }))
+}
+
+fn resolve_check_outcome(
+    raw: &CheckOutcomeDef,
+    registry: &PackRegistry,
+    file: &SceneFile,
+) -> Result<CheckOutcome, SceneLoadError> {
+    prose_in_file(&raw.prose, registry, file)?;
+    let effect = effect_in_file(raw.effect.as_ref(), registry, file)?;
+    let next = raw
+        .next
+        .iter()
+        .map(|nb| resolve_next_branch(nb.clone(), registry, file))
+        .collect::<Result<Vec<_>, _>>()?;
+    let thoughts = raw
+        .thoughts
+        .iter()
+        .map(|t| resolve_thought(t.clone(), registry, file))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CheckOutcome {
+        prose: raw.prose.get_ref().clone(),
+        effect,
+        next,
+        thoughts,
     })
-    .transpose()
 }
 
 fn resolve_npc_action(
     raw: NpcActionDef,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
 ) -> Result<NpcAction, SceneLoadError> {
     let condition = raw
         .condition
-        .as_deref()
-        .map(|s| parse_condition_checked(s, registry, scene_id))
+        .as_ref()
+        .map(|s| condition_in_file(s, registry, file))
         .transpose()?;
 
-    let effect = compile_effect_checked(raw.effect.as_deref(), registry, scene_id)?;
+    let effect = effect_in_file(raw.effect.as_ref(), registry, file)?;
 
     let next = raw
         .next
         .into_iter()
-        .map(|n| resolve_next_branch(n, registry, scene_id))
+        .map(|n| resolve_next_branch(n, registry, file))
         .collect::<Result<Vec<_>, _>>()?;
 
-    validate_prose_checked(&raw.prose, registry, scene_id)?;
+    prose_in_file(&raw.prose, registry, file)?;
 
     Ok(NpcAction {
         id: raw.id,
         condition,
-        prose: raw.prose,
+        prose: raw.prose.into_inner(),
         weight: raw.weight,
         effect,
         next,
@@ -410,12 +848,63 @@ fn resolve_npc_action(
 fn resolve_next_branch(
     raw: NextBranchDef,
     registry: &PackRegistry,
-    scene_id: &str,
+    file: &SceneFile,
 ) -> Result<NextBranch, SceneLoadError> {
     let condition = raw
         .condition
-        .as_deref()
-        .map(|s| parse_condition_checked(s, registry, scene_id))
+        .as_ref()
+        .map(|s| condition_in_file(s, registry, file))
+        .transpose()?;
+
+    let bad_branch = |message: &str| SceneLoadError::BadBranch {
+        scene_id: file.scene_id.to_string(),
+        message: message.to_string(),
+    };
+    let jumps = raw.goto.is_some() || raw.slot.is_some() || raw.finish;
+    if raw.call.is_some() && (jumps || raw.returns.is_some()) {
+        return Err(bad_branch(
+            "'call' can't be combined with goto, slot, finish or return",
+        ));
+    }
+    if raw.returns.is_some() && jumps {
+        return Err(bad_branch(
+            "'return' can't be combined with goto, slot or finish",
+        ));
+    }
+    if raw.call.is_none() && (!raw.params.is_empty() || !raw.then.is_empty()) {
+        return Err(bad_branch("'params' and 'then' need a 'call'"));
+    }
+
+    let call = match raw.call {
+        Some(scene) => {
+            let mut params = BTreeMap::new();
+            for (name, value) in raw.params {
+                let value = param_value(&value).ok_or_else(|| {
+                    bad_branch(&format!(
+                        "param '{name}' must be a string, integer or boolean"
+                    ))
+                })?;
+                params.insert(name, value);
+            }
+            let then = raw
+                .then
+                .into_iter()
+                .map(|nb| resolve_next_branch(nb, registry, file))
+                .collect::<Result<Vec<_>, _>>()?;
+            Some(SceneCall {
+                scene,
+                params,
+                then,
+            })
+        }
+        None => None,
+    };
+    let returns = raw
+        .returns
+        .map(|value| {
+            param_value(&value)
+                .ok_or_else(|| bad_branch("'return' must be a string, integer or boolean"))
+        })
         .transpose()?;
 
     Ok(NextBranch {
@@ -423,9 +912,21 @@ fn resolve_next_branch(
         goto: raw.goto,
         slot: raw.slot,
         finish: raw.finish,
+        call,
+        returns,
     })
 }
 
+/// A literal `call` param or `return` value.
+fn param_value(value: &toml::Value) -> Option<ApiValue> {
+    match value {
+        toml::Value::String(s) => Some(ApiValue::Str(s.clone())),
+        toml::Value::Integer(i) => Some(ApiValue::Int(*i)),
+        toml::Value::Boolean(b) => Some(ApiValue::Bool(*b)),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -489,6 +990,8 @@ mod tests {
             id: "test::a".into(),
             pack: "test".into(),
             intro_prose: "A".into(),
+            params: BTreeMap::new(),
+            vars: BTreeMap::new(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -504,8 +1007,11 @@ mod tests {
                     goto: Some("test::nonexistent".into()),
                     slot: None,
                     finish: false,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         });
@@ -526,6 +1032,8 @@ mod tests {
             id: "test::a".into(),
             pack: "test".into(),
             intro_prose: "A".into(),
+            params: BTreeMap::new(),
+            vars: BTreeMap::new(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -541,8 +1049,11 @@ mod tests {
                     goto: Some("test::b".into()),
                     slot: None,
                     finish: false,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         });
@@ -551,6 +1062,8 @@ mod tests {
             id: "test::b".into(),
             pack: "test".into(),
             intro_prose: "B".into(),
+            params: BTreeMap::new(),
+            vars: BTreeMap::new(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![],
@@ -565,6 +1078,65 @@ mod tests {
         assert!(result.is_ok(), "valid goto should pass");
     }
 
+    #[test]
+    fn npc_action_goto_naming_an_action_is_rejected() {
+        // BREAKS IF: an npc_action's `goto` is read as an action of its own
+        // scene. `goto` always starts a scene; an action id there used to fail
+        // only at runtime, after the NPC's prose had played.
+        use crate::types::{Action, NextBranch, NpcAction, SceneDefinition};
+        use std::sync::Arc;
+
+        let scene = Arc::new(SceneDefinition {
+            id: "test::library".into(),
+            pack: "test".into(),
+            intro_prose: "A".into(),
+            params: BTreeMap::new(),
+            vars: BTreeMap::new(),
+            intro_variants: vec![],
+            intro_thoughts: vec![],
+            actions: vec![Action {
+                id: "keep_working".into(),
+                label: "Keep working".into(),
+                detail: String::new(),
+                condition: None,
+                prose: String::new(),
+                allow_npc_actions: true,
+                effect: None,
+                next: vec![],
+                thoughts: vec![],
+                check: None,
+            }],
+            npc_actions: vec![NpcAction {
+                id: "looks_up".into(),
+                condition: None,
+                prose: "He looks up.".into(),
+                weight: 1,
+                effect: None,
+                next: vec![NextBranch {
+                    condition: None,
+                    goto: Some("keep_working".into()),
+                    slot: None,
+                    finish: false,
+                    call: None,
+                    returns: None,
+                }],
+            }],
+        });
+
+        let mut scenes = HashMap::new();
+        scenes.insert("test::library".into(), scene);
+
+        let err = validate_cross_references(&scenes).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SceneLoadError::UnknownGotoTarget { ref action_id, ref target, .. }
+                    if action_id == "looks_up" && target == "keep_working"
+            ),
+            "got: {err}"
+        );
+    }
+
     #[test]
     fn load_scenes_rejects_duplicate_scene_ids() {
         let dir = temp_scene_dir("dup_scene");
@@ -642,6 +1214,69 @@ condition = "true"
         std::fs::remove_dir_all(dir).unwrap();
     }
 
+    #[test]
+    fn load_scenes_resolves_check_actions_and_rejects_half_declared_ones() {
+        let mut registry = PackRegistry::new();
+        registry.register_skills(vec![undone_packs::data::SkillDef {
+            id: "COMPOSURE".into(),
+            name: "Composure".into(),
+            description: String::new(),
+            min: 0,
+            max: 100,
+            progression: None,
+        }]);
+        let dir = temp_scene_dir("check_action");
+        let scene = |extra: &str| {
+            format!(
+                r#"
+[scene]
+id = "test::check"
+pack = "test"
+description = "check"
+
+[intro]
+prose = "intro"
+
+[[actions]]
+id = "hold"
+label = "Hold still"
+kind = "check"
+skill = "COMPOSURE"
+base_dc = 40
+check_type = "red"
+{extra}
+"#
+            )
+        };
+        let outcomes = r#"
+  [actions.pass]
+  prose = "You hold."
+
+  [actions.fail]
+  prose = "You don't."
+  effect = 'w.addArousal(1);'
+    [[actions.fail.next]]
+    finish = true
+"#;
+        std::fs::write(dir.join("scene.toml"), scene(outcomes)).unwrap();
+        let scenes = load_scenes(&dir, &registry).unwrap();
+        let check = scenes["test::check"].actions[0].check.as_ref().unwrap();
+        assert_eq!(check.id, "test::check::hold");
+        assert_eq!(check.check_type, crate::types::CheckType::Red);
+        assert!(check.fail.effect.is_some());
+        assert_eq!(scenes["test::check"].actions[0].next_branches().count(), 1);
+
+        std::fs::write(dir.join("scene.toml"), scene("")).unwrap();
+        let result = load_scenes(&dir, &registry);
+        assert!(
+            matches!(result, Err(SceneLoadError::BadCheck { .. })),
+            "expected bad check error, got {:?}",
+            result
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     fn validate_condition_rejects_unknown_category() {
         let registry = undone_packs::PackRegistry::new(); // no categories registered
@@ -697,6 +1332,7 @@ condition = "true"
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         registry.register_skills(vec![undone_packs::data::SkillDef {
             id: "FEMININITY".into(),
@@ -704,6 +1340,7 @@ condition = "true"
             description: "...".into(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         registry.register_categories(vec![undone_packs::data::CategoryDef {
             id: "AGE_YOUNG".into(),
@@ -751,6 +1388,7 @@ condition = "true"
             description: "...".into(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         // checkSkill's 2nd arg must be an int; a string there is a load error.
         let result = parse_condition_checked(
@@ -779,4 +1417,223 @@ condition = "true"
             result
         );
     }
+
+    #[test]
+    fn gate_errors_point_into_the_scene_file() {
+        let registry = PackRegistry::new();
+        let dir = temp_scene_dir("spans");
+        let path = dir.join("spans.toml");
+        std::fs::write(
+            &path,
+            r#"[scene]
+id = "test::spans"
+pack = "test"
+description = "spans"
+
+[intro]
+prose = """
+It begins.
+{{ w.notAReal() }}
+"""
+
+[[actions]]
+id = "go"
+label = "Go"
+effect = '''
+w.addArousal(1);
+  w.hasTrait("NOPE")'''
+"#,
+        )
+        .unwrap();
+
+        let err = load_scenes(&dir, &registry).unwrap_err();
+        let span = err.span().expect("gate errors carry a span");
+        assert_eq!(
+            (span.path.as_path(), span.line, span.column),
+            (path.as_path(), 17, 5)
+        );
+        assert!(
+            err.render()
+                .ends_with("17 |   w.hasTrait(\"NOPE\")'''\n   |     ^^^^^^^^"),
+            "got:\n{}",
+            err.render()
+        );
+
+        // Prose errors point at the offending `{{ … }}` tag.
+        let fixed = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace(r#"w.hasTrait("NOPE")"#, "w.addArousal(2);");
+        std::fs::write(&path, fixed).unwrap();
+        let err = load_scenes(&dir, &registry).unwrap_err();
+        let span = err.span().unwrap();
+        assert_eq!((span.line, span.column), (9, 1));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn calls_are_checked_against_the_callee_params() {
+        let dir = temp_scene_dir("calls");
+        let callee = r#"
+[scene]
+id = "test::reply"
+pack = "test"
+description = "callee"
+
+[scene.params]
+sender = "string"
+
+[intro]
+prose = "{{ scene.param('sender') }} texted."
+"#;
+        let caller = |next: &str| {
+            format!(
+                r#"
+[scene]
+id = "test::phone"
+pack = "test"
+description = "caller"
+
+[intro]
+prose = "Buzz."
+
+[[actions]]
+id = "read"
+label = "Read it"
+  [[actions.next]]
+  {next}
+"#
+            )
+        };
+        std::fs::write(dir.join("reply.toml"), callee).unwrap();
+        let check = |next: &str| {
+            std::fs::write(dir.join("phone.toml"), caller(next)).unwrap();
+            load_scenes(&dir, &PackRegistry::new()).and_then(|scenes| {
+                validate_cross_references(&scenes)?;
+                Ok(scenes)
+            })
+        };
+
+        let scenes = check(
+            r#"call = "test::reply"
+  params = { sender = "Jake" }"#,
+        )
+        .unwrap();
+        assert_eq!(scenes["test::reply"].params.len(), 1);
+
+        for (next, wanted) in [
+            (r#"call = "test::reply""#, "missing param 'sender'"),
+            (
+                r#"call = "test::reply"
+  params = { sender = 3 }"#,
+                "param 'sender' is string, got int",
+            ),
+            (
+                r#"call = "test::reply"
+  params = { sender = "Jake", mood = "bad" }"#,
+                "unknown param 'mood'",
+            ),
+            (r#"goto = "test::reply""#, "reach it with 'call'"),
+            (
+                r#"call = "test::reply"
+  finish = true"#,
+                "can't be combined",
+            ),
+        ] {
+            let err = check(next).unwrap_err();
+            assert!(err.to_string().contains(wanted), "{next}: got {err}");
+        }
+
+        // A scene can only read the params it declares.
+        std::fs::write(
+            dir.join("reply.toml"),
+            callee.replace("scene.param('sender')", "scene.param('sendr')"),
+        )
+        .unwrap();
+        let err = check(r#"return = "done""#).unwrap_err();
+        assert!(
+            matches!(err, SceneLoadError::UndeclaredParam { ref name, .. } if name == "sendr"),
+            "got {err:?}"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn scene_var_uses_are_checked_against_the_declarations() {
+        let dir = temp_scene_dir("vars");
+        let scene = |vars: &str, effect: &str| {
+            format!(
+                r#"
+[scene]
+id = "test::bar"
+pack = "test"
+description = "vars"
+
+[scene.vars]
+{vars}
+
+[intro]
+prose = "You've had {{{{ scene.get('drinks') }}}}."
+
+[[actions]]
+id = "drink"
+label = "Drink"
+effect = '{effect}'
+"#
+            )
+        };
+        let mut registry = PackRegistry::new();
+        registry.register_flags(
+            toml::from_str(
+                r#"
+                [[var]]
+                id   = "BAR_TENSION"
+                type = "int"
+                "#,
+            )
+            .unwrap(),
+        );
+        let check = |vars: &str, effect: &str| {
+            std::fs::write(dir.join("bar.toml"), scene(vars, effect)).unwrap();
+            load_scenes(&dir, &registry)
+        };
+
+        let scenes = check(
+            r#"drinks = 0
+tension = { persist = "BAR_TENSION" }"#,
+            r#"scene.add("drinks", 1); scene.add("tension", 2);"#,
+        )
+        .unwrap();
+        assert_eq!(
+            scenes["test::bar"].vars["tension"].persist.as_deref(),
+            Some("BAR_TENSION")
+        );
+
+        let err = check("drink = 0", r#"scene.add("drink", 1);"#).unwrap_err();
+        assert!(
+            matches!(err, SceneLoadError::UndeclaredVar { ref name, .. } if name == "drinks"),
+            "prose reads are checked too, got {err:?}"
+        );
+        let err = check(r#"drinks = """#, r#"scene.add("drinks", 1);"#).unwrap_err();
+        assert!(err.to_string().contains("needs an int var"), "got {err}");
+        let err = check("drinks = 0", r#"scene.set("drinks", "two");"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("it is int, but is set to a string"),
+            "got {err}"
+        );
+        let err = check(
+            r#"drinks = 0
+tension = { persist = "TENSION" }"#,
+            "",
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, SceneLoadError::UnknownGameVar { ref id, .. } if id == "TENSION"),
+            "got {err:?}"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
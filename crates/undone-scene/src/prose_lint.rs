@@ -0,0 +1,727 @@
+//! Pack prose-lint rules: phrase lists and regexes a pack ships in its
+//! `prose_lint_file`, run over every prose template in a scene file.
+//!
+//! A template is linted in every combination of its branches: each
+//! `{% if %}` chain contributes one of its `{% if %}` / `{% elif %}` /
+//! `{% else %}` bodies, or nothing when it has no `{% else %}`, and the
+//! pieces are joined the way they would render, with `{{ }}` expressions and
+//! other tags dropped. A phrase that runs across a branch boundary is seen in
+//! the combinations that render it, within [`JOIN_WINDOW`] of the boundary.
+//! Past [`MAX_COMBINATIONS`], each branch is linted once, with the first
+//! branch of every other chain.
+//!
+//! ```toml
+//! [[rules]]
+//! id       = "erotic_cliche"
+//! severity = "error"            # or "warning"
+//! message  = "AI erotic cliché"
+//! phrases  = ["feminine core"]  # case-insensitive, whole words
+//! patterns = ['bit (her|your) lip']  # regex crate syntax, as written
+//! ```
+
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::coverage::{
+    prose_branches, prose_sites, scan_branches, template_tags, FoundBranch, ProseBranch, ProseSite,
+};
+use crate::diagnostic::SourceSpan;
+use crate::types::{SceneToml, SpannedText};
+
+#[derive(Debug, Error)]
+pub enum ProseLintError {
+    #[error("io error reading {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("toml parse error in {path}: {message}")]
+    Toml { path: PathBuf, message: String },
+    #[error("prose-lint rule '{id}' in {path}: {message}")]
+    InvalidRule {
+        path: PathBuf,
+        id: String,
+        message: String,
+    },
+}
+
+/// What a match does to validation: an `error` fails it, a `warning` is a
+/// prose finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    rules: Vec<RuleDef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDef {
+    id: String,
+    severity: LintSeverity,
+    message: String,
+    #[serde(default)]
+    phrases: Vec<String>,
+    #[serde(default)]
+    patterns: Vec<String>,
+}
+
+/// One rule: its phrases and patterns compiled into a single regex.
+#[derive(Debug, Clone)]
+pub struct LintRule {
+    pub id: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    matcher: Regex,
+}
+
+/// The rules of every loaded pack.
+#[derive(Debug, Clone, Default)]
+pub struct ProseLintRules {
+    rules: Vec<LintRule>,
+}
+
+/// A rule match in one prose template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateMatch {
+    /// Index into the rule set, in load order.
+    pub rule: usize,
+    /// The branch the match starts in, by template order (as in
+    /// [`prose_branches`]); `None` for text outside every branch.
+    pub branch: Option<usize>,
+    /// Byte offset of the match start in the template.
+    pub offset: usize,
+    pub matched: String,
+}
+
+/// A rule match in a scene file.
+#[derive(Debug, Clone)]
+pub struct LintFinding {
+    pub rule_id: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    pub site: ProseSite,
+    pub branch: Option<ProseBranch>,
+    pub matched: String,
+    pub span: SourceSpan,
+}
+
+impl LintFinding {
+    /// Where the match is within its scene, e.g. `action 'wait' prose` or
+    /// `intro prose branch `{% if w.hasTrait("SHY") %}``.
+    pub fn location(&self) -> String {
+        match &self.branch {
+            Some(branch) => format!("{} branch `{{% {} %}}`", self.site, branch.tag),
+            None => self.site.to_string(),
+        }
+    }
+}
+
+impl ProseLintRules {
+    /// Load a pack's rule file.
+    pub fn load(path: &Path) -> Result<Self, ProseLintError> {
+        let src = std::fs::read_to_string(path).map_err(|source| ProseLintError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(path, &src)
+    }
+
+    /// Parse rule-file source; `path` is for error messages.
+    pub fn parse(path: &Path, src: &str) -> Result<Self, ProseLintError> {
+        let file: RulesFile = toml::from_str(src).map_err(|e| ProseLintError::Toml {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        let invalid = |id: &str, message: String| ProseLintError::InvalidRule {
+            path: path.to_path_buf(),
+            id: id.to_string(),
+            message,
+        };
+        let mut rules = Vec::with_capacity(file.rules.len());
+        for def in file.rules {
+            if def.phrases.is_empty() && def.patterns.is_empty() {
+                return Err(invalid(&def.id, "has no phrases or patterns".into()));
+            }
+            let mut alternatives = Vec::new();
+            for phrase in &def.phrases {
+                let word_start = phrase.starts_with(|c: char| c.is_alphanumeric());
+                let word_end = phrase.ends_with(|c: char| c.is_alphanumeric());
+                alternatives.push(format!(
+                    "(?i:{}{}{})",
+                    if word_start { r"\b" } else { "" },
+                    regex::escape(phrase),
+                    if word_end { r"\b" } else { "" },
+                ));
+            }
+            for pattern in &def.patterns {
+                Regex::new(pattern)
+                    .map_err(|e| invalid(&def.id, format!("pattern '{pattern}': {e}")))?;
+                alternatives.push(format!("(?:{pattern})"));
+            }
+            let matcher =
+                Regex::new(&alternatives.join("|")).map_err(|e| invalid(&def.id, e.to_string()))?;
+            rules.push(LintRule {
+                id: def.id,
+                severity: def.severity,
+                message: def.message,
+                matcher,
+            });
+        }
+        Ok(Self { rules })
+    }
+
+    /// Add another pack's rules after these.
+    pub fn extend(&mut self, other: ProseLintRules) {
+        self.rules.extend(other.rules);
+    }
+
+    pub fn rules(&self) -> &[LintRule] {
+        &self.rules
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Every rule match in `template`, over its branch combinations. A match
+    /// several combinations render is reported once.
+    pub fn lint_template(&self, template: &str) -> Vec<TemplateMatch> {
+        let outline = Outline::parse(template);
+        let mut matches = Vec::new();
+        // A run of template text reads the same in every combination, so only
+        // the text around the joins between runs differs between them.
+        for (range, branch) in &outline.texts {
+            self.find(&template[range.clone()], |start, found, rule| {
+                matches.push(TemplateMatch {
+                    rule,
+                    branch: *branch,
+                    offset: range.start + start,
+                    matched: found.to_string(),
+                });
+            });
+        }
+        let mut windows = HashSet::new();
+        for choice in outline.choices() {
+            let variant = outline.variant(template, &choice);
+            for &(join, _, _) in variant.pieces.iter().skip(1) {
+                let start = char_boundary(&variant.text, join.saturating_sub(JOIN_WINDOW));
+                let end = char_boundary(&variant.text, join + JOIN_WINDOW);
+                let window = &variant.text[start..end];
+                let seen = (variant.template_offset(start).0, join - start);
+                if !windows.insert((seen, window.to_string())) {
+                    continue;
+                }
+                self.find(window, |found_start, found, rule| {
+                    let found_start = start + found_start;
+                    if found_start < join && join < found_start + found.len() {
+                        let (offset, branch) = variant.template_offset(found_start);
+                        matches.push(TemplateMatch {
+                            rule,
+                            branch,
+                            offset,
+                            matched: found.to_string(),
+                        });
+                    }
+                });
+            }
+        }
+        matches.sort_by_key(|m| (m.offset, m.rule));
+        matches.dedup_by_key(|m| (m.offset, m.rule));
+        matches
+    }
+
+    /// Call `f` with the start, text and rule index of every match in `text`.
+    fn find(&self, text: &str, mut f: impl FnMut(usize, &str, usize)) {
+        for (index, rule) in self.rules.iter().enumerate() {
+            for found in rule.matcher.find_iter(text) {
+                f(found.start(), found.as_str(), index);
+            }
+        }
+    }
+
+    /// Every rule match in the prose of scene file `text` at `path`. Fails
+    /// when the file is not a scene; the scene loader reports why.
+    pub fn lint_scene_file(
+        &self,
+        path: &Path,
+        text: &str,
+    ) -> Result<Vec<LintFinding>, toml::de::Error> {
+        let scene: SceneToml = toml::from_str(text)?;
+        let mut findings = Vec::new();
+        for (site, prose) in prose_sites(&scene) {
+            let template = prose.get_ref();
+            let branches = prose_branches(template);
+            for found in self.lint_template(template) {
+                let rule = &self.rules[found.rule];
+                findings.push(LintFinding {
+                    rule_id: rule.id.clone(),
+                    severity: rule.severity,
+                    message: rule.message.clone(),
+                    site: site.clone(),
+                    branch: found.branch.map(|index| branches[index].clone()),
+                    matched: found.matched,
+                    span: span_in_value(path, text, prose, found.offset),
+                });
+            }
+        }
+        Ok(findings)
+    }
+}
+
+/// The file span of byte `offset` of a prose value's decoded text.
+fn span_in_value(path: &Path, text: &str, prose: &SpannedText, offset: usize) -> SourceSpan {
+    let template = prose.get_ref();
+    let line_start = template[..offset].rfind('\n').map_or(0, |nl| nl + 1);
+    let line = template[..offset].matches('\n').count() + 1;
+    let column = template[line_start..offset].chars().count() + 1;
+    match (u16::try_from(line), u16::try_from(column)) {
+        (Ok(line), Ok(column)) => {
+            SourceSpan::in_value(path, text, prose.span(), rhai::Position::new(line, column))
+        }
+        _ => SourceSpan::new(path, text, prose.span()),
+    }
+}
+
+/// Most branch combinations linted per template.
+pub const MAX_COMBINATIONS: usize = 256;
+
+/// How far either side of a join between two runs of text a match that
+/// crosses it is looked for, in bytes.
+pub const JOIN_WINDOW: usize = 100;
+
+/// A template as text runs and `{% if %}` chains.
+struct Outline {
+    root: Vec<Node>,
+    chains: Vec<Chain>,
+    /// Every run of text, with the branch it is in.
+    texts: Vec<(Range<usize>, Option<usize>)>,
+}
+
+enum Node {
+    /// Template text, by byte range.
+    Text(Range<usize>),
+    /// An `{% if %}` chain, by index into [`Outline::chains`].
+    Chain(usize),
+}
+
+struct Chain {
+    /// Each branch's index in template order and its body.
+    branches: Vec<(usize, Vec<Node>)>,
+    /// The chain ends in `{% else %}`, so one of its branches always renders.
+    exhaustive: bool,
+    /// The chain and branch this chain is nested in.
+    parent: Option<(usize, usize)>,
+}
+
+impl Chain {
+    /// The ways the chain can render: one per branch, plus rendering nothing
+    /// when it has no `{% else %}`.
+    fn options(&self) -> usize {
+        self.branches.len() + usize::from(!self.exhaustive)
+    }
+}
+
+impl Outline {
+    /// Outline `template` from its [`scan_branches`] chains.
+    fn parse(template: &str) -> Self {
+        let found = scan_branches(template);
+        let mut chains: Vec<Chain> = Vec::new();
+        // Each chain's byte range, from its `{% if %}` to its `{% endif %}`,
+        // and the branch it is nested in.
+        let mut extents: Vec<Range<usize>> = Vec::new();
+        let mut outer: Vec<Option<usize>> = Vec::new();
+        for (index, branch) in found.iter().enumerate() {
+            if branch.chain == chains.len() {
+                let within = innermost_branch(&found, branch.tag.start);
+                chains.push(Chain {
+                    branches: Vec::new(),
+                    exhaustive: false,
+                    parent: within.map(|within| {
+                        let chain = found[within].chain;
+                        (chain, chains[chain].branches.len() - 1)
+                    }),
+                });
+                extents.push(branch.tag.start..branch.body.end);
+                outer.push(within);
+            }
+            let chain = &mut chains[branch.chain];
+            chain.branches.push((index, Vec::new()));
+            chain.exhaustive = branch.branch.tag == "else";
+            extents[branch.chain].end = branch.body.end;
+        }
+
+        let tags: Vec<Range<usize>> = template_tags(template)
+            .into_iter()
+            .map(|tag| tag.range)
+            .collect();
+        let nested = |within: Option<usize>| -> Vec<usize> {
+            (0..outer.len())
+                .filter(|&chain| outer[chain] == within)
+                .collect()
+        };
+        let mut outline = Outline {
+            root: Vec::new(),
+            chains,
+            texts: Vec::new(),
+        };
+        outline.root = outline.nodes(&tags, &extents, 0..template.len(), &nested(None), None);
+        for (index, branch) in found.iter().enumerate() {
+            let body = outline.nodes(
+                &tags,
+                &extents,
+                branch.body.clone(),
+                &nested(Some(index)),
+                Some(index),
+            );
+            let chain = &mut outline.chains[branch.chain];
+            if let Some((_, nodes)) = chain.branches.iter_mut().find(|(i, _)| *i == index) {
+                *nodes = body;
+            }
+        }
+        outline
+    }
+
+    /// The nodes of `range`: the `chains` in it, and the text around them
+    /// with every tag dropped.
+    fn nodes(
+        &mut self,
+        tags: &[Range<usize>],
+        extents: &[Range<usize>],
+        range: Range<usize>,
+        chains: &[usize],
+        branch: Option<usize>,
+    ) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut text_start = range.start;
+        for &chain in chains {
+            self.push_text(tags, &mut nodes, text_start..extents[chain].start, branch);
+            nodes.push(Node::Chain(chain));
+            text_start = extents[chain].end;
+        }
+        self.push_text(tags, &mut nodes, text_start..range.end, branch);
+        nodes
+    }
+
+    /// Push the runs of `text` between its tags.
+    fn push_text(
+        &mut self,
+        tags: &[Range<usize>],
+        nodes: &mut Vec<Node>,
+        text: Range<usize>,
+        branch: Option<usize>,
+    ) {
+        let mut runs = Vec::new();
+        let mut start = text.start;
+        for tag in tags.iter().filter(|tag| text.contains(&tag.start)) {
+            runs.push(start..tag.start);
+            start = tag.end;
+        }
+        runs.push(start..text.end);
+        for run in runs.into_iter().filter(|run| !run.is_empty()) {
+            self.texts.push((run.clone(), branch));
+            nodes.push(Node::Text(run));
+        }
+    }
+
+    /// The option each chain takes, per combination to lint: every
+    /// combination up to [`MAX_COMBINATIONS`], else one per option with the
+    /// chains around it taking their first branch.
+    fn choices(&self) -> Vec<Vec<usize>> {
+        let unset = vec![0; self.chains.len()];
+        if self
+            .count(&self.root)
+            .is_some_and(|count| count <= MAX_COMBINATIONS)
+        {
+            return self.expand(&self.root, vec![unset]);
+        }
+        let mut choices = Vec::new();
+        for (chain, options) in self.chains.iter().enumerate() {
+            for option in 0..options.options() {
+                let mut choice = unset.clone();
+                choice[chain] = option;
+                let mut inner = chain;
+                while let Some((outer, branch)) = self.chains[inner].parent {
+                    choice[outer] = branch;
+                    inner = outer;
+                }
+                choices.push(choice);
+            }
+        }
+        choices
+    }
+
+    /// How many combinations `nodes` render; `None` past `usize`.
+    fn count(&self, nodes: &[Node]) -> Option<usize> {
+        nodes.iter().try_fold(1usize, |total, node| {
+            let Node::Chain(chain) = node else {
+                return Some(total);
+            };
+            let chain = &self.chains[*chain];
+            let rendered = chain
+                .branches
+                .iter()
+                .try_fold(usize::from(!chain.exhaustive), |sum, (_, body)| {
+                    sum.checked_add(self.count(body)?)
+                })?;
+            total.checked_mul(rendered)
+        })
+    }
+
+    /// Extend each of `partial` with every combination of the chains in
+    /// `nodes`.
+    fn expand(&self, nodes: &[Node], mut partial: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        for node in nodes {
+            let Node::Chain(chain) = node else {
+                continue;
+            };
+            let mut extended = Vec::new();
+            for choice in partial {
+                for option in 0..self.chains[*chain].options() {
+                    let mut choice = choice.clone();
+                    choice[*chain] = option;
+                    match self.chains[*chain].branches.get(option) {
+                        Some((_, body)) => extended.extend(self.expand(body, vec![choice])),
+                        None => extended.push(choice),
+                    }
+                }
+            }
+            partial = extended;
+        }
+        partial
+    }
+
+    /// The text `choice` renders.
+    fn variant(&self, template: &str, choice: &[usize]) -> Variant {
+        let mut variant = Variant::default();
+        self.push_nodes(template, &self.root, choice, None, &mut variant);
+        variant
+    }
+
+    fn push_nodes(
+        &self,
+        template: &str,
+        nodes: &[Node],
+        choice: &[usize],
+        branch: Option<usize>,
+        variant: &mut Variant,
+    ) {
+        for node in nodes {
+            match node {
+                Node::Text(range) => variant.push(range.start, &template[range.clone()], branch),
+                Node::Chain(chain) => {
+                    if let Some((index, body)) = self.chains[*chain].branches.get(choice[*chain]) {
+                        self.push_nodes(template, body, choice, Some(*index), variant);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The branch, by index into `found`, with the innermost body holding
+/// `offset`.
+fn innermost_branch(found: &[FoundBranch], offset: usize) -> Option<usize> {
+    found
+        .iter()
+        .enumerate()
+        .filter(|(_, branch)| branch.body.contains(&offset))
+        .min_by_key(|(_, branch)| branch.body.len())
+        .map(|(index, _)| index)
+}
+
+/// The nearest char boundary of `text` at or before `offset`, clamped to its
+/// length.
+fn char_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// The text of one branch combination.
+#[derive(Default)]
+struct Variant {
+    text: String,
+    /// `(offset in text, offset in template, branch)` at the start of each
+    /// piece.
+    pieces: Vec<(usize, usize, Option<usize>)>,
+}
+
+impl Variant {
+    fn push(&mut self, template_offset: usize, piece: &str, branch: Option<usize>) {
+        if !piece.is_empty() {
+            self.pieces.push((self.text.len(), template_offset, branch));
+            self.text.push_str(piece);
+        }
+    }
+
+    /// The template offset of `text_offset`, and the branch it is in.
+    fn template_offset(&self, text_offset: usize) -> (usize, Option<usize>) {
+        let index = self
+            .pieces
+            .partition_point(|&(start, _, _)| start <= text_offset)
+            .saturating_sub(1);
+        let (start, template_start, branch) = self.pieces[index];
+        (template_start + (text_offset - start), branch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(src: &str) -> ProseLintRules {
+        ProseLintRules::parse(Path::new("prose_lint.toml"), src).unwrap()
+    }
+
+    const CLICHE: &str = r#"
+        [[rules]]
+        id       = "erotic_cliche"
+        severity = "error"
+        message  = "AI erotic cliché"
+        phrases  = ["throbbing"]
+        patterns = ['bit (her|your) lip']
+    "#;
+
+    #[test]
+    fn phrases_match_whole_words_in_any_case() {
+        let rules = rules(CLICHE);
+        let matched: Vec<String> = rules
+            .lint_template("Throbbing bass. A throbbingly dull night. You bit your lip.")
+            .into_iter()
+            .map(|m| m.matched)
+            .collect();
+        assert_eq!(matched, ["Throbbing", "bit your lip"]);
+    }
+
+    #[test]
+    fn branch_combinations_are_linted_as_they_render() {
+        let rules = rules(CLICHE);
+        let template = "You {% if a %}bit{% else %}chewed{% endif %} your lip.\n\
+                        {% if b %}Outside.{% if c %}\nThe throbbing{{ x }} stops.{% endif %}{% endif %}";
+        let matches = rules.lint_template(template);
+        let found: Vec<(&str, Option<usize>)> = matches
+            .iter()
+            .map(|m| (m.matched.as_str(), m.branch))
+            .collect();
+        assert_eq!(found, [("bit your lip", Some(0)), ("throbbing", Some(3))]);
+        assert_eq!(&template[matches[1].offset..][..9], "throbbing");
+        assert_eq!(prose_branches(template)[3].tag, "if c");
+    }
+
+    #[test]
+    fn a_phrase_is_not_joined_across_branches_that_never_render_together() {
+        let rules = rules(CLICHE);
+        let template = "You bit {% if a %}your{% else %}her{% endif %}{% if b %} lip{% endif %}.";
+        assert_eq!(rules.lint_template(template).len(), 1, "reported once");
+        let exclusive = "You bit {% if a %}your{% else %} lip{% endif %}.";
+        assert!(rules.lint_template(exclusive).is_empty());
+    }
+
+    #[test]
+    fn past_the_combination_limit_every_branch_is_still_linted() {
+        let rules = rules(CLICHE);
+        let mut template =
+            "{% if z %}{% if y %}A throbbing ache.{% endif %}{% endif %}".to_string();
+        for index in 0..9 {
+            template.push_str(&format!("{{% if c{index} %}}Rain.{{% endif %}}"));
+        }
+        let matches = rules.lint_template(&template);
+        assert_eq!(matches.len(), 1, "{matches:?}");
+        assert_eq!(matches[0].branch, Some(1));
+    }
+
+    #[test]
+    fn scene_findings_point_at_the_prose_line() {
+        let rules = rules(CLICHE);
+        let scene = r#"[scene]
+id = "base::test"
+pack = "base"
+description = "Test."
+
+[intro]
+prose = """
+The room is quiet.
+{% if w.hasTrait("SHY") %}
+You bit your lip.
+{% endif %}
+"""
+
+[[actions]]
+id = "wait"
+label = "Wait"
+prose = "A throbbing headache."
+"#;
+        let findings = rules
+            .lint_scene_file(Path::new("test.toml"), scene)
+            .unwrap();
+        let summary: Vec<(String, usize, String)> = findings
+            .iter()
+            .map(|f| (f.location(), f.span.line, f.matched.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "intro prose branch `{% if w.hasTrait(\"SHY\") %}`".to_string(),
+                    10,
+                    "bit your lip".to_string()
+                ),
+                (
+                    "action 'wait' prose".to_string(),
+                    17,
+                    "throbbing".to_string()
+                ),
+            ]
+        );
+        assert!(findings
+            .iter()
+            .all(|f| f.rule_id == "erotic_cliche" && f.severity == LintSeverity::Error));
+    }
+
+    #[test]
+    fn bad_rules_are_load_errors() {
+        let empty = r#"
+            [[rules]]
+            id = "empty"
+            severity = "warning"
+            message = "Nothing to match."
+        "#;
+        let bad_regex = r#"
+            [[rules]]
+            id = "lookahead"
+            severity = "warning"
+            message = "No lookaround in the regex crate."
+            patterns = ['come(?! in)']
+        "#;
+        for (src, id) in [(empty, "empty"), (bad_regex, "lookahead")] {
+            match ProseLintRules::parse(Path::new("prose_lint.toml"), src) {
+                Err(ProseLintError::InvalidRule { id: found, .. }) => assert_eq!(found, id),
+                other => panic!("expected an invalid-rule error, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn shipped_rules_load() {
+        let path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../packs/base/data/prose_lint.toml");
+        let rules = ProseLintRules::load(&path).unwrap();
+        assert!(!rules.is_empty());
+    }
+}
@@ -2,7 +2,8 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::script::{
-    source_advance_arcs, source_has_liking_overshoot, source_set_game_flags, CompiledScript,
+    source_advance_arcs, source_eq_calls, source_game_flag_checks, source_has_liking_overshoot,
+    source_set_game_flags, source_unverifiable_args, CompiledScript, ScriptSource,
 };
 use crate::types::SceneDefinition;
 
@@ -36,35 +37,50 @@ pub fn check_reachability(
     let mut seen = HashSet::new();
 
     for (context, script) in schedule_conditions {
-        inspect_source(&script.source, context, &facts, &mut warnings, &mut seen);
+        inspect_source(script, context, &facts, &mut warnings, &mut seen);
+        for message in source_unverifiable_args(script) {
+            push_warning(context, message, &mut warnings, &mut seen);
+        }
+    }
+
+    // Effects whose flag/arc args are computed can't feed the facts above.
+    let mut scene_ids: Vec<&String> = scenes.keys().collect();
+    scene_ids.sort();
+    for id in scene_ids {
+        for script in effect_scripts(&scenes[id]) {
+            for message in source_unverifiable_args(script) {
+                push_warning(&format!("scene '{id}'"), message, &mut warnings, &mut seen);
+            }
+        }
     }
 
     warnings
 }
 
+fn effect_scripts(scene: &SceneDefinition) -> impl Iterator<Item = &CompiledScript> {
+    scene
+        .actions
+        .iter()
+        .flat_map(|a| a.effects())
+        .chain(scene.npc_actions.iter().filter_map(|a| a.effect.as_ref()))
+}
+
 fn collect_effect_facts(scenes: &HashMap<String, Arc<SceneDefinition>>) -> EffectFacts {
     let mut facts = EffectFacts::default();
 
     for scene in scenes.values() {
-        let effect_sources = scene
-            .actions
-            .iter()
-            .filter_map(|a| a.effect.as_ref())
-            .chain(scene.npc_actions.iter().filter_map(|a| a.effect.as_ref()))
-            .map(|script| script.source.as_str());
-
-        for src in effect_sources {
-            for flag in source_set_game_flags(src) {
+        for script in effect_scripts(scene) {
+            for flag in source_set_game_flags(script) {
                 facts.set_game_flags.insert(flag);
             }
-            for (arc, state) in source_advance_arcs(src) {
+            for (arc, state) in source_advance_arcs(script) {
                 facts
                     .reachable_arc_states
                     .entry(arc)
                     .or_default()
                     .insert(state);
             }
-            if source_has_liking_overshoot(src) {
+            if source_has_liking_overshoot(script) {
                 facts.npc_liking_can_overshoot = true;
             }
         }
@@ -73,11 +89,11 @@ fn collect_effect_facts(scenes: &HashMap<String, Arc<SceneDefinition>>) -> Effec
     facts
 }
 
-/// Scan a compiled condition's source for the three reachability patterns. This
-/// reconstructs the legacy `Expr` walk over the authored Rhai source — sound
-/// because flag / arc / liking args are string literals (the design's rule).
+/// Check a compiled condition for the three reachability patterns, pack helpers
+/// included. This reconstructs the legacy `Expr` walk over the script AST;
+/// computed args are reported separately by `source_unverifiable_args`.
 fn inspect_source(
-    src: &str,
+    src: &CompiledScript,
     context: &str,
     facts: &EffectFacts,
     warnings: &mut Vec<ReachabilityWarning>,
@@ -85,7 +101,7 @@ fn inspect_source(
 ) {
     // hasGameFlag("X") — warn if no scene sets X. A negated check (`!gd.hasGameFlag`)
     // means "absence is intended", so skip it.
-    for (flag, negated) in find_hasgameflag(src) {
+    for (flag, negated) in source_game_flag_checks(src) {
         if !negated && !facts.set_game_flags.contains(&flag) {
             push_warning(
                 context,
@@ -97,7 +113,7 @@ fn inspect_source(
     }
 
     // arcState("ARC") == "STATE" — warn if no scene advances ARC to STATE.
-    for (arc, state) in find_eq_call(src, "arcState") {
+    for (arc, state) in source_eq_calls(src, "arcState") {
         let reachable = facts
             .reachable_arc_states
             .get(&arc)
@@ -114,7 +130,7 @@ fn inspect_source(
 
     // npcLiking("ROLE") == "LEVEL" — warn if an AddNpcLiking delta > 1 can overshoot it.
     if facts.npc_liking_can_overshoot {
-        for (role, level) in find_eq_call(src, "npcLiking") {
+        for (role, level) in source_eq_calls(src, "npcLiking") {
             push_warning(
                 context,
                 format!("exact npc liking check '{role} == {level}' may be skipped by AddNpcLiking deltas larger than 1"),
@@ -128,101 +144,14 @@ fn inspect_source(
 /// All `hasGameFlag("FLAG")` references in a condition source, each paired with
 /// whether it is logically negated (`!gd.hasGameFlag(...)`). A negated reference
 /// is an anti-requirement (the flag must be ABSENT), not a dependency.
-pub fn required_game_flags(src: &str) -> Vec<(String, bool)> {
-    find_hasgameflag(src)
+pub fn required_game_flags(src: &(impl ScriptSource + ?Sized)) -> Vec<(String, bool)> {
+    source_game_flag_checks(src)
 }
 
 /// All `arcState("ARC") == "STATE"` equality references in a condition source,
 /// returned as `(arc, state)` pairs.
-pub fn arc_state_eqs(src: &str) -> Vec<(String, String)> {
-    find_eq_call(src, "arcState")
-}
-
-/// Find every `hasGameFlag("FLAG")` and whether it is logically negated (the
-/// nearest non-space char before `gd.hasGameFlag` is `!`).
-fn find_hasgameflag(src: &str) -> Vec<(String, bool)> {
-    const NEEDLE: &str = "hasGameFlag(";
-    let bytes = src.as_bytes();
-    let mut out = Vec::new();
-    for (idx, _) in src.match_indices(NEEDLE) {
-        if let Some(flag) = string_arg_at(src, idx + NEEDLE.len()) {
-            // Walk back past `gd.hasGameFlag` to the receiver start, then skip
-            // whitespace; a leading `!` (possibly with spaces) means negated.
-            let mut j = idx;
-            // step back over "gd." or any "recv."
-            while j > 0
-                && (bytes[j - 1].is_ascii_alphanumeric()
-                    || bytes[j - 1] == b'.'
-                    || bytes[j - 1] == b'_')
-            {
-                j -= 1;
-            }
-            while j > 0 && bytes[j - 1].is_ascii_whitespace() {
-                j -= 1;
-            }
-            let negated = j > 0 && bytes[j - 1] == b'!';
-            out.push((flag, negated));
-        }
-    }
-    out
-}
-
-/// Find `method("ARG") == "VALUE"` (and the reversed `"VALUE" == method("ARG")`)
-/// occurrences, returning `(arg, value)` pairs.
-fn find_eq_call(src: &str, method: &str) -> Vec<(String, String)> {
-    let needle = format!("{method}(");
-    let bytes = src.as_bytes();
-    let mut out = Vec::new();
-    for (idx, _) in src.match_indices(&needle) {
-        let arg_start = idx + needle.len();
-        let Some(arg) = string_arg_at(src, arg_start) else {
-            continue;
-        };
-        // advance past the call's closing ')'
-        let mut k = arg_start;
-        while k < bytes.len() && bytes[k] != b')' {
-            k += 1;
-        }
-        k += 1; // past ')'
-                // method-first order: `) == "value"`
-        if let Some(value) = eq_string_after(src, k) {
-            out.push((arg.clone(), value));
-            continue;
-        }
-        // value-first order: `"value" == method(...)` — look before the receiver.
-        if let Some(value) = eq_string_before(src, idx) {
-            out.push((arg, value));
-        }
-    }
-    out
-}
-
-/// If `src[from..]` begins (after optional whitespace) with a `"..."` literal,
-/// return its contents.
-fn string_arg_at(src: &str, from: usize) -> Option<String> {
-    let s = &src[from..];
-    let s = s.trim_start();
-    let rest = s.strip_prefix('"')?;
-    let end = rest.find('"')?;
-    Some(rest[..end].to_string())
-}
-
-/// After position `from`, expect `== "value"` (whitespace-tolerant); return value.
-fn eq_string_after(src: &str, from: usize) -> Option<String> {
-    let s = src.get(from..)?.trim_start();
-    let s = s.strip_prefix("==")?.trim_start();
-    let rest = s.strip_prefix('"')?;
-    let end = rest.find('"')?;
-    Some(rest[..end].to_string())
-}
-
-/// Before position `to`, expect `"value" ==` (whitespace-tolerant); return value.
-fn eq_string_before(src: &str, to: usize) -> Option<String> {
-    let s = src.get(..to)?.trim_end();
-    let s = s.strip_suffix("==")?.trim_end();
-    let inner = s.strip_suffix('"')?;
-    let start = inner.rfind('"')?;
-    Some(inner[start + 1..].to_string())
+pub fn arc_state_eqs(src: &(impl ScriptSource + ?Sized)) -> Vec<(String, String)> {
+    source_eq_calls(src, "arcState")
 }
 
 fn push_warning(
@@ -280,6 +209,8 @@ mod tests {
             id: "test::scene".into(),
             pack: "test".into(),
             intro_prose: "Intro.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -295,8 +226,11 @@ mod tests {
                     goto: None,
                     slot: None,
                     finish: true,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         })
@@ -394,4 +328,48 @@ mod tests {
         assert_eq!(warnings.len(), 1);
         assert!(warnings[0].message.contains("ROLE_JAKE"));
     }
+
+    #[test]
+    fn computed_effect_flag_is_reported_unverifiable() {
+        // A `let`-bound flag can't be seen statically: the gate on it warns as
+        // unset, and the effect itself is called out rather than skipped.
+        let scenes = HashMap::from([(
+            "test::scene".to_string(),
+            scene_with_effect(r#"let flag = "JAKE_MET"; gd.setGameFlag(flag);"#),
+        )]);
+
+        let warnings = check_reachability(
+            &[(
+                "slot 'free_time', scene 'base::jake_first_date'".to_string(),
+                cond(r#"gd.hasGameFlag("JAKE_MET")"#),
+            )],
+            &scenes,
+            &HashSet::new(),
+        );
+
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert_eq!(warnings[1].context, "scene 'test::scene'");
+        assert!(warnings[1]
+            .message
+            .contains("'gd.setGameFlag' arg 1 is computed at runtime"));
+    }
+
+    #[test]
+    fn flag_set_by_helper_function_counts() {
+        let scenes = HashMap::from([(
+            "test::scene".to_string(),
+            scene_with_effect(r#"fn meet(gd) { gd.setGameFlag("JAKE_" + "MET") } meet(gd);"#),
+        )]);
+
+        let warnings = check_reachability(
+            &[(
+                "slot 'free_time', scene 'base::jake_first_date'".to_string(),
+                cond(r#"gd.hasGameFlag("JAKE_MET")"#),
+            )],
+            &scenes,
+            &HashSet::new(),
+        );
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
 }
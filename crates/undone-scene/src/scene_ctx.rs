@@ -2,8 +2,9 @@
 //!
 //! `SceneCtx` carries the scene-local state that conditions and effects read and
 //! write during a scene run: the active male/female NPC, role bindings,
-//! scene-local flags, the per-scene skill-roll cache, and the current scene id
-//! (for red-check tracking). It lives only for the duration of one scene run.
+//! scene-local flags and variables, the per-scene skill-roll cache, the current
+//! scene id (for red-check tracking), and — for a called sub-scene — its
+//! params. It lives only for the duration of one scene run.
 //!
 //! Moved here from the deleted `undone-expr` crate when conditions/effects were
 //! cut over to Rhai; it has no dependency on the old expression parser.
@@ -11,8 +12,13 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
+use rand::rngs::SmallRng;
 use rand::Rng;
-use undone_domain::{FemaleNpcKey, MaleNpcKey};
+use undone_domain::{FemaleNpcKey, MaleNpcKey, NpcKey, VarValue};
+use undone_world::GameData;
+
+use crate::script::api::ApiValue;
+use crate::types::SceneVar;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SceneNpcRef {
@@ -20,18 +26,28 @@ pub enum SceneNpcRef {
     Female(FemaleNpcKey),
 }
 
+#[derive(Clone)]
 pub struct SceneCtx {
     pub active_male: Option<MaleNpcKey>,
     pub active_female: Option<FemaleNpcKey>,
     pub role_bindings: HashMap<String, SceneNpcRef>,
     pub scene_flags: HashSet<String>,
-    pub weighted_map: HashMap<String, i32>,
+    /// The scene's declared variables (`scene.get` / `scene.set` / `scene.add`).
+    pub vars: HashMap<String, SceneVar>,
     /// Cached percentile rolls (1–100) keyed by skill_id string.
     /// Interior mutability so eval() can cache without needing &mut SceneCtx.
     pub skill_rolls: RefCell<HashMap<String, i32>>,
+    /// Where uncached rolls come from. The engine hands each scene one drawn
+    /// from its own rng so a seeded engine rolls the same every run; without
+    /// one, rolls come from the thread rng.
+    pub roll_rng: RefCell<Option<SmallRng>>,
     /// Scene ID set by the engine before evaluating conditions.
     /// Required for red-check failure tracking.
     pub scene_id: Option<String>,
+    /// Arguments the scene was called with (`scene.param`).
+    pub params: HashMap<String, ApiValue>,
+    /// The value the last sub-scene this scene called returned (`scene.result`).
+    pub returned: Option<ApiValue>,
 }
 
 impl SceneCtx {
@@ -41,9 +57,12 @@ impl SceneCtx {
             active_female: None,
             role_bindings: HashMap::new(),
             scene_flags: HashSet::new(),
-            weighted_map: HashMap::new(),
+            vars: HashMap::new(),
             skill_rolls: RefCell::new(HashMap::new()),
+            roll_rng: RefCell::new(None),
             scene_id: None,
+            params: HashMap::new(),
+            returned: None,
         }
     }
 
@@ -55,6 +74,28 @@ impl SceneCtx {
         self.scene_flags.insert(flag.into());
     }
 
+    /// The current value of scene variable `name`; a persistent one reads
+    /// through to `gd`.
+    pub fn var<'a>(&'a self, gd: &'a GameData, name: &str) -> Option<&'a VarValue> {
+        let var = self.vars.get(name)?;
+        Some(match &var.persist {
+            Some(id) => gd.var(id).unwrap_or(&var.value),
+            None => &var.value,
+        })
+    }
+
+    /// Store `value` in scene variable `name` (a persistent one in `gd`).
+    /// Callers check the name is declared and the type matches.
+    pub fn set_var(&mut self, gd: &mut GameData, name: &str, value: VarValue) {
+        let Some(var) = self.vars.get_mut(name) else {
+            return;
+        };
+        match &var.persist {
+            Some(id) => gd.set_var(id.clone(), value),
+            None => var.value = value,
+        }
+    }
+
     pub fn bind_role(&mut self, role: impl Into<String>, npc: SceneNpcRef) {
         self.role_bindings.insert(role.into(), npc);
     }
@@ -63,6 +104,30 @@ impl SceneCtx {
         self.role_bindings.get(role).copied()
     }
 
+    /// Every NPC in the scene — active male, active female, then role bindings
+    /// in role order — each listed once.
+    pub fn participants(&self) -> Vec<NpcKey> {
+        let mut roles: Vec<_> = self.role_bindings.iter().collect();
+        roles.sort_by(|left, right| left.0.cmp(right.0));
+        let bound = roles.into_iter().map(|(_, npc)| match *npc {
+            SceneNpcRef::Male(key) => NpcKey::Male(key),
+            SceneNpcRef::Female(key) => NpcKey::Female(key),
+        });
+        let mut keys: Vec<NpcKey> = Vec::new();
+        for key in self
+            .active_male
+            .map(NpcKey::Male)
+            .into_iter()
+            .chain(self.active_female.map(NpcKey::Female))
+            .chain(bound)
+        {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+
     /// Force a specific roll value for testing. Call before evaluating checkSkill.
     pub fn set_skill_roll(&self, skill_id: &str, roll: i32) {
         self.skill_rolls
@@ -73,9 +138,12 @@ impl SceneCtx {
     /// Return a cached roll for this skill, or generate and cache a new one (1–100).
     pub fn get_or_roll_skill(&self, skill_id: &str) -> i32 {
         let mut rolls = self.skill_rolls.borrow_mut();
-        *rolls
-            .entry(skill_id.to_string())
-            .or_insert_with(|| rand::thread_rng().gen_range(1_i32..=100))
+        *rolls.entry(skill_id.to_string()).or_insert_with(|| {
+            match self.roll_rng.borrow_mut().as_mut() {
+                Some(rng) => rng.gen_range(1_i32..=100),
+                None => rand::thread_rng().gen_range(1_i32..=100),
+            }
+        })
     }
 }
 
@@ -89,6 +157,35 @@ impl Default for SceneCtx {
 mod tests {
     use super::*;
 
+    #[test]
+    fn persistent_vars_read_and_write_through_game_data() {
+        let mut gd = GameData::default();
+        let mut ctx = SceneCtx::new();
+        ctx.vars.insert(
+            "drinks".into(),
+            SceneVar {
+                value: VarValue::Int(0),
+                persist: None,
+            },
+        );
+        ctx.vars.insert(
+            "tension".into(),
+            SceneVar {
+                value: VarValue::Int(2),
+                persist: Some("BAR_TENSION".into()),
+            },
+        );
+        assert_eq!(ctx.var(&gd, "tension"), Some(&VarValue::Int(2)));
+
+        ctx.set_var(&mut gd, "drinks", VarValue::Int(1));
+        ctx.set_var(&mut gd, "tension", VarValue::Int(5));
+        assert_eq!(ctx.var(&gd, "drinks"), Some(&VarValue::Int(1)));
+        assert_eq!(ctx.var(&gd, "tension"), Some(&VarValue::Int(5)));
+        assert_eq!(gd.var("BAR_TENSION"), Some(&VarValue::Int(5)));
+        assert_eq!(gd.var("drinks"), None);
+        assert_eq!(ctx.var(&gd, "missing"), None);
+    }
+
     #[test]
     fn set_and_get_skill_roll_returns_same_value() {
         let ctx = SceneCtx::new();
@@ -8,7 +8,8 @@ use crate::scene_ctx::SceneCtx;
 use rand::Rng;
 use serde::Deserialize;
 use thiserror::Error;
-use undone_packs::{LoadedPackMeta, PackRegistry};
+use undone_domain::NpcKey;
+use undone_packs::{LoadedPackMeta, PackRegistry, ONCE_FLAG_PREFIX};
 use undone_world::World;
 
 use crate::script::{eval_bool, source_references_game_flag, CompiledScript};
@@ -69,6 +70,11 @@ struct ScheduleEventToml {
     /// desire bias; the engine never decides what counts as "adult".
     #[serde(default)]
     desire_scaled: bool,
+    /// When true, this event plays NPC outreach queued by pack routines
+    /// (`routines.toml`) for this scene, binding the NPC that reached out.
+    /// Outreach events never enter the weighted pool.
+    #[serde(default)]
+    outreach: bool,
 }
 
 fn default_weight() -> u32 {
@@ -106,6 +112,7 @@ pub(crate) struct ScheduleEvent {
     pub(crate) trigger: Option<CompiledScript>,
     pub(crate) npc_role: Option<String>,
     pub(crate) desire_scaled: bool,
+    pub(crate) outreach: bool,
 }
 
 #[derive(Clone)]
@@ -129,6 +136,24 @@ pub struct PickResult {
     /// When set, the runtime should bind the NPC with this role as the active
     /// male (or female) NPC before starting the scene.
     pub npc_role: Option<String>,
+    /// The specific NPC to bind, set when the pick answers queued outreach.
+    /// Takes precedence over `npc_role`.
+    pub npc: Option<NpcKey>,
+}
+
+impl PickResult {
+    /// Game-loop bookkeeping once the picked scene starts: marks once-only
+    /// scenes as fired and consumes the outreach this pick answered.
+    pub fn mark_started(&self, world: &mut World) {
+        if self.once_only {
+            world
+                .game_data
+                .set_flag_from(format!("{ONCE_FLAG_PREFIX}{}", self.scene_id), "scheduler");
+        }
+        if let Some(npc) = self.npc {
+            world.game_data.clear_outreach(&self.scene_id, npc);
+        }
+    }
 }
 
 /// A read-only projection of one scheduled event's binding metadata, for
@@ -142,6 +167,7 @@ pub struct SceneBinding {
     pub once_only: bool,
     pub npc_role: Option<String>,
     pub desire_scaled: bool,
+    pub outreach: bool,
     pub condition_source: Option<String>,
     pub trigger_source: Option<String>,
 }
@@ -188,11 +214,11 @@ impl Scheduler {
                 event
                     .condition
                     .as_ref()
-                    .is_some_and(|s| source_references_game_flag(&s.source, flag))
+                    .is_some_and(|s| source_references_game_flag(s, flag))
                     || event
                         .trigger
                         .as_ref()
-                        .is_some_and(|s| source_references_game_flag(&s.source, flag))
+                        .is_some_and(|s| source_references_game_flag(s, flag))
             })
     }
 
@@ -226,6 +252,7 @@ impl Scheduler {
                     once_only: event.once_only,
                     npc_role: event.npc_role.clone(),
                     desire_scaled: event.desire_scaled,
+                    outreach: event.outreach,
                     condition_source: event.condition.as_ref().map(|s| s.source.clone()),
                     trigger_source: event.trigger.as_ref().map(|s| s.source.clone()),
                 });
@@ -316,6 +343,9 @@ impl Scheduler {
     ///    route flags (e.g. `gd.hasGameFlag('ROUTE_WORKPLACE')`), so events from
     ///    inactive arcs are naturally excluded without any special-casing here.
     ///
+    /// Between the two, `outreach` events play any NPC outreach queued by pack
+    /// routines, oldest first, with the reaching-out NPC set on `PickResult::npc`.
+    ///
     /// Returns `None` if no eligible events exist in any slot.
     pub fn pick_next(
         &self,
@@ -336,6 +366,24 @@ impl Scheduler {
             }
         }
 
+        // Queued NPC outreach, oldest first.
+        for queued in &world.game_data.npc_outreach {
+            if let Some(candidate) = slots
+                .iter()
+                .flat_map(|slot| Self::candidates_for_slot(slot))
+                .find(|candidate| {
+                    candidate.event.outreach
+                        && candidate.event.scene == queued.scene
+                        && Self::is_outreach_candidate(*candidate, world, &ctx, registry)
+                })
+            {
+                return Some(PickResult {
+                    npc: Some(queued.npc),
+                    ..Self::pick_result(candidate)
+                });
+            }
+        }
+
         // 2. Weighted pick across all eligible events from all slots.
         let eligible: Vec<_> = slots
             .iter()
@@ -366,6 +414,7 @@ impl Scheduler {
         registry: &PackRegistry,
     ) -> bool {
         candidate.event.weight > 0
+            && !candidate.event.outreach
             && !Self::already_fired(candidate.event, world)
             && match &candidate.event.condition {
                 Some(expr) => {
@@ -390,8 +439,26 @@ impl Scheduler {
             }
     }
 
+    fn is_outreach_candidate(
+        candidate: ScheduleCandidate<'_>,
+        world: &World,
+        ctx: &SceneCtx,
+        registry: &PackRegistry,
+    ) -> bool {
+        !Self::already_fired(candidate.event, world)
+            && match &candidate.event.condition {
+                Some(expr) => {
+                    Self::eval_event_expr("condition", candidate, expr, world, ctx, registry)
+                }
+                None => true,
+            }
+    }
+
     fn already_fired(event: &ScheduleEvent, world: &World) -> bool {
-        event.once_only && world.game_data.has_flag(&format!("ONCE_{}", event.scene))
+        event.once_only
+            && world
+                .game_data
+                .has_flag(&format!("{ONCE_FLAG_PREFIX}{}", event.scene))
     }
 
     fn eval_event_expr(
@@ -448,6 +515,7 @@ impl Scheduler {
             slot_name: candidate.slot.name.clone(),
             consumes_time: candidate.slot.consumes_time,
             npc_role: candidate.event.npc_role.clone(),
+            npc: None,
         }
     }
 
@@ -568,6 +636,7 @@ pub fn load_schedule(
                     trigger,
                     npc_role: ev.npc_role,
                     desire_scaled: ev.desire_scaled,
+                    outreach: ev.outreach,
                 });
             }
         }
@@ -618,6 +687,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled,
+            outreach: false,
         }
     }
 
@@ -632,6 +702,7 @@ mod tests {
             trigger: Some(cond(r#"gd.week() >= 2"#)),
             npc_role: Some("ROLE_JAKE".to_string()),
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("free_time".to_string(), vec![event]);
@@ -790,6 +861,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let work_event = ScheduleEvent {
             scene: "test::work_scene".into(),
@@ -799,6 +871,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let scheduler = Scheduler {
             slots: HashMap::from([
@@ -933,6 +1006,13 @@ mod tests {
                     races_file: None,
                     categories_file: None,
                     arcs_file: None,
+                    characters_file: None,
+                    routines_file: None,
+                    body_file: None,
+                    odds_file: None,
+                    buffs_file: None,
+                    flags_file: None,
+                    prose_lint_file: None,
                 },
             },
             pack_dir: pack_dir.clone(),
@@ -999,6 +1079,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("test_slot".into(), vec![event]);
@@ -1026,6 +1107,7 @@ mod tests {
                 trigger: None,
                 npc_role: None,
                 desire_scaled: false,
+                outreach: false,
             },
             ScheduleEvent {
                 scene: "test::scene_b".into(),
@@ -1035,6 +1117,7 @@ mod tests {
                 trigger: None,
                 npc_role: None,
                 desire_scaled: false,
+                outreach: false,
             },
         ];
         let mut slots = HashMap::new();
@@ -1073,6 +1156,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("test_slot".into(), vec![event]);
@@ -1110,6 +1194,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("test_slot".into(), vec![event]);
@@ -1140,6 +1225,7 @@ mod tests {
             trigger: Some(trigger_expr),
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("test_slot".into(), vec![event]);
@@ -1167,6 +1253,7 @@ mod tests {
             trigger: Some(trigger_expr),
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("test_slot".into(), vec![event]);
@@ -1193,6 +1280,7 @@ mod tests {
             trigger: Some(trigger_expr),
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("test_slot".into(), vec![event]);
@@ -1219,6 +1307,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("free_time".into(), vec![event]);
@@ -1274,6 +1363,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("free_time".into(), vec![event]);
@@ -1286,6 +1376,81 @@ mod tests {
         assert_eq!(result.unwrap().scene_id, "test::free_scene");
     }
 
+    #[test]
+    fn pick_next_plays_queued_outreach_with_npc_bound() {
+        // BREAKS IF: queued NPC outreach never reaches the scheduler, loses the
+        // NPC binding, or is replayed after it has started.
+        let registry = PackRegistry::new();
+        let mut outreach = bare_event("test::phone", 10, false);
+        outreach.outreach = true;
+        let mut slots = HashMap::new();
+        slots.insert(
+            "free_time".into(),
+            vec![outreach, bare_event("test::free_scene", 10, false)],
+        );
+        let scheduler = scheduler_for_test_slots(slots);
+        let mut world = make_world();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        for _ in 0..20 {
+            let result = scheduler.pick_next(&world, &registry, &mut rng).unwrap();
+            assert_eq!(
+                result.scene_id, "test::free_scene",
+                "outreach events must stay out of the weighted pool"
+            );
+        }
+
+        let npc = NpcKey::Male(undone_domain::MaleNpcKey::default());
+        world.game_data.queue_outreach(undone_world::NpcOutreach {
+            rule: "test::texts".into(),
+            scene: "test::phone".into(),
+            npc,
+            queued_at: world.game_data.absolute_slot(),
+        });
+        let result = scheduler.pick_next(&world, &registry, &mut rng).unwrap();
+        assert_eq!(result.scene_id, "test::phone");
+        assert_eq!(result.npc, Some(npc));
+
+        result.mark_started(&mut world);
+        assert!(world.game_data.npc_outreach.is_empty());
+        let result = scheduler.pick_next(&world, &registry, &mut rng).unwrap();
+        assert_eq!(result.scene_id, "test::free_scene");
+    }
+
+    #[test]
+    fn queued_outreach_lapses_when_its_event_never_plays() {
+        // BREAKS IF: outreach whose event is never eligible stays queued for
+        // good, so its rule can never reach the player again.
+        let registry = PackRegistry::new();
+        let mut slots = HashMap::new();
+        slots.insert(
+            "free_time".into(),
+            vec![bare_event("test::free_scene", 10, false)],
+        );
+        let scheduler = scheduler_for_test_slots(slots);
+        let mut world = make_world();
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        world.game_data.queue_outreach(undone_world::NpcOutreach {
+            rule: "test::texts".into(),
+            scene: "test::phone".into(),
+            npc: NpcKey::Male(undone_domain::MaleNpcKey::default()),
+            queued_at: world.game_data.absolute_slot(),
+        });
+        for _ in 1..undone_world::OUTREACH_EXPIRY_SLOTS {
+            let result = scheduler.pick_next(&world, &registry, &mut rng).unwrap();
+            assert_eq!(result.scene_id, "test::free_scene");
+            world.game_data.advance_time_slot();
+        }
+        assert_eq!(world.game_data.npc_outreach.len(), 1);
+
+        world.game_data.advance_time_slot();
+        assert!(
+            world.game_data.npc_outreach.is_empty(),
+            "unplayed outreach must lapse after OUTREACH_EXPIRY_SLOTS"
+        );
+    }
+
     #[test]
     fn pick_next_trigger_fires_before_weighted_pick() {
         let registry = PackRegistry::new();
@@ -1298,6 +1463,7 @@ mod tests {
             trigger: Some(trigger_expr),
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let weighted_event = ScheduleEvent {
             scene: "test::weighted".into(),
@@ -1307,6 +1473,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         // Put triggered in "a_slot" (sorts first alphabetically) and weighted in "b_slot"
@@ -1363,6 +1530,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let route_condition = cond(r#"gd.hasGameFlag("ROUTE_WORKPLACE")"#);
         let arc_event = ScheduleEvent {
@@ -1373,6 +1541,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let mut slots = HashMap::new();
         slots.insert("free_time".into(), vec![free_event]);
@@ -1558,6 +1727,7 @@ mod tests {
             trigger: None,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let scheduler = scheduler_for_test_slots(HashMap::from([("intro".into(), vec![event])]));
 
@@ -1575,6 +1745,7 @@ mod tests {
             trigger: Some(cond(r#"gd.hasGameFlag("ROUTE_CAMPUS")"#)),
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
         };
         let scheduler = scheduler_for_test_slots(HashMap::from([("intro".into(), vec![event])]));
 
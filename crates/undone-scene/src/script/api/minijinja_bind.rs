@@ -35,13 +35,16 @@ fn marshal_args<'a>(
     };
     Ok(match shape {
         ArgShape::None => Vec::new(),
-        ArgShape::Id(_) | ArgShape::Str => vec![ApiArg::Str(str_at(0)?)],
+        ArgShape::Id(_) | ArgShape::Str | ArgShape::Flag | ArgShape::Body(_) => {
+            vec![ApiArg::Str(str_at(0)?)]
+        }
         ArgShape::StrStr => vec![ApiArg::Str(str_at(0)?), ApiArg::Str(str_at(1)?)],
         // Not prose-reachable (contexts.prose == false): handle defensively.
         ArgShape::IdInt(_)
         | ArgShape::Int { .. }
         | ArgShape::Bool
         | ArgShape::StrInt
+        | ArgShape::StrAny
         | ArgShape::StrOpt => {
             return Err(Error::new(
                 ErrorKind::InvalidOperation,
@@ -6,6 +6,7 @@
 //! the Minijinja prose objects (`minijinja_bind`), and the prose load gate
 //! (`prose_validate`) are all driven from this one table.
 
+use undone_domain::{BodyAttribute, VarType, VarValue};
 use undone_packs::PackRegistry;
 use undone_world::World;
 
@@ -44,6 +45,24 @@ impl ApiValue {
             ApiValue::Str(s) => minijinja::Value::from(s),
         }
     }
+
+    pub fn var_type(&self) -> VarType {
+        match self {
+            ApiValue::Str(_) => VarType::String,
+            ApiValue::Int(_) => VarType::Int,
+            ApiValue::Bool(_) => VarType::Bool,
+        }
+    }
+}
+
+impl From<VarValue> for ApiValue {
+    fn from(value: VarValue) -> Self {
+        match value {
+            VarValue::Bool(b) => ApiValue::Bool(b),
+            VarValue::Int(i) => ApiValue::Int(i),
+            VarValue::Str(s) => ApiValue::Str(s),
+        }
+    }
 }
 
 /// A literal argument as seen by an accessor, borrowed from the call site.
@@ -135,6 +154,21 @@ pub enum Receiver {
     Npc,
 }
 
+impl Receiver {
+    /// The source token authors write, the inverse of [`receiver_from_token`].
+    pub fn token(self) -> &'static str {
+        match self {
+            Receiver::W => "w",
+            Receiver::Gd => "gd",
+            Receiver::M => "m",
+            Receiver::F => "f",
+            Receiver::Role => "role",
+            Receiver::Scene => "scene",
+            Receiver::Npc => "npc",
+        }
+    }
+}
+
 /// Declarative argument contract. `ArgShape` describes the **source** arg list the
 /// static gate counts. For `Receiver::Role` the leading role id IS source-arg 0
 /// (counted here). For `Receiver::Npc` the ref comes from the `npc(ref)` constructor
@@ -149,20 +183,61 @@ pub enum ArgShape {
     IdInt(IdKind),
     /// One int literal. `i8_range` => must fit i8 (step deltas).
     Int { i8_range: bool },
-    /// One opaque string (NOT registry-validated): flags, stuff, job titles, refs.
+    /// One opaque string (NOT registry-validated): scene/NPC flags, stuff, job
+    /// titles, refs.
     Str,
+    /// One game flag name. A literal must be declared in a pack's `flags.toml`
+    /// once any pack declares its flags; a computed one is allowed.
+    Flag,
+    /// One value on a body attribute's scale (`setHairLength("Short")`). A
+    /// literal must be on the scale.
+    Body(BodyAttribute),
     /// One bool literal.
     Bool,
     /// One opaque string (arg0) + one int literal (arg1), neither id-validated.
-    /// Only `gd.npcLikingAtLeast(role, threshold)`.
+    /// `npc(ref).addCloseness(other, delta)`.
     StrInt,
-    /// Two opaque strings (role.hasFlag / role.hasRole: role id + flag/role).
+    /// One opaque string (arg0) + one value of any script type (arg1).
+    /// `scene.set(name, value)`.
+    StrAny,
+    /// Two opaque strings (role.hasFlag / role.hasRole: role id + flag/role;
+    /// `npc(ref).setRelation(other, kind)`).
     StrStr,
     /// A leading bool (arg0) plus an optional trailing string (arg1) — overloaded
     /// arity. Only `setVirgin(value)` / `setVirgin(value, "type")`.
     StrOpt,
 }
 
+impl ArgShape {
+    /// The source arg list as authors see it, e.g. `(skill, amount)`.
+    pub fn params(self) -> String {
+        let params = match self {
+            ArgShape::None => String::new(),
+            ArgShape::Id(IdKind::Arc) => "arc, state".into(),
+            ArgShape::Id(kind) => kind.label().into(),
+            ArgShape::IdInt(kind) => format!("{}, amount", kind.label()),
+            ArgShape::Int { .. } => "amount".into(),
+            ArgShape::Str => "name".into(),
+            ArgShape::Flag => "flag".into(),
+            ArgShape::Body(_) => "value".into(),
+            ArgShape::Bool => "value".into(),
+            ArgShape::StrInt => "name, amount".into(),
+            ArgShape::StrAny => "name, value".into(),
+            ArgShape::StrStr => "id, name".into(),
+            ArgShape::StrOpt => "value, [kind]".into(),
+        };
+        format!("({params})")
+    }
+
+    /// The content id kind expected at source arg `index`, if any.
+    pub fn id_kind_at(self, index: usize) -> Option<IdKind> {
+        match (self, index) {
+            (ArgShape::Id(kind) | ArgShape::IdInt(kind), 0) => Some(kind),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Contexts {
     pub condition: bool,
@@ -202,6 +277,40 @@ pub struct MethodDescriptor {
     pub accessor: Accessor,
 }
 
+impl MethodDescriptor {
+    /// How a call reads in source: `w.getSkill(skill)`, `npc(ref).addLiking(amount)`.
+    pub fn signature(&self) -> String {
+        let receiver = match self.receiver {
+            Receiver::Npc if self.name == "npc" => return "npc(ref)".into(),
+            Receiver::Npc => "npc(ref)",
+            other => other.token(),
+        };
+        format!("{receiver}.{}{}", self.name, self.args.params())
+    }
+
+    /// Where the method may be used, e.g. `conditions, prose`.
+    pub fn usable_in(&self) -> String {
+        let Contexts {
+            condition,
+            effect,
+            prose,
+        } = self.contexts;
+        [
+            (condition, "conditions"),
+            (effect, "effects"),
+            (prose, "prose"),
+        ]
+        .into_iter()
+        .filter_map(|(allowed, name)| allowed.then_some(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    pub fn is_write(&self) -> bool {
+        matches!(self.accessor, Accessor::Write(_))
+    }
+}
+
 /// Look up a descriptor by receiver token + method name. O(n) over a small static
 /// slice — fine; called at load time and once per script call.
 pub fn lookup(receiver: Receiver, method: &str) -> Option<&'static MethodDescriptor> {
@@ -261,4 +370,23 @@ mod tests {
         // lookup returns None for a nonsense method
         assert!(lookup(Receiver::W, "definitelyNotAMethod").is_none());
     }
+
+    #[test]
+    fn signatures_read_like_source_calls() {
+        let sig = |receiver, method| lookup(receiver, method).unwrap().signature();
+        assert_eq!(sig(Receiver::W, "getSkill"), "w.getSkill(skill)");
+        assert_eq!(sig(Receiver::Gd, "advanceArc"), "gd.advanceArc(arc, state)");
+        assert_eq!(sig(Receiver::Npc, "npc"), "npc(ref)");
+        assert_eq!(
+            sig(Receiver::Npc, "addLiking"),
+            "npc(ref).addLiking(amount)"
+        );
+        for d in table::REGISTRY {
+            assert_eq!(receiver_from_token(d.receiver.token()), Some(d.receiver));
+        }
+        assert_eq!(
+            lookup(Receiver::W, "hasTrait").unwrap().usable_in(),
+            "conditions, prose"
+        );
+    }
 }
@@ -143,6 +143,26 @@ pub fn get_stat(
     }))
 }
 
+/// A pack game variable: its stored value, or the declared default.
+pub fn get_var(
+    w: &World,
+    r: &PackRegistry,
+    _c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let id = str0(a, "getVar")?;
+    match w.game_data.var(id) {
+        Some(value) => Ok(value.clone().into()),
+        None => r
+            .get_var(id)
+            .map(|def| def.default_value().into())
+            .ok_or_else(|| ApiError::UnknownId {
+                kind: "var",
+                id: id.to_string(),
+            }),
+    }
+}
+
 // ── string ──────────────────────────────────────────────────────────────────
 
 pub fn time_slot(
@@ -0,0 +1,246 @@
+//! Memory-log reads: how often and how long ago something happened, for the
+//! player (`w`), the active NPCs (`m`/`f`), a role-bound NPC (`role`), and when
+//! the player last saw an NPC (`gd.lastSeen`).
+//!
+//! "Since" reads count whole days (or weeks, = days / 7) from the latest
+//! occurrence to now, and return `-1` for something that never happened.
+
+use undone_domain::{MemoryLog, MemoryStamp};
+use undone_packs::PackRegistry;
+use undone_world::World;
+
+use super::npc_key;
+use crate::scene_ctx::SceneCtx;
+use crate::script::api::{ApiArg, ApiError, ApiValue};
+
+fn str_at<'a>(a: &[ApiArg<'a>], i: usize, method: &'static str) -> Result<&'a str, ApiError> {
+    a.get(i)
+        .and_then(ApiArg::as_str)
+        .ok_or(ApiError::BadArgs { method })
+}
+
+fn npc_log<'w>(ref_: &str, w: &'w World, c: &SceneCtx) -> Result<&'w MemoryLog, ApiError> {
+    let key = npc_key(ref_, w, c)?;
+    w.npc_core(key)
+        .map(|core| &core.memory)
+        .ok_or(ApiError::NpcNotFound)
+}
+
+fn days_since(last: Option<MemoryStamp>, w: &World) -> i64 {
+    last.map_or(-1, |at| at.days_until(w.game_data.memory_stamp()) as i64)
+}
+
+fn times_did(log: &MemoryLog, event: &str) -> ApiValue {
+    ApiValue::Int(log.times(event) as i64)
+}
+
+fn days_since_did(log: &MemoryLog, event: &str, w: &World) -> ApiValue {
+    ApiValue::Int(days_since(log.tally(event).map(|t| t.last), w))
+}
+
+fn weeks_since_did(log: &MemoryLog, event: &str, w: &World) -> ApiValue {
+    let days = days_since(log.tally(event).map(|t| t.last), w);
+    ApiValue::Int(if days < 0 { -1 } else { days / 7 })
+}
+
+// ── w (player) ────────────────────────────────────────────────────────────────
+
+pub fn w_times_did(
+    w: &World,
+    _r: &PackRegistry,
+    _c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(times_did(&w.player.memory, str_at(a, 0, "timesDid")?))
+}
+
+pub fn w_days_since(
+    w: &World,
+    _r: &PackRegistry,
+    _c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(days_since_did(
+        &w.player.memory,
+        str_at(a, 0, "daysSince")?,
+        w,
+    ))
+}
+
+pub fn w_weeks_since(
+    w: &World,
+    _r: &PackRegistry,
+    _c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(weeks_since_did(
+        &w.player.memory,
+        str_at(a, 0, "weeksSince")?,
+        w,
+    ))
+}
+
+// ── m (active male) ───────────────────────────────────────────────────────────
+
+pub fn m_times_did(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(times_did(npc_log("m", w, c)?, str_at(a, 0, "timesDid")?))
+}
+
+pub fn m_days_since(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(days_since_did(
+        npc_log("m", w, c)?,
+        str_at(a, 0, "daysSince")?,
+        w,
+    ))
+}
+
+pub fn m_weeks_since(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(weeks_since_did(
+        npc_log("m", w, c)?,
+        str_at(a, 0, "weeksSince")?,
+        w,
+    ))
+}
+
+// ── f (active female) ─────────────────────────────────────────────────────────
+
+pub fn f_times_did(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(times_did(npc_log("f", w, c)?, str_at(a, 0, "timesDid")?))
+}
+
+pub fn f_days_since(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(days_since_did(
+        npc_log("f", w, c)?,
+        str_at(a, 0, "daysSince")?,
+        w,
+    ))
+}
+
+pub fn f_weeks_since(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(weeks_since_did(
+        npc_log("f", w, c)?,
+        str_at(a, 0, "weeksSince")?,
+        w,
+    ))
+}
+
+// ── role ──────────────────────────────────────────────────────────────────────
+
+pub fn role_times_did(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let log = npc_log(str_at(a, 0, "timesDid")?, w, c)?;
+    Ok(times_did(log, str_at(a, 1, "timesDid")?))
+}
+
+pub fn role_days_since(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let log = npc_log(str_at(a, 0, "daysSince")?, w, c)?;
+    Ok(days_since_did(log, str_at(a, 1, "daysSince")?, w))
+}
+
+pub fn role_weeks_since(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let log = npc_log(str_at(a, 0, "weeksSince")?, w, c)?;
+    Ok(weeks_since_did(log, str_at(a, 1, "weeksSince")?, w))
+}
+
+// ── gd ────────────────────────────────────────────────────────────────────────
+
+/// Days since the NPC's most recent memory of any kind — every scene it appears
+/// in records one when the scene finishes. `-1` if the player never met them.
+pub fn gd_last_seen(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let log = npc_log(str_at(a, 0, "lastSeen")?, w, c)?;
+    Ok(ApiValue::Int(days_since(log.latest().map(|m| m.at), w)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene_ctx::SceneNpcRef;
+    use undone_world::test_helpers::{make_test_male_npc, make_test_world};
+
+    #[test]
+    fn counts_and_gaps_read_from_the_log() {
+        let mut r = PackRegistry::new();
+        let personality = r.intern_personality("ROMANTIC");
+        let mut w = make_test_world();
+        let key = w.male_npcs.insert(make_test_male_npc(personality));
+        let mut c = SceneCtx::new();
+        c.bind_role("ROLE_X", SceneNpcRef::Male(key));
+
+        let at = w.game_data.memory_stamp();
+        w.male_npcs[key].core.memory.record("kissed", at);
+        w.male_npcs[key].core.memory.record("kissed", at);
+        w.game_data.week = 2;
+        w.game_data.day = 3;
+
+        let role_args = [ApiArg::Str("ROLE_X"), ApiArg::Str("kissed")];
+        assert_eq!(
+            role_times_did(&w, &r, &c, &role_args).unwrap(),
+            ApiValue::Int(2)
+        );
+        assert_eq!(
+            role_weeks_since(&w, &r, &c, &role_args).unwrap(),
+            ApiValue::Int(2)
+        );
+        assert_eq!(
+            role_days_since(&w, &r, &c, &role_args).unwrap(),
+            ApiValue::Int(17)
+        );
+        assert_eq!(
+            gd_last_seen(&w, &r, &c, &[ApiArg::Str("ROLE_X")]).unwrap(),
+            ApiValue::Int(17)
+        );
+        assert_eq!(
+            w_weeks_since(&w, &r, &c, &[ApiArg::Str("kissed")]).unwrap(),
+            ApiValue::Int(-1)
+        );
+    }
+}
@@ -7,7 +7,41 @@
 //! are baked in here and recorded in `table.rs`.
 
 pub mod game_data;
+pub mod memory;
 pub mod npc;
 pub mod player;
+pub mod relations;
 pub mod role;
 pub mod scene;
+
+use undone_domain::NpcKey;
+use undone_world::World;
+
+use crate::scene_ctx::{SceneCtx, SceneNpcRef};
+use crate::script::api::ApiError;
+
+/// Resolve an npc ref (`"m"`, `"f"`, a bound role, or a story character id) to its
+/// world key, for reads that name an NPC by argument. Unbound roles and unknown
+/// story ids are `UnboundRole`, matching `role.*` reads.
+fn npc_key(ref_: &str, w: &World, c: &SceneCtx) -> Result<NpcKey, ApiError> {
+    match ref_ {
+        "m" => c
+            .active_male
+            .map(NpcKey::Male)
+            .ok_or(ApiError::NoActiveNpc { sex: "male" }),
+        "f" => c
+            .active_female
+            .map(NpcKey::Female)
+            .ok_or(ApiError::NoActiveNpc { sex: "female" }),
+        other => c
+            .role_binding(other)
+            .map(|binding| match binding {
+                SceneNpcRef::Male(key) => NpcKey::Male(key),
+                SceneNpcRef::Female(key) => NpcKey::Female(key),
+            })
+            .or_else(|| w.story_npc(other))
+            .ok_or(ApiError::UnboundRole {
+                role: other.to_string(),
+            }),
+    }
+}
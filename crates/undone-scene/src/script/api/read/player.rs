@@ -31,6 +31,25 @@ pub fn has_trait(
     Ok(ApiValue::Bool(w.player.has_trait(tid)))
 }
 
+/// Whether any stack of buff `id` is on the player, scene-applied or granted.
+pub fn has_buff(
+    w: &World,
+    r: &PackRegistry,
+    _c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let id = str0(a, "hasBuff")?;
+    if r.get_buff(id).is_none() {
+        return Err(ApiError::UnknownId {
+            kind: "buff",
+            id: id.to_string(),
+        });
+    }
+    Ok(ApiValue::Bool(
+        w.player.buffs.iter().any(|buff| buff.id == id),
+    ))
+}
+
 pub fn is_virgin(
     w: &World,
     _r: &PackRegistry,
@@ -747,6 +766,7 @@ mod tests {
             description: String::new(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         r
     }
@@ -0,0 +1,194 @@
+//! NPC-to-NPC relationship reads over `World::npc_relations`. The other NPC is an
+//! npc ref — `"m"`, `"f"`, a bound role, or a story character id — the same forms
+//! `npc(ref)` accepts. `m`/`f` take it as source-arg 0; `role` takes the role id
+//! first and the other ref second.
+//!
+//! Unrelated pairs read as `""` (kind and closeness) and `false` (`knows`), so
+//! prose can branch on them without a guard.
+
+use undone_packs::PackRegistry;
+use undone_world::{NpcRelation, World};
+
+use super::npc_key;
+use crate::scene_ctx::SceneCtx;
+use crate::script::api::{ApiArg, ApiError, ApiValue};
+
+fn str_at<'a>(a: &[ApiArg<'a>], i: usize, method: &'static str) -> Result<&'a str, ApiError> {
+    a.get(i)
+        .and_then(ApiArg::as_str)
+        .ok_or(ApiError::BadArgs { method })
+}
+
+/// The edge between the receiver NPC (`from`) and the ref at `a[other]`.
+fn edge<'w>(
+    from: &str,
+    other: usize,
+    method: &'static str,
+    w: &'w World,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<Option<&'w NpcRelation>, ApiError> {
+    let x = npc_key(from, w, c)?;
+    let y = npc_key(str_at(a, other, method)?, w, c)?;
+    Ok(w.npc_relations.get(x, y))
+}
+
+fn kind_of(e: Option<&NpcRelation>) -> ApiValue {
+    ApiValue::Str(e.map(|e| e.kind.to_string()).unwrap_or_default())
+}
+
+fn closeness_of(e: Option<&NpcRelation>) -> ApiValue {
+    ApiValue::Str(e.map(|e| e.closeness.to_string()).unwrap_or_default())
+}
+
+// ── m (active male) ───────────────────────────────────────────────────────────
+
+pub fn m_knows(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(ApiValue::Bool(edge("m", 0, "knows", w, c, a)?.is_some()))
+}
+
+pub fn m_relation_to(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(kind_of(edge("m", 0, "relationTo", w, c, a)?))
+}
+
+pub fn m_closeness_to(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(closeness_of(edge("m", 0, "closenessTo", w, c, a)?))
+}
+
+// ── f (active female) ─────────────────────────────────────────────────────────
+
+pub fn f_knows(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(ApiValue::Bool(edge("f", 0, "knows", w, c, a)?.is_some()))
+}
+
+pub fn f_relation_to(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(kind_of(edge("f", 0, "relationTo", w, c, a)?))
+}
+
+pub fn f_closeness_to(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(closeness_of(edge("f", 0, "closenessTo", w, c, a)?))
+}
+
+// ── role ──────────────────────────────────────────────────────────────────────
+
+pub fn role_knows(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let role = str_at(a, 0, "knows")?;
+    Ok(ApiValue::Bool(edge(role, 1, "knows", w, c, a)?.is_some()))
+}
+
+pub fn role_relation_to(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let role = str_at(a, 0, "relationTo")?;
+    Ok(kind_of(edge(role, 1, "relationTo", w, c, a)?))
+}
+
+pub fn role_closeness_to(
+    w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let role = str_at(a, 0, "closenessTo")?;
+    Ok(closeness_of(edge(role, 1, "closenessTo", w, c, a)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene_ctx::SceneNpcRef;
+    use undone_domain::{LikingLevel, NpcKey, NpcRelationKind};
+    use undone_world::test_helpers::{make_test_male_npc, make_test_world};
+
+    #[test]
+    fn reads_follow_the_edge_from_either_end() {
+        let mut r = PackRegistry::new();
+        let personality = r.intern_personality("ROMANTIC");
+        let mut w = make_test_world();
+        let jake = w.male_npcs.insert(make_test_male_npc(personality));
+        let marcus = w.male_npcs.insert(make_test_male_npc(personality));
+        w.npc_relations.set(
+            NpcKey::Male(jake),
+            NpcKey::Male(marcus),
+            NpcRelationKind::Housemate,
+            LikingLevel::Like,
+        );
+        let mut c = SceneCtx::new();
+        c.active_male = Some(jake);
+        c.bind_role("ROLE_FLATMATE", SceneNpcRef::Male(marcus));
+
+        assert_eq!(
+            m_relation_to(&w, &r, &c, &[ApiArg::Str("ROLE_FLATMATE")]).unwrap(),
+            ApiValue::Str("Housemate".into())
+        );
+        assert_eq!(
+            role_closeness_to(
+                &w,
+                &r,
+                &c,
+                &[ApiArg::Str("ROLE_FLATMATE"), ApiArg::Str("m")]
+            )
+            .unwrap(),
+            ApiValue::Str("Like".into())
+        );
+        w.npc_relations
+            .remove(NpcKey::Male(marcus), NpcKey::Male(jake));
+        assert_eq!(
+            m_knows(&w, &r, &c, &[ApiArg::Str("ROLE_FLATMATE")]).unwrap(),
+            ApiValue::Bool(false)
+        );
+        assert_eq!(
+            m_relation_to(&w, &r, &c, &[ApiArg::Str("ROLE_FLATMATE")]).unwrap(),
+            ApiValue::Str(String::new())
+        );
+    }
+
+    #[test]
+    fn unbound_ref_errors() {
+        let w = make_test_world();
+        let r = PackRegistry::new();
+        let c = SceneCtx::new();
+        assert!(matches!(
+            role_knows(&w, &r, &c, &[ApiArg::Str("ROLE_A"), ApiArg::Str("ROLE_B")]),
+            Err(ApiError::UnboundRole { .. })
+        ));
+    }
+}
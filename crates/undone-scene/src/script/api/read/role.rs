@@ -5,7 +5,7 @@
 //! divergence fix (design §1): prose already used the display name; Rhai used the
 //! raw spawn name. The unified accessor adopts the display name.
 
-use undone_domain::{FemaleNpc, MaleNpc};
+use undone_domain::{FemaleNpc, MaleNpc, NpcKey};
 use undone_packs::PackRegistry;
 use undone_world::World;
 
@@ -24,9 +24,17 @@ fn role0<'a>(a: &[ApiArg<'a>]) -> Result<&'a str, ApiError> {
 }
 
 /// Resolve a role id to its bound NPC. Unbound → `UnboundRole`; stale key →
-/// `NpcNotFound` (mirrors `read_api/role.rs::resolve_role_npc`).
+/// `NpcNotFound` (mirrors `read_api/role.rs::resolve_role_npc`). A namespaced
+/// story character id (`base::jake`) resolves through the world instead, so
+/// `role.getLiking("base::jake")` works without a scene binding.
 fn resolve<'a>(role: &str, w: &'a World, c: &SceneCtx) -> Result<Resolved<'a>, ApiError> {
-    match c.role_binding(role).ok_or(ApiError::UnboundRole {
+    let binding = c.role_binding(role).or_else(|| {
+        w.story_npc(role).map(|key| match key {
+            NpcKey::Male(key) => SceneNpcRef::Male(key),
+            NpcKey::Female(key) => SceneNpcRef::Female(key),
+        })
+    });
+    match binding.ok_or(ApiError::UnboundRole {
         role: role.to_string(),
     })? {
         SceneNpcRef::Male(key) => w
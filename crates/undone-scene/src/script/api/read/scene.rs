@@ -1,4 +1,4 @@
-//! `scene` (scene-local flag) read accessors. Lifted from `read_api/scene.rs`.
+//! `scene` (scene-local flag and var, sub-scene param) read accessors. Lifted from `read_api/scene.rs`.
 
 use undone_packs::PackRegistry;
 use undone_world::World;
@@ -19,6 +19,51 @@ pub fn has_flag(
     Ok(ApiValue::Bool(c.has_flag(flag)))
 }
 
+/// A scene variable's current value. Names are checked against `[scene.vars]`
+/// at load.
+pub fn get(w: &World, _r: &PackRegistry, c: &SceneCtx, a: &[ApiArg]) -> Result<ApiValue, ApiError> {
+    let name = a
+        .first()
+        .and_then(ApiArg::as_str)
+        .ok_or(ApiError::BadArgs { method: "get" })?;
+    c.var(&w.game_data, name)
+        .map(|value| value.clone().into())
+        .ok_or(ApiError::UnknownId {
+            kind: "scene var",
+            id: name.to_string(),
+        })
+}
+
+/// A param the scene was called with. Names are checked against the scene's
+/// declared params at load, so a miss here means the scene wasn't `call`ed.
+pub fn param(
+    _w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    let name = a
+        .first()
+        .and_then(ApiArg::as_str)
+        .ok_or(ApiError::BadArgs { method: "param" })?;
+    c.params.get(name).cloned().ok_or(ApiError::UnknownId {
+        kind: "scene param",
+        id: name.to_string(),
+    })
+}
+
+/// What the last called sub-scene returned; `""` before any has.
+pub fn result(
+    _w: &World,
+    _r: &PackRegistry,
+    c: &SceneCtx,
+    _a: &[ApiArg],
+) -> Result<ApiValue, ApiError> {
+    Ok(c.returned
+        .clone()
+        .unwrap_or_else(|| ApiValue::Str(String::new())))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +85,24 @@ mod tests {
             ApiValue::Bool(false)
         );
     }
+
+    #[test]
+    fn param_and_result_read_the_call_state() {
+        let w = make_test_world();
+        let r = PackRegistry::new();
+        let mut c = SceneCtx::new();
+        c.params
+            .insert("sender".into(), ApiValue::Str("Jake".into()));
+        assert_eq!(
+            param(&w, &r, &c, &[ApiArg::Str("sender")]).unwrap(),
+            ApiValue::Str("Jake".into())
+        );
+        assert!(param(&w, &r, &c, &[ApiArg::Str("missing")]).is_err());
+        assert_eq!(
+            result(&w, &r, &c, &[]).unwrap(),
+            ApiValue::Str(String::new())
+        );
+        c.returned = Some(ApiValue::Bool(true));
+        assert_eq!(result(&w, &r, &c, &[]).unwrap(), ApiValue::Bool(true));
+    }
 }
@@ -59,7 +59,7 @@ fn reg_read<T: Clone + 'static>(engine: &mut Engine, d: &'static MethodDescripto
         ArgShape::None => {
             engine.register_fn(name, move |_t: &mut T| read_call(f, &[]));
         }
-        ArgShape::Id(_) | ArgShape::Str => {
+        ArgShape::Id(_) | ArgShape::Str | ArgShape::Flag | ArgShape::Body(_) => {
             engine.register_fn(name, move |_t: &mut T, a: ImmutableString| {
                 read_call(f, &[ApiArg::Str(&a)])
             });
@@ -107,7 +107,7 @@ fn reg_write<T: Clone + 'static>(engine: &mut Engine, d: &'static MethodDescript
                 },
             );
         }
-        ArgShape::Id(_) | ArgShape::Str => {
+        ArgShape::Id(_) | ArgShape::Str | ArgShape::Flag | ArgShape::Body(_) => {
             engine.register_fn(name, move |_t: &mut T, a: ImmutableString| {
                 with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&a)]));
             });
@@ -126,7 +126,27 @@ fn reg_write<T: Clone + 'static>(engine: &mut Engine, d: &'static MethodDescript
                 with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Bool(a), ApiArg::Str(&b)]));
             });
         }
-        ArgShape::None | ArgShape::StrStr | ArgShape::StrInt => {}
+        ArgShape::StrInt => {
+            engine.register_fn(name, move |_t: &mut T, a: ImmutableString, b: i64| {
+                with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&a), ApiArg::Int(b)]));
+            });
+        }
+        // scene.set(name, value): one native registration per value type.
+        ArgShape::StrAny => {
+            engine.register_fn(name, move |_t: &mut T, a: ImmutableString, b: i64| {
+                with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&a), ApiArg::Int(b)]));
+            });
+            engine.register_fn(
+                name,
+                move |_t: &mut T, a: ImmutableString, b: ImmutableString| {
+                    with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&a), ApiArg::Str(&b)]));
+                },
+            );
+            engine.register_fn(name, move |_t: &mut T, a: ImmutableString, b: bool| {
+                with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&a), ApiArg::Bool(b)]));
+            });
+        }
+        ArgShape::None | ArgShape::StrStr => {}
     }
 }
 
@@ -146,7 +166,7 @@ fn reg_write_npc(engine: &mut Engine, d: &'static MethodDescriptor, f: WriteFn)
                 with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&id), ApiArg::Int(a)]));
             });
         }
-        ArgShape::Id(_) | ArgShape::Str => {
+        ArgShape::Id(_) | ArgShape::Str | ArgShape::Flag | ArgShape::Body(_) => {
             engine.register_fn(name, move |this: &mut Npc, a: ImmutableString| {
                 let id = this.id.clone();
                 with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&id), ApiArg::Str(&a)]));
@@ -158,6 +178,35 @@ fn reg_write_npc(engine: &mut Engine, d: &'static MethodDescriptor, f: WriteFn)
                 with_write_ctx(|w, c, r| f(w, c, r, &[ApiArg::Str(&id), ApiArg::Bool(a)]));
             });
         }
+        ArgShape::StrStr => {
+            engine.register_fn(
+                name,
+                move |this: &mut Npc, a: ImmutableString, b: ImmutableString| {
+                    let id = this.id.clone();
+                    with_write_ctx(|w, c, r| {
+                        f(
+                            w,
+                            c,
+                            r,
+                            &[ApiArg::Str(&id), ApiArg::Str(&a), ApiArg::Str(&b)],
+                        )
+                    });
+                },
+            );
+        }
+        ArgShape::StrInt => {
+            engine.register_fn(name, move |this: &mut Npc, a: ImmutableString, b: i64| {
+                let id = this.id.clone();
+                with_write_ctx(|w, c, r| {
+                    f(
+                        w,
+                        c,
+                        r,
+                        &[ApiArg::Str(&id), ApiArg::Str(&a), ApiArg::Int(b)],
+                    )
+                });
+            });
+        }
         _ => {}
     }
 }
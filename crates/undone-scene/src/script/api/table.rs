@@ -9,13 +9,15 @@
 //!   unified accessor adopts the prose behavior. `getName` is ADDED to `m`/`f` (the
 //!   Rhai surface lacked it).
 //! - `w.getSkill` unknown id → error (matches Rhai, NOT the snapshot's silent 0).
-//! - intentionally-unvalidated id args (`hasStuff`, `getStat`, flags, …) are `Str`.
+//! - intentionally-unvalidated id args (`hasStuff`, `getStat`, scene/NPC flags, …)
+//!   are `Str`. Game flags are `Flag`: checked against the declared flag schema.
 
 use super::ArgShape as Arg;
 use super::Contexts;
 use super::Receiver::{self as R};
 use super::{read, write, Accessor, MethodDescriptor, ReadFn, WriteFn};
 use crate::script::validate::IdKind;
+use undone_domain::BodyAttribute;
 
 /// Compact read-row constructor.
 const fn rd(
@@ -59,6 +61,13 @@ pub static REGISTRY: &[MethodDescriptor] = &[
         READ,
         read::player::has_trait,
     ),
+    rd(
+        R::W,
+        "hasBuff",
+        Arg::Id(IdKind::Buff),
+        READ,
+        read::player::has_buff,
+    ),
     rd(R::W, "isVirgin", Arg::None, READ, read::player::is_virgin),
     rd(
         R::W,
@@ -352,11 +361,26 @@ pub static REGISTRY: &[MethodDescriptor] = &[
         READ,
         read::player::before_figure,
     ),
+    rd(R::W, "timesDid", Arg::Str, READ, read::memory::w_times_did),
+    rd(
+        R::W,
+        "daysSince",
+        Arg::Str,
+        READ,
+        read::memory::w_days_since,
+    ),
+    rd(
+        R::W,
+        "weeksSince",
+        Arg::Str,
+        READ,
+        read::memory::w_weeks_since,
+    ),
     // ── gd (game data) reads ──────────────────────────────────────────────────
     rd(
         R::Gd,
         "hasGameFlag",
-        Arg::Str,
+        Arg::Flag,
         READ,
         read::game_data::has_game_flag,
     ),
@@ -392,6 +416,13 @@ pub static REGISTRY: &[MethodDescriptor] = &[
     rd(R::Gd, "day", Arg::None, READ, read::game_data::day),
     rd(R::Gd, "desire", Arg::None, READ, read::game_data::desire),
     rd(R::Gd, "getStat", Arg::Str, READ, read::game_data::get_stat),
+    rd(
+        R::Gd,
+        "getVar",
+        Arg::Id(IdKind::Var),
+        READ,
+        read::game_data::get_var,
+    ),
     rd(
         R::Gd,
         "timeSlot",
@@ -420,6 +451,13 @@ pub static REGISTRY: &[MethodDescriptor] = &[
         READ,
         read::game_data::npc_liking,
     ),
+    rd(
+        R::Gd,
+        "lastSeen",
+        Arg::Str,
+        READ,
+        read::memory::gd_last_seen,
+    ),
     // ── m (active male) reads ─────────────────────────────────────────────────
     rd(R::M, "isPartner", Arg::None, READ, read::npc::m_is_partner),
     rd(R::M, "isFriend", Arg::None, READ, read::npc::m_is_friend),
@@ -507,6 +545,36 @@ pub static REGISTRY: &[MethodDescriptor] = &[
     ),
     // getName ADDED to m (effective_name).
     rd(R::M, "getName", Arg::None, READ, read::npc::m_get_name),
+    rd(R::M, "knows", Arg::Str, READ, read::relations::m_knows),
+    rd(
+        R::M,
+        "relationTo",
+        Arg::Str,
+        READ,
+        read::relations::m_relation_to,
+    ),
+    rd(
+        R::M,
+        "closenessTo",
+        Arg::Str,
+        READ,
+        read::relations::m_closeness_to,
+    ),
+    rd(R::M, "timesDid", Arg::Str, READ, read::memory::m_times_did),
+    rd(
+        R::M,
+        "daysSince",
+        Arg::Str,
+        READ,
+        read::memory::m_days_since,
+    ),
+    rd(
+        R::M,
+        "weeksSince",
+        Arg::Str,
+        READ,
+        read::memory::m_weeks_since,
+    ),
     // ── f (active female) reads ───────────────────────────────────────────────
     rd(R::F, "isPartner", Arg::None, READ, read::npc::f_is_partner),
     rd(R::F, "isFriend", Arg::None, READ, read::npc::f_is_friend),
@@ -538,6 +606,36 @@ pub static REGISTRY: &[MethodDescriptor] = &[
     ),
     // getName ADDED to f (effective_name).
     rd(R::F, "getName", Arg::None, READ, read::npc::f_get_name),
+    rd(R::F, "knows", Arg::Str, READ, read::relations::f_knows),
+    rd(
+        R::F,
+        "relationTo",
+        Arg::Str,
+        READ,
+        read::relations::f_relation_to,
+    ),
+    rd(
+        R::F,
+        "closenessTo",
+        Arg::Str,
+        READ,
+        read::relations::f_closeness_to,
+    ),
+    rd(R::F, "timesDid", Arg::Str, READ, read::memory::f_times_did),
+    rd(
+        R::F,
+        "daysSince",
+        Arg::Str,
+        READ,
+        read::memory::f_days_since,
+    ),
+    rd(
+        R::F,
+        "weeksSince",
+        Arg::Str,
+        READ,
+        read::memory::f_weeks_since,
+    ),
     // ── role (role-bound NPC) reads — role id is source-arg 0 ──────────────────
     rd(R::Role, "isPartner", Arg::Str, READ, read::role::is_partner),
     rd(R::Role, "isFriend", Arg::Str, READ, read::role::is_friend),
@@ -577,8 +675,53 @@ pub static REGISTRY: &[MethodDescriptor] = &[
         READ,
         read::role::get_behaviour,
     ),
-    // ── scene (scene-local flags) reads ───────────────────────────────────────
+    rd(
+        R::Role,
+        "knows",
+        Arg::StrStr,
+        READ,
+        read::relations::role_knows,
+    ),
+    rd(
+        R::Role,
+        "relationTo",
+        Arg::StrStr,
+        READ,
+        read::relations::role_relation_to,
+    ),
+    rd(
+        R::Role,
+        "closenessTo",
+        Arg::StrStr,
+        READ,
+        read::relations::role_closeness_to,
+    ),
+    rd(
+        R::Role,
+        "timesDid",
+        Arg::StrStr,
+        READ,
+        read::memory::role_times_did,
+    ),
+    rd(
+        R::Role,
+        "daysSince",
+        Arg::StrStr,
+        READ,
+        read::memory::role_days_since,
+    ),
+    rd(
+        R::Role,
+        "weeksSince",
+        Arg::StrStr,
+        READ,
+        read::memory::role_weeks_since,
+    ),
+    // ── scene (scene-local flags and vars, sub-scene params) reads ────────────
     rd(R::Scene, "hasFlag", Arg::Str, READ, read::scene::has_flag),
+    rd(R::Scene, "get", Arg::Str, READ, read::scene::get),
+    rd(R::Scene, "param", Arg::Str, READ, read::scene::param),
+    rd(R::Scene, "result", Arg::None, READ, read::scene::result),
     // ── w (player) writes ─────────────────────────────────────────────────────
     wr(R::W, "changeStress", INT, write::player::change_stress),
     wr(R::W, "changeMoney", INT, write::player::change_money),
@@ -597,6 +740,12 @@ pub static REGISTRY: &[MethodDescriptor] = &[
         Arg::IdInt(IdKind::Skill),
         write::player::skill_increase,
     ),
+    wr(
+        R::W,
+        "practice",
+        Arg::IdInt(IdKind::Skill),
+        write::player::practice,
+    ),
     wr(
         R::W,
         "addTrait",
@@ -612,19 +761,72 @@ pub static REGISTRY: &[MethodDescriptor] = &[
     wr(R::W, "addStuff", Arg::Str, write::player::add_stuff),
     wr(R::W, "removeStuff", Arg::Str, write::player::remove_stuff),
     wr(R::W, "setVirgin", Arg::StrOpt, write::player::set_virgin),
-    wr(R::W, "setPartner", Arg::Str, write::player::set_partner),
-    wr(R::W, "addFriend", Arg::Str, write::player::add_friend),
+    wr(
+        R::W,
+        "setPartner",
+        Arg::Id(IdKind::Character),
+        write::player::set_partner,
+    ),
+    wr(
+        R::W,
+        "addFriend",
+        Arg::Id(IdKind::Character),
+        write::player::add_friend,
+    ),
+    wr(R::W, "remember", Arg::Str, write::player::remember),
+    wr(
+        R::W,
+        "setHairLength",
+        Arg::Body(BodyAttribute::HairLength),
+        write::player::set_hair_length,
+    ),
+    wr(
+        R::W,
+        "setPubicHair",
+        Arg::Body(BodyAttribute::PubicHair),
+        write::player::set_pubic_hair,
+    ),
+    wr(
+        R::W,
+        "setFigure",
+        Arg::Body(BodyAttribute::Figure),
+        write::player::set_figure,
+    ),
+    wr(
+        R::W,
+        "setComplexion",
+        Arg::Body(BodyAttribute::Complexion),
+        write::player::set_complexion,
+    ),
+    wr(
+        R::W,
+        "setSmoothLegs",
+        Arg::Bool,
+        write::player::set_smooth_legs,
+    ),
+    wr(
+        R::W,
+        "addBuff",
+        Arg::Id(IdKind::Buff),
+        write::player::add_buff,
+    ),
+    wr(
+        R::W,
+        "removeBuff",
+        Arg::Id(IdKind::Buff),
+        write::player::remove_buff,
+    ),
     // ── gd (game data) writes ─────────────────────────────────────────────────
     wr(
         R::Gd,
         "setGameFlag",
-        Arg::Str,
+        Arg::Flag,
         write::game_data::set_game_flag,
     ),
     wr(
         R::Gd,
         "removeGameFlag",
-        Arg::Str,
+        Arg::Flag,
         write::game_data::remove_game_flag,
     ),
     wr(
@@ -661,13 +863,20 @@ pub static REGISTRY: &[MethodDescriptor] = &[
         Arg::Id(IdKind::Skill),
         write::game_data::fail_red_check,
     ),
-    // ── scene (scene-local flags) writes ──────────────────────────────────────
+    // ── scene (scene-local flags and vars) writes ─────────────────────────────
     wr(R::Scene, "setFlag", Arg::Str, write::scene::set_flag),
     wr(R::Scene, "removeFlag", Arg::Str, write::scene::remove_flag),
+    wr(R::Scene, "set", Arg::StrAny, write::scene::set),
+    wr(R::Scene, "add", Arg::StrInt, write::scene::add),
     // ── npc(ref).* writes — ref injected as ApiArg index 0 by the adapter ──────
     // The constructor `npc(ref)` is gate-visible here but handled specially by the
     // Rhai adapter (no-op accessor; see write::npc::npc_ctor).
-    wr(R::Npc, "npc", Arg::Str, write::npc::npc_ctor),
+    wr(
+        R::Npc,
+        "npc",
+        Arg::Id(IdKind::Character),
+        write::npc::npc_ctor,
+    ),
     wr(R::Npc, "addLiking", INT8, write::npc::add_liking),
     wr(R::Npc, "addLove", INT8, write::npc::add_love),
     wr(R::Npc, "addWLiking", INT8, write::npc::add_w_liking),
@@ -700,4 +909,18 @@ pub static REGISTRY: &[MethodDescriptor] = &[
     ),
     wr(R::Npc, "setRole", Arg::Str, write::npc::set_role),
     wr(R::Npc, "setName", Arg::Str, write::npc::set_name),
+    wr(R::Npc, "setRelation", Arg::StrStr, write::npc::set_relation),
+    wr(
+        R::Npc,
+        "addCloseness",
+        Arg::StrInt,
+        write::npc::add_closeness,
+    ),
+    wr(
+        R::Npc,
+        "removeRelation",
+        Arg::Str,
+        write::npc::remove_relation,
+    ),
+    wr(R::Npc, "remember", Arg::Str, write::npc::remember),
 ];
@@ -20,11 +20,12 @@ fn str0<'a>(a: &[ApiArg<'a>], m: &'static str) -> Result<&'a str, EffectError> {
 
 pub fn set_game_flag(
     w: &mut World,
-    _c: &mut SceneCtx,
+    c: &mut SceneCtx,
     _r: &PackRegistry,
     a: &[ApiArg],
 ) -> Result<(), EffectError> {
-    w.game_data.set_flag(str0(a, "setGameFlag")?.to_string());
+    let source = c.scene_id.as_deref().unwrap_or("script");
+    w.game_data.set_flag_from(str0(a, "setGameFlag")?, source);
     Ok(())
 }
 
@@ -107,12 +108,12 @@ pub fn set_desire(
 pub fn advance_time(
     w: &mut World,
     _c: &mut SceneCtx,
-    _r: &PackRegistry,
+    r: &PackRegistry,
     a: &[ApiArg],
 ) -> Result<(), EffectError> {
     let slots = int0(a, "advanceTime")?;
     for _ in 0..slots {
-        w.game_data.advance_time_slot();
+        undone_packs::advance_time_slot(w, r);
     }
     Ok(())
 }
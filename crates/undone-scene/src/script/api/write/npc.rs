@@ -3,13 +3,13 @@
 //! The resolved ref string is `ApiArg` index 0 (injected by the adapter from the
 //! `npc(ref)` constructor); the method's own argument is index 1.
 
-use undone_domain::NpcCore;
+use undone_domain::{LikingLevel, NpcCore, NpcKey};
 use undone_packs::PackRegistry;
 use undone_world::World;
 
 use crate::effects::{
-    parse_behaviour, parse_relationship_status, resolve_npc_ref, step_attraction, step_liking,
-    step_love, EffectError, NpcRef,
+    parse_behaviour, parse_npc_relation_kind, parse_relationship_status, resolve_npc_ref,
+    step_attraction, step_liking, step_love, EffectError, NpcRef,
 };
 use crate::scene_ctx::SceneCtx;
 use crate::script::api::ApiArg;
@@ -36,22 +36,37 @@ fn arg_bool(a: &[ApiArg], m: &'static str) -> Result<bool, EffectError> {
         .ok_or(EffectError::BadArgs(m))
 }
 
-/// Resolve the ref to a mutable `&mut NpcCore` (male or female).
+/// Resolve the receiver ref and the `other` ref (source-arg 0, `ApiArg` index 1)
+/// to the two ends of an NPC-to-NPC relationship edge. Relationship edges are
+/// between NPCs, so unlike `core_mut` this does not stamp player contact.
+fn pair(
+    w: &World,
+    c: &SceneCtx,
+    a: &[ApiArg],
+    m: &'static str,
+) -> Result<(NpcKey, NpcKey), EffectError> {
+    let other = arg_str(a, m)?;
+    let x = resolve_npc_ref(ref0(a)?, c, w)?.into();
+    let y = resolve_npc_ref(other, c, w)?.into();
+    Ok((x, y))
+}
+
+/// Resolve the ref to a mutable `&mut NpcCore` (male or female). Every write
+/// through here counts as the player dealing with the NPC, so it also stamps
+/// `agenda.last_contact` for the off-screen routines.
 fn core_mut<'a>(
     w: &'a mut World,
     c: &SceneCtx,
     ref_: &str,
 ) -> Result<&'a mut NpcCore, EffectError> {
-    match resolve_npc_ref(ref_, c)? {
-        NpcRef::Male(key) => w
-            .male_npc_mut(key)
-            .map(|n| &mut n.core)
-            .ok_or(EffectError::NpcNotFound),
-        NpcRef::Female(key) => w
-            .female_npc_mut(key)
-            .map(|n| &mut n.core)
-            .ok_or(EffectError::NpcNotFound),
+    let now = w.game_data.absolute_slot();
+    let core = match resolve_npc_ref(ref_, c, w)? {
+        NpcRef::Male(key) => w.male_npc_mut(key).map(|n| &mut n.core),
+        NpcRef::Female(key) => w.female_npc_mut(key).map(|n| &mut n.core),
     }
+    .ok_or(EffectError::NpcNotFound)?;
+    core.agenda.last_contact = Some(now);
+    Ok(core)
 }
 
 /// The `npc(ref)` constructor is handled specially by the Rhai adapter (it returns
@@ -218,6 +233,73 @@ pub fn set_name(
     Ok(())
 }
 
+/// Append `event` to the NPC's memory log at the current game time.
+pub fn remember(
+    w: &mut World,
+    c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let event = arg_str(a, "remember")?;
+    let at = w.game_data.memory_stamp();
+    let core = core_mut(w, c, ref0(a)?)?;
+    core.memory.record(event, at);
+    Ok(())
+}
+
+/// `setRelation(other, kind)` — a new edge starts at `Ok` closeness; changing
+/// the kind of an existing edge keeps its closeness.
+pub fn set_relation(
+    w: &mut World,
+    c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let kind = a
+        .get(2)
+        .and_then(ApiArg::as_str)
+        .ok_or(EffectError::BadArgs("setRelation"))?;
+    let parsed = parse_npc_relation_kind(kind)
+        .ok_or_else(|| EffectError::UnknownNpcRelation(kind.into()))?;
+    let (x, y) = pair(w, c, a, "setRelation")?;
+    let closeness = w
+        .npc_relations
+        .get(x, y)
+        .map_or(LikingLevel::Ok, |e| e.closeness);
+    w.npc_relations.set(x, y, parsed, closeness);
+    Ok(())
+}
+
+/// `addCloseness(other, delta)` — steps an existing edge; no-op if the two
+/// don't know each other.
+pub fn add_closeness(
+    w: &mut World,
+    c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let delta = a
+        .get(2)
+        .and_then(ApiArg::as_int)
+        .ok_or(EffectError::BadArgs("addCloseness"))? as i8;
+    let (x, y) = pair(w, c, a, "addCloseness")?;
+    if let Some(edge) = w.npc_relations.get_mut(x, y) {
+        edge.closeness = step_liking(edge.closeness, delta);
+    }
+    Ok(())
+}
+
+pub fn remove_relation(
+    w: &mut World,
+    c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let (x, y) = pair(w, c, a, "removeRelation")?;
+    w.npc_relations.remove(x, y);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,6 +335,58 @@ mod tests {
         assert_eq!(w.male_npcs.get(key).unwrap().core.effective_name(), "Jake");
     }
 
+    #[test]
+    fn relation_writes_link_two_npcs() {
+        let (mut w, mut c, mut r) = world_with_active_male();
+        let personality = r.intern_personality("ROMANTIC");
+        let flatmate = w.male_npcs.insert(make_test_male_npc(personality));
+        c.bind_role(
+            "ROLE_FLATMATE",
+            crate::scene_ctx::SceneNpcRef::Male(flatmate),
+        );
+        let jake = NpcKey::Male(c.active_male.unwrap());
+        let marcus = NpcKey::Male(flatmate);
+        let args = [
+            ApiArg::Str("m"),
+            ApiArg::Str("ROLE_FLATMATE"),
+            ApiArg::Str("Housemate"),
+        ];
+
+        set_relation(&mut w, &mut c, &r, &args).unwrap();
+        add_closeness(
+            &mut w,
+            &mut c,
+            &r,
+            &[
+                ApiArg::Str("m"),
+                ApiArg::Str("ROLE_FLATMATE"),
+                ApiArg::Int(2),
+            ],
+        )
+        .unwrap();
+        let edge = w.npc_relations.get(marcus, jake).unwrap();
+        assert_eq!(edge.kind, undone_domain::NpcRelationKind::Housemate);
+        assert_eq!(edge.closeness, LikingLevel::Close);
+        // NPC-to-NPC writes are not player contact.
+        assert_eq!(w.male_npcs[flatmate].core.agenda.last_contact, None);
+
+        assert!(matches!(
+            set_relation(
+                &mut w,
+                &mut c,
+                &r,
+                &[
+                    ApiArg::Str("m"),
+                    ApiArg::Str("ROLE_FLATMATE"),
+                    ApiArg::Str("Lover")
+                ]
+            ),
+            Err(EffectError::UnknownNpcRelation(_))
+        ));
+        remove_relation(&mut w, &mut c, &r, &args[..2]).unwrap();
+        assert!(w.npc_relations.is_empty());
+    }
+
     #[test]
     fn bad_ref_errors() {
         let (mut w, mut c, r) = world_with_active_male();
@@ -1,6 +1,6 @@
 //! `w.*` write accessors. Bodies lifted from `write_api/player.rs`.
 
-use undone_domain::{NpcKey, SkillValue};
+use undone_domain::{BodyAttribute, NpcKey, SkillValue};
 use undone_packs::PackRegistry;
 use undone_world::World;
 
@@ -122,6 +122,27 @@ pub fn skill_increase(
     Ok(())
 }
 
+/// Bank practice XP toward a skill; the pack's progression curve decides how
+/// many points it buys.
+pub fn practice(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let skill = str0(a, "practice")?;
+    let xp = a
+        .get(1)
+        .and_then(ApiArg::as_int)
+        .and_then(|xp| u32::try_from(xp).ok())
+        .ok_or(EffectError::BadArgs("practice"))?;
+    let sid = r
+        .resolve_skill(skill)
+        .map_err(|_| EffectError::UnknownSkill(skill.to_string()))?;
+    undone_packs::practice(w, r, sid, xp);
+    Ok(())
+}
+
 pub fn add_trait(
     w: &mut World,
     _c: &mut SceneCtx,
@@ -136,6 +157,7 @@ pub fn add_trait(
         return Err(EffectError::TraitConflict(conflict_msg));
     }
     w.player.traits.insert(tid);
+    undone_packs::refresh_buffs(w, r);
     Ok(())
 }
 
@@ -150,6 +172,7 @@ pub fn remove_trait(
         .resolve_trait(trait_id)
         .map_err(|_| EffectError::UnknownTrait(trait_id.to_string()))?;
     w.player.traits.remove(&tid);
+    undone_packs::refresh_buffs(w, r);
     Ok(())
 }
 
@@ -164,6 +187,7 @@ pub fn add_stuff(
         .resolve_stuff(item)
         .ok_or_else(|| EffectError::UnknownStuff(item.to_string()))?;
     w.player.stuff.insert(stuff_id);
+    undone_packs::refresh_buffs(w, r);
     Ok(())
 }
 
@@ -178,6 +202,7 @@ pub fn remove_stuff(
         .resolve_stuff(item)
         .ok_or_else(|| EffectError::UnknownStuff(item.to_string()))?;
     w.player.stuff.remove(&stuff_id);
+    undone_packs::refresh_buffs(w, r);
     Ok(())
 }
 
@@ -213,7 +238,7 @@ pub fn set_partner(
     a: &[ApiArg],
 ) -> Result<(), EffectError> {
     let npc = str0(a, "setPartner")?;
-    let npc_key = match resolve_npc_ref(npc, c)? {
+    let npc_key = match resolve_npc_ref(npc, c, w)? {
         NpcRef::Male(key) => NpcKey::Male(key),
         NpcRef::Female(key) => NpcKey::Female(key),
     };
@@ -228,7 +253,7 @@ pub fn add_friend(
     a: &[ApiArg],
 ) -> Result<(), EffectError> {
     let npc = str0(a, "addFriend")?;
-    let npc_key = match resolve_npc_ref(npc, c)? {
+    let npc_key = match resolve_npc_ref(npc, c, w)? {
         NpcRef::Male(key) => NpcKey::Male(key),
         NpcRef::Female(key) => NpcKey::Female(key),
     };
@@ -238,6 +263,118 @@ pub fn add_friend(
     Ok(())
 }
 
+/// Append `event` to the player's memory log at the current game time.
+pub fn remember(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let event = str0(a, "remember")?;
+    let at = w.game_data.memory_stamp();
+    w.player.memory.record(event, at);
+    Ok(())
+}
+
+fn set_body(
+    w: &mut World,
+    r: &PackRegistry,
+    attribute: BodyAttribute,
+    value: &str,
+) -> Result<(), EffectError> {
+    if undone_packs::set_body_attribute(w, r, attribute, value) {
+        Ok(())
+    } else {
+        Err(EffectError::UnknownBodyValue(value.to_string()))
+    }
+}
+
+/// Deliberate body changes (a haircut, a wax, a new routine). Each restarts the
+/// attribute's body-change clock, so pack rules measure from the scene.
+pub fn set_hair_length(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let value = str0(a, "setHairLength")?;
+    set_body(w, r, BodyAttribute::HairLength, value)
+}
+
+pub fn set_pubic_hair(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let value = str0(a, "setPubicHair")?;
+    set_body(w, r, BodyAttribute::PubicHair, value)
+}
+
+pub fn set_figure(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let value = str0(a, "setFigure")?;
+    set_body(w, r, BodyAttribute::Figure, value)
+}
+
+pub fn set_complexion(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let value = str0(a, "setComplexion")?;
+    set_body(w, r, BodyAttribute::Complexion, value)
+}
+
+pub fn set_smooth_legs(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let smooth = a
+        .first()
+        .and_then(ApiArg::as_bool)
+        .ok_or(EffectError::BadArgs("setSmoothLegs"))?;
+    let value = if smooth { "true" } else { "false" };
+    set_body(w, r, BodyAttribute::SmoothLegs, value)
+}
+
+/// Apply a pack buff, sourced to the running scene. Re-applying stacks it.
+pub fn add_buff(
+    w: &mut World,
+    c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let id = str0(a, "addBuff")?;
+    let source = c.scene_id.as_deref().unwrap_or("scene");
+    if undone_packs::add_buff(w, r, id, source) {
+        Ok(())
+    } else {
+        Err(EffectError::UnknownBuff(id.to_string()))
+    }
+}
+
+pub fn remove_buff(
+    w: &mut World,
+    _c: &mut SceneCtx,
+    r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let id = str0(a, "removeBuff")?;
+    if r.get_buff(id).is_none() {
+        return Err(EffectError::UnknownBuff(id.to_string()));
+    }
+    undone_packs::remove_buff(w, r, id);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,4 +418,47 @@ mod tests {
             Err(EffectError::UnknownTrait(_))
         ));
     }
+
+    #[test]
+    fn add_trait_grants_its_buffs_immediately() {
+        let mut w = make_test_world();
+        let mut c = SceneCtx::new();
+        let mut r = PackRegistry::new();
+        r.register_traits(vec![undone_packs::TraitDef {
+            id: "CONFIDENT".into(),
+            name: "Confident".into(),
+            description: String::new(),
+            hidden: false,
+            group: None,
+            conflicts: vec![],
+            composure_penalty: 0,
+        }]);
+        r.register_buffs(
+            toml::from_str::<undone_packs::BuffsFile>(
+                r#"
+                [[buff]]
+                id         = "POISED"
+                name       = "Poised"
+                skills     = {}
+                granted_by = { trait = "CONFIDENT" }
+                "#,
+            )
+            .unwrap(),
+        );
+        add_trait(&mut w, &mut c, &r, &[ApiArg::Str("CONFIDENT")]).unwrap();
+        assert!(w.player.buffs.iter().any(|b| b.id == "POISED"));
+        remove_trait(&mut w, &mut c, &r, &[ApiArg::Str("CONFIDENT")]).unwrap();
+        assert!(w.player.buffs.is_empty());
+    }
+
+    #[test]
+    fn add_buff_unknown_errors() {
+        let mut w = make_test_world();
+        let mut c = SceneCtx::new();
+        let r = PackRegistry::new();
+        assert!(matches!(
+            add_buff(&mut w, &mut c, &r, &[ApiArg::Str("NOPE")]),
+            Err(EffectError::UnknownBuff(_))
+        ));
+    }
 }
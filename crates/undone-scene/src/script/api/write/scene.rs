@@ -1,12 +1,15 @@
-//! `scene.*` write accessors (scene-local, NON-persistent). Lifted from
-//! `write_api/scene.rs`.
+//! `scene.*` write accessors. Flags and local vars are scene-local; a var
+//! declared `{ persist = "ID" }` writes through to `GameData::vars`. Flag
+//! writes lifted from `write_api/scene.rs`.
 
+use undone_domain::{VarType, VarValue};
 use undone_packs::PackRegistry;
 use undone_world::World;
 
 use crate::effects::EffectError;
 use crate::scene_ctx::SceneCtx;
 use crate::script::api::ApiArg;
+use crate::types::SceneVar;
 
 fn str0<'a>(a: &[ApiArg<'a>], m: &'static str) -> Result<&'a str, EffectError> {
     a.first()
@@ -33,3 +36,95 @@ pub fn remove_flag(
     c.scene_flags.remove(str0(a, "removeFlag")?);
     Ok(())
 }
+
+/// The declared var `name`, for a write that must match its type.
+fn declared<'c>(c: &'c SceneCtx, name: &str) -> Result<&'c SceneVar, EffectError> {
+    c.vars
+        .get(name)
+        .ok_or_else(|| EffectError::UnknownSceneVar(name.to_string()))
+}
+
+pub fn set(
+    w: &mut World,
+    c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let name = str0(a, "set")?;
+    let value = match a.get(1) {
+        Some(ApiArg::Int(i)) => VarValue::Int(*i),
+        Some(ApiArg::Str(s)) => VarValue::Str(s.to_string()),
+        Some(ApiArg::Bool(b)) => VarValue::Bool(*b),
+        None => return Err(EffectError::BadArgs("set")),
+    };
+    let declared = declared(c, name)?.var_type();
+    if value.var_type() != declared {
+        return Err(EffectError::SceneVarType {
+            name: name.to_string(),
+            declared,
+            got: value.var_type(),
+        });
+    }
+    c.set_var(&mut w.game_data, name, value);
+    Ok(())
+}
+
+/// Add `delta` to an int var.
+pub fn add(
+    w: &mut World,
+    c: &mut SceneCtx,
+    _r: &PackRegistry,
+    a: &[ApiArg],
+) -> Result<(), EffectError> {
+    let name = str0(a, "add")?;
+    let delta = a
+        .get(1)
+        .and_then(ApiArg::as_int)
+        .ok_or(EffectError::BadArgs("add"))?;
+    let current = match c.var(&w.game_data, name) {
+        Some(VarValue::Int(i)) => *i,
+        Some(other) => {
+            return Err(EffectError::SceneVarType {
+                name: name.to_string(),
+                declared: other.var_type(),
+                got: VarType::Int,
+            })
+        }
+        None => return Err(EffectError::UnknownSceneVar(name.to_string())),
+    };
+    c.set_var(&mut w.game_data, name, VarValue::Int(current + delta));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use undone_world::test_helpers::make_test_world;
+
+    #[test]
+    fn set_and_add_respect_the_declared_type() {
+        let mut w = make_test_world();
+        let r = PackRegistry::new();
+        let mut c = SceneCtx::new();
+        c.vars.insert(
+            "drinks".into(),
+            SceneVar {
+                value: VarValue::Int(0),
+                persist: None,
+            },
+        );
+        add(&mut w, &mut c, &r, &[ApiArg::Str("drinks"), ApiArg::Int(2)]).unwrap();
+        add(&mut w, &mut c, &r, &[ApiArg::Str("drinks"), ApiArg::Int(1)]).unwrap();
+        assert_eq!(c.var(&w.game_data, "drinks"), Some(&VarValue::Int(3)));
+
+        let err = set(
+            &mut w,
+            &mut c,
+            &r,
+            &[ApiArg::Str("drinks"), ApiArg::Str("lots")],
+        );
+        assert!(matches!(err, Err(EffectError::SceneVarType { .. })));
+        let err = add(&mut w, &mut c, &r, &[ApiArg::Str("tab"), ApiArg::Int(1)]);
+        assert!(matches!(err, Err(EffectError::UnknownSceneVar(_))));
+    }
+}
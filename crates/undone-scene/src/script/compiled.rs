@@ -5,28 +5,60 @@ use std::sync::Arc;
 use thiserror::Error;
 use undone_packs::PackRegistry;
 
+use crate::diagnostic::SourceSpan;
 use crate::script::engine::{read_scope, with_engines};
+use crate::script::library::{script_library, ScriptLibrary};
 use crate::script::validate;
 
 #[derive(Debug, Error)]
 pub enum ScriptError {
-    #[error("script compile error in {context}: {message}\n  source: {source_text}")]
+    #[error(
+        "script compile error in {context}{}: {message}\n  source: {source_text}",
+        at(*.position)
+    )]
     Compile {
         context: String,
         message: String,
+        /// Where in the script the error is; `Position::NONE` if it has no spot.
+        position: rhai::Position,
         source_text: String,
     },
-    #[error("unknown content id '{id}' ({kind}) in {context}\n  source: {source_text}")]
+    #[error(
+        "unknown content id '{id}' ({kind}) in {context}{}\n  source: {source_text}",
+        at(*.position)
+    )]
     UnknownId {
         context: String,
         kind: String,
         id: String,
+        /// The offending call in the script (or prose template).
+        position: rhai::Position,
         source_text: String,
     },
     #[error("script runtime error in {context}: {message}")]
     Runtime { context: String, message: String },
 }
 
+impl ScriptError {
+    /// The error's position inside the script or prose source, if it has one.
+    pub fn position(&self) -> rhai::Position {
+        match self {
+            ScriptError::Compile { position, .. } | ScriptError::UnknownId { position, .. } => {
+                *position
+            }
+            ScriptError::Runtime { .. } => rhai::Position::NONE,
+        }
+    }
+}
+
+fn at(position: rhai::Position) -> String {
+    if position.is_none() {
+        String::new()
+    } else {
+        format!(" ({position})")
+    }
+}
+
 /// A compiled condition or effect script. The AST is the direct analog of the
 /// pre-parsed `undone_expr::Expr` it replaces: compiled once at pack load,
 /// evaluated many times at runtime.
@@ -34,30 +66,55 @@ pub enum ScriptError {
 pub struct CompiledScript {
     pub ast: Arc<rhai::AST>,
     pub source: String,
+    /// Where the script was authored, for scripts loaded from a scene file.
+    pub origin: Option<Arc<SourceSpan>>,
+}
+
+impl CompiledScript {
+    /// Tag the script with the file location it was loaded from.
+    pub fn with_origin(mut self, origin: SourceSpan) -> Self {
+        self.origin = Some(Arc::new(origin));
+        self
+    }
+
+    /// The context runtime errors report: the source, and its file location
+    /// when known.
+    pub(crate) fn error_context(&self) -> String {
+        match &self.origin {
+            Some(origin) => format!("{} at {origin}", self.source),
+            None => self.source.clone(),
+        }
+    }
 }
 
 /// Compile + validate a condition at pack load. The two-layer gate:
 /// 1. `compile_with_scope` (the handle vars in scope) → syntax + unknown-variable
 ///    errors via `strict_variables`.
-/// 2. static source scan → unknown method / arity / unknown content id / range,
+/// 2. static AST walk → unknown method / arity / unknown content id / range,
 ///    across ALL branches (the legacy `validate_condition_ids` guarantee). Only
-///    READ methods are valid; an effect mutator in a condition fails here.
+///    READ methods are valid; an effect mutator in a condition fails here. Calls
+///    into the pack helpers are walked into, and must name a known function.
+///
+/// The compiled script carries the pack helpers (`scripts/*.rhai`) with it.
 pub fn compile_condition(
     src: &str,
     registry: &PackRegistry,
     context: &str,
 ) -> Result<CompiledScript, ScriptError> {
+    let library = library_for(registry, context, src)?;
     let ast = with_engines(|engines| engines.cond.compile_with_scope(&read_scope(), src)).map_err(
         |e| ScriptError::Compile {
             context: context.into(),
-            message: e.to_string(),
+            message: e.0.to_string(),
+            position: e.1,
             source_text: src.into(),
         },
     )?;
-    validate::validate_condition_source(src, registry, context)?;
+    validate::validate_condition_source(src, library.functions(), registry, context)?;
     Ok(CompiledScript {
-        ast: Arc::new(ast),
+        ast: Arc::new(library.functions().merge(&ast)),
         source: src.into(),
+        origin: None,
     })
 }
 
@@ -69,16 +126,34 @@ pub fn compile_effect(
     registry: &PackRegistry,
     context: &str,
 ) -> Result<CompiledScript, ScriptError> {
+    let library = library_for(registry, context, src)?;
     let ast = with_engines(|engines| engines.effect.compile_with_scope(&read_scope(), src))
         .map_err(|e| ScriptError::Compile {
             context: context.into(),
-            message: e.to_string(),
+            message: e.0.to_string(),
+            position: e.1,
             source_text: src.into(),
         })?;
-    validate::validate_effect_source(src, registry, context)?;
+    validate::validate_effect_source(src, library.functions(), registry, context)?;
     Ok(CompiledScript {
-        ast: Arc::new(ast),
+        ast: Arc::new(library.functions().merge(&ast)),
         source: src.into(),
+        origin: None,
+    })
+}
+
+/// The pack helper library, or a compile error in `context` if it doesn't load
+/// (scene loading reports the library's own error first).
+fn library_for(
+    registry: &PackRegistry,
+    context: &str,
+    src: &str,
+) -> Result<ScriptLibrary, ScriptError> {
+    script_library(registry).map_err(|e| ScriptError::Compile {
+        context: context.into(),
+        message: format!("pack script library failed to load: {e}"),
+        position: rhai::Position::NONE,
+        source_text: src.into(),
     })
 }
 
@@ -95,6 +170,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         reg.register_skills(vec![undone_packs::SkillDef {
             id: "FEMININITY".into(),
@@ -102,6 +178,7 @@ mod tests {
             description: "...".into(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         reg
     }
@@ -117,6 +194,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn undeclared_game_flags_fail_once_the_schema_exists() {
+        let mut reg = base_registry();
+        assert!(compile_condition(r#"gd.hasGameFlag("MET_JACK")"#, &reg, "test").is_ok());
+        assert!(
+            compile_effect(
+                r#"gd.setGameFlag("MET_" + w.getHairColour())"#,
+                &reg,
+                "test"
+            )
+            .is_ok(),
+            "computed flag names are only rejected once the packs declare flags"
+        );
+
+        reg.register_flags(
+            toml::from_str(
+                r#"
+                [[flag]]
+                id          = "MET_JAKE"
+                description = "She met Jake."
+                "#,
+            )
+            .unwrap(),
+        );
+        assert!(compile_condition(r#"gd.hasGameFlag("MET_JAKE")"#, &reg, "test").is_ok());
+        assert!(compile_effect(r#"gd.setGameFlag("MET_JAKE")"#, &reg, "test").is_ok());
+        reg.register_scene_id("base::bar".into());
+        assert!(compile_condition(r#"gd.hasGameFlag("ONCE_base::bar")"#, &reg, "test").is_ok());
+        let computed = r#"gd.setGameFlag("MET_" + w.getHairColour())"#;
+        let err = compile_effect(computed, &reg, "test").unwrap_err();
+        assert!(
+            err.to_string().contains("string-literal game flag"),
+            "a computed flag name must fail at LOAD, got: {err:?}"
+        );
+        for src in [
+            r#"gd.hasGameFlag("MET_JACK")"#,
+            r#"gd.hasGameFlag("ONCE_base::baar")"#,
+            r#"gd.removeGameFlag("MET_JACK")"#,
+        ] {
+            let err = if src.contains("has") {
+                compile_condition(src, &reg, "test")
+            } else {
+                compile_effect(src, &reg, "test")
+            }
+            .unwrap_err();
+            assert!(
+                matches!(err, ScriptError::UnknownId { ref kind, .. } if kind == "game flag"),
+                "undeclared flag must fail at LOAD, got: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn eval_and_function_pointers_fail_at_load() {
+        // BREAKS IF: a script can reach the API through a string the load
+        // gate never scanned, e.g. to set an undeclared flag.
+        let mut reg = base_registry();
+        reg.register_flags(
+            toml::from_str(
+                r#"
+                [[flag]]
+                id          = "MET_JAKE"
+                description = "She met Jake."
+                "#,
+            )
+            .unwrap(),
+        );
+        for src in [
+            r#"eval("gd.hasGameFlag(\"UNDECLARED\")")"#,
+            r#"call(Fn("type_of"), 1) == "i64""#,
+            r#"Fn("type_of").call(1) == "i64""#,
+            r#"Fn("type_of").curry(1).call() == "i64""#,
+        ] {
+            assert!(
+                compile_condition(src, &reg, "test").is_err(),
+                "{src} must fail at LOAD"
+            );
+        }
+        assert!(compile_effect(r#"eval("gd.setGameFlag(\"UNDECLARED\")")"#, &reg, "test").is_err());
+    }
+
     #[test]
     fn valid_condition_compiles() {
         let reg = base_registry();
@@ -190,4 +348,52 @@ mod tests {
         let err = compile_effect(r#"w.skillIncrease("NOPE", 5)"#, &reg, "test").unwrap_err();
         assert!(matches!(err, ScriptError::UnknownId { kind, .. } if kind == "skill"));
     }
+
+    #[test]
+    fn body_values_off_the_scale_fail_at_load() {
+        let reg = base_registry();
+        assert!(compile_effect(r#"w.setHairLength("Short")"#, &reg, "test").is_ok());
+        for src in [
+            r#"w.setHairLength("Shrt")"#,
+            r#"w.setPubicHair("Shaved")"#,
+            r#"w.setFigure("Tall")"#,
+            r#"w.setComplexion("Pale")"#,
+        ] {
+            let err = compile_effect(src, &reg, "test").unwrap_err();
+            assert!(
+                matches!(err, ScriptError::Compile { ref message, .. } if message.contains("is not one of")),
+                "{src}: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn constant_folded_ids_resolve_at_load() {
+        let reg = base_registry();
+        assert!(compile_condition(r#"const T = "SH" + "Y"; w.hasTrait(T)"#, &reg, "test").is_ok());
+        assert!(compile_condition(r#"w.getSkill(`FEMININ${"ITY"}`) > 1"#, &reg, "test").is_ok());
+        // A `let` binding is runtime state, not a content-id literal.
+        let err = compile_condition(r#"let t = "SHY"; w.hasTrait(t)"#, &reg, "test").unwrap_err();
+        assert!(matches!(err, ScriptError::Compile { .. }), "got: {err:?}");
+    }
+
+    #[test]
+    fn gate_errors_point_at_the_call() {
+        let reg = base_registry();
+        let err = compile_condition(
+            "w.hasTrait(\"SHY\") &&\n  w.hasTrait(\"NOPE\")",
+            &reg,
+            "test",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("(line 2, position 5)"),
+            "got: {err}"
+        );
+        let err = compile_effect("w.addArousal(1);\nw.notAReal()", &reg, "test").unwrap_err();
+        assert!(
+            err.to_string().contains("(line 2, position 3)"),
+            "got: {err}"
+        );
+    }
 }
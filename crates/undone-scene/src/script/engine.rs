@@ -5,6 +5,7 @@ use undone_packs::PackRegistry;
 use undone_world::World;
 
 use crate::script::api::rhai_bind::{register_reads, register_writes, Gd, Role, Scene, F, M, W};
+use crate::script::api::ApiValue;
 use crate::script::compiled::{CompiledScript, ScriptError};
 use crate::script::context::{ReadCtxGuard, WriteCtxGuard};
 
@@ -30,9 +31,25 @@ fn new_bounded_engine() -> rhai::Engine {
     engine.set_strict_variables(true);
     engine.set_max_operations(MAX_OPERATIONS);
     engine.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH);
+    // eval runs a string the load gate never scanned.
+    engine.disable_symbol("eval");
     engine
 }
 
+/// Parse `src` without optimisation, for static analysis. The runtime engines
+/// constant-fold and prune dead branches, which would hide the calls in
+/// short-circuited code from the load gate.
+pub(crate) fn parse_unoptimized(src: &str) -> Result<rhai::AST, rhai::ParseError> {
+    thread_local! {
+        static PARSER: rhai::Engine = {
+            let mut engine = new_bounded_engine();
+            engine.set_optimization_level(rhai::OptimizationLevel::None);
+            engine
+        };
+    }
+    PARSER.with(|engine| engine.compile_with_scope(&read_scope(), src))
+}
+
 /// Build the condition + effect engines with their bounds and API surfaces.
 ///
 /// Used by the per-thread [`with_engines`] cache and by the rhai-mcp-server
@@ -40,14 +57,37 @@ fn new_bounded_engine() -> rhai::Engine {
 pub fn build_engines() -> ScriptEngines {
     let mut cond = new_bounded_engine();
     register_reads(&mut cond);
+    resolve_handles_in_functions(&mut cond);
 
     let mut effect = new_bounded_engine();
     register_reads(&mut effect);
     register_writes(&mut effect);
+    resolve_handles_in_functions(&mut effect);
 
     ScriptEngines { cond, effect }
 }
 
+/// Script functions (pack helpers, or helpers a script defines) can't see the
+/// caller's scope, so resolve a handle name there to its handle — unless the
+/// function has its own variable of that name (`fn open(gd, arc)`).
+#[allow(deprecated)] // `on_var` is marked volatile, not deprecated
+fn resolve_handles_in_functions(engine: &mut rhai::Engine) {
+    engine.on_var(|name, _, ctx| {
+        if ctx.call_level() == 0 || ctx.scope().contains(name) {
+            return Ok(None);
+        }
+        Ok(match name {
+            "w" => Some(rhai::Dynamic::from(W)),
+            "gd" => Some(rhai::Dynamic::from(Gd)),
+            "m" => Some(rhai::Dynamic::from(M)),
+            "f" => Some(rhai::Dynamic::from(F)),
+            "role" => Some(rhai::Dynamic::from(Role)),
+            "scene" => Some(rhai::Dynamic::from(Scene)),
+            _ => None,
+        })
+    });
+}
+
 thread_local! {
     /// One stateless engine pair per thread. The engines hold only the fixed
     /// registered API + bounds — no pack/world state (that flows through the
@@ -98,7 +138,7 @@ pub fn eval_bool(
             .cond
             .eval_ast_with_scope::<bool>(&mut scope, &script.ast)
             .map_err(|e| ScriptError::Runtime {
-                context: script.source.clone(),
+                context: script.error_context(),
                 message: e.to_string(),
             })
     })
@@ -118,7 +158,7 @@ pub fn eval_int(
             .cond
             .eval_ast_with_scope::<i64>(&mut scope, &script.ast)
             .map_err(|e| ScriptError::Runtime {
-                context: script.source.clone(),
+                context: script.error_context(),
                 message: e.to_string(),
             })
     })
@@ -138,12 +178,47 @@ pub fn eval_string(
             .cond
             .eval_ast_with_scope::<String>(&mut scope, &script.ast)
             .map_err(|e| ScriptError::Runtime {
-                context: script.source.clone(),
+                context: script.error_context(),
                 message: e.to_string(),
             })
     })
 }
 
+/// Evaluate a compiled read expression to the bool, int or string it yields
+/// (dev tools, which evaluate whatever expression a tester types).
+pub fn eval_value(
+    script: &CompiledScript,
+    world: &World,
+    ctx: &SceneCtx,
+    registry: &PackRegistry,
+) -> Result<ApiValue, ScriptError> {
+    let _guard = ReadCtxGuard::install(world, registry, ctx);
+    let value = with_engines(|engines| {
+        let mut scope = read_scope();
+        engines
+            .cond
+            .eval_ast_with_scope::<rhai::Dynamic>(&mut scope, &script.ast)
+    })
+    .map_err(|e| ScriptError::Runtime {
+        context: script.error_context(),
+        message: e.to_string(),
+    })?;
+    if let Ok(b) = value.as_bool() {
+        return Ok(ApiValue::Bool(b));
+    }
+    if let Ok(i) = value.as_int() {
+        return Ok(ApiValue::Int(i));
+    }
+    let type_name = value.type_name();
+    value
+        .into_string()
+        .map(ApiValue::Str)
+        .map_err(|_| ScriptError::Runtime {
+            context: script.error_context(),
+            message: format!("expression evaluated to {type_name}, not a bool, int or string"),
+        })
+}
+
 /// Test-only: evaluate an expression to its string form via the condition engine,
 /// installing a read context. Mirrors how prose method results stringify so the
 /// read/prose equivalence harness can compare apples to apples (a bool renders as
@@ -309,6 +384,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         reg.register_skills(vec![undone_packs::SkillDef {
             id: "FEMININITY".into(),
@@ -316,6 +392,7 @@ mod tests {
             description: "...".into(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         let shy = reg.resolve_trait("SHY").unwrap();
         let fem = reg.resolve_skill("FEMININITY").unwrap();
@@ -339,6 +416,7 @@ mod tests {
         let script = CompiledScript {
             ast: Arc::new(ast),
             source: src.into(),
+            origin: None,
         };
 
         let got = super::eval_bool(&script, &world, &ctx, &reg).unwrap();
@@ -382,6 +460,7 @@ mod tests {
             let script = CompiledScript {
                 ast: Arc::new(ast),
                 source: src.into(),
+                origin: None,
             };
             super::eval_bool(&script, world, ctx, reg).unwrap()
         };
@@ -418,6 +497,7 @@ mod tests {
         let script = CompiledScript {
             ast: Arc::new(ast),
             source: src.into(),
+            origin: None,
         };
         let name = super::eval_string(&script, &world, &ctx, &reg).unwrap();
         assert_eq!(name, "Jake");
@@ -449,6 +529,7 @@ mod tests {
         let script = CompiledScript {
             ast: Arc::new(ast),
             source: src.into(),
+            origin: None,
         };
         let errors = super::apply_effect_script(&script, &mut world, &mut ctx, &reg);
         assert!(errors.is_empty(), "no errors expected: {errors:?}");
@@ -472,8 +553,10 @@ mod tests {
 
         let mut world = make_test_world();
         let key = world.male_npcs.insert(make_test_male_npc(personality));
+        let boss = world.male_npcs.insert(make_test_male_npc(personality));
         let mut ctx = SceneCtx::new();
         ctx.active_male = Some(key);
+        ctx.bind_role("ROLE_BOSS", crate::scene_ctx::SceneNpcRef::Male(boss));
         ctx.scene_id = Some("test::scene".into());
 
         let src = r#"
@@ -483,6 +566,10 @@ mod tests {
             npc("m").addLiking(2);
             npc("m").setName("Jake");
             npc("m").setRole("ROLE_X");
+            npc("m").setRelation("ROLE_BOSS", "Coworker");
+            npc("ROLE_BOSS").addCloseness("m", -1);
+            npc("m").remember("kissed");
+            w.remember("first_kiss");
         "#;
         let ast = engines
             .effect
@@ -491,6 +578,7 @@ mod tests {
         let script = CompiledScript {
             ast: Arc::new(ast),
             source: src.into(),
+            origin: None,
         };
         let errors = super::apply_effect_script(&script, &mut world, &mut ctx, &reg);
         assert!(errors.is_empty(), "no errors expected: {errors:?}");
@@ -502,6 +590,17 @@ mod tests {
         assert_eq!(npc.core.pc_liking, undone_domain::LikingLevel::Like);
         assert_eq!(npc.core.effective_name(), "Jake");
         assert!(npc.core.roles.contains("ROLE_X"));
+        let edge = world
+            .npc_relations
+            .get(
+                undone_domain::NpcKey::Male(key),
+                undone_domain::NpcKey::Male(boss),
+            )
+            .unwrap();
+        assert_eq!(edge.kind, undone_domain::NpcRelationKind::Coworker);
+        assert_eq!(edge.closeness, undone_domain::LikingLevel::Neutral);
+        assert_eq!(world.male_npcs[key].core.memory.times("kissed"), 1);
+        assert_eq!(world.player.memory.times("first_kiss"), 1);
     }
 
     /// continue-on-error: a failing mutator records an error but the rest of the
@@ -527,6 +626,7 @@ mod tests {
         let script = CompiledScript {
             ast: Arc::new(ast),
             source: src.into(),
+            origin: None,
         };
         let errors = super::apply_effect_script(&script, &mut world, &mut ctx, &reg);
         assert_eq!(errors.len(), 1, "one collected error expected: {errors:?}");
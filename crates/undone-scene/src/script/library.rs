@@ -0,0 +1,326 @@
+//! Pack script libraries — the `scripts/*.rhai` helper functions every
+//! condition, effect and schedule gate can call (`workplace_settled()` instead
+//! of a copy-pasted gate expression).
+//!
+//! A library file may only define functions. Helpers are read-only: their
+//! bodies are checked against the READ surface once, at load, so a helper
+//! reached from a condition can never mutate. The library is parsed once per
+//! thread and merged into every script compiled against it; inside a helper
+//! body the handle variables (`w`, `gd`, …) resolve like they do at top level.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use undone_packs::{PackRegistry, ScriptFile};
+
+use crate::diagnostic::SourceSpan;
+use crate::script::compiled::ScriptError;
+use crate::script::engine::parse_unoptimized;
+use crate::script::validate::{self, ScriptSource};
+
+/// A pack helper library that failed to load, located in its file.
+#[derive(Debug, Error)]
+#[error("{}: {message}", location(path, span))]
+pub struct ScriptLibraryError {
+    pub path: PathBuf,
+    pub message: String,
+    pub span: Option<SourceSpan>,
+}
+
+fn location(path: &Path, span: &Option<SourceSpan>) -> String {
+    span.as_ref()
+        .map_or_else(|| path.display().to_string(), ToString::to_string)
+}
+
+impl ScriptLibraryError {
+    fn new(file: &ScriptFile, error: ScriptError) -> Self {
+        let message = match &error {
+            ScriptError::Compile { message, .. } | ScriptError::Runtime { message, .. } => {
+                message.clone()
+            }
+            ScriptError::UnknownId { kind, id, .. } => {
+                format!("unknown content id '{id}' ({kind})")
+            }
+        };
+        let position = error.position();
+        let span =
+            (!position.is_none()).then(|| SourceSpan::at(&file.path, &file.source, position));
+        ScriptLibraryError {
+            path: file.path.clone(),
+            message,
+            span,
+        }
+    }
+
+    /// The error with its source line underlined, when it has a position.
+    pub fn render(&self) -> String {
+        match &self.span {
+            Some(span) => span.render(&self.message),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// Every helper the loaded packs define, as one functions-only AST.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptLibrary {
+    functions: rhai::AST,
+}
+
+impl ScriptLibrary {
+    /// Parse and check `files`: functions only, no helper defined twice, every
+    /// body valid on the READ surface against `registry`.
+    pub fn compile(
+        files: &[ScriptFile],
+        registry: &PackRegistry,
+    ) -> Result<Self, ScriptLibraryError> {
+        let mut functions = rhai::AST::empty();
+        let mut defined_in: Vec<(String, usize, &Path)> = Vec::new();
+        let mut parsed = Vec::new();
+        for file in files {
+            let context = file.path.display().to_string();
+            let ast = parse_unoptimized(&file.source).map_err(|e| {
+                ScriptLibraryError::new(file, compile_error(&context, e.1, e.0.to_string()))
+            })?;
+            if let Some(stmt) = ast.statements().first() {
+                return Err(ScriptLibraryError::new(
+                    file,
+                    compile_error(
+                        &context,
+                        stmt.position(),
+                        "pack scripts may only define functions".into(),
+                    ),
+                ));
+            }
+            for helper in ast.iter_fn_def() {
+                let arity = helper.params.len();
+                if let Some((.., other)) = defined_in
+                    .iter()
+                    .find(|(name, params, _)| *name == helper.name && *params == arity)
+                {
+                    return Err(ScriptLibraryError::new(
+                        file,
+                        compile_error(
+                            &context,
+                            helper.body.start_position(),
+                            format!(
+                                "helper '{}' with {arity} arg(s) is already defined in {}",
+                                helper.name,
+                                other.display()
+                            ),
+                        ),
+                    ));
+                }
+                defined_in.push((helper.name.to_string(), arity, &file.path));
+            }
+            functions.combine(ast.clone_functions_only());
+            parsed.push((file, ast));
+        }
+
+        for (file, ast) in parsed {
+            let context = file.path.display().to_string();
+            for helper in ast.iter_functions() {
+                validate::validate_helper(
+                    &functions,
+                    helper.name,
+                    helper.params.len(),
+                    registry,
+                    &context,
+                    &file.source,
+                )
+                .map_err(|e| ScriptLibraryError::new(file, e))?;
+            }
+        }
+        Ok(ScriptLibrary { functions })
+    }
+
+    /// The helpers, for the static gate and for merging into compiled scripts.
+    pub(crate) fn functions(&self) -> &rhai::AST {
+        &self.functions
+    }
+
+    /// Raw script source read against this library, for the `source_*` fact
+    /// helpers when only the source text is at hand.
+    pub fn with_source<'a>(&'a self, source: &'a str) -> LibraryScript<'a> {
+        LibraryScript {
+            library: self,
+            source,
+        }
+    }
+}
+
+/// See [`ScriptLibrary::with_source`].
+pub struct LibraryScript<'a> {
+    library: &'a ScriptLibrary,
+    source: &'a str,
+}
+
+impl ScriptSource for LibraryScript<'_> {
+    fn source_text(&self) -> &str {
+        self.source
+    }
+
+    fn helpers(&self) -> Option<&rhai::AST> {
+        Some(&self.library.functions)
+    }
+}
+
+fn compile_error(context: &str, position: rhai::Position, message: String) -> ScriptError {
+    ScriptError::Compile {
+        context: context.into(),
+        message,
+        position,
+        source_text: String::new(),
+    }
+}
+
+/// The compiled library for `registry`'s pack scripts. Compiled once per
+/// thread and reused while the registry's script files are unchanged.
+pub fn script_library(registry: &PackRegistry) -> Result<ScriptLibrary, ScriptLibraryError> {
+    thread_local! {
+        static CACHE: RefCell<Option<(Vec<ScriptFile>, ScriptLibrary)>> =
+            const { RefCell::new(None) };
+    }
+    let files = registry.scripts();
+    let cached = CACHE.with(|cache| {
+        cache
+            .borrow()
+            .as_ref()
+            .filter(|(cached, _)| cached.as_slice() == files)
+            .map(|(_, library)| library.clone())
+    });
+    if let Some(library) = cached {
+        return Ok(library);
+    }
+    let library = ScriptLibrary::compile(files, registry)?;
+    CACHE.with(|cache| *cache.borrow_mut() = Some((files.to_vec(), library.clone())));
+    Ok(library)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene_ctx::SceneCtx;
+    use crate::script::{
+        compile_condition, eval_bool, source_game_flag_checks, source_references_game_flag,
+    };
+    use undone_world::test_helpers::make_test_world;
+
+    fn registry_with(source: &str) -> PackRegistry {
+        let mut reg = PackRegistry::new();
+        reg.register_traits(vec![undone_packs::TraitDef {
+            id: "SHY".into(),
+            name: "Shy".into(),
+            description: "...".into(),
+            hidden: false,
+            group: None,
+            conflicts: vec![],
+            composure_penalty: 0,
+        }]);
+        reg.register_scripts(vec![ScriptFile {
+            path: "scripts/test.rhai".into(),
+            source: source.into(),
+        }]);
+        reg
+    }
+
+    #[test]
+    fn helpers_are_callable_and_see_the_handles() {
+        let reg = registry_with("fn late(n) { gd.week() >= n }\nfn settled() { late(2) }");
+        let script = compile_condition("settled() && !late(10)", &reg, "test").unwrap();
+        let mut world = make_test_world();
+        let ctx = SceneCtx::new();
+        world.game_data.week = 1;
+        assert!(!eval_bool(&script, &world, &ctx, &reg).unwrap());
+        world.game_data.week = 3;
+        assert!(eval_bool(&script, &world, &ctx, &reg).unwrap());
+    }
+
+    #[test]
+    fn helper_may_not_call_write_verbs() {
+        let reg = registry_with("fn bump() {\n    w.addArousal(1)\n}");
+        let err = ScriptLibrary::compile(reg.scripts(), &reg).unwrap_err();
+        assert!(err.message.contains("read-only"), "got: {err}");
+        assert_eq!(err.span.as_ref().map(|s| s.line), Some(2), "got: {err}");
+    }
+
+    #[test]
+    fn top_level_statements_are_rejected() {
+        let reg = registry_with("let x = 1;\nfn f() { true }");
+        let err = ScriptLibrary::compile(reg.scripts(), &reg).unwrap_err();
+        assert!(err.message.contains("only define functions"), "got: {err}");
+    }
+
+    #[test]
+    fn duplicate_helpers_across_files_are_rejected() {
+        let mut reg = registry_with("fn f() { true }");
+        let mut files = reg.scripts().to_vec();
+        files.push(ScriptFile {
+            path: "scripts/other.rhai".into(),
+            source: "fn f() { false }".into(),
+        });
+        reg.register_scripts(files);
+        let err = ScriptLibrary::compile(reg.scripts(), &reg).unwrap_err();
+        assert!(err.message.contains("already defined"), "got: {err}");
+    }
+
+    #[test]
+    fn unknown_function_fails_at_load() {
+        let reg = registry_with("fn settled() { true }");
+        let err = compile_condition("setled()", &reg, "test").unwrap_err();
+        assert!(
+            matches!(err, ScriptError::Compile { ref message, .. } if message.contains("unknown function")),
+            "got: {err:?}"
+        );
+        let err = compile_condition("settled(1)", &reg, "test").unwrap_err();
+        assert!(matches!(err, ScriptError::Compile { .. }), "got: {err:?}");
+    }
+
+    #[test]
+    fn ids_passed_to_helpers_are_checked_at_the_call_site() {
+        let reg = registry_with("fn has(t) { w.hasTrait(t) }");
+        assert!(compile_condition(r#"has("SHY")"#, &reg, "test").is_ok());
+        let err = compile_condition(r#"has("NOPE")"#, &reg, "test").unwrap_err();
+        assert!(
+            matches!(err, ScriptError::UnknownId { ref id, .. } if id == "NOPE"),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn flags_passed_to_helpers_are_checked_at_the_call_site() {
+        // BREAKS IF: a helper body is walked together with the other helpers,
+        // so a flag param reads as a computed flag name.
+        let mut reg = registry_with(
+            "fn settled() { gd.week() >= 2 }\nfn due(flag) { settled() && !gd.hasGameFlag(flag) }",
+        );
+        reg.register_flags(
+            toml::from_str(
+                r#"
+                [[flag]]
+                id          = "SEEN"
+                description = "Seen."
+                "#,
+            )
+            .unwrap(),
+        );
+        assert!(compile_condition(r#"due("SEEN")"#, &reg, "test").is_ok());
+        let err = compile_condition(r#"due("UNSEEN")"#, &reg, "test").unwrap_err();
+        assert!(
+            matches!(err, ScriptError::UnknownId { ref id, .. } if id == "UNSEEN"),
+            "got: {err:?}"
+        );
+    }
+
+    #[test]
+    fn source_facts_see_through_helpers() {
+        let reg = registry_with("fn due(flag) { !gd.hasGameFlag(flag) }");
+        let script = compile_condition(r#"!due("SEEN")"#, &reg, "test").unwrap();
+        assert!(source_references_game_flag(&script, "SEEN"));
+        assert_eq!(
+            source_game_flag_checks(&script),
+            vec![("SEEN".to_string(), false)]
+        );
+    }
+}
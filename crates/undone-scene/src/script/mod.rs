@@ -11,13 +11,16 @@ pub mod api;
 pub mod compiled;
 pub mod context;
 pub mod engine;
+pub mod library;
 pub mod validate;
 
 pub use compiled::{compile_condition, compile_effect, CompiledScript, ScriptError};
 pub use engine::{
-    apply_effect_script, build_engines, eval_bool, eval_int, eval_string, ScriptEngines,
+    apply_effect_script, build_engines, eval_bool, eval_int, eval_string, eval_value, ScriptEngines,
 };
+pub use library::{script_library, LibraryScript, ScriptLibrary, ScriptLibraryError};
 pub use validate::{
-    source_advance_arcs, source_has_liking_overshoot, source_has_persistent_mutation,
-    source_references_game_flag, source_set_game_flags,
+    known_ids, prose_scene_refs, source_advance_arcs, source_eq_calls, source_game_flag_checks,
+    source_has_liking_overshoot, source_has_persistent_mutation, source_references_game_flag,
+    source_scene_refs, source_set_game_flags, source_unverifiable_args, SceneNameRef, ScriptSource,
 };
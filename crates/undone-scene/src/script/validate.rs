@@ -1,13 +1,15 @@
-//! Static source-scan validation — the load-time fail-fast guarantee.
+//! Static AST validation — the load-time fail-fast guarantee.
 //!
 //! Rhai's `compile()` + `strict_variables` catches syntax and unknown *variables*,
 //! but NOT unknown content IDs, unknown *methods* (functions resolve at runtime),
 //! or arg mistakes. The legacy loader guaranteed all of these at load via
 //! `validate_call_signature` + `validate_condition_ids` + `validate_effects`. This
-//! module reconstructs that guarantee by scanning the authored script source —
-//! the design mandates content-id args be string literals precisely so a static
-//! scan is complete across ALL branches (including short-circuited ones a runtime
-//! dry-run would skip).
+//! module reconstructs that guarantee by walking the parsed `rhai::AST` — every
+//! call site, including helper-function bodies and short-circuited branches a
+//! runtime dry-run would skip (the walk uses an unoptimised parse, so dead code
+//! is still there). Arguments are constant-folded (`"A" + "B"`, `const` bindings,
+//! constant interpolation); the design mandates content-id args fold to string
+//! literals precisely so the walk is complete.
 //!
 //! What it checks (faithful to the legacy passes):
 //! 1. Every `receiver.method(...)` is a known method for that receiver. For
@@ -20,10 +22,25 @@
 //!    A content-id arg that is not a string literal is rejected (the string-literal rule).
 //! 4. Step-delta args that the legacy `EffectDef` stored as `i8` must be in
 //!    `i8` range (so an out-of-range delta fails at load instead of wrapping).
+//! 5. Every free call names a pack helper (`scripts/*.rhai`) or a Rhai builtin,
+//!    with the right arity. A helper call is walked into with its params bound
+//!    to the call's args, so checks 1–4 hold through helpers; a helper body
+//!    itself is checked once against the READ surface.
+//!
+//! Errors carry the call's `(line, position)`. The `source_*` fact extractors
+//! walk the same AST; opaque string args that don't fold to a literal are
+//! reported by [`source_unverifiable_args`] rather than silently skipped.
+//! Minijinja prose has no Rhai AST, so the prose gate keeps a small tokenizer.
+
+use std::collections::{HashMap, HashSet};
+
+use rhai::{ASTFlags, ASTNode, Expr, Position, Stmt};
 
+use undone_domain::{BodyAttribute, VarType};
 use undone_packs::PackRegistry;
 
-use crate::script::compiled::ScriptError;
+use crate::script::compiled::{CompiledScript, ScriptError};
+use crate::script::engine::{parse_unoptimized, with_engines};
 
 // ---------------------------------------------------------------------------
 // Method spec table
@@ -39,6 +56,50 @@ pub enum IdKind {
     /// `advanceArc(arc, state)` — resolve arc at index 0 and validate the state
     /// literal at index 1 belongs to that arc.
     Arc,
+    /// An npc ref (`npc(ref)`, `setPartner`, `addFriend`). `m`, `f` and role ids
+    /// pass through (roles are bound at runtime); a namespaced id (`base::jake`)
+    /// must name a story character declared in a pack's `characters.toml`.
+    Character,
+    /// A buff declared in a pack's `buffs.toml`.
+    Buff,
+    /// A game variable declared as `[[var]]` in a pack's `flags.toml`.
+    Var,
+}
+
+impl IdKind {
+    /// The kind's name in error messages and signatures.
+    pub fn label(self) -> &'static str {
+        match self {
+            IdKind::Trait => "trait",
+            IdKind::NpcTrait => "npc_trait",
+            IdKind::Skill => "skill",
+            IdKind::Stat => "stat",
+            IdKind::Category => "category",
+            IdKind::Arc => "arc",
+            IdKind::Character => "character",
+            IdKind::Buff => "buff",
+            IdKind::Var => "var",
+        }
+    }
+}
+
+/// Every id of `kind` the registry knows, sorted: what `resolve_id` accepts,
+/// less the pass-through npc refs (`m`, `f`, role ids) for
+/// [`IdKind::Character`].
+pub fn known_ids(registry: &PackRegistry, kind: IdKind) -> Vec<String> {
+    let mut ids: Vec<String> = match kind {
+        IdKind::Trait => registry.trait_defs().map(|d| d.id.clone()).collect(),
+        IdKind::NpcTrait => registry.npc_trait_defs().map(|d| d.id.clone()).collect(),
+        IdKind::Skill => registry.skill_defs().map(|d| d.id.clone()).collect(),
+        IdKind::Stat => registry.registered_stats().map(str::to_string).collect(),
+        IdKind::Category => registry.categories().map(|d| d.id.clone()).collect(),
+        IdKind::Arc => registry.arcs().map(|d| d.id.clone()).collect(),
+        IdKind::Character => registry.characters().iter().map(|d| d.id.clone()).collect(),
+        IdKind::Buff => registry.buffs().map(|d| d.id.clone()).collect(),
+        IdKind::Var => registry.vars().map(|d| d.id.clone()).collect(),
+    };
+    ids.sort();
+    ids
 }
 
 /// What a single authored method call is allowed to look like.
@@ -55,6 +116,12 @@ struct MethodSpec {
     /// Argument indices that the legacy `EffectDef` stored as `i8` and so must
     /// be an integer literal in `i8` range.
     i8_args: &'static [usize],
+    /// Argument index of a game flag name, checked against the packs' declared
+    /// flags when it is a literal.
+    flag_arg: Option<usize>,
+    /// `(arg_index, attribute)` for a body value, checked against the
+    /// attribute's scale when it is a literal.
+    body_arg: Option<(usize, BodyAttribute)>,
 }
 
 const fn spec(arity: usize) -> MethodSpec {
@@ -64,6 +131,8 @@ const fn spec(arity: usize) -> MethodSpec {
         id_arg: None,
         int_args: &[],
         i8_args: &[],
+        flag_arg: None,
+        body_arg: None,
     }
 }
 
@@ -75,6 +144,8 @@ const fn spec_arity(min: usize, max: usize) -> MethodSpec {
         id_arg: None,
         int_args: &[],
         i8_args: &[],
+        flag_arg: None,
+        body_arg: None,
     }
 }
 
@@ -85,6 +156,8 @@ const fn spec_id(arity: usize, idx: usize, kind: IdKind) -> MethodSpec {
         id_arg: Some((idx, kind)),
         int_args: &[],
         i8_args: &[],
+        flag_arg: None,
+        body_arg: None,
     }
 }
 
@@ -95,6 +168,8 @@ const fn spec_id_int(arity: usize, idx: usize, kind: IdKind, int_idx: usize) ->
         id_arg: Some((idx, kind)),
         int_args: int_idx_slice(int_idx),
         i8_args: &[],
+        flag_arg: None,
+        body_arg: None,
     }
 }
 
@@ -112,6 +187,34 @@ const fn spec_i8(arity: usize, i8_args: &'static [usize]) -> MethodSpec {
         id_arg: None,
         int_args: &[],
         i8_args,
+        flag_arg: None,
+        body_arg: None,
+    }
+}
+
+/// A method taking one game flag name.
+const fn spec_flag() -> MethodSpec {
+    MethodSpec {
+        arity: 1,
+        arity_max: 1,
+        id_arg: None,
+        int_args: &[],
+        i8_args: &[],
+        flag_arg: Some(0),
+        body_arg: None,
+    }
+}
+
+/// A method taking one value on `attribute`'s body scale.
+const fn spec_body(attribute: BodyAttribute) -> MethodSpec {
+    MethodSpec {
+        arity: 1,
+        arity_max: 1,
+        id_arg: None,
+        int_args: &[],
+        i8_args: &[],
+        flag_arg: None,
+        body_arg: Some((0, attribute)),
     }
 }
 
@@ -153,15 +256,17 @@ fn method_spec_from_argshape(args: crate::script::api::ArgShape) -> MethodSpec {
         ArgShape::Int { i8_range: true } => spec_i8(1, &[0]),
         ArgShape::Int { i8_range: false } => spec(1),
         ArgShape::Str => spec(1),
+        ArgShape::Flag => spec_flag(),
+        ArgShape::Body(attribute) => spec_body(attribute),
         ArgShape::Bool => spec(1),
-        ArgShape::StrInt => spec(2),
+        ArgShape::StrInt | ArgShape::StrAny => spec(2),
         ArgShape::StrStr => spec(2),
         ArgShape::StrOpt => spec_arity(1, 2),
     }
 }
 
 // ---------------------------------------------------------------------------
-// Tokenizer
+// Prose tokenizer
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, PartialEq)]
@@ -176,9 +281,8 @@ enum Tok {
     Other,
 }
 
-/// Tokenize enough of the Rhai source to find call sites and their literal args.
-/// Returns an error on an unterminated string (a syntax error `compile` also
-/// reports, but we may run before/independently).
+/// Tokenize enough of a Minijinja expression region to find call sites and their
+/// literal args. Returns an error on an unterminated string.
 fn tokenize(src: &str) -> Result<Vec<Tok>, String> {
     let bytes: Vec<char> = src.chars().collect();
     let mut toks = Vec::new();
@@ -294,10 +398,15 @@ fn tokenize(src: &str) -> Result<Vec<Tok>, String> {
 // Call extraction
 // ---------------------------------------------------------------------------
 
+/// A call argument after constant folding. `Other` is anything not known until
+/// runtime (a `let` variable, a read result, …) or not a string/int.
 #[derive(Debug, Clone, PartialEq)]
 enum Arg {
     Str(String),
     Int(i64),
+    /// A pack helper's parameter while the helper body is checked on its own;
+    /// its value is only known at each call site.
+    Param,
     Other,
 }
 
@@ -305,8 +414,317 @@ struct Call {
     receiver: Option<String>,
     method: String,
     args: Vec<Arg>,
+    /// Where the call sits in the script; `Position::NONE` for prose calls.
+    position: Position,
+    /// The call is the operand of a logical `!`.
+    negated: bool,
+    /// The other operand when the call is one side of an `==`.
+    compared_to: Option<Arg>,
+    /// A free function call `name(args)` (not `npc`): a script function, a pack
+    /// helper or a Rhai builtin.
+    free: bool,
+    /// The pack helper whose body the call was expanded from.
+    via: Option<String>,
+}
+
+/// Every `recv.method(args)`, `npc(args)` and free `name(args)` call in the
+/// AST, script-function bodies included. `receiver` is the handle variable,
+/// `Some("npc")` for a method chained off `npc(...)` and for the constructor
+/// itself, or `None` for a free call or a method called on a computed value (a
+/// Rhai builtin such as `.len()`). `bound` pre-binds names (helper params) for
+/// folding.
+fn script_calls(ast: &rhai::AST, bound: &HashMap<String, Arg>) -> Vec<Call> {
+    let consts = constants(ast, bound);
+    let mut calls = Vec::new();
+    ast.walk(&mut |path: &[ASTNode]| {
+        let (fc, position, receiver, free) = match path.last() {
+            Some(ASTNode::Expr(Expr::MethodCall(fc, pos))) => {
+                (fc, *pos, method_receiver(path), false)
+            }
+            Some(ASTNode::Expr(Expr::FnCall(fc, pos)) | ASTNode::Stmt(Stmt::FnCall(fc, pos)))
+                if fc.namespace.is_empty() && is_identifier(&fc.name) =>
+            {
+                if fc.name == "npc" {
+                    (fc, *pos, Some("npc".to_string()), false)
+                } else {
+                    (fc, *pos, None, true)
+                }
+            }
+            _ => return true,
+        };
+        let (negated, compared_to) = call_operator(path, &consts);
+        calls.push(Call {
+            receiver,
+            method: fc.name.to_string(),
+            args: fc.args.iter().map(|arg| fold(arg, &consts)).collect(),
+            position,
+            negated,
+            compared_to,
+            free,
+            via: None,
+        });
+        true
+    });
+    calls
+}
+
+/// A function name rather than an operator (`+`, `==`, `!`, …).
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
 }
 
+/// `(name, arity)` of every script function `ast` defines.
+fn fn_keys(ast: &rhai::AST) -> HashSet<(String, usize)> {
+    ast.iter_functions()
+        .map(|f| (f.name.to_string(), f.params.len()))
+        .collect()
+}
+
+/// [`script_calls`] plus, for every call into a function of `helpers` the
+/// script doesn't define itself, the calls in that function's body with its
+/// params bound to the call-site args — recursively. Expanded calls take the
+/// outermost call's position, so load errors point at the script, and flip
+/// `negated` under a negated helper call.
+fn reaching_calls(ast: &rhai::AST, helpers: Option<&rhai::AST>) -> Vec<Call> {
+    let calls = script_calls(ast, &HashMap::new());
+    let Some(helpers) = helpers else {
+        return calls;
+    };
+    let mut out = Vec::new();
+    expand_helpers(calls, helpers, &fn_keys(ast), &mut Vec::new(), &mut out);
+    out
+}
+
+fn expand_helpers(
+    calls: Vec<Call>,
+    helpers: &rhai::AST,
+    local: &HashSet<(String, usize)>,
+    stack: &mut Vec<(String, usize)>,
+    out: &mut Vec<Call>,
+) {
+    for call in calls {
+        let key = (call.method.clone(), call.args.len());
+        let inner = (call.free && !local.contains(&key) && !stack.contains(&key))
+            .then(|| helper_calls(helpers, &call))
+            .flatten();
+        if let Some(inner) = inner {
+            let inner = inner
+                .into_iter()
+                .map(|c| Call {
+                    position: call.position,
+                    negated: c.negated != call.negated,
+                    via: Some(call.method.clone()),
+                    ..c
+                })
+                .collect();
+            stack.push(key);
+            out.push(call);
+            expand_helpers(inner, helpers, local, stack, out);
+            stack.pop();
+        } else {
+            out.push(call);
+        }
+    }
+}
+
+/// The calls in the body of the `helpers` function `call` invokes, with its
+/// params bound to the call's args; `None` if `helpers` has no such function.
+fn helper_calls(helpers: &rhai::AST, call: &Call) -> Option<Vec<Call>> {
+    let arity = call.args.len();
+    let helper = helpers
+        .iter_functions()
+        .find(|f| f.name == call.method && f.params.len() == arity)?;
+    let bound = helper
+        .params
+        .iter()
+        .map(|param| param.to_string())
+        .zip(call.args.iter().cloned())
+        .collect();
+    Some(script_calls(
+        &helper_body(helpers, &call.method, arity),
+        &bound,
+    ))
+}
+
+/// Just the `helpers` function `name/arity`, for walking its body alone.
+/// (`clone_functions_only_filtered` skips its filter when cloning into an
+/// empty module, so it would keep every helper.)
+fn helper_body(helpers: &rhai::AST, name: &str, arity: usize) -> rhai::AST {
+    let mut body = helpers.clone_functions_only();
+    body.retain_functions(|_, _, fn_name, params| fn_name == name && params == arity);
+    body
+}
+
+/// The operator applied to the call expression at the end of `path` (the whole
+/// `recv.method(...)` chain): whether it is negated, and what it is `==` to.
+fn call_operator(path: &[ASTNode], consts: &HashMap<String, Arg>) -> (bool, Option<Arg>) {
+    let mut idx = path.len() - 1;
+    while idx > 0 && matches!(path[idx - 1], ASTNode::Expr(Expr::Dot(..))) {
+        idx -= 1;
+    }
+    let (Some(ASTNode::Expr(expr)), Some(ASTNode::Expr(Expr::FnCall(op, _)))) =
+        (path.get(idx), idx.checked_sub(1).and_then(|i| path.get(i)))
+    else {
+        return (false, None);
+    };
+    match (op.name.as_str(), op.args.as_slice()) {
+        ("!", [_]) => (true, None),
+        ("==", [lhs, rhs]) => {
+            let other = if std::ptr::eq(lhs, *expr) { rhs } else { lhs };
+            (false, Some(fold(other, consts)))
+        }
+        _ => (false, None),
+    }
+}
+
+/// The receiver of the `MethodCall` at the end of `path`. A chain `x.a().b()`
+/// parses as `Dot(x, Dot(a(), b()))`: `a` climbs to the outer `Dot` whose lhs is
+/// `x`; `b`'s lhs is the result of `a()`, so it has no handle receiver.
+fn method_receiver(path: &[ASTNode]) -> Option<String> {
+    let Some(ASTNode::Expr(mut child)) = path.last().copied() else {
+        return None;
+    };
+    for node in path.iter().rev().skip(1) {
+        let ASTNode::Expr(dot @ Expr::Dot(bin, ..)) = node else {
+            return None;
+        };
+        if std::ptr::eq(&bin.lhs, child) {
+            child = dot;
+            continue;
+        }
+        return match &bin.lhs {
+            Expr::Variable(var, ..) => Some(var.1.to_string()),
+            Expr::FnCall(fc, _) if fc.name == "npc" => Some("npc".to_string()),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// Foldable `const` bindings, on top of `bound`. A name bound more than once
+/// (shadowing, or a `let` of the same name) is dropped — its value depends on
+/// where it is read.
+fn constants(ast: &rhai::AST, bound: &HashMap<String, Arg>) -> HashMap<String, Arg> {
+    let mut consts = bound.clone();
+    let mut shadowed = Vec::new();
+    ast.walk(&mut |path: &[ASTNode]| {
+        if let Some(ASTNode::Stmt(Stmt::Var(var, flags, _))) = path.last() {
+            let name = var.0.name.to_string();
+            if consts.contains_key(&name) || shadowed.contains(&name) {
+                shadowed.push(name);
+            } else if flags.contains(ASTFlags::CONSTANT) {
+                let value = fold(&var.1, &consts);
+                consts.insert(name, value);
+            } else {
+                shadowed.push(name);
+            }
+        }
+        true
+    });
+    for name in shadowed {
+        consts.remove(&name);
+    }
+    consts
+}
+
+/// Constant-fold an argument expression: literals, `+` on strings/ints, unary
+/// `-`, constant interpolation and `const` bindings.
+fn fold(expr: &Expr, consts: &HashMap<String, Arg>) -> Arg {
+    match expr {
+        Expr::StringConstant(s, _) => Arg::Str(s.to_string()),
+        Expr::IntegerConstant(n, _) => Arg::Int(*n),
+        Expr::DynamicConstant(value, _) => {
+            if let Some(s) = value.read_lock::<rhai::ImmutableString>() {
+                Arg::Str(s.to_string())
+            } else {
+                value.as_int().map_or(Arg::Other, Arg::Int)
+            }
+        }
+        Expr::Variable(var, ..) => consts.get(var.1.as_str()).cloned().unwrap_or(Arg::Other),
+        Expr::FnCall(fc, _) if fc.name == "+" && fc.args.len() == 2 => {
+            match (fold(&fc.args[0], consts), fold(&fc.args[1], consts)) {
+                (Arg::Int(a), Arg::Int(b)) => a.checked_add(b).map_or(Arg::Other, Arg::Int),
+                (Arg::Str(a), Arg::Str(b)) => Arg::Str(format!("{a}{b}")),
+                (Arg::Str(a), Arg::Int(b)) => Arg::Str(format!("{a}{b}")),
+                (Arg::Int(a), Arg::Str(b)) => Arg::Str(format!("{a}{b}")),
+                _ => Arg::Other,
+            }
+        }
+        Expr::FnCall(fc, _) if fc.name == "-" && fc.args.len() == 1 => {
+            match fold(&fc.args[0], consts) {
+                Arg::Int(n) => n.checked_neg().map_or(Arg::Other, Arg::Int),
+                _ => Arg::Other,
+            }
+        }
+        Expr::InterpolatedString(parts, _) => {
+            let mut out = String::new();
+            for part in parts.iter() {
+                let part = match part {
+                    Expr::Stmt(block) => match block.statements() {
+                        [Stmt::Expr(inner)] => fold(inner, consts),
+                        _ => Arg::Other,
+                    },
+                    part => fold(part, consts),
+                };
+                match part {
+                    Arg::Str(s) => out.push_str(&s),
+                    Arg::Int(n) => out.push_str(&n.to_string()),
+                    Arg::Param | Arg::Other => return Arg::Other,
+                }
+            }
+            Arg::Str(out)
+        }
+        _ => Arg::Other,
+    }
+}
+
+/// Script text the `source_*` fact helpers walk. Raw source sees the functions
+/// it defines itself; a [`CompiledScript`] also follows its calls into the pack
+/// helpers it was compiled against.
+pub trait ScriptSource {
+    fn source_text(&self) -> &str;
+
+    /// The script functions calls may resolve to beyond the source's own.
+    fn helpers(&self) -> Option<&rhai::AST> {
+        None
+    }
+}
+
+impl ScriptSource for str {
+    fn source_text(&self) -> &str {
+        self
+    }
+}
+
+impl ScriptSource for String {
+    fn source_text(&self) -> &str {
+        self
+    }
+}
+
+impl ScriptSource for CompiledScript {
+    fn source_text(&self) -> &str {
+        &self.source
+    }
+
+    fn helpers(&self) -> Option<&rhai::AST> {
+        Some(&self.ast)
+    }
+}
+
+/// Parse a script and extract its calls, helper bodies included; empty if it
+/// doesn't parse (the compile gate reports that).
+fn source_calls(src: &(impl ScriptSource + ?Sized)) -> Vec<Call> {
+    parse_unoptimized(src.source_text())
+        .map(|ast| reaching_calls(&ast, src.helpers()))
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Prose call extraction
+// ---------------------------------------------------------------------------
+
 /// Extract every `name(args)` and `recv.name(args)` and `).name(args)` call.
 /// `receiver` = the handle token for `recv.name`, `Some("npc")` for a `).name`
 /// chained call (only `npc(...)` produces a chained method call in our
@@ -333,6 +751,11 @@ fn extract_calls(toks: &[Tok]) -> Vec<Call> {
                     receiver,
                     method: name.clone(),
                     args,
+                    position: Position::NONE,
+                    negated: false,
+                    compared_to: None,
+                    free: false,
+                    via: None,
                 });
                 idx = end;
                 continue;
@@ -402,60 +825,177 @@ fn parse_args(toks: &[Tok], lparen: usize) -> (Vec<Arg>, usize) {
 // Validation entry points
 // ---------------------------------------------------------------------------
 
+/// Which API surface a script is checked against.
+#[derive(Clone, Copy, PartialEq)]
+enum Surface {
+    /// READ methods only.
+    Condition,
+    /// READ and WRITE methods.
+    Effect,
+    /// A pack helper: READ only, whatever it is called from.
+    Helper,
+}
+
 /// Validate a condition source. Only READ methods are valid (effect mutators in
 /// a condition are "unknown method" → enforces the read/write split at load).
+/// Calls into the pack `helpers` are checked with the call-site args bound.
 pub(crate) fn validate_condition_source(
     src: &str,
+    helpers: &rhai::AST,
     registry: &PackRegistry,
     context: &str,
 ) -> Result<(), ScriptError> {
-    validate(src, registry, context, false)
+    validate(src, helpers, registry, context, Surface::Condition)
 }
 
 /// Validate an effect source. READ and WRITE methods are valid.
 pub(crate) fn validate_effect_source(
     src: &str,
+    helpers: &rhai::AST,
     registry: &PackRegistry,
     context: &str,
 ) -> Result<(), ScriptError> {
-    validate(src, registry, context, true)
+    validate(src, helpers, registry, context, Surface::Effect)
 }
 
 fn validate(
     src: &str,
+    helpers: &rhai::AST,
     registry: &PackRegistry,
     context: &str,
-    allow_write: bool,
+    surface: Surface,
 ) -> Result<(), ScriptError> {
-    let toks = tokenize(src).map_err(|message| ScriptError::Compile {
-        context: context.into(),
-        message,
-        source_text: src.into(),
-    })?;
-    for call in extract_calls(&toks) {
-        validate_call(&call, registry, context, src, allow_write)?;
+    let ast =
+        parse_unoptimized(src).map_err(|e| compile_err(context, src, e.1, e.0.to_string()))?;
+    let local = fn_keys(&ast);
+    for call in reaching_calls(&ast, Some(helpers)) {
+        // Free calls inside a helper were checked when the library loaded.
+        if call.free && call.via.is_none() {
+            validate_free_call(&call, &local, helpers, context, src)?;
+        }
+        validate_call(&call, registry, context, src, surface)?;
+    }
+    Ok(())
+}
+
+/// Validate the body of pack helper `name/arity` in `helpers` (one library file
+/// is `src`): READ methods only, ids resolved except those passed in through
+/// params — the call sites check those.
+pub(crate) fn validate_helper(
+    helpers: &rhai::AST,
+    name: &str,
+    arity: usize,
+    registry: &PackRegistry,
+    context: &str,
+    src: &str,
+) -> Result<(), ScriptError> {
+    let Some(helper) = helpers
+        .iter_functions()
+        .find(|f| f.name == name && f.params.len() == arity)
+    else {
+        return Ok(());
+    };
+    let bound = helper
+        .params
+        .iter()
+        .map(|param| (param.to_string(), Arg::Param))
+        .collect();
+    for call in script_calls(&helper_body(helpers, name, arity), &bound) {
+        if call.free {
+            validate_free_call(&call, &HashSet::new(), helpers, context, src)?;
+        }
+        validate_call(&call, registry, context, src, Surface::Helper)?;
     }
     Ok(())
 }
 
+/// A free call must name a script function, a pack helper or a Rhai builtin,
+/// with an arity one of them accepts — a misspelt helper fails at load rather
+/// than as "function not found" mid-scene.
+fn validate_free_call(
+    call: &Call,
+    local: &HashSet<(String, usize)>,
+    helpers: &rhai::AST,
+    context: &str,
+    src: &str,
+) -> Result<(), ScriptError> {
+    let arity = call.args.len();
+    let mut arities: Vec<usize> = local
+        .iter()
+        .filter(|(name, _)| *name == call.method)
+        .map(|(_, params)| *params)
+        .chain(
+            helpers
+                .iter_functions()
+                .filter(|f| f.name == call.method)
+                .map(|f| f.params.len()),
+        )
+        .collect();
+    if arities.contains(&arity) || is_builtin_fn(&call.method) {
+        return Ok(());
+    }
+    let message = if arities.is_empty() {
+        format!("unknown function '{}'", call.method)
+    } else {
+        arities.sort_unstable();
+        arities.dedup();
+        let expect: Vec<String> = arities.iter().map(usize::to_string).collect();
+        format!(
+            "function '{}' takes {} arg(s), got {arity}",
+            call.method,
+            expect.join(" or ")
+        )
+    };
+    Err(compile_err(context, src, call.position, message))
+}
+
+/// Whether `name` is a function the engines provide: the Rhai standard
+/// packages, the registered API, or a language keyword function. `eval` and
+/// the function-pointer keywords (`Fn`, `call`, `curry`) are left out: they
+/// reach the API through a computed string the static scan cannot see.
+fn is_builtin_fn(name: &str) -> bool {
+    const KEYWORD_FNS: &[&str] = &[
+        "type_of",
+        "print",
+        "debug",
+        "is_shared",
+        "is_def_fn",
+        "is_def_var",
+    ];
+    thread_local! {
+        static NAMES: HashSet<String> = with_engines(|engines| {
+            engines
+                .effect
+                .gen_fn_signatures(true)
+                .iter()
+                .filter_map(|sig| sig.split('(').next())
+                .map(str::to_string)
+                .collect()
+        });
+    }
+    KEYWORD_FNS.contains(&name) || NAMES.with(|names| names.contains(name))
+}
+
 fn validate_call(
     call: &Call,
     registry: &PackRegistry,
     context: &str,
     src: &str,
-    allow_write: bool,
+    surface: Surface,
 ) -> Result<(), ScriptError> {
+    let err = |message: String| {
+        let message = match &call.via {
+            Some(helper) => format!("{message} (in helper '{helper}')"),
+            None => message,
+        };
+        compile_err(context, src, call.position, message)
+    };
+    let allow_write = surface == Surface::Effect;
     let receiver = match &call.receiver {
         Some(r) => r.as_str(),
-        // A bare free call: only `npc(...)` is legal, and only in effects.
-        None => {
-            if call.method == "npc" {
-                "npc"
-            } else {
-                // Not a handle method (could be a Rhai builtin) — leave to compile.
-                return Ok(());
-            }
-        }
+        // Not a handle method (a free call, or a Rhai builtin on a computed
+        // value) — free calls are checked by `validate_free_call`.
+        None => return Ok(()),
     };
 
     // Look up the method. Conditions: read only. Effects: read ∪ write.
@@ -471,20 +1011,19 @@ fn validate_call(
         // If it's a known WRITE method but we're in a condition, give the precise
         // read/write-split message; otherwise it's simply unknown.
         if !allow_write && write_spec(receiver, &call.method).is_some() {
-            return Err(compile_err(
-                context,
-                src,
-                format!(
-                    "effect mutator '{}.{}' is not allowed in a condition",
-                    receiver, call.method
-                ),
-            ));
-        }
-        return Err(compile_err(
-            context,
-            src,
-            format!("unknown method '{}.{}'", receiver, call.method),
-        ));
+            let place = match surface {
+                Surface::Helper => "a pack helper (helpers are read-only)",
+                _ => "a condition",
+            };
+            return Err(err(format!(
+                "effect mutator '{}.{}' is not allowed in {place}",
+                receiver, call.method
+            )));
+        }
+        return Err(err(format!(
+            "unknown method '{}.{}'",
+            receiver, call.method
+        )));
     };
 
     if call.args.len() < spec.arity || call.args.len() > spec.arity_max {
@@ -493,80 +1032,89 @@ fn validate_call(
         } else {
             format!("{}..={}", spec.arity, spec.arity_max)
         };
-        return Err(compile_err(
-            context,
-            src,
-            format!(
-                "method '{}.{}' expects {} arg(s), got {}",
-                receiver,
-                call.method,
-                expect,
-                call.args.len()
-            ),
-        ));
+        return Err(err(format!(
+            "method '{}.{}' expects {} arg(s), got {}",
+            receiver,
+            call.method,
+            expect,
+            call.args.len()
+        )));
     }
 
-    // Content-id resolution.
+    // Content-id resolution. A helper param is resolved at each call site.
     if let Some((idx, kind)) = spec.id_arg {
-        let Some(Arg::Str(id)) = call.args.get(idx) else {
-            return Err(compile_err(
-                context,
-                src,
-                format!(
+        match call.args.get(idx) {
+            Some(Arg::Str(id)) => resolve_id(kind, id, call, registry, context, src)?,
+            Some(Arg::Param) => {}
+            _ => {
+                return Err(err(format!(
                     "method '{}.{}' arg {} must be a string-literal content id",
                     receiver,
                     call.method,
                     idx + 1
-                ),
-            ));
-        };
-        resolve_id(kind, id, call, registry, context, src)?;
+                )));
+            }
+        }
     }
 
-    // Plain integer-literal args (legacy typed-arg check).
-    for &idx in spec.int_args {
-        if !matches!(call.args.get(idx), Some(Arg::Int(_))) {
-            return Err(compile_err(
-                context,
-                src,
-                format!(
-                    "method '{}.{}' arg {} must be an integer literal",
+    // Game flags: once the packs declare their flags, a flag must be a
+    // declared literal. A computed name could be anything, so it is rejected.
+    if let Some(idx) = spec.flag_arg {
+        match call.args.get(idx) {
+            Some(Arg::Str(flag)) => check_flag(flag, call, registry, context, src)?,
+            Some(Arg::Other) if registry.declares_flags() => {
+                return Err(err(format!(
+                    "method '{}.{}' arg {} must be a string-literal game flag",
                     receiver,
                     call.method,
                     idx + 1
-                ),
-            ));
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    // Body values: a literal must be on the attribute's scale, or the write
+    // would fail in play.
+    if let Some((idx, attribute)) = spec.body_arg {
+        if let Some(Arg::Str(value)) = call.args.get(idx) {
+            check_body_value(attribute, value, call, context, src)?;
+        }
+    }
+
+    // Plain integer-literal args (legacy typed-arg check).
+    for &idx in spec.int_args {
+        if !matches!(call.args.get(idx), Some(Arg::Int(_) | Arg::Param)) {
+            return Err(err(format!(
+                "method '{}.{}' arg {} must be an integer literal",
+                receiver,
+                call.method,
+                idx + 1
+            )));
         }
     }
 
     // i8-range checks for legacy `i8` step deltas — must be an integer in range.
     for &idx in spec.i8_args {
         match call.args.get(idx) {
+            Some(Arg::Param) => {}
             Some(Arg::Int(n)) if *n >= i8::MIN as i64 && *n <= i8::MAX as i64 => {}
             Some(Arg::Int(n)) => {
-                return Err(compile_err(
-                    context,
-                    src,
-                    format!(
-                        "method '{}.{}' arg {} = {} is out of range (must fit in i8: -128..=127)",
-                        receiver,
-                        call.method,
-                        idx + 1,
-                        n
-                    ),
-                ));
+                return Err(err(format!(
+                    "method '{}.{}' arg {} = {} is out of range (must fit in i8: -128..=127)",
+                    receiver,
+                    call.method,
+                    idx + 1,
+                    n
+                )));
             }
             _ => {
-                return Err(compile_err(
-                    context,
-                    src,
-                    format!(
-                        "method '{}.{}' arg {} must be an integer literal",
-                        receiver,
-                        call.method,
-                        idx + 1
-                    ),
-                ));
+                return Err(err(format!(
+                    "method '{}.{}' arg {} must be an integer literal",
+                    receiver,
+                    call.method,
+                    idx + 1
+                )));
             }
         }
     }
@@ -586,6 +1134,7 @@ fn resolve_id(
         context: context.into(),
         kind: kind_str.into(),
         id: id.into(),
+        position: call.position,
         source_text: src.into(),
     };
     match kind {
@@ -624,47 +1173,134 @@ fn resolve_id(
                         context: context.into(),
                         kind: "arc_state".into(),
                         id: format!("{id} -> {state}"),
+                        position: call.position,
                         source_text: src.into(),
                     });
                 }
             }
             Ok(())
         }
+        IdKind::Character => {
+            if !id.contains("::") || registry.get_character(id).is_some() {
+                Ok(())
+            } else {
+                Err(unknown("character"))
+            }
+        }
+        IdKind::Buff => {
+            if registry.get_buff(id).is_some() {
+                Ok(())
+            } else {
+                Err(unknown("buff"))
+            }
+        }
+        IdKind::Var => {
+            if registry.get_var(id).is_some() {
+                Ok(())
+            } else {
+                Err(unknown("var"))
+            }
+        }
+    }
+}
+
+fn check_flag(
+    flag: &str,
+    call: &Call,
+    registry: &PackRegistry,
+    context: &str,
+    src: &str,
+) -> Result<(), ScriptError> {
+    if registry.is_known_flag(flag) {
+        return Ok(());
+    }
+    Err(ScriptError::UnknownId {
+        context: context.into(),
+        kind: "game flag".into(),
+        id: flag.into(),
+        position: call.position,
+        source_text: src.into(),
+    })
+}
+
+fn check_body_value(
+    attribute: BodyAttribute,
+    value: &str,
+    call: &Call,
+    context: &str,
+    src: &str,
+) -> Result<(), ScriptError> {
+    let scale = undone_packs::body_scale(attribute);
+    if scale.contains(&value) {
+        return Ok(());
     }
+    Err(compile_err(
+        context,
+        src,
+        call.position,
+        format!(
+            "method '{}' value '{value}' is not one of {}",
+            call.method,
+            scale.join(", ")
+        ),
+    ))
 }
 
-fn compile_err(context: &str, src: &str, message: String) -> ScriptError {
+fn compile_err(context: &str, src: &str, position: Position, message: String) -> ScriptError {
     ScriptError::Compile {
         context: context.into(),
         message,
+        position,
         source_text: src.into(),
     }
 }
 
 // ---------------------------------------------------------------------------
-// Source-scan helpers reused by the migrated static-analysis tooling
+// Source fact helpers reused by the migrated static-analysis tooling
 // (`Scheduler::references_game_flag`, `validate-pack` persistent-mutation lint,
 // reachability). These reconstruct the legacy `Expr`/`EffectDef` walks over the
-// authored Rhai source — sound because content-id/flag args are string literals.
+// script AST, with args constant-folded; calls whose args don't fold are left to
+// `source_unverifiable_args`. Given a `CompiledScript`, they also follow calls into
+// the pack helpers, with the helper params bound to the call-site args.
 // ---------------------------------------------------------------------------
 
 /// True if the condition source calls `gd.hasGameFlag("<flag>")` for this flag.
 /// Reconstructs the legacy `expr_references_game_flag` walk.
-pub fn source_references_game_flag(src: &str, flag: &str) -> bool {
-    let Ok(toks) = tokenize(src) else {
-        return false;
-    };
-    extract_calls(&toks).iter().any(|c| {
+pub fn source_references_game_flag(src: &(impl ScriptSource + ?Sized), flag: &str) -> bool {
+    source_calls(src).iter().any(|c| {
         c.method == "hasGameFlag" && matches!(c.args.first(), Some(Arg::Str(s)) if s == flag)
     })
 }
 
+/// All `gd.hasGameFlag("X")` flags in a condition source, each paired with
+/// whether the check is negated (`!gd.hasGameFlag(...)` — an anti-requirement).
+pub fn source_game_flag_checks(src: &(impl ScriptSource + ?Sized)) -> Vec<(String, bool)> {
+    source_calls(src)
+        .into_iter()
+        .filter(|c| c.method == "hasGameFlag")
+        .filter_map(|c| match c.args.first() {
+            Some(Arg::Str(s)) => Some((s.clone(), c.negated)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// All `method("ARG") == "VALUE"` comparisons (either operand order) in a
+/// condition source, as `(arg, value)` pairs — `arcState`, `npcLiking`.
+pub fn source_eq_calls(src: &(impl ScriptSource + ?Sized), method: &str) -> Vec<(String, String)> {
+    source_calls(src)
+        .into_iter()
+        .filter(|c| c.method == method)
+        .filter_map(|c| match (c.args.first(), &c.compared_to) {
+            (Some(Arg::Str(arg)), Some(Arg::Str(value))) => Some((arg.clone(), value.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
 /// All `gd.setGameFlag("X")` flag args in an effect source (reachability facts).
-pub fn source_set_game_flags(src: &str) -> Vec<String> {
-    let Ok(toks) = tokenize(src) else {
-        return Vec::new();
-    };
-    extract_calls(&toks)
+pub fn source_set_game_flags(src: &(impl ScriptSource + ?Sized)) -> Vec<String> {
+    source_calls(src)
         .iter()
         .filter(|c| c.method == "setGameFlag")
         .filter_map(|c| match c.args.first() {
@@ -675,11 +1311,8 @@ pub fn source_set_game_flags(src: &str) -> Vec<String> {
 }
 
 /// All `gd.advanceArc("ARC", "STATE")` pairs in an effect source.
-pub fn source_advance_arcs(src: &str) -> Vec<(String, String)> {
-    let Ok(toks) = tokenize(src) else {
-        return Vec::new();
-    };
-    extract_calls(&toks)
+pub fn source_advance_arcs(src: &(impl ScriptSource + ?Sized)) -> Vec<(String, String)> {
+    source_calls(src)
         .iter()
         .filter(|c| c.method == "advanceArc")
         .filter_map(|c| match (c.args.first(), c.args.get(1)) {
@@ -691,11 +1324,8 @@ pub fn source_advance_arcs(src: &str) -> Vec<(String, String)> {
 
 /// True if any `npc(...).addLiking(N)` in the effect source has `|N| > 1`
 /// (an overshoot that could skip an exact npc-liking equality gate).
-pub fn source_has_liking_overshoot(src: &str) -> bool {
-    let Ok(toks) = tokenize(src) else {
-        return false;
-    };
-    extract_calls(&toks).iter().any(|c| {
+pub fn source_has_liking_overshoot(src: &(impl ScriptSource + ?Sized)) -> bool {
+    source_calls(src).iter().any(|c| {
         c.method == "addLiking"
             && matches!(c.args.first(), Some(Arg::Int(n)) if n.unsigned_abs() > 1)
     })
@@ -705,24 +1335,123 @@ pub fn source_has_liking_overshoot(src: &str) -> bool {
 /// effect call OTHER than the scene-local `scene.setFlag`/`scene.removeFlag`
 /// (and the `npc(ref)` constructor, which on its own mutates nothing).
 /// Reconstructs `EffectDef::mutates_persistent_world` over the call-list source.
-pub fn source_has_persistent_mutation(src: &str) -> bool {
-    let Ok(toks) = tokenize(src) else {
-        return false;
-    };
-    extract_calls(&toks).iter().any(|c| {
+pub fn source_has_persistent_mutation(src: &(impl ScriptSource + ?Sized)) -> bool {
+    source_calls(src).iter().any(|c| {
         let m = c.method.as_str();
-        // The constructor and the two scene-local mutators are not persistent.
-        if m == "npc"
-            || (c.receiver.as_deref() == Some("scene") && (m == "setFlag" || m == "removeFlag"))
-        {
+        let receiver = c.receiver.as_deref().unwrap_or("");
+        // The constructor and the scene-local mutators are not persistent. (A
+        // `scene.set` on a persistent scene var is; `SceneDefinition` counts those.)
+        if m == "npc" || receiver == "scene" {
             return false;
         }
         // Any other recognised write mutator is persistent.
-        write_spec(c.receiver.as_deref().unwrap_or(""), m).is_some()
-            || (c.receiver.as_deref() == Some("npc") && write_spec("npc", m).is_some())
+        write_spec(receiver, m).is_some()
     })
 }
 
+/// One line per opaque string arg (a flag, stuff id, role, …) that doesn't fold
+/// to a literal, e.g. `gd.setGameFlag(name)` with `name` a `let` binding. Those
+/// ids can't be checked — or seen by the fact helpers above — until runtime.
+/// Content-id args never appear here: the gate rejects them outright.
+pub fn source_unverifiable_args(src: &(impl ScriptSource + ?Sized)) -> Vec<String> {
+    use crate::script::api::ArgShape;
+    let mut out = Vec::new();
+    for call in source_calls(src) {
+        let Some(receiver) = call.receiver.as_deref() else {
+            continue;
+        };
+        let Some(d) = crate::script::api::receiver_from_token(receiver)
+            .and_then(|recv| crate::script::api::lookup(recv, &call.method))
+        else {
+            continue;
+        };
+        let string_args: &[usize] = match d.args {
+            ArgShape::Str
+            | ArgShape::Flag
+            | ArgShape::Body(_)
+            | ArgShape::StrInt
+            | ArgShape::StrAny => &[0],
+            ArgShape::StrStr => &[0, 1],
+            ArgShape::StrOpt => &[1],
+            _ => &[],
+        };
+        for &idx in string_args {
+            if let Some(Arg::Other) = call.args.get(idx) {
+                let via = call
+                    .via
+                    .as_ref()
+                    .map(|helper| format!(", in helper '{helper}'"))
+                    .unwrap_or_default();
+                out.push(format!(
+                    "'{}.{}' arg {} is computed at runtime and can't be checked at load ({}{via})",
+                    receiver,
+                    call.method,
+                    idx + 1,
+                    call.position
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// A `scene.param` / `scene.get` / `scene.set` / `scene.add` call naming its
+/// param or variable with a string literal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneNameRef {
+    pub method: String,
+    pub name: String,
+    /// The type of a literal value argument (`scene.set("drinks", 3)`); `None`
+    /// when there is none or it isn't a string or integer literal.
+    pub value: Option<VarType>,
+    pub position: Position,
+}
+
+/// Every named `scene.*` param or variable access in a script, with its
+/// position, for the loader to check against the scene's declarations.
+pub fn source_scene_refs(src: &(impl ScriptSource + ?Sized)) -> Vec<SceneNameRef> {
+    scene_name_refs(source_calls(src))
+}
+
+/// Every named `scene.*` param or variable access in a prose template;
+/// positions are those of the enclosing `{{ … }}` / `{% … %}` region.
+pub fn prose_scene_refs(template: &str) -> Vec<SceneNameRef> {
+    let mut calls = Vec::new();
+    for (region, position) in expression_regions(template) {
+        let Ok(toks) = tokenize(&region) else {
+            continue; // validate_prose reports it
+        };
+        calls.extend(extract_calls(&toks).into_iter().map(|mut call| {
+            call.position = position;
+            call
+        }));
+    }
+    scene_name_refs(calls)
+}
+
+fn scene_name_refs(calls: Vec<Call>) -> Vec<SceneNameRef> {
+    calls
+        .into_iter()
+        .filter(|c| {
+            c.receiver.as_deref() == Some("scene")
+                && matches!(c.method.as_str(), "param" | "get" | "set" | "add")
+        })
+        .filter_map(|c| match c.args.first() {
+            Some(Arg::Str(name)) => Some(SceneNameRef {
+                name: name.clone(),
+                value: match c.args.get(1) {
+                    Some(Arg::Str(_)) => Some(VarType::String),
+                    Some(Arg::Int(_)) => Some(VarType::Int),
+                    _ => None,
+                },
+                method: c.method,
+                position: c.position,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Prose load gate (design §5.4) — validates the method surface of authored prose
 // templates at load. Single-quote-aware (the tokenizer accepts both quote styles).
@@ -739,13 +1468,11 @@ pub fn validate_prose(
     registry: &PackRegistry,
     context: &str,
 ) -> Result<(), ScriptError> {
-    for region in expression_regions(template) {
-        let toks = tokenize(&region).map_err(|message| ScriptError::Compile {
-            context: context.into(),
-            message,
-            source_text: region.clone(),
-        })?;
-        for call in extract_calls(&toks) {
+    for (region, position) in expression_regions(template) {
+        let toks = tokenize(&region)
+            .map_err(|message| compile_err(context, &region, position, message))?;
+        for mut call in extract_calls(&toks) {
+            call.position = position;
             let Some(recv_tok) = call.receiver.as_deref() else {
                 continue; // bare call: a Minijinja filter/function/test — out of scope (§5.4)
             };
@@ -756,6 +1483,7 @@ pub fn validate_prose(
                 return Err(compile_err(
                     context,
                     &region,
+                    position,
                     format!("unknown prose method '{}.{}'", recv_tok, call.method),
                 ));
             };
@@ -763,6 +1491,7 @@ pub fn validate_prose(
                 return Err(compile_err(
                     context,
                     &region,
+                    position,
                     format!(
                         "method '{}.{}' is not callable in prose",
                         recv_tok, call.method
@@ -787,6 +1516,12 @@ fn validate_prose_id_arg(
     use crate::script::api::ArgShape;
     let kind = match shape {
         ArgShape::Id(k) | ArgShape::IdInt(k) => k,
+        ArgShape::Flag => {
+            return match call.args.first() {
+                Some(Arg::Str(flag)) => check_flag(flag, call, registry, context, src),
+                _ => Ok(()),
+            }
+        }
         _ => return Ok(()),
     };
     if let Some(Arg::Str(id)) = call.args.first() {
@@ -798,8 +1533,9 @@ fn validate_prose_id_arg(
 /// Extract the contents of each `{{ … }}` / `{% … %}` region, skipping `{# … #}`
 /// comments and `{% raw %}…{% endraw %}` blocks and stripping whitespace-control
 /// markers (`{%-`, `-%}`, …). The live corpus uses none of the exotic forms, but
-/// the scan must not false-positive on them (design §5.4).
-fn expression_regions(template: &str) -> Vec<String> {
+/// the scan must not false-positive on them (design §5.4). Each region comes with
+/// the position of its opening delimiter in the template.
+fn expression_regions(template: &str) -> Vec<(String, Position)> {
     let b = template.as_bytes();
     let n = b.len();
     let mut regions = Vec::new();
@@ -813,6 +1549,7 @@ fn expression_regions(template: &str) -> Vec<String> {
                 b'%' => (b'%', b'}'),
                 _ => (b'#', b'}'),
             };
+            let position = template_position(template, i);
             let start = i + 2;
             let mut j = start;
             while j + 1 < n && !(b[j] == ca && b[j + 1] == cb) {
@@ -835,12 +1572,12 @@ fn expression_regions(template: &str) -> Vec<String> {
                     } else if head == "endraw" {
                         in_raw = false;
                     } else if !in_raw {
-                        regions.push(content);
+                        regions.push((content, position));
                     }
                 }
                 _ => {
                     if !in_raw {
-                        regions.push(content);
+                        regions.push((content, position));
                     }
                 }
             }
@@ -851,6 +1588,20 @@ fn expression_regions(template: &str) -> Vec<String> {
     regions
 }
 
+/// The 1-based line/column of byte `offset` in `template`, counting chars.
+fn template_position(template: &str, offset: usize) -> Position {
+    let before = &template[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |nl| nl + 1)..]
+        .chars()
+        .count()
+        + 1;
+    Position::new(
+        u16::try_from(line).unwrap_or(u16::MAX),
+        u16::try_from(column).unwrap_or(u16::MAX),
+    )
+}
+
 #[cfg(test)]
 mod prose_gate_tests {
     use super::*;
@@ -865,6 +1616,7 @@ mod prose_gate_tests {
                     description: String::new(),
                     min: 0,
                     max: 100,
+                    progression: None,
                 })
                 .collect(),
         );
@@ -931,6 +1683,64 @@ mod prose_gate_tests {
     }
 }
 
+#[cfg(test)]
+mod fact_tests {
+    use super::*;
+
+    #[test]
+    fn facts_see_through_helpers_and_folded_constants() {
+        let src = r#"
+            fn open(gd, arc) { gd.advanceArc(arc, "open") }
+            const PREFIX = "ROUTE_";
+            gd.setGameFlag(PREFIX + "WORK");
+            gd.setGameFlag(`${PREFIX}CAMPUS`);
+            open(gd, "base::a");
+        "#;
+        assert_eq!(
+            source_set_game_flags(src),
+            vec!["ROUTE_WORK".to_string(), "ROUTE_CAMPUS".to_string()]
+        );
+        // The helper's `arc` param is only known at the call site.
+        assert!(source_advance_arcs(src).is_empty());
+        assert_eq!(source_unverifiable_args(src), Vec::<String>::new());
+    }
+
+    #[test]
+    fn computed_string_args_are_unverifiable() {
+        let src = r#"let flag = "X"; if w.hasTrait("SHY") { gd.setGameFlag(flag) }"#;
+        assert!(source_set_game_flags(src).is_empty());
+        assert_eq!(
+            source_unverifiable_args(src),
+            vec![
+                "'gd.setGameFlag' arg 1 is computed at runtime and can't be checked at load \
+                 (line 1, position 43)"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn dead_branches_are_still_walked() {
+        let src = r#"false && gd.hasGameFlag("A") || !(gd.hasGameFlag("B"))"#;
+        assert_eq!(
+            source_game_flag_checks(src),
+            vec![("A".to_string(), false), ("B".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn eq_calls_match_either_operand_order() {
+        let src = r#"gd.npcLiking("ROLE_JAKE") == "Like" || "Ok" == gd.npcLiking("ROLE_JAKE")"#;
+        assert_eq!(
+            source_eq_calls(src, "npcLiking"),
+            vec![
+                ("ROLE_JAKE".to_string(), "Like".to_string()),
+                ("ROLE_JAKE".to_string(), "Ok".to_string())
+            ]
+        );
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Negative-surface tests — the per-receiver / per-context sets the registry
 // keys must NOT union (design §2, §9). These must STAY unknown methods.
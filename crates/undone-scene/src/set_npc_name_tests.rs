@@ -57,6 +57,9 @@ mod set_npc_name_acceptance_tests {
             core: NpcCore {
                 name: name.to_string(),
                 display_name: None, // starts with no override
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: Age::MidLateTwenties,
                 race: "white".into(),
                 eye_colour: "brown".into(),
@@ -117,7 +120,8 @@ effect = 'npc("m").setName("Jake");'
         let meet = raw.actions.iter().find(|a| a.id == "meet").unwrap();
         let effect = meet
             .effect
-            .as_deref()
+            .as_ref()
+            .map(|effect| effect.get_ref().as_str())
             .expect("action should have an effect string");
         assert!(
             effect.contains(r#"setName("Jake")"#),
@@ -149,7 +153,7 @@ effect = 'npc("ROLE_THEO").setName("Theo");'
 "#;
         let raw: SceneToml = toml::from_str(toml).unwrap();
         let meet = raw.actions.iter().find(|a| a.id == "meet").unwrap();
-        let effect = meet.effect.as_deref().unwrap();
+        let effect = meet.effect.as_ref().unwrap().get_ref();
         assert!(effect.contains(r#"npc("ROLE_THEO")"#));
         assert!(effect.contains(r#"setName("Theo")"#));
     }
@@ -796,6 +800,9 @@ effect = 'npc("ROLE_THEO").setName("Theo");'
             core: NpcCore {
                 name: "SomeSpawnName".into(),
                 display_name: None,
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: Age::MidLateTwenties,
                 race: "white".into(),
                 eye_colour: "Brown".into(),
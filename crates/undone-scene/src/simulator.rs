@@ -2,10 +2,11 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use rand::{rngs::SmallRng, SeedableRng};
-use undone_domain::TimeSlot;
+use undone_domain::{NpcKey, TimeSlot};
 use undone_packs::PackRegistry;
 use undone_world::World;
 
+use crate::coverage::Coverage;
 use crate::engine::{EngineCommand, EngineEvent, SceneEngine};
 use crate::scheduler::{PickResult, Scheduler};
 use crate::types::SceneDefinition;
@@ -21,6 +22,24 @@ pub struct SimulationResult {
     pub total_picks: u64,
     pub runs: u32,
     pub weeks: u32,
+    pub skill_trajectories: Vec<SkillTrajectory>,
+    /// What the scenes did across all runs; see `coverage::coverage_report`.
+    pub coverage: Coverage,
+}
+
+/// One run's base skill values at the start of each simulated week, ending with
+/// the values when the run stopped.
+#[derive(Default)]
+pub struct SkillTrajectory {
+    pub weeks: Vec<HashMap<String, i32>>,
+}
+
+pub struct SkillTrajectoryStats {
+    pub skill_id: String,
+    pub avg_start: f64,
+    pub avg_end: f64,
+    pub min_end: i32,
+    pub max_end: i32,
 }
 
 pub struct SceneStats {
@@ -95,6 +114,50 @@ impl SimulationResult {
         stats.sort_by(|left, right| right.count.cmp(&left.count));
         stats
     }
+
+    /// Start and end values across runs for every skill that moved in at least
+    /// one run, biggest average change first.
+    pub fn skill_stats(&self) -> Vec<SkillTrajectoryStats> {
+        let endpoints = |skill: &str| -> Vec<(i32, i32)> {
+            self.skill_trajectories
+                .iter()
+                .filter_map(|run| {
+                    let value = |week: &HashMap<String, i32>| week.get(skill).copied();
+                    Some((value(run.weeks.first()?)?, value(run.weeks.last()?)?))
+                })
+                .collect()
+        };
+        let skill_ids: HashSet<&String> = self
+            .skill_trajectories
+            .iter()
+            .flat_map(|run| run.weeks.iter().flat_map(|week| week.keys()))
+            .collect();
+
+        let mut stats: Vec<SkillTrajectoryStats> = skill_ids
+            .into_iter()
+            .filter_map(|skill_id| {
+                let runs = endpoints(skill_id);
+                if runs.iter().all(|(start, end)| start == end) {
+                    return None;
+                }
+                let count = runs.len() as f64;
+                Some(SkillTrajectoryStats {
+                    skill_id: skill_id.clone(),
+                    avg_start: runs.iter().map(|(start, _)| *start as f64).sum::<f64>() / count,
+                    avg_end: runs.iter().map(|(_, end)| *end as f64).sum::<f64>() / count,
+                    min_end: runs.iter().map(|(_, end)| *end).min()?,
+                    max_end: runs.iter().map(|(_, end)| *end).max()?,
+                })
+            })
+            .collect();
+        stats.sort_by(|left, right| {
+            let change = |stat: &SkillTrajectoryStats| (stat.avg_end - stat.avg_start).abs();
+            change(right)
+                .total_cmp(&change(left))
+                .then_with(|| left.skill_id.cmp(&right.skill_id))
+        });
+        stats
+    }
 }
 
 pub fn simulate(
@@ -111,12 +174,17 @@ pub fn simulate(
         .map(|scene_id| (scene_id, 0))
         .collect();
     let mut total_picks = 0u64;
+    let mut skill_trajectories = Vec::new();
+    let mut coverage = Coverage::default();
     let target_week = base_world.game_data.week + config.weeks;
 
     for _ in 0..config.runs {
         let mut world = base_world.clone();
         let mut engine = SceneEngine::new(scenes.clone());
+        engine.enable_coverage();
         let mut tried_actions: HashSet<(String, String)> = HashSet::new();
+        let mut trajectory = SkillTrajectory::default();
+        let mut recorded_week = None;
 
         let Some((mut pending_events, mut current_scene_time_anchor)) = start_global_scene(
             scheduler,
@@ -127,12 +195,17 @@ pub fn simulate(
             &mut scene_counts,
             &mut total_picks,
         ) else {
+            coverage.merge(engine.take_coverage().unwrap_or_default());
             continue;
         };
         for _ in 0..MAX_RUNTIME_STEPS_PER_RUN {
             if world.game_data.week >= target_week {
                 break;
             }
+            if recorded_week != Some(world.game_data.week) {
+                recorded_week = Some(world.game_data.week);
+                trajectory.weeks.push(skill_snapshot(&world, registry));
+            }
 
             if let Some(slot_name) = requested_slot(&pending_events) {
                 tried_actions.clear();
@@ -151,7 +224,7 @@ pub fn simulate(
                     continue;
                 }
 
-                consume_scene_time(&mut world, &mut current_scene_time_anchor);
+                consume_scene_time(&mut world, registry, &mut current_scene_time_anchor);
                 let Some((events, scene_time_anchor)) = start_global_scene(
                     scheduler,
                     registry,
@@ -170,7 +243,7 @@ pub fn simulate(
 
             if scene_finished(&pending_events) {
                 tried_actions.clear();
-                consume_scene_time(&mut world, &mut current_scene_time_anchor);
+                consume_scene_time(&mut world, registry, &mut current_scene_time_anchor);
                 if world.game_data.week >= target_week {
                     break;
                 }
@@ -209,6 +282,9 @@ pub fn simulate(
                 .clone();
             pending_events = engine.advance_with_action(&action_id, &mut world, registry);
         }
+        trajectory.weeks.push(skill_snapshot(&world, registry));
+        skill_trajectories.push(trajectory);
+        coverage.merge(engine.take_coverage().unwrap_or_default());
     }
 
     SimulationResult {
@@ -216,9 +292,23 @@ pub fn simulate(
         total_picks,
         runs: config.runs,
         weeks: config.weeks,
+        skill_trajectories,
+        coverage,
     }
 }
 
+/// Base (unbuffed) value of every registered skill.
+fn skill_snapshot(world: &World, registry: &PackRegistry) -> HashMap<String, i32> {
+    registry
+        .skill_defs()
+        .filter_map(|def| {
+            let id = registry.resolve_skill(&def.id).ok()?;
+            let value = world.player.skills.get(&id).map_or(0, |skill| skill.value);
+            Some((def.id.clone(), value))
+        })
+        .collect()
+}
+
 fn start_global_scene(
     scheduler: &Scheduler,
     registry: &PackRegistry,
@@ -271,12 +361,18 @@ fn start_scheduler_scene(
 ) -> (Vec<EngineEvent>, Option<SceneTimeAnchor>) {
     *scene_counts.entry(pick.scene_id.clone()).or_insert(0) += 1;
     *total_picks += 1;
-    if pick.once_only {
-        world.game_data.set_flag(format!("ONCE_{}", pick.scene_id));
-    }
+    pick.mark_started(world);
 
     let scene_time_anchor = pick.consumes_time.then(|| SceneTimeAnchor::capture(world));
-    engine.send(EngineCommand::StartScene(pick.scene_id), world, registry);
+    match pick.npc {
+        Some(NpcKey::Male(key)) => {
+            engine.start_scene_with_bindings(pick.scene_id, Some(key), None, world, registry)
+        }
+        Some(NpcKey::Female(key)) => {
+            engine.start_scene_with_bindings(pick.scene_id, None, Some(key), world, registry)
+        }
+        None => engine.send(EngineCommand::StartScene(pick.scene_id), world, registry),
+    }
     (engine.drain(), scene_time_anchor)
 }
 
@@ -306,12 +402,16 @@ fn visible_actions(events: &[EngineEvent]) -> Option<Vec<crate::engine::ActionVi
     })
 }
 
-fn consume_scene_time(world: &mut World, current_scene_time_anchor: &mut Option<SceneTimeAnchor>) {
+fn consume_scene_time(
+    world: &mut World,
+    registry: &PackRegistry,
+    current_scene_time_anchor: &mut Option<SceneTimeAnchor>,
+) {
     let should_advance =
         current_scene_time_anchor.is_some_and(|anchor| anchor.matches_world(world));
     *current_scene_time_anchor = None;
     if should_advance {
-        world.game_data.advance_time_slot();
+        undone_packs::advance_time_slot(world, registry);
     }
 }
 
@@ -344,6 +444,8 @@ mod tests {
             id: id.into(),
             pack: "test".into(),
             intro_prose: "Test scene.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![],
@@ -362,6 +464,9 @@ mod tests {
             core: NpcCore {
                 name: "Marcus".into(),
                 display_name: None,
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: Age::MidLateTwenties,
                 race: "white".into(),
                 eye_colour: "blue".into(),
@@ -406,6 +511,7 @@ mod tests {
                     trigger: None,
                     npc_role: None,
                     desire_scaled: false,
+                    outreach: false,
                 },
                 crate::scheduler::ScheduleEvent {
                     scene: "test::b".into(),
@@ -415,6 +521,7 @@ mod tests {
                     trigger: None,
                     npc_role: None,
                     desire_scaled: false,
+                    outreach: false,
                 },
             ],
         )]));
@@ -452,6 +559,7 @@ mod tests {
                     trigger: None,
                     npc_role: None,
                     desire_scaled: false,
+                    outreach: false,
                 },
                 crate::scheduler::ScheduleEvent {
                     scene: "test::never".into(),
@@ -464,6 +572,7 @@ mod tests {
                     trigger: None,
                     npc_role: None,
                     desire_scaled: false,
+                    outreach: false,
                 },
             ],
         )]));
@@ -489,6 +598,110 @@ mod tests {
                 && stat.warning.as_deref() == Some("NEVER FIRES")));
     }
 
+    #[test]
+    fn skill_stats_report_only_skills_that_moved() {
+        let run = |dance: &[i32], charm: i32| SkillTrajectory {
+            weeks: dance
+                .iter()
+                .map(|&value| {
+                    HashMap::from([("DANCE".to_string(), value), ("CHARM".to_string(), charm)])
+                })
+                .collect(),
+        };
+        let result = SimulationResult {
+            scene_counts: HashMap::new(),
+            total_picks: 0,
+            runs: 2,
+            weeks: 2,
+            skill_trajectories: vec![run(&[10, 14, 20], 30), run(&[10, 12, 12], 30)],
+            coverage: Coverage::default(),
+        };
+
+        let stats = result.skill_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].skill_id, "DANCE");
+        assert_eq!(stats[0].avg_start, 10.0);
+        assert_eq!(stats[0].avg_end, 16.0);
+        assert_eq!((stats[0].min_end, stats[0].max_end), (12, 20));
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn simulation_reports_dead_prose_branches_and_unchosen_actions() {
+        let action = |id: &str, condition: &str| crate::types::Action {
+            id: id.into(),
+            label: id.into(),
+            detail: String::new(),
+            condition: Some(
+                crate::script::compile_condition(condition, &PackRegistry::new(), "test").unwrap(),
+            ),
+            prose: String::new(),
+            allow_npc_actions: false,
+            effect: None,
+            next: vec![crate::types::NextBranch {
+                condition: None,
+                goto: None,
+                slot: None,
+                finish: true,
+                call: None,
+                returns: None,
+            }],
+            thoughts: vec![],
+            check: None,
+        };
+        let scene = Arc::new(SceneDefinition {
+            id: "test::branchy".into(),
+            pack: "test".into(),
+            intro_prose: "{% if false %}Never.{% else %}Always.{% endif %}".into(),
+            params: Default::default(),
+            vars: Default::default(),
+            intro_variants: vec![],
+            intro_thoughts: vec![],
+            actions: vec![action("leave", "true"), action("fly", "false")],
+            npc_actions: vec![],
+        });
+        let scheduler = Scheduler::from_slots_for_tests(HashMap::from([(
+            "free_time".to_string(),
+            vec![crate::scheduler::ScheduleEvent {
+                scene: "test::branchy".into(),
+                condition: None,
+                weight: 10,
+                once_only: false,
+                trigger: None,
+                npc_role: None,
+                desire_scaled: false,
+                outreach: false,
+            }],
+        )]));
+        let scenes = HashMap::from([("test::branchy".to_string(), scene)]);
+
+        let result = simulate(
+            &scheduler,
+            &scenes,
+            &PackRegistry::new(),
+            &make_world(),
+            SimulationConfig {
+                weeks: 1,
+                runs: 2,
+                seed: 7,
+            },
+        );
+
+        let report = crate::coverage::coverage_report(&result.coverage, &scenes);
+        let scene = &report[0];
+        assert!(scene.starts > 0);
+        let dead: Vec<&str> = scene.dead().map(|point| point.point.as_str()).collect();
+        assert_eq!(
+            dead,
+            [
+                "intro prose `{% if false %}` (line 1)",
+                "action 'fly' offered",
+                "action 'fly' chosen",
+            ]
+        );
+        assert_eq!(scene.covered, 3);
+    }
+
     #[test]
     fn simulation_can_reach_follow_up_scenes_that_depend_on_runtime_progression() {
         let (registry, metas) = load_packs(&packs_dir()).unwrap();
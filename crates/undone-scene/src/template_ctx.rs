@@ -6,10 +6,13 @@
 //! snapshot-era logic that remains is NPC *presence*: `m`/`f` bind to their view when an
 //! NPC of that sex is active, else `Value::UNDEFINED` so `{% if m %}` stays falsy.
 
+use std::sync::{Arc, Mutex};
+
 use minijinja::value::Value;
 use undone_packs::PackRegistry;
 use undone_world::World;
 
+use crate::coverage::{instrument_prose, BRANCH_MARKER_FN};
 use crate::scene_ctx::SceneCtx;
 use crate::script::api::minijinja_bind::{FView, GdView, MView, RoleView, SceneView, WView};
 use crate::script::context::ReadCtxGuard;
@@ -25,6 +28,44 @@ pub fn render_prose(
     world: &World,
     ctx: &SceneCtx,
     registry: &PackRegistry,
+) -> Result<String, minijinja::Error> {
+    render_in(
+        minijinja::Environment::new(),
+        template_str,
+        world,
+        ctx,
+        registry,
+    )
+}
+
+/// Render like [`render_prose`], also returning which `{% if %}` branches
+/// rendered, as indices into [`prose_branches`](crate::coverage::prose_branches).
+pub fn render_prose_traced(
+    template_str: &str,
+    world: &World,
+    ctx: &SceneCtx,
+    registry: &PackRegistry,
+) -> Result<(String, Vec<usize>), minijinja::Error> {
+    let hits = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&hits);
+    let mut env = minijinja::Environment::new();
+    env.add_function(BRANCH_MARKER_FN, move |index: usize| {
+        sink.lock().expect("branch trace lock").push(index);
+        String::new()
+    });
+    let rendered = render_in(env, &instrument_prose(template_str), world, ctx, registry)?;
+    let mut hits = std::mem::take(&mut *hits.lock().expect("branch trace lock"));
+    hits.sort_unstable();
+    hits.dedup();
+    Ok((rendered, hits))
+}
+
+fn render_in<'source>(
+    mut env: minijinja::Environment<'source>,
+    template_str: &'source str,
+    world: &World,
+    ctx: &SceneCtx,
+    registry: &PackRegistry,
 ) -> Result<String, minijinja::Error> {
     // NPC presence — computed from the owned `ctx` borrow BEFORE installing the guard,
     // so the guard's "ctx borrowed for the whole call" invariant is unaffected (§6.1).
@@ -46,7 +87,6 @@ pub fn render_prose(
     // live-context reading — is the one way to invoke a view after the guard drops.
     let _guard = ReadCtxGuard::install(world, registry, ctx);
 
-    let mut env = minijinja::Environment::new();
     env.add_template("prose", template_str)?;
     let tmpl = env.get_template("prose")?;
     tmpl.render(minijinja::context! {
@@ -85,6 +125,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         let shy_id = registry.resolve_trait("SHY").unwrap();
         let mut world = make_world();
@@ -109,6 +150,7 @@ mod tests {
             description: "".into(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         let skill_id = registry.resolve_skill("CHARM").unwrap();
         let mut world = make_world();
@@ -216,6 +258,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         let trait_id = registry.resolve_trait("NATURALLY_SMOOTH").unwrap();
         let mut world = make_world();
@@ -236,6 +279,7 @@ mod tests {
             description: "".into(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         let fem_id = registry.resolve_skill("FEMININITY").unwrap();
         let mut world = make_world();
@@ -270,6 +314,9 @@ mod tests {
             core: undone_domain::NpcCore {
                 name: "Dan".into(),
                 display_name: None,
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: undone_domain::Age::MidLateTwenties,
                 race: "white".into(),
                 eye_colour: "blue".into(),
@@ -303,6 +350,9 @@ mod tests {
             core: undone_domain::NpcCore {
                 name: "Mia".into(),
                 display_name: None,
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: undone_domain::Age::MidLateTwenties,
                 race: "white".into(),
                 eye_colour: "green".into(),
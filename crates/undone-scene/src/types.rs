@@ -1,4 +1,16 @@
-use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use toml::Spanned;
+use undone_domain::{VarType, VarValue};
+
+/// A scene-file string together with its byte range in the file, so load errors
+/// can point at it.
+pub type SpannedText = Spanned<String>;
+
+fn empty_text() -> SpannedText {
+    Spanned::new(0..0, String::new())
+}
 
 /// Raw TOML deserialization target for a scene file.
 #[derive(Debug, Deserialize)]
@@ -20,15 +32,15 @@ pub struct SceneToml {
 /// A narrator variant: replaces the base intro when its condition passes.
 #[derive(Debug, Deserialize, Clone)]
 pub struct NarratorVariantDef {
-    pub condition: String,
-    pub prose: String,
+    pub condition: SpannedText,
+    pub prose: SpannedText,
 }
 
 /// A thought block: fires automatically (optionally conditioned) with a style tag.
 #[derive(Debug, Deserialize, Clone)]
 pub struct ThoughtDef {
-    pub condition: Option<String>,
-    pub prose: String,
+    pub condition: Option<SpannedText>,
+    pub prose: SpannedText,
     /// Visual style tag for the UI. "inner_voice" = italicised inner monologue.
     #[serde(default = "default_thought_style")]
     pub style: String,
@@ -43,11 +55,27 @@ pub struct SceneMeta {
     pub id: String,
     pub pack: String,
     pub description: String,
+    /// Parameters a caller must pass when it `call`s this scene, by name.
+    #[serde(default)]
+    pub params: BTreeMap<String, VarType>,
+    /// Typed variables the scene's scripts and prose read and write with
+    /// `scene.get` / `scene.set` / `scene.add`, by name.
+    #[serde(default)]
+    pub vars: BTreeMap<String, SceneVarDef>,
+}
+
+/// A `[scene.vars]` entry: a literal is a scene-local variable starting at
+/// that value; `{ persist = "ID" }` binds the name to a pack game variable.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum SceneVarDef {
+    Local(VarValue),
+    Persistent { persist: String },
 }
 
 #[derive(Debug, Deserialize)]
 pub struct IntroDef {
-    pub prose: String,
+    pub prose: SpannedText,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,32 +84,73 @@ pub struct ActionDef {
     pub label: String,
     #[serde(default)]
     pub detail: String,
-    pub condition: Option<String>,
-    #[serde(default)]
-    pub prose: String,
+    pub condition: Option<SpannedText>,
+    #[serde(default = "empty_text")]
+    pub prose: SpannedText,
     #[serde(default)]
     pub allow_npc_actions: bool,
     /// Rhai effect call-list, e.g. `effect = 'w.addArousal(1); gd.setGameFlag("X");'`.
     #[serde(default)]
-    pub effect: Option<String>,
+    pub effect: Option<SpannedText>,
     #[serde(default)]
     pub next: Vec<NextBranchDef>,
     /// Thoughts fired after the action prose is displayed.
     #[serde(default)]
     pub thoughts: Vec<ThoughtDef>,
+    /// `"check"` makes this a skill check: `skill`, `base_dc` and both
+    /// `[actions.pass]` / `[actions.fail]` outcomes are then required.
+    #[serde(default)]
+    pub kind: ActionKind,
+    pub skill: Option<String>,
+    pub base_dc: Option<i32>,
+    #[serde(default)]
+    pub check_type: CheckType,
+    pub pass: Option<CheckOutcomeDef>,
+    pub fail: Option<CheckOutcomeDef>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    #[default]
+    Choice,
+    Check,
+}
+
+/// White checks can be retried on a later visit; a failed red check is spent
+/// for good.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckType {
+    #[default]
+    White,
+    Red,
+}
+
+/// One side of a check action. Played after the action's own prose and effect.
+#[derive(Debug, Deserialize)]
+pub struct CheckOutcomeDef {
+    #[serde(default = "empty_text")]
+    pub prose: SpannedText,
+    #[serde(default)]
+    pub effect: Option<SpannedText>,
+    #[serde(default)]
+    pub next: Vec<NextBranchDef>,
+    #[serde(default)]
+    pub thoughts: Vec<ThoughtDef>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct NpcActionDef {
     pub id: String,
-    pub condition: Option<String>,
-    #[serde(default)]
-    pub prose: String,
+    pub condition: Option<SpannedText>,
+    #[serde(default = "empty_text")]
+    pub prose: SpannedText,
     #[serde(default = "default_weight")]
     pub weight: u32,
     /// Rhai effect call-list (see [`ActionDef::effect`]).
     #[serde(default)]
-    pub effect: Option<String>,
+    pub effect: Option<SpannedText>,
     #[serde(default)]
     pub next: Vec<NextBranchDef>,
 }
@@ -90,20 +159,32 @@ fn default_weight() -> u32 {
     1
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct NextBranchDef {
     #[serde(rename = "if")]
-    pub condition: Option<String>,
+    pub condition: Option<SpannedText>,
     pub goto: Option<String>,
     pub slot: Option<String>,
     #[serde(default)]
     pub finish: bool,
+    /// Run another scene as a sub-scene, resuming this one when it returns.
+    pub call: Option<String>,
+    /// Arguments for `call`, checked against the callee's declared params.
+    #[serde(default)]
+    pub params: toml::Table,
+    /// Branches evaluated in this scene once the called scene returns.
+    #[serde(default)]
+    pub then: Vec<NextBranchDef>,
+    /// End a called scene, handing this value back to the caller.
+    #[serde(rename = "return")]
+    pub returns: Option<toml::Value>,
 }
 
 // ---------------------------------------------------------------------------
 // Resolved runtime types (after TOML structs)
 // ---------------------------------------------------------------------------
 
+use crate::script::api::ApiValue;
 use crate::script::CompiledScript;
 
 /// Resolved thought — condition compiled, ready for runtime evaluation.
@@ -135,6 +216,71 @@ pub struct Action {
     pub next: Vec<NextBranch>,
     /// Thoughts displayed after the action prose.
     pub thoughts: Vec<Thought>,
+    /// Set for `kind = "check"` actions; `next` is then empty and each outcome
+    /// carries its own.
+    pub check: Option<Check>,
+}
+
+impl Action {
+    /// Every effect the action can apply — its own and those of both check
+    /// outcomes.
+    pub fn effects(&self) -> impl Iterator<Item = &CompiledScript> {
+        self.effect.iter().chain(
+            self.check
+                .iter()
+                .flat_map(|c| c.outcomes().filter_map(|o| o.effect.as_ref())),
+        )
+    }
+
+    /// Every next branch the action can take, including both check outcomes'
+    /// and the continuations of any sub-scene calls.
+    pub fn next_branches(&self) -> impl Iterator<Item = &NextBranch> {
+        self.next
+            .iter()
+            .chain(
+                self.check
+                    .iter()
+                    .flat_map(|c| c.outcomes().flat_map(|o| o.next.iter())),
+            )
+            .flat_map(NextBranch::flatten)
+    }
+
+    /// Every thought the action can fire, including both check outcomes'.
+    pub fn all_thoughts(&self) -> impl Iterator<Item = &Thought> {
+        self.thoughts.iter().chain(
+            self.check
+                .iter()
+                .flat_map(|c| c.outcomes().flat_map(|o| o.thoughts.iter())),
+        )
+    }
+}
+
+/// A resolved check action. The roll uses the scene's cached roll for `skill`,
+/// against `base_dc` adjusted by `check::effective_dc`.
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// Stable id — `<scene id>::<action id>` — that red-check failures are
+    /// recorded under.
+    pub id: String,
+    pub skill: String,
+    pub base_dc: i32,
+    pub check_type: CheckType,
+    pub pass: CheckOutcome,
+    pub fail: CheckOutcome,
+}
+
+impl Check {
+    pub fn outcomes(&self) -> impl Iterator<Item = &CheckOutcome> {
+        [&self.pass, &self.fail].into_iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckOutcome {
+    pub prose: String,
+    pub effect: Option<CompiledScript>,
+    pub next: Vec<NextBranch>,
+    pub thoughts: Vec<Thought>,
 }
 
 #[derive(Debug, Clone)]
@@ -143,6 +289,37 @@ pub struct NextBranch {
     pub goto: Option<String>,
     pub slot: Option<String>,
     pub finish: bool,
+    pub call: Option<SceneCall>,
+    /// Set for a `return` branch: the value handed back to the calling scene.
+    pub returns: Option<ApiValue>,
+}
+
+impl NextBranch {
+    /// This branch followed by every branch of its call's continuation, depth
+    /// first.
+    pub fn flatten(&self) -> Vec<&NextBranch> {
+        let mut out = vec![self];
+        if let Some(call) = &self.call {
+            out.extend(call.then.iter().flat_map(NextBranch::flatten));
+        }
+        out
+    }
+
+    /// The scene this branch starts directly: its `goto` target or called scene.
+    pub fn target_scene(&self) -> Option<&str> {
+        self.goto
+            .as_deref()
+            .or(self.call.as_ref().map(|call| call.scene.as_str()))
+    }
+}
+
+/// A resolved sub-scene call. The caller's frame stays on the stack; when the
+/// callee returns, `then` is evaluated in the caller.
+#[derive(Debug, Clone)]
+pub struct SceneCall {
+    pub scene: String,
+    pub params: BTreeMap<String, ApiValue>,
+    pub then: Vec<NextBranch>,
 }
 
 #[derive(Debug, Clone)]
@@ -156,12 +333,32 @@ pub struct NpcAction {
     pub next: Vec<NextBranch>,
 }
 
+/// A resolved scene variable. A local one holds its value in `value`, reset
+/// to the declared start on every run; a persistent one lives in
+/// `GameData::vars` under `persist`, and `value` is the pack default it reads
+/// as until a scene first sets it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneVar {
+    pub value: VarValue,
+    pub persist: Option<String>,
+}
+
+impl SceneVar {
+    pub fn var_type(&self) -> VarType {
+        self.value.var_type()
+    }
+}
+
 /// Immutable scene definition. Wrap in Arc for cheap cloning.
 #[derive(Debug)]
 pub struct SceneDefinition {
     pub id: String,
     pub pack: String,
     pub intro_prose: String,
+    /// Parameters the scene takes when called as a sub-scene.
+    pub params: BTreeMap<String, VarType>,
+    /// The scene's variables; see `SceneVar`.
+    pub vars: BTreeMap<String, SceneVar>,
     /// Narrator variants evaluated at scene start; first match replaces intro_prose.
     pub intro_variants: Vec<NarratorVariant>,
     /// Thoughts fired after intro prose (before actions are shown).
@@ -174,16 +371,20 @@ impl SceneDefinition {
     /// Returns true when any player or NPC action in the scene can mutate
     /// persistent world state. Scans the compiled effect call-lists' source for
     /// any non-scene-local mutator (reconstructs the legacy `EffectDef` walk).
+    /// A scene with a persistent variable counts: its `scene.set`/`scene.add`
+    /// calls write `GameData`.
     pub fn has_persistent_world_mutation(&self) -> bool {
-        self.actions
-            .iter()
-            .filter_map(|action| action.effect.as_ref())
-            .chain(
-                self.npc_actions
-                    .iter()
-                    .filter_map(|action| action.effect.as_ref()),
-            )
-            .any(|script| crate::script::source_has_persistent_mutation(&script.source))
+        self.vars.values().any(|var| var.persist.is_some())
+            || self
+                .actions
+                .iter()
+                .flat_map(Action::effects)
+                .chain(
+                    self.npc_actions
+                        .iter()
+                        .filter_map(|action| action.effect.as_ref()),
+                )
+                .any(crate::script::source_has_persistent_mutation)
     }
 }
 
@@ -231,7 +432,10 @@ effect = "w.changeStress(-1);"
     fn parses_action_effect() {
         let raw: SceneToml = toml::from_str(MINIMAL_SCENE).unwrap();
         let leave = raw.actions.iter().find(|a| a.id == "leave").unwrap();
-        assert_eq!(leave.effect.as_deref(), Some("w.changeStress(-1);"));
+        assert_eq!(
+            leave.effect.as_ref().map(|e| e.get_ref().as_str()),
+            Some("w.changeStress(-1);")
+        );
     }
 
     #[test]
@@ -260,6 +464,8 @@ effect = "w.changeStress(-1);"
             id: "test::scene".into(),
             pack: "test".into(),
             intro_prose: "Intro.".into(),
+            params: BTreeMap::new(),
+            vars: BTreeMap::new(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -273,6 +479,7 @@ effect = "w.changeStress(-1);"
                 effect: Some(effect(r#"scene.setFlag("local");"#)),
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![NpcAction {
                 id: "answer".into(),
@@ -294,6 +501,8 @@ effect = "w.changeStress(-1);"
             id: "test::scene".into(),
             pack: "test".into(),
             intro_prose: "Intro.".into(),
+            params: BTreeMap::new(),
+            vars: BTreeMap::new(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -306,6 +515,7 @@ effect = "w.changeStress(-1);"
                 effect: Some(effect(r#"scene.setFlag("local");"#)),
                 next: vec![],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         };
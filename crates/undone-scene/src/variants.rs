@@ -0,0 +1,837 @@
+//! Exhaustive prose rendering for review.
+//!
+//! A template's `{% if %}` branches only render when the world happens to
+//! match, so most variants never reach a reader. [`review_scene`] reads the
+//! calls each template's branch conditions make, builds worlds covering every
+//! combination of their values (up to [`VariantLimits::max_worlds`] per
+//! template), renders the template in each, and keeps every distinct output
+//! with the branches it took and the values that produced it.
+//! [`review_document`] writes the result as Markdown.
+//!
+//! Only reads backed by a settable world field are varied: `w.hasTrait`,
+//! `w.getSkill`, `w.composure`, `w.wasTransformed`, the appearance,
+//! sensitivity, wetness and before-body getters, `gd.hasGameFlag`,
+//! `gd.desire`, `gd.timeSlot`, `gd.isWeekday` and `scene.hasFlag`. Any other
+//! call is held at the base world's value and listed as held.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Write as _};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use undone_domain::{
+    Appearance, ClitSensitivity, Height, MaleFigure, NippleSensitivity, PcOrigin, SkillId,
+    SkillValue, TimeSlot, TraitId, WetnessBaseline,
+};
+use undone_packs::PackRegistry;
+use undone_world::World;
+
+use crate::coverage::{prose_branches, prose_sites, ProseBranch, ProseSite};
+use crate::scene_ctx::SceneCtx;
+use crate::template_ctx::render_prose_traced;
+use crate::types::SceneDefinition;
+
+/// Bounds on a review.
+#[derive(Debug, Clone, Copy)]
+pub struct VariantLimits {
+    /// Most worlds rendered per template. Past it, the later knobs stay at
+    /// their first value.
+    pub max_worlds: usize,
+}
+
+impl Default for VariantLimits {
+    fn default() -> Self {
+        Self { max_worlds: 256 }
+    }
+}
+
+/// A value a condition read can take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Setting {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+}
+
+impl fmt::Display for Setting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Setting::Bool(value) => write!(f, "{value}"),
+            Setting::Int(value) => write!(f, "{value}"),
+            Setting::Str(value) => write!(f, "\"{value}\""),
+        }
+    }
+}
+
+/// A read a template branches on, and the values the review tries for it.
+/// The first value is the base world's.
+#[derive(Debug, Clone)]
+pub struct Knob {
+    /// The call as the condition writes it, e.g. `w.hasTrait("SHY")`.
+    pub call: String,
+    pub values: Vec<Setting>,
+    lever: Lever,
+}
+
+/// The world field behind a knob.
+#[derive(Debug, Clone)]
+enum Lever {
+    Trait(TraitId),
+    GameFlag(String),
+    SceneFlag(String),
+    Skill(SkillId),
+    Desire,
+    Transformed,
+    Weekday,
+    TimeSlot,
+    Appearance,
+    NippleSensitivity,
+    ClitSensitivity,
+    Wetness,
+    BeforeHeight,
+    BeforeFigure,
+}
+
+/// One distinct rendering of a template.
+#[derive(Debug, Clone)]
+pub struct RenderedVariant {
+    pub text: String,
+    /// The branches that rendered, as indices into [`SiteReview::branches`].
+    pub branches: Vec<usize>,
+    /// The knob values of the first world that rendered this text.
+    pub settings: Vec<Setting>,
+    /// How many of the rendered worlds produced this text.
+    pub worlds: usize,
+}
+
+/// A template that failed to render in some worlds.
+#[derive(Debug, Clone)]
+pub struct RenderFailure {
+    pub message: String,
+    /// The knob values of the first world that failed this way.
+    pub settings: Vec<Setting>,
+    pub worlds: usize,
+}
+
+/// Every distinct rendering of one prose template.
+#[derive(Debug, Clone)]
+pub struct SiteReview {
+    pub site: ProseSite,
+    pub branches: Vec<ProseBranch>,
+    pub knobs: Vec<Knob>,
+    /// Calls the branch conditions make that the review cannot vary.
+    pub held: Vec<String>,
+    pub worlds: usize,
+    /// More combinations than [`VariantLimits::max_worlds`] allowed.
+    pub truncated: bool,
+    pub variants: Vec<RenderedVariant>,
+    pub failures: Vec<RenderFailure>,
+}
+
+impl SiteReview {
+    /// Branches no rendered world took.
+    pub fn unreached(&self) -> impl Iterator<Item = &ProseBranch> {
+        let reached: BTreeSet<usize> = self
+            .variants
+            .iter()
+            .flat_map(|variant| variant.branches.iter().copied())
+            .collect();
+        self.branches
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| !reached.contains(index))
+            .map(|(_, branch)| branch)
+    }
+}
+
+/// Review every prose template in `def`, varying `world` and `ctx`. `ctx`
+/// carries the scene's NPC bindings; the scene id and vars are set here the
+/// way the engine sets them when the scene starts.
+pub fn review_scene(
+    def: &SceneDefinition,
+    world: &World,
+    ctx: &SceneCtx,
+    registry: &PackRegistry,
+    limits: VariantLimits,
+) -> Vec<SiteReview> {
+    let mut ctx = ctx.clone();
+    ctx.scene_id = Some(def.id.clone());
+    ctx.vars = def.vars.clone().into_iter().collect();
+    prose_sites(def)
+        .into_iter()
+        .filter(|(_, template)| !template.trim().is_empty())
+        .map(|(site, template)| review_template(site, template, world, &ctx, registry, limits))
+        .collect()
+}
+
+/// Review one template: render it in every combination of its knobs.
+pub fn review_template(
+    site: ProseSite,
+    template: &str,
+    world: &World,
+    ctx: &SceneCtx,
+    registry: &PackRegistry,
+    limits: VariantLimits,
+) -> SiteReview {
+    let branches = prose_branches(template);
+    let (knobs, held) = condition_knobs(&branches, world, ctx, registry);
+    let combinations = knobs
+        .iter()
+        .try_fold(1usize, |total, knob| total.checked_mul(knob.values.len()))
+        .unwrap_or(usize::MAX);
+    let worlds = combinations.min(limits.max_worlds.max(1));
+
+    let mut variants: Vec<RenderedVariant> = Vec::new();
+    let mut failures: Vec<RenderFailure> = Vec::new();
+    for index in 0..worlds {
+        let settings = combination(&knobs, index);
+        let mut world = world.clone();
+        let mut ctx = ctx.clone();
+        for (knob, setting) in knobs.iter().zip(&settings) {
+            knob.lever
+                .write(&mut world, &mut ctx, setting)
+                .expect("knob values are checked when the knob is built");
+        }
+        match render_prose_traced(template, &world, &ctx, registry) {
+            Ok((text, hits)) => match variants.iter_mut().find(|v| v.text == text) {
+                Some(variant) => variant.worlds += 1,
+                None => variants.push(RenderedVariant {
+                    text,
+                    branches: hits,
+                    settings,
+                    worlds: 1,
+                }),
+            },
+            Err(error) => {
+                let message = error.to_string();
+                match failures.iter_mut().find(|f| f.message == message) {
+                    Some(failure) => failure.worlds += 1,
+                    None => failures.push(RenderFailure {
+                        message,
+                        settings,
+                        worlds: 1,
+                    }),
+                }
+            }
+        }
+    }
+
+    SiteReview {
+        site,
+        branches,
+        knobs,
+        held,
+        worlds,
+        truncated: combinations > worlds,
+        variants,
+        failures,
+    }
+}
+
+/// The knob values of world `index`, the first knob varying fastest.
+fn combination(knobs: &[Knob], index: usize) -> Vec<Setting> {
+    let mut rest = index;
+    knobs
+        .iter()
+        .map(|knob| {
+            let value = knob.values[rest % knob.values.len()].clone();
+            rest /= knob.values.len();
+            value
+        })
+        .collect()
+}
+
+/// A `w.`/`gd.`/`scene.` call in a condition, with an optional comparison
+/// or `in [...]` list after it.
+fn call_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"\b(w|gd|scene|m|f|role)\.([A-Za-z]+)\(([^()]*)\)(?:\s*(==|!=|<=|>=|<|>)\s*(-?\d+|"[^"]*"|'[^']*')|\s+(?:not\s+)?in\s*\[([^\]]*)\])?"#,
+        )
+        .expect("call pattern compiles")
+    })
+}
+
+fn literal_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#""([^"]*)"|'([^']*)'"#).expect("literal pattern compiles"))
+}
+
+fn unquote(literal: &str) -> Option<&str> {
+    let literal = literal.trim();
+    literal
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .or_else(|| {
+            literal
+                .strip_prefix('\'')
+                .and_then(|rest| rest.strip_suffix('\''))
+        })
+}
+
+/// What the conditions compare one call against.
+#[derive(Default)]
+struct Uses {
+    /// `(operator, literal)` comparisons against an int.
+    thresholds: Vec<(String, i64)>,
+    /// String literals the call is compared with or looked up in.
+    literals: Vec<String>,
+}
+
+/// The knobs the branch conditions turn, and the calls they make that no
+/// knob covers.
+fn condition_knobs(
+    branches: &[ProseBranch],
+    world: &World,
+    ctx: &SceneCtx,
+    registry: &PackRegistry,
+) -> (Vec<Knob>, Vec<String>) {
+    let mut calls: Vec<(String, Lever, Uses)> = Vec::new();
+    let mut held: Vec<String> = Vec::new();
+    for branch in branches {
+        for found in call_regex().captures_iter(&branch.tag) {
+            let (receiver, method, args) = (&found[1], &found[2], found[3].trim());
+            let call = format!("{receiver}.{method}({args})");
+            let Some(lever) = Lever::for_call(receiver, method, unquote(args), registry) else {
+                if !held.contains(&call) {
+                    held.push(call);
+                }
+                continue;
+            };
+            let index = match calls.iter().position(|(known, ..)| *known == call) {
+                Some(index) => index,
+                None => {
+                    calls.push((call, lever, Uses::default()));
+                    calls.len() - 1
+                }
+            };
+            let uses = &mut calls[index].2;
+            if let (Some(op), Some(literal)) = (found.get(4), found.get(5)) {
+                match literal.as_str().parse::<i64>() {
+                    Ok(n) => uses.thresholds.push((op.as_str().to_string(), n)),
+                    Err(_) => uses
+                        .literals
+                        .extend(unquote(literal.as_str()).map(str::to_string)),
+                }
+            }
+            if let Some(list) = found.get(6) {
+                for literal in literal_regex().captures_iter(list.as_str()) {
+                    let value = literal.get(1).or(literal.get(2)).map_or("", |m| m.as_str());
+                    uses.literals.push(value.to_string());
+                }
+            }
+        }
+    }
+
+    let knobs = calls
+        .into_iter()
+        .map(|(call, lever, uses)| {
+            let base = lever.read(world, ctx);
+            let candidates = match &base {
+                Setting::Bool(value) => vec![Setting::Bool(*value), Setting::Bool(!value)],
+                Setting::Int(value) => int_candidates(*value, &uses.thresholds),
+                Setting::Str(value) => {
+                    let mut values = vec![Setting::Str(value.clone())];
+                    for literal in &uses.literals {
+                        let setting = Setting::Str(literal.clone());
+                        if !values.contains(&setting) {
+                            values.push(setting);
+                        }
+                    }
+                    // When the base value is one the conditions name, also try
+                    // one they don't, so the fall-through branch renders.
+                    if uses.literals.contains(value) {
+                        let other = lever
+                            .choices()
+                            .into_iter()
+                            .find(|choice| !uses.literals.contains(choice));
+                        values.extend(other.map(Setting::Str));
+                    }
+                    values
+                }
+            };
+            // Drop values the world cannot hold (an enum literal with a typo).
+            let values = candidates
+                .into_iter()
+                .filter(|value| {
+                    lever
+                        .write(&mut world.clone(), &mut ctx.clone(), value)
+                        .is_ok()
+                })
+                .collect();
+            Knob {
+                call,
+                values,
+                lever,
+            }
+        })
+        .filter(|knob: &Knob| !knob.values.is_empty())
+        .collect();
+    (knobs, held)
+}
+
+/// One value from each range the thresholds split the ints into, the base
+/// value standing in for its own range and coming first.
+fn int_candidates(base: i64, thresholds: &[(String, i64)]) -> Vec<Setting> {
+    let mut splits = BTreeSet::new();
+    for (op, n) in thresholds {
+        match op.as_str() {
+            "<" | ">=" => {
+                splits.insert(*n);
+            }
+            "<=" | ">" => {
+                splits.insert(n + 1);
+            }
+            _ => {
+                splits.insert(*n);
+                splits.insert(n + 1);
+            }
+        }
+    }
+    let mut starts: Vec<i64> = Vec::new();
+    if let Some(first) = splits.first() {
+        starts.push(first - 1);
+    }
+    starts.extend(splits.iter().copied());
+    // A value's range starts at the last split at or below it.
+    let range_of = |value: i64| splits.range(..=value).next_back().copied();
+    let mut values = vec![Setting::Int(base)];
+    for start in starts {
+        if range_of(start) != range_of(base) {
+            values.push(Setting::Int(start));
+        }
+    }
+    values
+}
+
+fn names<T: fmt::Debug>(values: &[T]) -> Vec<String> {
+    values.iter().map(|value| format!("{value:?}")).collect()
+}
+
+fn parse_enum<T: DeserializeOwned>(value: &str) -> Result<T, String> {
+    T::deserialize(toml::Value::String(value.to_string()))
+        .map_err(|_| format!("'{value}' is not a valid value"))
+}
+
+impl Lever {
+    fn for_call(
+        receiver: &str,
+        method: &str,
+        arg: Option<&str>,
+        registry: &PackRegistry,
+    ) -> Option<Lever> {
+        Some(match (receiver, method) {
+            ("w", "hasTrait") => Lever::Trait(registry.resolve_trait(arg?).ok()?),
+            ("w", "getSkill") => Lever::Skill(registry.resolve_skill(arg?).ok()?),
+            ("w", "composure") => Lever::Skill(registry.composure_skill().ok()?),
+            ("w", "wasTransformed") => Lever::Transformed,
+            ("w", "getAppearance") => Lever::Appearance,
+            ("w", "getNippleSensitivity") => Lever::NippleSensitivity,
+            ("w", "getClitSensitivity") => Lever::ClitSensitivity,
+            ("w", "getWetness") => Lever::Wetness,
+            ("w", "beforeHeight") => Lever::BeforeHeight,
+            ("w", "beforeFigure") => Lever::BeforeFigure,
+            ("gd", "hasGameFlag") => Lever::GameFlag(arg?.to_string()),
+            ("gd", "desire") => Lever::Desire,
+            ("gd", "isWeekday") => Lever::Weekday,
+            ("gd", "timeSlot") => Lever::TimeSlot,
+            ("scene", "hasFlag") => Lever::SceneFlag(arg?.to_string()),
+            _ => return None,
+        })
+    }
+
+    /// Every value a string read can return; empty for other reads.
+    fn choices(&self) -> Vec<String> {
+        match self {
+            Lever::TimeSlot => names(&[
+                TimeSlot::Morning,
+                TimeSlot::Afternoon,
+                TimeSlot::Evening,
+                TimeSlot::Night,
+            ]),
+            Lever::Appearance => names(&[
+                Appearance::Plain,
+                Appearance::Average,
+                Appearance::Attractive,
+                Appearance::Beautiful,
+                Appearance::Stunning,
+                Appearance::Devastating,
+            ]),
+            Lever::NippleSensitivity => names(&[
+                NippleSensitivity::Low,
+                NippleSensitivity::Normal,
+                NippleSensitivity::High,
+                NippleSensitivity::Extreme,
+            ]),
+            Lever::ClitSensitivity => names(&[
+                ClitSensitivity::Low,
+                ClitSensitivity::Normal,
+                ClitSensitivity::High,
+                ClitSensitivity::Extreme,
+            ]),
+            Lever::Wetness => names(&[
+                WetnessBaseline::Dry,
+                WetnessBaseline::Normal,
+                WetnessBaseline::Wet,
+                WetnessBaseline::Soaking,
+            ]),
+            Lever::BeforeHeight => names(&[
+                Height::VeryShort,
+                Height::Short,
+                Height::Average,
+                Height::Tall,
+                Height::VeryTall,
+            ]),
+            Lever::BeforeFigure => names(&[
+                MaleFigure::Average,
+                MaleFigure::Skinny,
+                MaleFigure::Toned,
+                MaleFigure::Muscular,
+                MaleFigure::Thickset,
+                MaleFigure::Paunchy,
+                MaleFigure::Fat,
+            ]),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The value the read returns in `world`, the way the script API reads it.
+    fn read(&self, world: &World, ctx: &SceneCtx) -> Setting {
+        let player = &world.player;
+        let before = player.before.as_ref();
+        match self {
+            Lever::Trait(id) => Setting::Bool(player.has_trait(*id)),
+            Lever::GameFlag(flag) => Setting::Bool(world.game_data.has_flag(flag)),
+            Lever::SceneFlag(flag) => Setting::Bool(ctx.has_flag(flag)),
+            Lever::Skill(id) => Setting::Int(player.skill(*id) as i64),
+            Lever::Desire => Setting::Int(world.game_data.desire() as i64),
+            Lever::Transformed => Setting::Bool(player.origin.was_transformed()),
+            Lever::Weekday => Setting::Bool(world.game_data.is_weekday()),
+            Lever::TimeSlot => Setting::Str(format!("{:?}", world.game_data.time_slot)),
+            Lever::Appearance => Setting::Str(format!("{:?}", player.appearance)),
+            Lever::NippleSensitivity => Setting::Str(format!("{:?}", player.nipple_sensitivity)),
+            Lever::ClitSensitivity => Setting::Str(format!("{:?}", player.clit_sensitivity)),
+            Lever::Wetness => Setting::Str(format!("{:?}", player.wetness_baseline)),
+            Lever::BeforeHeight => Setting::Str(
+                before
+                    .map(|b| format!("{:?}", b.height))
+                    .unwrap_or_default(),
+            ),
+            Lever::BeforeFigure => Setting::Str(
+                before
+                    .map(|b| format!("{:?}", b.figure))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// Set the world field so the read returns `value`.
+    fn write(&self, world: &mut World, ctx: &mut SceneCtx, value: &Setting) -> Result<(), String> {
+        let player = &mut world.player;
+        match (self, value) {
+            (Lever::Trait(id), Setting::Bool(on)) => {
+                if *on {
+                    player.traits.insert(*id);
+                } else {
+                    player.traits.remove(id);
+                }
+            }
+            (Lever::GameFlag(flag), Setting::Bool(on)) => {
+                if *on {
+                    world.game_data.set_flag(flag.clone());
+                } else {
+                    world.game_data.remove_flag(flag);
+                }
+            }
+            (Lever::SceneFlag(flag), Setting::Bool(on)) => {
+                if *on {
+                    ctx.set_flag(flag.clone());
+                } else {
+                    ctx.scene_flags.remove(flag);
+                }
+            }
+            (Lever::Skill(id), Setting::Int(n)) => {
+                let skill = player.skills.entry(*id).or_insert(SkillValue {
+                    value: 0,
+                    modifier: 0,
+                });
+                skill.value = *n as i32 - skill.modifier;
+            }
+            (Lever::Desire, Setting::Int(n)) => world.game_data.set_desire(*n as i32),
+            (Lever::Transformed, Setting::Bool(on)) => {
+                if !*on {
+                    player.origin = PcOrigin::AlwaysFemale;
+                } else if !player.origin.was_transformed() {
+                    player.origin = PcOrigin::CisMaleTransformed;
+                }
+            }
+            (Lever::Weekday, Setting::Bool(on)) => {
+                if world.game_data.is_weekday() != *on {
+                    world.game_data.day = if *on { 0 } else { 5 };
+                }
+            }
+            (Lever::TimeSlot, Setting::Str(s)) => world.game_data.time_slot = parse_enum(s)?,
+            (Lever::Appearance, Setting::Str(s)) => player.appearance = parse_enum(s)?,
+            (Lever::NippleSensitivity, Setting::Str(s)) => {
+                player.nipple_sensitivity = parse_enum(s)?
+            }
+            (Lever::ClitSensitivity, Setting::Str(s)) => player.clit_sensitivity = parse_enum(s)?,
+            (Lever::Wetness, Setting::Str(s)) => player.wetness_baseline = parse_enum(s)?,
+            (Lever::BeforeHeight, Setting::Str(s)) => {
+                let before = player.before.as_mut().ok_or("no before-life body")?;
+                before.height = parse_enum(s)?;
+            }
+            (Lever::BeforeFigure, Setting::Str(s)) => {
+                let before = player.before.as_mut().ok_or("no before-life body")?;
+                before.figure = parse_enum(s)?;
+            }
+            _ => return Err(format!("{value} is the wrong kind of value")),
+        }
+        Ok(())
+    }
+}
+
+/// A Markdown review document: per template, what it branches on and every
+/// distinct rendering, each with the branches it took and its knob values.
+pub fn review_document(scene_id: &str, reviews: &[SiteReview]) -> String {
+    let variants: usize = reviews.iter().map(|review| review.variants.len()).sum();
+    let branches: usize = reviews.iter().map(|review| review.branches.len()).sum();
+    let unreached: usize = reviews
+        .iter()
+        .map(|review| review.unreached().count())
+        .sum();
+    let mut doc = String::new();
+    let _ = writeln!(doc, "# Prose variants: {scene_id}\n");
+    let _ = writeln!(
+        doc,
+        "{}, {}, {}/{branches} branches rendered.",
+        count(reviews.len(), "prose template"),
+        count(variants, "distinct variant"),
+        branches - unreached
+    );
+
+    for review in reviews {
+        let _ = writeln!(doc, "\n## {}\n", review.site);
+        if !review.knobs.is_empty() {
+            let knobs: Vec<String> = review
+                .knobs
+                .iter()
+                .map(|knob| {
+                    let values: Vec<String> = knob.values.iter().map(Setting::to_string).collect();
+                    format!("`{}` ({})", knob.call, values.join(", "))
+                })
+                .collect();
+            let _ = writeln!(doc, "Varies: {}", knobs.join("; "));
+        }
+        if !review.held.is_empty() {
+            let held: Vec<String> = review.held.iter().map(|call| format!("`{call}`")).collect();
+            let _ = writeln!(doc, "Held at the base world: {}", held.join(", "));
+        }
+        let _ = write!(
+            doc,
+            "{} rendered, {}.",
+            count(review.worlds, "world"),
+            count(review.variants.len(), "distinct variant")
+        );
+        if review.truncated {
+            let _ = write!(doc, " Stopped at the world limit; later knobs held.");
+        }
+        let _ = writeln!(doc);
+        let unreached: Vec<String> = review
+            .unreached()
+            .map(|branch| format!("`{{% {} %}}` (line {})", branch.tag, branch.line))
+            .collect();
+        if !unreached.is_empty() {
+            let _ = writeln!(doc, "Never rendered: {}", unreached.join(", "));
+        }
+
+        for (number, variant) in review.variants.iter().enumerate() {
+            let _ = writeln!(
+                doc,
+                "\n### Variant {} ({})\n",
+                number + 1,
+                count(variant.worlds, "world")
+            );
+            if !variant.branches.is_empty() {
+                let taken: Vec<String> = variant
+                    .branches
+                    .iter()
+                    .map(|&index| {
+                        let branch = &review.branches[index];
+                        format!("`{{% {} %}}` (line {})", branch.tag, branch.line)
+                    })
+                    .collect();
+                let _ = writeln!(doc, "Branches: {}", taken.join(", "));
+            }
+            write_settings(&mut doc, &review.knobs, &variant.settings);
+            let _ = writeln!(doc, "\n```text\n{}\n```", variant.text.trim());
+        }
+        for failure in &review.failures {
+            let _ = writeln!(
+                doc,
+                "\n### Render error ({})\n",
+                count(failure.worlds, "world")
+            );
+            write_settings(&mut doc, &review.knobs, &failure.settings);
+            let _ = writeln!(doc, "\n```text\n{}\n```", failure.message);
+        }
+    }
+    doc
+}
+
+/// `n` and `noun`, pluralised.
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+fn write_settings(doc: &mut String, knobs: &[Knob], settings: &[Setting]) {
+    if knobs.is_empty() {
+        return;
+    }
+    let values: Vec<String> = knobs
+        .iter()
+        .zip(settings)
+        .map(|(knob, value)| format!("`{}` = {value}", knob.call))
+        .collect();
+    let _ = writeln!(doc, "World: {}", values.join(", "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coverage::Beat;
+    use undone_world::test_helpers::make_test_world;
+
+    fn registry() -> PackRegistry {
+        let mut registry = PackRegistry::new();
+        registry.register_traits(vec![undone_packs::TraitDef {
+            id: "SHY".into(),
+            name: "Shy".into(),
+            description: "...".into(),
+            hidden: false,
+            group: None,
+            conflicts: vec![],
+            composure_penalty: 0,
+        }]);
+        registry.register_skills(vec![undone_packs::SkillDef {
+            id: "CHARM".into(),
+            name: "Charm".into(),
+            description: "".into(),
+            min: 0,
+            max: 100,
+            progression: None,
+        }]);
+        registry
+    }
+
+    fn review(template: &str, limits: VariantLimits) -> SiteReview {
+        review_template(
+            ProseSite::Beat(Beat::Intro),
+            template,
+            &make_test_world(),
+            &SceneCtx::new(),
+            &registry(),
+            limits,
+        )
+    }
+
+    fn texts(review: &SiteReview) -> Vec<&str> {
+        review.variants.iter().map(|v| v.text.trim()).collect()
+    }
+
+    #[test]
+    fn every_branch_combination_renders() {
+        let template = r#"{% if w.hasTrait("SHY") %}shy{% else %}bold{% endif %} {% if w.getSkill("CHARM") > 50 %}smooth{% elif w.getSkill("CHARM") > 20 %}fine{% else %}awkward{% endif %}"#;
+        let review = review(template, VariantLimits::default());
+
+        assert_eq!(review.knobs.len(), 2);
+        assert_eq!(review.knobs[0].values.len(), 2);
+        assert_eq!(review.knobs[1].values.len(), 3);
+        assert_eq!(review.worlds, 6);
+        assert!(!review.truncated);
+        let mut rendered = texts(&review);
+        rendered.sort();
+        assert_eq!(
+            rendered,
+            [
+                "bold awkward",
+                "bold fine",
+                "bold smooth",
+                "shy awkward",
+                "shy fine",
+                "shy smooth"
+            ]
+        );
+        assert_eq!(review.unreached().count(), 0);
+    }
+
+    #[test]
+    fn the_base_world_renders_first() {
+        let template = r#"{% if w.getAppearance() == "Stunning" %}wow{% else %}meh{% endif %}"#;
+        let review = review(template, VariantLimits::default());
+        let base = format!("{:?}", make_test_world().player.appearance);
+        assert_eq!(review.knobs[0].values[0], Setting::Str(base));
+        assert_eq!(
+            review.knobs[0].values[1],
+            Setting::Str(format!("{:?}", Appearance::Stunning))
+        );
+        assert_eq!(texts(&review).len(), 2);
+    }
+
+    #[test]
+    fn an_unnamed_enum_value_renders_the_fall_through() {
+        let template = r#"{% if gd.timeSlot() == "Morning" %}early{% else %}later{% endif %}"#;
+        let review = review(template, VariantLimits::default());
+        assert_eq!(texts(&review), ["early", "later"]);
+    }
+
+    #[test]
+    fn unknown_enum_literals_and_other_calls_are_not_varied() {
+        let template = r#"{% if w.getAppearance() == "Dazzling" %}typo{% elif gd.arcState("base::job") == "working" %}job{% else %}plain{% endif %}"#;
+        let review = review(template, VariantLimits::default());
+        assert_eq!(review.knobs[0].values.len(), 1);
+        assert_eq!(review.held, ["gd.arcState(\"base::job\")"]);
+        assert_eq!(texts(&review), ["plain"]);
+        assert_eq!(review.unreached().count(), 2);
+    }
+
+    #[test]
+    fn the_world_limit_truncates_the_product() {
+        let template = r#"{% if w.hasTrait("SHY") %}a{% endif %}{% if gd.hasGameFlag("X") %}b{% endif %}{% if scene.hasFlag("Y") %}c{% endif %}"#;
+        let review = review(template, VariantLimits { max_worlds: 4 });
+        assert_eq!(review.worlds, 4);
+        assert!(review.truncated);
+        assert!(review.unreached().any(|branch| branch.tag.contains("Y")));
+    }
+
+    #[test]
+    fn int_candidates_cover_each_range_once() {
+        let thresholds = [(">".to_string(), 50), ("<=".to_string(), 20)];
+        assert_eq!(
+            int_candidates(30, &thresholds),
+            [Setting::Int(30), Setting::Int(20), Setting::Int(51)]
+        );
+        let equality = [("==".to_string(), 3)];
+        assert_eq!(
+            int_candidates(0, &equality),
+            [Setting::Int(0), Setting::Int(3), Setting::Int(4)]
+        );
+    }
+
+    #[test]
+    fn document_annotates_each_variant_with_its_world() {
+        let template = r#"{% if w.hasTrait("SHY") %}shy{% else %}bold{% endif %}"#;
+        let doc = review_document("base::test", &[review(template, VariantLimits::default())]);
+        assert!(doc.starts_with("# Prose variants: base::test"), "{doc}");
+        assert!(doc.contains("## intro prose"), "{doc}");
+        assert!(doc.contains("`w.hasTrait(\"SHY\")` = true"), "{doc}");
+        assert!(doc.contains("```text\nshy\n```"), "{doc}");
+        assert!(doc.contains("2 distinct variants"), "{doc}");
+    }
+}
@@ -477,6 +477,13 @@ fn desire_zero_no_advantage_hundred_dominates_same_weight() {
                 races_file: None,
                 categories_file: None,
                 arcs_file: None,
+                characters_file: None,
+                routines_file: None,
+                body_file: None,
+                odds_file: None,
+                buffs_file: None,
+                flags_file: None,
+                prose_lint_file: None,
             },
         },
         pack_dir: pack_dir.clone(),
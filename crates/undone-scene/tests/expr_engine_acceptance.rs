@@ -134,9 +134,9 @@ fn composed_boolean_condition_evaluates_correctly() {
     let mut world = make_test_world();
     let ctx = SceneCtx::new();
 
-    // "flag A set AND flag B not set"
+    // "flag A set AND flag B not set" (both declared in the base pack's flags.toml)
     let script = compile_condition(
-        r#"gd.hasGameFlag("ALPHA") && !gd.hasGameFlag("BETA")"#,
+        r#"gd.hasGameFlag("MET_JAKE") && !gd.hasGameFlag("JAKE_KISSED")"#,
         &registry,
         "accept",
     )
@@ -146,11 +146,11 @@ fn composed_boolean_condition_evaluates_correctly() {
     assert!(!eval_bool(&script, &world, &ctx, &registry).unwrap());
 
     // A set, B unset → true.
-    world.game_data.set_flag("ALPHA");
+    world.game_data.set_flag("MET_JAKE");
     assert!(eval_bool(&script, &world, &ctx, &registry).unwrap());
 
     // Both set → the !B clause makes it false.
-    world.game_data.set_flag("BETA");
+    world.game_data.set_flag("JAKE_KISSED");
     assert!(!eval_bool(&script, &world, &ctx, &registry).unwrap());
 }
 
@@ -169,7 +169,7 @@ fn effect_mutates_money_and_flag() {
 
     let before = world.player.money;
     let script = compile_effect(
-        r#"w.changeMoney(-5); gd.setGameFlag("ACCEPT_TEST_FLAG");"#,
+        r#"w.changeMoney(-5); gd.setGameFlag("BOOKSTORE_VISITED");"#,
         &registry,
         "accept",
     )
@@ -188,7 +188,7 @@ fn effect_mutates_money_and_flag() {
         "changeMoney(-5) must decrement money by exactly 5"
     );
     assert!(
-        world.game_data.has_flag("ACCEPT_TEST_FLAG"),
+        world.game_data.has_flag("BOOKSTORE_VISITED"),
         "setGameFlag must persist the flag"
     );
 }
@@ -282,6 +282,7 @@ fn valid_registered_trait_and_skill_load_ok() {
         hidden: false,
         group: None,
         conflicts: vec![],
+        composure_penalty: 0,
     }]);
     registry.register_skills(vec![SkillDef {
         id: "ACCEPT_REAL_SKILL".to_string(),
@@ -289,6 +290,7 @@ fn valid_registered_trait_and_skill_load_ok() {
         description: "A skill registered for the positive load-direction test.".to_string(),
         min: 0,
         max: 100,
+        progression: None,
     }]);
 
     let dir = temp_scene_dir("goodids");
@@ -40,6 +40,7 @@ fn registry_with(traits: &[&str], skills: &[&str]) -> PackRegistry {
                 hidden: false,
                 group: None,
                 conflicts: vec![],
+                composure_penalty: 0,
             })
             .collect(),
     );
@@ -52,6 +53,7 @@ fn registry_with(traits: &[&str], skills: &[&str]) -> PackRegistry {
                 description: String::new(),
                 min: 0,
                 max: 100,
+                progression: None,
             })
             .collect(),
     );
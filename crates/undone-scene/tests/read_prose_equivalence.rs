@@ -24,6 +24,7 @@ fn test_registry() -> PackRegistry {
         description: String::new(),
         min: 0,
         max: 100,
+        progression: None,
     }]);
     r
 }
@@ -131,3 +132,38 @@ fn get_name_uses_effective_name_not_spawn_name() {
         "role.getName must be the effective (display) name, not the spawn name"
     );
 }
+
+/// NPC-to-NPC relationship reads share one accessor, so `m`/`role` views of the
+/// same edge agree across Rhai and prose, including the unrelated `""` case.
+#[test]
+fn npc_relation_reads_agree_across_backends() {
+    use undone_domain::{LikingLevel, NpcKey, NpcRelationKind};
+
+    let (mut world, mut ctx, mut registry) = world_with_named_male();
+    let personality = registry.intern_personality("ROMANTIC");
+    let jake = ctx.active_male.unwrap();
+    let marcus = world.male_npcs.insert(make_test_male_npc(personality));
+    let stranger = world.male_npcs.insert(make_test_male_npc(personality));
+    ctx.bind_role("ROLE_BOSS", SceneNpcRef::Male(marcus));
+    ctx.bind_role("ROLE_STRANGER", SceneNpcRef::Male(stranger));
+    world.npc_relations.set(
+        NpcKey::Male(jake),
+        NpcKey::Male(marcus),
+        NpcRelationKind::Coworker,
+        LikingLevel::Ok,
+    );
+
+    for (expr, expected) in [
+        (r#"m.relationTo("ROLE_BOSS")"#, "Coworker"),
+        (r#"m.closenessTo("ROLE_BOSS")"#, "Ok"),
+        (r#"role.knows("ROLE_BOSS", "m")"#, "true"),
+        (r#"role.relationTo("ROLE_BOSS", "ROLE_STRANGER")"#, ""),
+        (r#"m.knows("ROLE_STRANGER")"#, "false"),
+    ] {
+        let r = eval_string_for_test(expr, &world, &ctx, &registry).unwrap();
+        let p = render_prose(&format!("{{{{ {expr} }}}}"), &world, &ctx, &registry).unwrap();
+        assert_eq!(r, expected, "rhai {expr}");
+        // Minijinja renders bools as `True`/`False`.
+        assert_eq!(p.to_lowercase(), expected.to_lowercase(), "prose {expr}");
+    }
+}
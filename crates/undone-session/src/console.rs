@@ -0,0 +1,299 @@
+//! One-off reads and edits against a live game, for dev tools: evaluate a
+//! read expression or render a prose template, run an effect script, call a
+//! single `REGISTRY` write verb, and dump or patch the whole `World` as JSON.
+//!
+//! Scripts run in the running scene's context when there is one, so `m`, `f`,
+//! `role` and `scene` resolve as they do in that scene's own scripts; with no
+//! scene running they see an empty context.
+
+use serde_json::{Map, Value};
+use undone_packs::PackRegistry;
+use undone_scene::script::api::{lookup, receiver_from_token, table, Accessor, ApiArg, ApiValue};
+use undone_scene::script::{apply_effect_script, compile_condition, compile_effect, eval_value};
+use undone_scene::template_ctx::render_prose;
+use undone_scene::{SceneCtx, SceneEngine};
+use undone_world::World;
+
+/// Evaluate a read expression — anything a condition may contain, e.g.
+/// `w.getSkill("FEMININITY") > 10` or `gd.arcState("base::jake")`.
+pub fn eval_expression(
+    src: &str,
+    world: &World,
+    engine: &SceneEngine,
+    registry: &PackRegistry,
+) -> Result<ApiValue, String> {
+    let script = compile_condition(src, registry, "dev expression").map_err(|e| e.to_string())?;
+    let empty = SceneCtx::new();
+    let ctx = engine.current_ctx().unwrap_or(&empty);
+    eval_value(&script, world, ctx, registry).map_err(|e| e.to_string())
+}
+
+/// Render a prose template the way a scene would.
+pub fn render_template(
+    template: &str,
+    world: &World,
+    engine: &SceneEngine,
+    registry: &PackRegistry,
+) -> Result<String, String> {
+    let empty = SceneCtx::new();
+    let ctx = engine.current_ctx().unwrap_or(&empty);
+    render_prose(template, world, ctx, registry).map_err(|e| format!("{e:#}"))
+}
+
+/// Compile and run an effect script, e.g. `w.addTrait("SHY"); gd.setDesire(40)`.
+/// Like scene effects, every call runs even when an earlier one fails; the
+/// failures come back together.
+pub fn run_effect(
+    src: &str,
+    world: &mut World,
+    engine: &mut SceneEngine,
+    registry: &PackRegistry,
+) -> Result<(), String> {
+    let script = compile_effect(src, registry, "dev effect").map_err(|e| e.to_string())?;
+    let mut empty = SceneCtx::new();
+    let ctx = engine.current_ctx_mut().unwrap_or(&mut empty);
+    let errors = apply_effect_script(&script, world, ctx, registry);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Call one `REGISTRY` write verb with JSON arguments: `("w", "addTrait",
+/// None, ["SHY"])` is `w.addTrait("SHY")`, and `("npc", "setRole",
+/// Some("base::jake"), ["ROLE_EX"])` is `npc("base::jake").setRole("ROLE_EX")`.
+pub fn call_write_verb(
+    receiver: &str,
+    method: &str,
+    npc: Option<&str>,
+    args: &[Value],
+    world: &mut World,
+    engine: &mut SceneEngine,
+    registry: &PackRegistry,
+) -> Result<(), String> {
+    let receiver_kind =
+        receiver_from_token(receiver).ok_or_else(|| format!("unknown receiver '{receiver}'"))?;
+    let write = lookup(receiver_kind, method).and_then(|descriptor| match descriptor.accessor {
+        Accessor::Write(write) => Some(write),
+        Accessor::Read(_) => None,
+    });
+    let Some(write) = write else {
+        let verbs = write_verbs(receiver);
+        return Err(if verbs.is_empty() {
+            format!("'{receiver}' has no write verbs")
+        } else {
+            format!(
+                "'{receiver}.{method}' is not a write verb; try one of: {}",
+                verbs.join(", ")
+            )
+        });
+    };
+
+    let mut api_args = Vec::with_capacity(args.len() + 1);
+    if receiver == "npc" {
+        let npc = npc.ok_or("npc write verbs need an npc ref: 'm', 'f', a role or a story id")?;
+        api_args.push(ApiArg::Str(npc));
+    }
+    for arg in args {
+        api_args.push(match arg {
+            Value::String(s) => ApiArg::Str(s),
+            Value::Bool(b) => ApiArg::Bool(*b),
+            Value::Number(n) => ApiArg::Int(
+                n.as_i64()
+                    .ok_or_else(|| format!("argument {n} is not an integer"))?,
+            ),
+            other => return Err(format!("argument {other} is not a string, int or bool")),
+        });
+    }
+
+    match engine.current_ctx_mut() {
+        Some(ctx) => write(world, ctx, registry, &api_args),
+        None if receiver == "scene" => return Err("no scene is running".into()),
+        None => write(world, &mut SceneCtx::new(), registry, &api_args),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// The write verbs callable on `receiver`, as signatures like
+/// `w.addTrait(trait)`.
+pub fn write_verbs(receiver: &str) -> Vec<String> {
+    table::REGISTRY
+        .iter()
+        .filter(|descriptor| descriptor.is_write() && descriptor.receiver.token() == receiver)
+        .map(|descriptor| descriptor.signature())
+        .collect()
+}
+
+/// The whole world as JSON, in its save-file shape.
+pub fn world_json(world: &World) -> Result<Value, String> {
+    serde_json::to_value(world).map_err(|e| e.to_string())
+}
+
+/// Apply a JSON merge patch (RFC 7396) to the world: objects merge key by
+/// key, `null` removes a key, anything else replaces the value. The world is
+/// left unchanged if the patched JSON is not a valid world.
+pub fn patch_world(world: &mut World, patch: &Value) -> Result<(), String> {
+    let mut value = world_json(world)?;
+    merge_patch(&mut value, patch);
+    *world = serde_json::from_value(value).map_err(|e| format!("patched world is invalid: {e}"))?;
+    Ok(())
+}
+
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target) = target else {
+        return;
+    };
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::load_runtime_content;
+    use crate::presets::{config_from_preset, preset_index};
+    use crate::replay::{seeded_session, world_digest};
+    use crate::session::{GameSession, SessionCommand};
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn robin_session() -> GameSession {
+        let packs_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs");
+        let content = load_runtime_content(&packs_dir).unwrap();
+        let idx = preset_index(&content.registry, "Robin").expect("Robin preset");
+        let config = config_from_preset(&content.registry, idx);
+        seeded_session(content, config, 7).unwrap()
+    }
+
+    #[test]
+    fn expressions_and_prose_read_the_live_world() {
+        let mut session = robin_session();
+        session.apply(&SessionCommand::Continue).unwrap();
+        let GameSession {
+            world,
+            engine,
+            registry,
+            ..
+        } = &session;
+
+        assert_eq!(
+            eval_expression("w.hasTrait(\"SHY\")", world, engine, registry),
+            Ok(ApiValue::Bool(false))
+        );
+        assert_eq!(
+            eval_expression("gd.week()", world, engine, registry),
+            Ok(ApiValue::Int(world.game_data.week as i64))
+        );
+        assert!(eval_expression("w.noSuchMethod()", world, engine, registry).is_err());
+        assert_eq!(
+            render_template("Money: {{ w.getMoney() }}", world, engine, registry),
+            Ok(format!("Money: {}", world.player.money))
+        );
+    }
+
+    #[test]
+    fn effects_and_write_verbs_change_the_world() {
+        let mut session = robin_session();
+        let GameSession {
+            world,
+            engine,
+            registry,
+            ..
+        } = &mut session;
+
+        run_effect(
+            "w.addTrait(\"SHY\"); w.changeMoney(5)",
+            world,
+            engine,
+            registry,
+        )
+        .unwrap();
+        let money = world.player.money;
+        assert_eq!(
+            eval_expression("w.hasTrait(\"SHY\")", world, engine, registry),
+            Ok(ApiValue::Bool(true))
+        );
+
+        call_write_verb(
+            "w",
+            "changeMoney",
+            None,
+            &[json!(10)],
+            world,
+            engine,
+            registry,
+        )
+        .unwrap();
+        assert_eq!(world.player.money, money + 10);
+
+        let err = call_write_verb("w", "getMoney", None, &[], world, engine, registry).unwrap_err();
+        assert!(err.contains("w.changeMoney(amount)"), "{err}");
+        let err = call_write_verb(
+            "scene",
+            "setFlag",
+            None,
+            &[json!("X")],
+            world,
+            engine,
+            registry,
+        )
+        .unwrap_err();
+        assert_eq!(err, "no scene is running");
+        let err = call_write_verb(
+            "npc",
+            "addLiking",
+            None,
+            &[json!(1)],
+            world,
+            engine,
+            registry,
+        )
+        .unwrap_err();
+        assert!(err.contains("npc ref"), "{err}");
+    }
+
+    #[test]
+    fn world_patches_merge_into_the_save_shape() {
+        let mut world = robin_session().world;
+        let before = world_json(&world).unwrap();
+
+        patch_world(&mut world, &json!({ "player": { "money": 1234 } })).unwrap();
+        assert_eq!(world.player.money, 1234);
+        let mut expected = before.clone();
+        expected["player"]["money"] = json!(1234);
+        let expected: World = serde_json::from_value(expected).unwrap();
+        assert_eq!(world_digest(&world), world_digest(&expected));
+
+        let err = patch_world(&mut world, &json!({ "player": { "money": "lots" } })).unwrap_err();
+        assert!(err.starts_with("patched world is invalid"), "{err}");
+        assert_eq!(world.player.money, 1234);
+    }
+
+    #[test]
+    fn merge_patch_follows_rfc_7396() {
+        let mut target = json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+        merge_patch(
+            &mut target,
+            &json!({ "a": null, "b": { "c": 4 }, "e": [5] }),
+        );
+        assert_eq!(target, json!({ "b": { "c": 4, "d": 3 }, "e": [5] }));
+    }
+}
@@ -0,0 +1,137 @@
+//! Loading and validating everything a game session needs from `packs/`.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use undone_packs::{load_packs, PackRegistry};
+use undone_scene::loader::load_scenes;
+use undone_scene::scheduler::{load_schedule, validate_entry_scene_references, Scheduler};
+use undone_scene::types::SceneDefinition;
+
+pub struct LoadedRuntimeContent {
+    pub registry: PackRegistry,
+    pub scenes: HashMap<String, Arc<SceneDefinition>>,
+    pub scheduler: Scheduler,
+}
+
+/// Resolve the packs directory. Tries:
+/// 1. `<exe_dir>/packs` (distribution layout)
+/// 2. `./packs` (cargo run from workspace root)
+pub fn resolve_packs_dir() -> PathBuf {
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.parent() {
+            let candidate = dir.join("packs");
+            if candidate.is_dir() {
+                return candidate;
+            }
+        }
+    }
+    PathBuf::from("packs")
+}
+
+pub fn load_runtime_content(packs_dir: &Path) -> Result<LoadedRuntimeContent, String> {
+    let (registry, metas) =
+        load_packs(packs_dir).map_err(|e| format!("Failed to load packs: {e}"))?;
+
+    let conflict_errors = registry.validate_trait_conflicts();
+    if !conflict_errors.is_empty() {
+        return Err(format!(
+            "Trait conflict errors:\n{}",
+            conflict_errors.join("\n")
+        ));
+    }
+
+    let mut scenes: HashMap<String, Arc<SceneDefinition>> = HashMap::new();
+    let mut scene_sources: HashMap<String, String> = HashMap::new();
+    for meta in &metas {
+        let scene_dir = meta.pack_dir.join(&meta.manifest.content.scenes_dir);
+        let pack_scenes = load_scenes(&scene_dir, &registry).map_err(|e| {
+            format!(
+                "Scene load error in pack '{}': {}",
+                meta.manifest.pack.id,
+                e.render()
+            )
+        })?;
+        extend_scenes_checked(
+            &mut scenes,
+            &mut scene_sources,
+            pack_scenes,
+            &meta.manifest.pack.id,
+        )
+        .map_err(|e| format!("Scene load error in pack '{}': {e}", meta.manifest.pack.id))?;
+    }
+
+    undone_scene::loader::validate_cross_references(&scenes)
+        .map_err(|e| format!("Scene validation error: {e}"))?;
+
+    let scheduler =
+        load_schedule(&metas, &registry).map_err(|e| format!("Schedule load error: {e}"))?;
+    scheduler
+        .validate_scene_references(&scenes)
+        .map_err(|e| format!("Schedule validation error: {e}"))?;
+    validate_entry_scene_references(
+        &scenes,
+        registry.opening_scene(),
+        registry.transformation_scene(),
+    )
+    .map_err(|e| format!("Entry scene validation error: {e}"))?;
+
+    let char_creation_errors = crate::contracts::validate_runtime_contract(&registry, &scheduler);
+    if !char_creation_errors.is_empty() {
+        return Err(format!(
+            "Character creation contract error(s):\n{}",
+            char_creation_errors.join("\n")
+        ));
+    }
+
+    Ok(LoadedRuntimeContent {
+        registry,
+        scenes,
+        scheduler,
+    })
+}
+
+fn extend_scenes_checked(
+    scenes: &mut HashMap<String, Arc<SceneDefinition>>,
+    scene_sources: &mut HashMap<String, String>,
+    incoming: HashMap<String, Arc<SceneDefinition>>,
+    source: &str,
+) -> Result<(), String> {
+    for (scene_id, scene) in incoming {
+        if let Some(first_source) = scene_sources.insert(scene_id.clone(), source.to_string()) {
+            return Err(format!(
+                "duplicate scene id '{scene_id}': '{source}' conflicts with already-loaded '{first_source}'"
+            ));
+        }
+        scenes.insert(scene_id, scene);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packs_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs")
+    }
+
+    #[test]
+    fn load_runtime_content_returns_validated_registry_scenes_and_scheduler() {
+        let loaded = load_runtime_content(&packs_dir()).expect("shared loader should succeed");
+
+        assert!(!loaded.scenes.is_empty());
+        assert!(loaded.registry.opening_scene().is_some());
+    }
+
+    #[test]
+    fn resolve_packs_dir_returns_path_ending_in_packs() {
+        let dir = resolve_packs_dir();
+        assert_eq!(dir.file_name().unwrap(), "packs");
+    }
+}
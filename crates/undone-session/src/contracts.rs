@@ -3,7 +3,27 @@ use undone_domain::PcOrigin;
 use undone_packs::PackRegistry;
 use undone_scene::scheduler::Scheduler;
 
-use super::config::CUSTOM_STARTING_TRAIT_IDS;
+/// Traits offered on the custom character-creation form. Every one must be
+/// registered for character creation to work.
+pub const CUSTOM_STARTING_TRAIT_IDS: &[&str] = &[
+    "SHY",
+    "CUTE",
+    "POSH",
+    "SULTRY",
+    "DOWN_TO_EARTH",
+    "BITCHY",
+    "REFINED",
+    "ROMANTIC",
+    "FLIRTY",
+    "AMBITIOUS",
+    "OUTGOING",
+    "OVERACTIVE_IMAGINATION",
+    "ANALYTICAL",
+    "CONFIDENT",
+    "SEXIST",
+    "HOMOPHOBIC",
+    "OBJECTIFYING",
+];
 
 pub fn validate_registry_contract(registry: &PackRegistry) -> Vec<String> {
     let mut errors = Vec::new();
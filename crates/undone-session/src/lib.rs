@@ -0,0 +1,26 @@
+//! A game session with no UI attached: pack content loading, the
+//! character-creation contracts, preset configs and [`GameSession`], which
+//! drives `SceneEngine` and `Scheduler` the way the runtime does, plus recorded
+//! [`Replay`]s of sessions and a path finder that searches play for a route to
+//! a scene, flag or arc state, a dev console for reading and editing a live
+//! world, and hot reload of `packs/` into a running session. The Floem window
+//! and `undone-cli` are both front ends over these pieces.
+
+pub mod console;
+pub mod content;
+pub mod contracts;
+pub mod pathfind;
+pub mod presets;
+pub mod reload;
+pub mod replay;
+pub mod session;
+
+pub use content::{load_runtime_content, resolve_packs_dir, LoadedRuntimeContent};
+pub use pathfind::{find_path, FoundPath, NotFound, PathLimits, PathSearch, PathStep, PathTarget};
+pub use presets::{config_from_preset, preset_index};
+pub use reload::{reload_content, swap_content, PackWatcher};
+pub use replay::{run_replay, Replay, ReplayError, ReplayStep};
+pub use session::{
+    backfill_composure, backfill_story_npcs, start_scene, GameSession, SceneTimeAnchor,
+    SessionCommand, SessionResult, SessionState, SessionStep, NO_SCENE_AVAILABLE,
+};
@@ -0,0 +1,451 @@
+//! Goal-directed search over play: "how do I reach this scene / flag / arc
+//! state?"
+//!
+//! [`find_path`] runs a breadth-first search from a session's current state.
+//! Every state branches on the commands a player could send there — continue
+//! once a scene is over, each unlocked action while one runs, wait while
+//! nothing is eligible — and the session's seeded rngs travel with each state,
+//! so scheduler picks, NPC actions and skill rolls come out the same when the
+//! path is played again. The first state that reaches the [`PathTarget`] gives
+//! a shortest witness path; if none does, the [`NotFound`] report says whether
+//! the search ran out of states (nothing reachable within the step limit) or
+//! stopped at a limit first.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use serde::Serialize;
+use undone_scene::engine::SceneEngine;
+
+use crate::replay::world_digest;
+use crate::session::{GameSession, SessionCommand};
+
+/// What the search is looking for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathTarget {
+    /// The scene is running (or was just started) after a step.
+    Scene(String),
+    /// The game flag is set.
+    Flag(String),
+    /// The arc is in the given state.
+    Arc { arc: String, state: String },
+}
+
+impl PathTarget {
+    /// `ARC=STATE` names an arc state, a loaded scene id names a scene, and
+    /// anything else is read as a game flag.
+    pub fn parse(text: &str, engine: &SceneEngine) -> Result<Self, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Path target cannot be empty".to_string());
+        }
+        if let Some((arc, state)) = text.split_once('=') {
+            let (arc, state) = (arc.trim(), state.trim());
+            if arc.is_empty() || state.is_empty() {
+                return Err(format!("Arc target '{text}' must look like ARC=STATE"));
+            }
+            return Ok(Self::Arc {
+                arc: arc.to_string(),
+                state: state.to_string(),
+            });
+        }
+        if engine.has_scene(text) {
+            return Ok(Self::Scene(text.to_string()));
+        }
+        Ok(Self::Flag(text.to_string()))
+    }
+
+    fn reached(&self, session: &GameSession, started_scene: Option<&str>) -> bool {
+        let game_data = &session.world.game_data;
+        match self {
+            Self::Scene(scene_id) => {
+                started_scene == Some(scene_id.as_str())
+                    || session.current_scene_id().as_deref() == Some(scene_id.as_str())
+            }
+            Self::Flag(flag) => game_data.has_flag(flag),
+            Self::Arc { arc, state } => game_data.arc_state(arc) == Some(state.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for PathTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scene(scene_id) => write!(f, "scene '{scene_id}'"),
+            Self::Flag(flag) => write!(f, "flag '{flag}'"),
+            Self::Arc { arc, state } => write!(f, "arc state {arc}={state}"),
+        }
+    }
+}
+
+/// How far the search may go.
+#[derive(Debug, Clone, Copy)]
+pub struct PathLimits {
+    /// Longest path, in commands.
+    pub max_steps: usize,
+    /// Most distinct states to expand before giving up.
+    pub max_states: usize,
+    /// Beam search: keep only the first `n` new states at each depth, in the
+    /// order the search meets them. Reaches deeper targets, but the path is no
+    /// longer guaranteed shortest and a miss proves nothing.
+    pub beam_width: Option<usize>,
+}
+
+impl Default for PathLimits {
+    fn default() -> Self {
+        Self {
+            max_steps: 80,
+            max_states: 5_000,
+            beam_width: None,
+        }
+    }
+}
+
+/// One command on a witness path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathStep {
+    pub command: SessionCommand,
+    /// The scene the command started, if it started one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_scene: Option<String>,
+}
+
+impl fmt::Display for PathStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.command {
+            SessionCommand::Continue => write!(f, "continue")?,
+            SessionCommand::ChooseAction(action_id) => write!(f, "choose {action_id}")?,
+            SessionCommand::JumpToScene(scene_id) => write!(f, "jump {scene_id}")?,
+            SessionCommand::Wait => write!(f, "wait")?,
+        }
+        match &self.started_scene {
+            Some(scene_id) => write!(f, " -> {scene_id}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FoundPath {
+    /// The shortest command sequence that reaches the target.
+    pub steps: Vec<PathStep>,
+    pub states_explored: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotFound {
+    pub max_steps: usize,
+    pub states_explored: usize,
+    /// Length of the longest path expanded.
+    pub depth_reached: usize,
+    /// Every state within `max_steps` was expanded (and none was dropped by
+    /// a beam): the target cannot be reached in that many commands from this
+    /// start.
+    pub exhausted: bool,
+    /// The search stopped at `PathLimits::max_states`.
+    pub hit_state_limit: bool,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.exhausted {
+            write!(
+                f,
+                "not reachable within {} steps: all {} reachable states explored (deepest path {})",
+                self.max_steps, self.states_explored, self.depth_reached
+            )
+        } else if self.hit_state_limit {
+            write!(
+                f,
+                "not found: stopped at the limit of {} states (deepest path {})",
+                self.states_explored, self.depth_reached
+            )
+        } else {
+            write!(
+                f,
+                "not found within {} steps ({} states explored)",
+                self.max_steps, self.states_explored
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum PathSearch {
+    Found(FoundPath),
+    NotFound(NotFound),
+}
+
+/// A state the search reached: how it got there, and from where.
+struct Node {
+    parent: Option<usize>,
+    step: Option<PathStep>,
+    depth: usize,
+}
+
+/// Search for the shortest command sequence from the session's current state
+/// to `target`.
+///
+/// When a path is found the session is left at the end of it, with every
+/// command played through [`GameSession::apply`] (a recording session records
+/// them). Otherwise the session is left where it started.
+pub fn find_path(
+    session: &mut GameSession,
+    target: &PathTarget,
+    limits: PathLimits,
+) -> Result<PathSearch, String> {
+    let recording = session.recording.take();
+    let start = session.state();
+
+    let mut nodes = vec![Node {
+        parent: None,
+        step: None,
+        depth: 0,
+    }];
+    let mut seen = HashSet::from([state_key(session)]);
+    let mut queue = VecDeque::from([(0usize, start.clone())]);
+    let mut goal = target.reached(session, None).then_some(0);
+    let mut depth_reached = 0;
+    let mut hit_state_limit = false;
+    let mut kept_per_depth = vec![1usize];
+    let mut pruned = false;
+
+    while goal.is_none() {
+        let Some((index, state)) = queue.pop_front() else {
+            break;
+        };
+        let depth = nodes[index].depth;
+        depth_reached = depth_reached.max(depth);
+        if depth >= limits.max_steps {
+            continue;
+        }
+        if nodes.len() >= limits.max_states {
+            hit_state_limit = true;
+            break;
+        }
+
+        session.restore(state.clone());
+        for command in next_commands(session) {
+            session.restore(state.clone());
+            let Ok(step) = session.apply(&command) else {
+                continue;
+            };
+            if !seen.insert(state_key(session)) {
+                continue;
+            }
+            let path_step = PathStep {
+                command,
+                started_scene: step.started_scene_id,
+            };
+            let reached = target.reached(session, path_step.started_scene.as_deref());
+            if kept_per_depth.len() <= depth + 1 {
+                kept_per_depth.push(0);
+            }
+            if !reached
+                && limits
+                    .beam_width
+                    .is_some_and(|w| kept_per_depth[depth + 1] >= w)
+            {
+                pruned = true;
+                continue;
+            }
+            kept_per_depth[depth + 1] += 1;
+            nodes.push(Node {
+                parent: Some(index),
+                step: Some(path_step),
+                depth: depth + 1,
+            });
+            if reached {
+                goal = Some(nodes.len() - 1);
+                break;
+            }
+            queue.push_back((nodes.len() - 1, session.state()));
+        }
+    }
+
+    session.restore(start);
+    session.recording = recording;
+
+    let Some(goal) = goal else {
+        return Ok(PathSearch::NotFound(NotFound {
+            max_steps: limits.max_steps,
+            states_explored: nodes.len(),
+            depth_reached,
+            exhausted: queue.is_empty() && !hit_state_limit && !pruned,
+            hit_state_limit,
+        }));
+    };
+
+    let mut steps = Vec::new();
+    let mut cursor = Some(goal);
+    while let Some(index) = cursor {
+        steps.extend(nodes[index].step.clone());
+        cursor = nodes[index].parent;
+    }
+    steps.reverse();
+
+    for (number, path_step) in steps.iter().enumerate() {
+        session
+            .apply(&path_step.command)
+            .map_err(|error| format!("path step {} ({path_step}) failed: {error}", number + 1))?;
+    }
+    Ok(PathSearch::Found(FoundPath {
+        steps,
+        states_explored: nodes.len(),
+    }))
+}
+
+/// The commands a player could send from here.
+fn next_commands(session: &GameSession) -> Vec<SessionCommand> {
+    if session.awaiting_continue() || session.opening_scene.is_some() {
+        return vec![SessionCommand::Continue];
+    }
+    if session.current_scene_id().is_none() {
+        return vec![SessionCommand::Wait];
+    }
+    session
+        .actions()
+        .iter()
+        .filter(|action| !action.is_locked())
+        .map(|action| SessionCommand::ChooseAction(action.id.clone()))
+        .collect()
+}
+
+/// States with the same world, scene-local state and offered actions play the
+/// same from here on, up to rng draws; the search visits each once.
+fn state_key(session: &GameSession) -> String {
+    let action_ids: Vec<&str> = session
+        .actions()
+        .iter()
+        .map(|action| action.id.as_str())
+        .collect();
+    format!(
+        "{}|{}|{}|{}|{:?}",
+        world_digest(&session.world),
+        session.engine.runtime_key(),
+        session.awaiting_continue(),
+        session.opening_scene.is_some(),
+        action_ids
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::load_runtime_content;
+    use crate::presets::{config_from_preset, preset_index};
+    use crate::replay::seeded_session;
+    use std::path::PathBuf;
+
+    fn robin_session() -> GameSession {
+        let packs_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs");
+        let content = load_runtime_content(&packs_dir).unwrap();
+        let idx = preset_index(&content.registry, "Robin").expect("Robin preset");
+        let config = config_from_preset(&content.registry, idx);
+        seeded_session(content, config, 7).unwrap()
+    }
+
+    #[test]
+    fn targets_parse_as_arc_scene_or_flag() {
+        let session = robin_session();
+        let parse = |text| PathTarget::parse(text, &session.engine).unwrap();
+
+        assert_eq!(
+            parse("base::workplace_landlord"),
+            PathTarget::Scene("base::workplace_landlord".into())
+        );
+        assert_eq!(
+            parse("base::workplace_opening = week_one"),
+            PathTarget::Arc {
+                arc: "base::workplace_opening".into(),
+                state: "week_one".into()
+            }
+        );
+        assert_eq!(
+            parse("MET_LANDLORD"),
+            PathTarget::Flag("MET_LANDLORD".into())
+        );
+        assert!(PathTarget::parse("ARC=", &session.engine).is_err());
+    }
+
+    #[test]
+    fn finds_the_shortest_path_to_a_scene_and_leaves_the_session_there() {
+        let mut session = robin_session();
+        let target = PathTarget::Scene("base::workplace_landlord".into());
+
+        let PathSearch::Found(found) =
+            find_path(&mut session, &target, PathLimits::default()).unwrap()
+        else {
+            panic!("the landlord scene follows the arrival scene");
+        };
+
+        assert_eq!(found.steps[0].command, SessionCommand::Continue);
+        assert_eq!(
+            found.steps[0].started_scene.as_deref(),
+            Some("base::workplace_arrival")
+        );
+        assert_eq!(
+            found.steps.last().unwrap().started_scene.as_deref(),
+            Some("base::workplace_landlord")
+        );
+        assert_eq!(
+            session.current_scene_id().as_deref(),
+            Some("base::workplace_landlord")
+        );
+    }
+
+    #[test]
+    fn flag_and_arc_targets_end_on_the_action_that_sets_them() {
+        let mut session = robin_session();
+        let flag = PathTarget::Flag("OPENING_ID_WAITED_OUT".into());
+        let PathSearch::Found(found) =
+            find_path(&mut session, &flag, PathLimits::default()).unwrap()
+        else {
+            panic!("the arrival scene sets this flag");
+        };
+        assert_eq!(
+            found.steps.last().unwrap().command,
+            SessionCommand::ChooseAction("id_wait".into())
+        );
+        assert!(session.world.game_data.has_flag("OPENING_ID_WAITED_OUT"));
+
+        let arc = PathTarget::Arc {
+            arc: "base::workplace_opening".into(),
+            state: "arrived".into(),
+        };
+        assert!(matches!(
+            find_path(&mut session, &arc, PathLimits::default()).unwrap(),
+            PathSearch::Found(_)
+        ));
+        assert_eq!(
+            session.world.game_data.arc_state("base::workplace_opening"),
+            Some("arrived")
+        );
+    }
+
+    #[test]
+    fn an_unreachable_target_reports_the_search_and_leaves_the_session_alone() {
+        let mut session = robin_session();
+        let target = PathTarget::Flag("NO_SCENE_EVER_SETS_THIS".into());
+        let limits = PathLimits {
+            max_steps: 3,
+            max_states: 5_000,
+            beam_width: None,
+        };
+
+        let PathSearch::NotFound(report) = find_path(&mut session, &target, limits).unwrap() else {
+            panic!("no scene sets this flag");
+        };
+
+        assert!(report.exhausted, "{report}");
+        assert_eq!(report.depth_reached, 3);
+        assert_eq!(session.current_scene_id(), None);
+        assert!(session.opening_scene.is_some());
+    }
+}
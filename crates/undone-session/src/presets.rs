@@ -0,0 +1,69 @@
+//! Character-creation configs built straight from pack presets.
+use undone_domain::{BeforeIdentity, TraitId};
+use undone_packs::{char_creation::CharCreationConfig, PackRegistry};
+
+/// Index of the preset whose before-name or fem name is `name`
+/// (case-insensitive), for picking a preset from the command line.
+pub fn preset_index(registry: &PackRegistry, name: &str) -> Option<usize> {
+    registry.presets().iter().position(|p| {
+        p.before_name.eq_ignore_ascii_case(name) || p.name_fem.eq_ignore_ascii_case(name)
+    })
+}
+
+/// Build a CharCreationConfig from a preset at the given index in `registry.presets()`.
+///
+/// Panics if the index is out of bounds.
+pub fn config_from_preset(registry: &PackRegistry, idx: usize) -> CharCreationConfig {
+    let p = &registry.presets()[idx];
+
+    let starting_traits: Vec<TraitId> = p
+        .trait_ids
+        .iter()
+        .filter_map(|trait_id| registry.resolve_trait(trait_id).ok())
+        .collect();
+
+    CharCreationConfig {
+        name_fem: p.name_fem.clone(),
+        name_masc: p.name_masc.clone(),
+        age: p.age,
+        race: p.race.clone(),
+        figure: p.figure,
+        breasts: p.breasts,
+        origin: p.origin,
+        before: Some(BeforeIdentity {
+            name: p.before_name.clone(),
+            age: p.before_age,
+            race: p.before_race.clone(),
+            sexuality: p.before_sexuality,
+            figure: p.before_figure,
+            height: p.before_height,
+            hair_colour: p.before_hair_colour,
+            eye_colour: p.before_eye_colour,
+            skin_tone: p.before_skin_tone,
+            penis_size: p.before_penis_size,
+            voice: p.before_voice,
+            traits: std::collections::HashSet::new(),
+        }),
+        starting_traits,
+        male_count: 6,
+        female_count: 3,
+        starting_flags: p.starting_flags.iter().cloned().collect(),
+        starting_arc_states: std::collections::HashMap::new(),
+        height: p.height,
+        butt: p.butt,
+        waist: p.waist,
+        lips: p.lips,
+        hair_colour: p.hair_colour,
+        hair_length: p.hair_length,
+        eye_colour: p.eye_colour,
+        skin_tone: p.skin_tone,
+        complexion: p.complexion,
+        appearance: p.appearance,
+        pubic_hair: p.pubic_hair,
+        natural_pubic_hair: p.natural_pubic_hair,
+        nipple_sensitivity: p.nipple_sensitivity,
+        clit_sensitivity: p.clit_sensitivity,
+        inner_labia: p.inner_labia,
+        wetness_baseline: p.wetness_baseline,
+    }
+}
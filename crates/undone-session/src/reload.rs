@@ -0,0 +1,219 @@
+//! Dev-mode hot reload. A [`PackWatcher`] notices edits under `packs/`;
+//! [`reload_content`] rebuilds pack data, scenes and the schedule through the
+//! same gates as startup, and [`swap_content`] puts the result into a live
+//! session. A reload a gate rejects changes nothing, so a typo in a scene file
+//! costs an error message, not the session.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use undone_packs::PackRegistry;
+use undone_scene::scheduler::Scheduler;
+use undone_scene::SceneEngine;
+
+use crate::content::{load_runtime_content, LoadedRuntimeContent};
+
+/// Polls a packs directory for added, removed and modified files.
+pub struct PackWatcher {
+    packs_dir: PathBuf,
+    stamps: BTreeMap<PathBuf, FileStamp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl PackWatcher {
+    /// Watch `packs_dir`, taking its current files as the starting point.
+    pub fn new(packs_dir: impl Into<PathBuf>) -> Self {
+        let packs_dir = packs_dir.into();
+        let stamps = stamp_files(&packs_dir);
+        Self { packs_dir, stamps }
+    }
+
+    pub fn packs_dir(&self) -> &Path {
+        &self.packs_dir
+    }
+
+    /// Whether anything under the packs directory changed since the watcher
+    /// was created or last reported a change.
+    pub fn poll(&mut self) -> bool {
+        let stamps = stamp_files(&self.packs_dir);
+        if stamps == self.stamps {
+            return false;
+        }
+        self.stamps = stamps;
+        true
+    }
+}
+
+fn stamp_files(dir: &Path) -> BTreeMap<PathBuf, FileStamp> {
+    let mut stamps = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else {
+                stamps.insert(
+                    entry.path(),
+                    FileStamp {
+                        modified: metadata.modified().ok(),
+                        len: metadata.len(),
+                    },
+                );
+            }
+        }
+    }
+    stamps
+}
+
+/// Load `packs_dir` afresh through the startup gates, ready to swap in under
+/// a world built against `registry`. Fails when a gate rejects the content, or
+/// when pack data changed in a way that moves interned IDs the live world
+/// already holds — that change needs a restart.
+pub fn reload_content(
+    packs_dir: &Path,
+    registry: &PackRegistry,
+) -> Result<LoadedRuntimeContent, String> {
+    let mut content = load_runtime_content(packs_dir)?;
+    undone_save::carry_over_ids(registry, &mut content.registry)
+        .map_err(|e| format!("Pack data change needs a restart: {e}"))?;
+    Ok(content)
+}
+
+/// Swap reloaded content into a live session. A running scene keeps the
+/// definition it started with until it is restarted
+/// ([`SceneEngine::restart_current_scene`]).
+pub fn swap_content(
+    content: LoadedRuntimeContent,
+    registry: &mut PackRegistry,
+    engine: &mut SceneEngine,
+    scheduler: &mut Scheduler,
+) {
+    *registry = content.registry;
+    engine.replace_scenes(content.scenes);
+    *scheduler = content.scheduler;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::{config_from_preset, preset_index};
+    use crate::replay::seeded_session;
+    use crate::session::{GameSession, SessionCommand};
+    use undone_scene::engine::EngineEvent;
+
+    fn packs_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs")
+    }
+
+    /// A scratch copy of the shipped packs that a test may edit.
+    fn scratch_packs(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("undone-reload-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        copy_dir(&packs_dir(), &dir);
+        dir
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let target = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &target);
+            } else {
+                std::fs::copy(entry.path(), target).unwrap();
+            }
+        }
+    }
+
+    fn robin_session(packs: &Path) -> GameSession {
+        let content = load_runtime_content(packs).unwrap();
+        let idx = preset_index(&content.registry, "Robin").expect("Robin preset");
+        let config = config_from_preset(&content.registry, idx);
+        seeded_session(content, config, 7).unwrap()
+    }
+
+    #[test]
+    fn watcher_reports_each_change_once() {
+        let packs = scratch_packs("watch");
+        let mut watcher = PackWatcher::new(&packs);
+        assert!(!watcher.poll());
+
+        std::fs::write(packs.join("notes.txt"), "draft").unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        std::fs::remove_file(packs.join("notes.txt")).unwrap();
+        assert!(watcher.poll());
+        std::fs::remove_dir_all(&packs).unwrap();
+    }
+
+    #[test]
+    fn reload_swaps_edited_scenes_into_a_live_session() {
+        let packs = scratch_packs("swap");
+        let mut session = robin_session(&packs);
+        session
+            .apply(&SessionCommand::JumpToScene("base::rain_shelter".into()))
+            .unwrap();
+        let path = packs.join("base/scenes/rain_shelter.toml");
+        let source = std::fs::read_to_string(&path).unwrap();
+
+        std::fs::write(&path, format!("{source}\nnot valid toml [")).unwrap();
+        let Err(err) = reload_content(&packs, &session.registry) else {
+            panic!("a scene that does not parse should fail the reload");
+        };
+        assert!(err.contains("Scene load error"), "{err}");
+
+        let marker = "Hot reloaded.";
+        std::fs::write(
+            &path,
+            source.replacen(
+                "The sky opened up",
+                &format!("{marker} The sky opened up"),
+                1,
+            ),
+        )
+        .unwrap();
+        let content = reload_content(&packs, &session.registry).unwrap();
+        let GameSession {
+            world,
+            registry,
+            engine,
+            scheduler,
+            ..
+        } = &mut session;
+        swap_content(content, registry, engine, scheduler);
+        assert!(engine.restart_current_scene(world, registry));
+        assert_eq!(
+            engine.current_scene_id().as_deref(),
+            Some("base::rain_shelter")
+        );
+        let prose: String = engine
+            .drain()
+            .into_iter()
+            .filter_map(|event| match event {
+                EngineEvent::ProseAdded(text) => Some(text),
+                _ => None,
+            })
+            .collect();
+        assert!(prose.contains(marker), "{prose}");
+        std::fs::remove_dir_all(&packs).unwrap();
+    }
+}
@@ -0,0 +1,461 @@
+//! Recorded play sessions and the runner that replays them.
+//!
+//! A [`Replay`] holds everything needed to drive a [`GameSession`] through the
+//! same run again: the character-creation config, the rng seed and every
+//! command sent through [`GameSession::apply`]. Next to each command it keeps
+//! the scene that command started and a hash of the prose it rendered, and at
+//! the end a digest of the whole world. [`run_replay`] re-drives a fresh
+//! session and fails at the first step that differs, so a recorded run doubles
+//! as a regression fixture for prose and effect changes along its path.
+//!
+//! The config names its traits by id string, resolved through the pack
+//! registry on read, so a replay survives new content shifting the interned
+//! numbers. The world digest still covers everything the run touched: after an
+//! intended content change along the path, record the run again.
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use undone_packs::char_creation::CharCreationConfig;
+use undone_packs::PackRegistry;
+use undone_scene::engine::EngineEvent;
+use undone_world::World;
+
+use crate::content::LoadedRuntimeContent;
+use crate::session::{GameSession, SessionCommand, SessionStep};
+
+pub const REPLAY_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    #[serde(with = "trait_names")]
+    pub config: CharCreationConfig,
+    pub steps: Vec<ReplayStep>,
+    /// [`world_digest`] of the world after the last step.
+    pub world_digest: String,
+}
+
+/// One recorded command and what it produced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub command: SessionCommand,
+    /// The scene the command started, if it started one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_scene: Option<String>,
+    /// [`prose_hash`] of the step's events.
+    pub prose_hash: String,
+}
+
+impl ReplayStep {
+    pub fn record(command: &SessionCommand, step: &SessionStep) -> Self {
+        Self {
+            command: command.clone(),
+            started_scene: step.started_scene_id.clone(),
+            prose_hash: prose_hash(&step.events),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("io error with {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("replay version {found} is not supported (expected {expected})")]
+    Version { found: u32, expected: u32 },
+
+    #[error("could not start the session: {0}")]
+    Start(String),
+
+    #[error("step {step} ({command:?}) failed: {message}")]
+    Step {
+        step: usize,
+        command: SessionCommand,
+        message: String,
+    },
+
+    #[error("step {step} ({command:?}) started {actual:?}, recorded {expected:?}")]
+    SceneMismatch {
+        step: usize,
+        command: SessionCommand,
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+
+    #[error("step {step} ({command:?}) rendered different prose than recorded")]
+    ProseMismatch {
+        step: usize,
+        command: SessionCommand,
+    },
+
+    #[error("final world digest is {actual}, recorded {expected}")]
+    WorldMismatch { expected: String, actual: String },
+}
+
+impl Replay {
+    /// Read a replay, resolving its trait names through `registry`.
+    pub fn read(path: &Path, registry: &PackRegistry) -> Result<Self, ReplayError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ReplayError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let replay: Replay = trait_names::with_registry(registry, || serde_json::from_str(&text))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::Version {
+                found: replay.version,
+                expected: REPLAY_VERSION,
+            });
+        }
+        Ok(replay)
+    }
+
+    /// Write the replay, naming its traits through `registry`.
+    pub fn write(&self, path: &Path, registry: &PackRegistry) -> Result<(), ReplayError> {
+        let mut text = trait_names::with_registry(registry, || serde_json::to_string_pretty(self))?;
+        text.push('\n');
+        std::fs::write(path, text).map_err(|source| ReplayError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// Serde adapter for the replay's config: trait ids are written as their id
+/// strings and resolved back on read, through the registry installed by
+/// [`with_registry`](trait_names::with_registry) for the duration of one
+/// (de)serialization.
+mod trait_names {
+    use super::*;
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserializer, Serializer};
+
+    thread_local! {
+        static REGISTRY: Cell<Option<*const PackRegistry>> = const { Cell::new(None) };
+    }
+
+    /// Restores the previously installed registry on drop, so nested installs
+    /// and early returns are sound.
+    struct RegistryGuard {
+        prev: Option<*const PackRegistry>,
+    }
+
+    impl Drop for RegistryGuard {
+        fn drop(&mut self) {
+            REGISTRY.with(|cell| cell.set(self.prev));
+        }
+    }
+
+    /// Run `f` with `registry` available to the adapter. The pointer is only
+    /// read while `f` runs, and `registry` outlives that call.
+    pub(super) fn with_registry<R>(registry: &PackRegistry, f: impl FnOnce() -> R) -> R {
+        let prev = REGISTRY.with(|cell| cell.replace(Some(registry as *const PackRegistry)));
+        let _guard = RegistryGuard { prev };
+        f()
+    }
+
+    fn registry() -> Option<&'static PackRegistry> {
+        // SAFETY: `with_registry` keeps the registry borrowed while the pointer
+        // is installed, and the reference does not escape the adapter call.
+        REGISTRY.with(|cell| cell.get()).map(|ptr| unsafe { &*ptr })
+    }
+
+    /// JSON pointers to the trait lists in a serialized config, and whether
+    /// the list is a set (written sorted) rather than an ordered list.
+    const TRAIT_LISTS: [(&str, bool); 2] = [("/starting_traits", false), ("/before/traits", true)];
+
+    pub fn serialize<S: Serializer>(
+        config: &CharCreationConfig,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let registry =
+            registry().ok_or_else(|| S::Error::custom("no registry to name trait ids"))?;
+        let mut value = serde_json::to_value(config).map_err(S::Error::custom)?;
+        for (pointer, is_set) in TRAIT_LISTS {
+            let Some(list) = value.pointer_mut(pointer).and_then(Value::as_array_mut) else {
+                continue;
+            };
+            let mut names = Vec::with_capacity(list.len());
+            for id in list.iter() {
+                let id = serde_json::from_value(id.clone()).map_err(S::Error::custom)?;
+                names.push(registry.trait_id_to_str(id).to_string());
+            }
+            if is_set {
+                names.sort();
+            }
+            *list = names.into_iter().map(Value::from).collect();
+        }
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CharCreationConfig, D::Error> {
+        let registry =
+            registry().ok_or_else(|| D::Error::custom("no registry to resolve trait names"))?;
+        let mut value = Value::deserialize(deserializer)?;
+        for (pointer, _) in TRAIT_LISTS {
+            let Some(list) = value.pointer_mut(pointer).and_then(Value::as_array_mut) else {
+                continue;
+            };
+            for item in list.iter_mut() {
+                let name = item
+                    .as_str()
+                    .ok_or_else(|| D::Error::custom(format!("trait {item} is not a name")))?;
+                let id = registry.resolve_trait(name).map_err(D::Error::custom)?;
+                *item = serde_json::to_value(id).map_err(D::Error::custom)?;
+            }
+        }
+        serde_json::from_value(value).map_err(D::Error::custom)
+    }
+}
+
+/// The commands a session has recorded so far, and what started it.
+#[derive(Debug, Clone)]
+pub(crate) struct Recording {
+    pub seed: u64,
+    pub config: CharCreationConfig,
+    pub steps: Vec<ReplayStep>,
+}
+
+impl Recording {
+    pub fn to_replay(&self, world: &World) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            seed: self.seed,
+            config: self.config.clone(),
+            steps: self.steps.clone(),
+            world_digest: world_digest(world),
+        }
+    }
+}
+
+/// A seeded session: the same seed drives character creation, the scheduler,
+/// and the engine's NPC action picks and skill rolls.
+pub(crate) fn seeded_session(
+    content: LoadedRuntimeContent,
+    config: CharCreationConfig,
+    seed: u64,
+) -> Result<GameSession, String> {
+    let mut session = GameSession::new_game(content, config, SmallRng::seed_from_u64(seed))?;
+    session.engine.seed_rng(seed);
+    Ok(session)
+}
+
+/// Play `replay` against `content` and check every step against the recording.
+pub fn run_replay(content: LoadedRuntimeContent, replay: &Replay) -> Result<(), ReplayError> {
+    let mut session =
+        seeded_session(content, replay.config.clone(), replay.seed).map_err(ReplayError::Start)?;
+
+    for (index, recorded) in replay.steps.iter().enumerate() {
+        let step_number = index + 1;
+        let command = recorded.command.clone();
+        let step = session
+            .apply(&command)
+            .map_err(|message| ReplayError::Step {
+                step: step_number,
+                command: command.clone(),
+                message,
+            })?;
+        if step.started_scene_id != recorded.started_scene {
+            return Err(ReplayError::SceneMismatch {
+                step: step_number,
+                command,
+                expected: recorded.started_scene.clone(),
+                actual: step.started_scene_id,
+            });
+        }
+        if prose_hash(&step.events) != recorded.prose_hash {
+            return Err(ReplayError::ProseMismatch {
+                step: step_number,
+                command,
+            });
+        }
+    }
+
+    let actual = world_digest(&session.world);
+    if actual != replay.world_digest {
+        return Err(ReplayError::WorldMismatch {
+            expected: replay.world_digest.clone(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Hash of the text a step showed the player: prose, thoughts, body notices
+/// and errors, in order.
+pub fn prose_hash(events: &[EngineEvent]) -> String {
+    let mut text = String::new();
+    for event in events {
+        let (tag, body) = match event {
+            EngineEvent::ProseAdded(prose) => ("prose", prose.as_str()),
+            EngineEvent::ThoughtAdded { text, .. } => ("thought", text.as_str()),
+            EngineEvent::BodyChanged(message) => ("body", message.as_str()),
+            EngineEvent::ErrorOccurred(message) => ("error", message.as_str()),
+            _ => continue,
+        };
+        let _ = write!(text, "{tag}\u{0}{body}\u{0}");
+    }
+    format!("{:016x}", fnv1a(text.as_bytes()))
+}
+
+/// Hash of the whole world, stable across runs. Hash sets and maps serialize
+/// in per-process order, so objects are written with sorted keys and the
+/// fields in [`SET_FIELDS`] as sorted multisets. Every other array keeps its
+/// order: memory and buff lists are part of the state.
+pub fn world_digest(world: &World) -> String {
+    let value = serde_json::to_value(world).expect("World serializes to JSON");
+    format!("{:016x}", fnv1a(canonical_json(&value, false).as_bytes()))
+}
+
+/// World fields that serialize a hash set.
+const SET_FIELDS: &[&str] = &[
+    "traits",
+    "stuff",
+    "flags",
+    "red_check_failures",
+    "relationship_flags",
+    "sexual_activities",
+    "roles",
+];
+
+fn canonical_json(value: &Value, is_set: bool) -> String {
+    match value {
+        Value::Array(items) => {
+            let mut items: Vec<String> = items
+                .iter()
+                .map(|item| canonical_json(item, false))
+                .collect();
+            if is_set {
+                items.sort();
+            }
+            format!("[{}]", items.join(","))
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let entries: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let is_set = SET_FIELDS.contains(&key.as_str());
+                    format!(
+                        "{}:{}",
+                        Value::from(key.as_str()),
+                        canonical_json(value, is_set)
+                    )
+                })
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+/// 64-bit FNV-1a: small, and the same on every platform and release.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonical_json_ignores_key_and_set_order() {
+        let a = json!({"flags": [3, 1, 2], "a": {"y": true, "x": null}});
+        let b = json!({"a": {"x": null, "y": true}, "flags": [2, 3, 1]});
+        assert_eq!(canonical_json(&a, false), canonical_json(&b, false));
+        assert_ne!(
+            canonical_json(&a, false),
+            canonical_json(
+                &json!({"a": {"x": null, "y": true}, "flags": [2, 3]}),
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn canonical_json_keeps_list_order() {
+        let a = json!({"recent": [1, 2], "buffs": ["a", "b"]});
+        let b = json!({"recent": [2, 1], "buffs": ["a", "b"]});
+        let c = json!({"recent": [1, 2], "buffs": ["b", "a"]});
+        assert_ne!(canonical_json(&a, false), canonical_json(&b, false));
+        assert_ne!(canonical_json(&a, false), canonical_json(&c, false));
+    }
+
+    #[test]
+    fn config_traits_are_written_by_name() {
+        let packs_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs");
+        let registry = crate::content::load_runtime_content(&packs_dir)
+            .unwrap()
+            .registry;
+        let mut config = crate::config_from_preset(&registry, 0);
+        let shy = registry.resolve_trait("SHY").unwrap();
+        let cute = registry.resolve_trait("CUTE").unwrap();
+        config.starting_traits = vec![shy, cute];
+        let replay = Replay {
+            version: REPLAY_VERSION,
+            seed: 1,
+            config,
+            steps: Vec::new(),
+            world_digest: String::new(),
+        };
+
+        let text =
+            trait_names::with_registry(&registry, || serde_json::to_string(&replay)).unwrap();
+        assert!(
+            text.contains(r#""starting_traits":["SHY","CUTE"]"#),
+            "{text}"
+        );
+        let read: Replay =
+            trait_names::with_registry(&registry, || serde_json::from_str(&text)).unwrap();
+        assert_eq!(read.config.starting_traits, vec![shy, cute]);
+
+        let unknown = text.replace(r#""CUTE""#, r#""CUET""#);
+        assert!(
+            trait_names::with_registry(&registry, || serde_json::from_str::<Replay>(&unknown))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn prose_hash_covers_text_but_not_action_lists() {
+        let prose = vec![EngineEvent::ProseAdded("Rain.".into())];
+        let with_actions = vec![
+            EngineEvent::ProseAdded("Rain.".into()),
+            EngineEvent::ActionsAvailable(Vec::new()),
+        ];
+        assert_eq!(prose_hash(&prose), prose_hash(&with_actions));
+        assert_ne!(
+            prose_hash(&prose),
+            prose_hash(&[EngineEvent::ProseAdded("Sun.".into())])
+        );
+    }
+}
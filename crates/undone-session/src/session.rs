@@ -0,0 +1,736 @@
+//! A UI-free game session: the world, the scene engine and the scheduler, plus
+//! the rules the runtime follows between scenes — continue, hub slot requests
+//! and time advancing once a time-consuming scene is done.
+
+use std::path::Path;
+
+use rand::rngs::SmallRng;
+use serde::{Deserialize, Serialize};
+use undone_domain::{NpcKey, SkillId, SkillValue, TimeSlot};
+use undone_packs::char_creation::{new_game, CharCreationConfig};
+use undone_packs::PackRegistry;
+use undone_scene::engine::{ActionView, EngineEvent, SceneEngine};
+use undone_scene::scheduler::{PickResult, Scheduler};
+use undone_world::World;
+
+use crate::content::LoadedRuntimeContent;
+use crate::contracts::validate_startup_contract;
+use crate::replay::{seeded_session, Recording, Replay, ReplayStep};
+
+/// Shown when neither the scheduler nor the opening scene has anything to run.
+pub const NO_SCENE_AVAILABLE: &str = "[No eligible scene is currently available.]";
+
+/// The world time a time-consuming scene started at. Continuing past the scene
+/// advances the slot only if nothing inside the scene already moved time on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SceneTimeAnchor {
+    pub week: u32,
+    pub day: u8,
+    pub time_slot: TimeSlot,
+}
+
+impl SceneTimeAnchor {
+    pub fn capture(world: &World) -> Self {
+        Self {
+            week: world.game_data.week,
+            day: world.game_data.day,
+            time_slot: world.game_data.time_slot,
+        }
+    }
+
+    pub fn matches_world(&self, world: &World) -> bool {
+        self.week == world.game_data.week
+            && self.day == world.game_data.day
+            && self.time_slot == world.game_data.time_slot
+    }
+}
+
+/// Start a scene and wire in the active NPCs so effects like `set_npc_role` and
+/// `add_npc_liking` can resolve their `npc = "m"` / `npc = "f"` references.
+///
+/// When `npc_role` is provided (from the schedule event), the NPC with that role
+/// is preferred as the active male/female. Falls back to the first NPC in the
+/// slotmap when no role is specified or no NPC has the requested role. `npc`
+/// (set when the scheduler answers queued NPC outreach) names the exact NPC and
+/// wins over both. Body-change notices the world is holding are reported
+/// before the scene's prose.
+pub fn start_scene(
+    engine: &mut SceneEngine,
+    world: &mut World,
+    registry: &PackRegistry,
+    scene_id: String,
+    npc_role: Option<&str>,
+    npc: Option<NpcKey>,
+) {
+    engine.report_body_notices(world);
+    if let Some(npc) = npc {
+        let (active_male, active_female) = match npc {
+            NpcKey::Male(key) => (Some(key), None),
+            NpcKey::Female(key) => (None, Some(key)),
+        };
+        engine.start_scene_with_bindings(scene_id, active_male, active_female, world, registry);
+        return;
+    }
+    let active_male = npc_role
+        .and_then(|role| {
+            world
+                .male_npcs
+                .iter()
+                .find(|(_, npc)| npc.core.roles.contains(role))
+                .map(|(key, _)| key)
+        })
+        .or_else(|| world.male_npcs.iter().next().map(|(key, _)| key));
+    let active_female = npc_role
+        .and_then(|role| {
+            world
+                .female_npcs
+                .iter()
+                .find(|(_, npc)| npc.core.roles.contains(role))
+                .map(|(key, _)| key)
+        })
+        .or_else(|| world.female_npcs.iter().next().map(|(key, _)| key));
+    engine.start_scene_with_bindings(scene_id, active_male, active_female, world, registry);
+}
+
+/// Structural-skill backfill for saves written before a structural skill
+/// existed. COMPOSURE was promoted to a structural skill in the looping-adult
+/// layer (save v7). A save predating it has no COMPOSURE entry, which would
+/// read as composure 0 (maximum loss-of-control) rather than the intended
+/// starting value — silently unlocking the reckless/spiral content gates. The
+/// value lives in one place (`char_creation::STARTING_COMPOSURE`). New games
+/// seed COMPOSURE during character creation and never need this.
+pub fn backfill_composure(world: &mut World, composure_id: SkillId) {
+    world.player.skills.entry(composure_id).or_insert_with(|| {
+        log::info!("backfilling missing COMPOSURE skill into loaded save");
+        SkillValue {
+            value: undone_packs::char_creation::STARTING_COMPOSURE,
+            modifier: 0,
+        }
+    });
+}
+
+/// Story-character backfill for saves written before a story character
+/// existed. Scenes reach story characters through `npc("base::jake")`, which
+/// fails with a bad NPC reference when the save has no NPC carrying that story
+/// id. The NPC already holding the character's role is adopted; a character
+/// with no such NPC is spawned as at new game.
+pub fn backfill_story_npcs(world: &mut World, registry: &mut PackRegistry) {
+    undone_packs::backfill_story_npcs(registry, &mut world.male_npcs, &mut world.female_npcs);
+}
+
+/// One player input, as a front end or a replay sends it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionCommand {
+    /// Move on once a scene has finished (or launch the first scene).
+    Continue,
+    ChooseAction(String),
+    JumpToScene(String),
+    /// Let a time slot pass while nothing is eligible, then look again.
+    Wait,
+}
+
+/// What one command produced, in order.
+#[derive(Debug, Clone, Default)]
+pub struct SessionStep {
+    pub events: Vec<EngineEvent>,
+    pub started_scene_id: Option<String>,
+    pub scene_finished: bool,
+    /// Nothing was eligible to run; the session is idle until time moves on.
+    pub no_scene_available: bool,
+    /// Where the started scene (or the idle state) begins in `events`. Earlier
+    /// events closed out the scene the command ran in, as when an action
+    /// requests a hub slot.
+    pub scene_start: usize,
+}
+
+pub type SessionResult = Result<SessionStep, String>;
+
+/// Everything a command can change in a session. The path finder branches
+/// from one of these and comes back to it; a front end hands over its live
+/// runtime as one through [`GameSession::from_state`].
+#[derive(Clone)]
+pub struct SessionState {
+    pub world: World,
+    pub engine: SceneEngine,
+    pub rng: SmallRng,
+    pub opening_scene: Option<String>,
+    pub current_scene_time_anchor: Option<SceneTimeAnchor>,
+    pub actions: Vec<ActionView>,
+    pub awaiting_continue: bool,
+}
+
+pub struct GameSession {
+    pub world: World,
+    pub registry: PackRegistry,
+    pub engine: SceneEngine,
+    pub scheduler: Scheduler,
+    pub rng: SmallRng,
+    /// The new-game opening scene, until the first continue launches it.
+    pub opening_scene: Option<String>,
+    pub current_scene_time_anchor: Option<SceneTimeAnchor>,
+    actions: Vec<ActionView>,
+    awaiting_continue: bool,
+    pub(crate) recording: Option<Recording>,
+}
+
+impl GameSession {
+    /// Create a character from `config` and stand ready to launch the first scene.
+    pub fn new_game(
+        content: LoadedRuntimeContent,
+        config: CharCreationConfig,
+        mut rng: SmallRng,
+    ) -> Result<Self, String> {
+        let startup_errors = validate_startup_contract(&content.registry, config.origin);
+        if !startup_errors.is_empty() {
+            return Err(format!(
+                "Character creation contract error(s):\n{}",
+                startup_errors.join("\n")
+            ));
+        }
+
+        let LoadedRuntimeContent {
+            mut registry,
+            scenes,
+            scheduler,
+        } = content;
+        let opening_scene = registry.opening_scene().map(str::to_owned);
+        let world = new_game(config, &mut registry, &mut rng);
+        Ok(Self {
+            world,
+            registry,
+            engine: SceneEngine::new(scenes),
+            scheduler,
+            rng,
+            opening_scene,
+            current_scene_time_anchor: None,
+            actions: Vec::new(),
+            awaiting_continue: false,
+            recording: None,
+        })
+    }
+
+    /// Like [`GameSession::new_game`] with a fixed seed, recording every
+    /// command sent through [`GameSession::apply`] so the run can be saved as
+    /// a [`Replay`].
+    pub fn new_recorded(
+        content: LoadedRuntimeContent,
+        config: CharCreationConfig,
+        seed: u64,
+    ) -> Result<Self, String> {
+        let recording = Recording {
+            seed,
+            config: config.clone(),
+            steps: Vec::new(),
+        };
+        let mut session = seeded_session(content, config, seed)?;
+        session.recording = Some(recording);
+        Ok(session)
+    }
+
+    /// The run so far as a replay, if this session is recording.
+    pub fn replay(&self) -> Option<Replay> {
+        self.recording
+            .as_ref()
+            .map(|recording| recording.to_replay(&self.world))
+    }
+
+    /// Wrap an existing world (a loaded save). The new-game opening scene is
+    /// never replayed.
+    pub fn from_world(
+        mut content: LoadedRuntimeContent,
+        mut world: World,
+        rng: SmallRng,
+    ) -> Result<Self, String> {
+        let composure_id = required_composure_skill(&content.registry)?;
+        backfill_composure(&mut world, composure_id);
+        backfill_story_npcs(&mut world, &mut content.registry);
+        Ok(Self {
+            world,
+            registry: content.registry,
+            engine: SceneEngine::new(content.scenes),
+            scheduler: content.scheduler,
+            rng,
+            opening_scene: None,
+            current_scene_time_anchor: None,
+            actions: Vec::new(),
+            awaiting_continue: false,
+            recording: None,
+        })
+    }
+
+    /// Pick up a runtime exactly where it is, mid-scene included.
+    pub fn from_state(registry: PackRegistry, scheduler: Scheduler, state: SessionState) -> Self {
+        Self {
+            world: state.world,
+            registry,
+            engine: state.engine,
+            scheduler,
+            rng: state.rng,
+            opening_scene: state.opening_scene,
+            current_scene_time_anchor: state.current_scene_time_anchor,
+            actions: state.actions,
+            awaiting_continue: state.awaiting_continue,
+            recording: None,
+        }
+    }
+
+    /// Open a save file as a new session. Call [`GameSession::resume`] to start playing.
+    pub fn load(
+        mut content: LoadedRuntimeContent,
+        save_path: &Path,
+        rng: SmallRng,
+    ) -> Result<Self, String> {
+        let world = undone_save::load_game(save_path, &mut content.registry)
+            .map_err(|e| format!("Load failed: {e}"))?;
+        Self::from_world(content, world, rng)
+    }
+
+    pub fn save(&self, save_path: &Path) -> Result<(), String> {
+        undone_save::save_game(&self.world, &self.registry, save_path)
+            .map_err(|e| format!("Save failed: {e}"))
+    }
+
+    /// Replace the world with a save file, then resume from it. A recording
+    /// stops here: a replay cannot reproduce the loaded world.
+    pub fn load_save(&mut self, save_path: &Path) -> SessionResult {
+        let mut world = undone_save::load_game(save_path, &mut self.registry)
+            .map_err(|e| format!("Load failed: {e}"))?;
+        backfill_composure(&mut world, required_composure_skill(&self.registry)?);
+        backfill_story_npcs(&mut world, &mut self.registry);
+        self.world = world;
+        self.recording = None;
+        self.resume()
+    }
+
+    /// Drop any running scene and pick the next one from the persisted world.
+    /// Stale scene frames and queued events never survive this.
+    pub fn resume(&mut self) -> SessionResult {
+        self.engine.reset_runtime();
+        self.opening_scene = None;
+        self.current_scene_time_anchor = None;
+        self.start_next_scene(false)
+    }
+
+    /// The actions the running scene currently offers.
+    pub fn actions(&self) -> &[ActionView] {
+        &self.actions
+    }
+
+    /// Whether the last scene has finished and the session waits for `Continue`.
+    pub fn awaiting_continue(&self) -> bool {
+        self.awaiting_continue
+    }
+
+    pub fn current_scene_id(&self) -> Option<String> {
+        self.engine.current_scene_id()
+    }
+
+    /// A copy of everything a command can change, to branch a second session
+    /// from with [`GameSession::from_state`].
+    pub fn state(&self) -> SessionState {
+        SessionState {
+            world: self.world.clone(),
+            engine: self.engine.clone(),
+            rng: self.rng.clone(),
+            opening_scene: self.opening_scene.clone(),
+            current_scene_time_anchor: self.current_scene_time_anchor,
+            actions: self.actions.clone(),
+            awaiting_continue: self.awaiting_continue,
+        }
+    }
+
+    /// Put the session back in `state`. A recording is left as it was.
+    pub(crate) fn restore(&mut self, state: SessionState) {
+        self.world = state.world;
+        self.engine = state.engine;
+        self.rng = state.rng;
+        self.opening_scene = state.opening_scene;
+        self.current_scene_time_anchor = state.current_scene_time_anchor;
+        self.actions = state.actions;
+        self.awaiting_continue = state.awaiting_continue;
+    }
+
+    /// Run one command. Recording sessions keep every command that succeeds.
+    pub fn apply(&mut self, command: &SessionCommand) -> SessionResult {
+        let result = match command {
+            SessionCommand::Continue => self.continue_flow(),
+            SessionCommand::ChooseAction(action_id) => self.choose_action(action_id),
+            SessionCommand::JumpToScene(scene_id) => self.jump_to_scene(scene_id),
+            SessionCommand::Wait => self.wait(),
+        };
+        if let (Some(recording), Ok(step)) = (self.recording.as_mut(), &result) {
+            recording.steps.push(ReplayStep::record(command, step));
+        }
+        result
+    }
+
+    pub fn continue_flow(&mut self) -> SessionResult {
+        let can_launch_initial = self.engine.current_scene_id().is_none();
+        if !self.awaiting_continue && !can_launch_initial {
+            return Err("Session is not awaiting continue".to_string());
+        }
+
+        if self.awaiting_continue {
+            self.consume_pending_scene_time();
+        }
+
+        self.start_next_scene(true)
+    }
+
+    pub fn choose_action(&mut self, action_id: &str) -> SessionResult {
+        let chosen = self
+            .actions
+            .iter()
+            .find(|action| action.id == action_id)
+            .ok_or_else(|| format!("Action '{action_id}' is not currently visible"))?;
+        if chosen.is_locked() {
+            return Err(format!("Action '{action_id}' is a spent check"));
+        }
+
+        let events = self
+            .engine
+            .advance_with_action(action_id, &mut self.world, &self.registry);
+        let requested_slot = events.iter().find_map(|event| {
+            if let EngineEvent::SlotRequested(slot) = event {
+                Some(slot.clone())
+            } else {
+                None
+            }
+        });
+        let mut step = self.absorb(events);
+
+        if let Some(slot_name) = requested_slot {
+            let next = self.start_requested_slot(&slot_name)?;
+            step.scene_start = step.events.len();
+            step.events.extend(next.events);
+            step.started_scene_id = next.started_scene_id;
+            step.scene_finished = next.scene_finished;
+            step.no_scene_available = next.no_scene_available;
+        }
+
+        Ok(step)
+    }
+
+    /// Advance one time slot with no scene running, then pick the next scene.
+    /// This is how a session gets past "no eligible scene".
+    pub fn wait(&mut self) -> SessionResult {
+        if self.awaiting_continue || self.engine.current_scene_id().is_some() {
+            return Err("Nothing to wait for: a scene is running or waiting to continue".into());
+        }
+
+        self.current_scene_time_anchor = None;
+        undone_packs::advance_time_slot(&mut self.world, &self.registry);
+        self.start_next_scene(false)
+    }
+
+    pub fn jump_to_scene(&mut self, scene_id: &str) -> SessionResult {
+        self.start_scene_internal(scene_id.to_string(), None, None, None)
+    }
+
+    /// Replay the running scene from its current definition — after a hot
+    /// reload, say — with the NPCs, roles and params it was started with.
+    pub fn restart_scene(&mut self) -> SessionResult {
+        let Some(scene_id) = self.engine.current_scene_id() else {
+            return Err("No scene is running".to_string());
+        };
+        if !self
+            .engine
+            .restart_current_scene(&self.world, &self.registry)
+        {
+            return Err(format!("Scene '{scene_id}' is no longer loaded"));
+        }
+
+        self.actions.clear();
+        self.awaiting_continue = false;
+        let events = self.engine.drain();
+        let mut step = self.absorb(events);
+        step.started_scene_id = Some(scene_id);
+        Ok(step)
+    }
+
+    fn start_scene_internal(
+        &mut self,
+        scene_id: String,
+        scene_time_anchor: Option<SceneTimeAnchor>,
+        npc_role: Option<&str>,
+        npc: Option<NpcKey>,
+    ) -> SessionResult {
+        if !self.engine.has_scene(&scene_id) {
+            return Err(format!("Unknown scene '{scene_id}'"));
+        }
+
+        self.current_scene_time_anchor = scene_time_anchor;
+        self.actions.clear();
+        self.awaiting_continue = false;
+        start_scene(
+            &mut self.engine,
+            &mut self.world,
+            &self.registry,
+            scene_id.clone(),
+            npc_role,
+            npc,
+        );
+        let events = self.engine.drain();
+        let mut step = self.absorb(events);
+        step.started_scene_id = Some(scene_id);
+        Ok(step)
+    }
+
+    fn start_next_scene(&mut self, allow_opening_scene: bool) -> SessionResult {
+        if let Some(result) = self
+            .scheduler
+            .pick_next(&self.world, &self.registry, &mut self.rng)
+        {
+            let _ = self.opening_scene.take();
+            return self.start_scheduled_scene(result);
+        }
+
+        if allow_opening_scene {
+            if let Some(scene_id) = self.opening_scene.take() {
+                return self.start_scene_internal(scene_id, None, None, None);
+            }
+        }
+
+        Ok(self.no_scene_available())
+    }
+
+    fn start_requested_slot(&mut self, slot_name: &str) -> SessionResult {
+        if let Some(result) =
+            self.scheduler
+                .pick(slot_name, &self.world, &self.registry, &mut self.rng)
+        {
+            return self.start_scheduled_scene(result);
+        }
+
+        Ok(self.no_scene_available())
+    }
+
+    fn start_scheduled_scene(&mut self, result: PickResult) -> SessionResult {
+        if !self.engine.has_scene(&result.scene_id) {
+            return Err(format!("Unknown scene '{}'", result.scene_id));
+        }
+
+        result.mark_started(&mut self.world);
+        let scene_time_anchor = result
+            .consumes_time
+            .then(|| SceneTimeAnchor::capture(&self.world));
+        self.start_scene_internal(
+            result.scene_id,
+            scene_time_anchor,
+            result.npc_role.as_deref(),
+            result.npc,
+        )
+    }
+
+    fn no_scene_available(&mut self) -> SessionStep {
+        self.current_scene_time_anchor = None;
+        self.actions.clear();
+        self.awaiting_continue = false;
+        SessionStep {
+            no_scene_available: true,
+            ..SessionStep::default()
+        }
+    }
+
+    /// Track the offered actions and whether the scene finished.
+    fn absorb(&mut self, events: Vec<EngineEvent>) -> SessionStep {
+        let mut scene_finished = false;
+        for event in &events {
+            match event {
+                EngineEvent::ActionsAvailable(actions) => self.actions = actions.clone(),
+                EngineEvent::SceneFinished => {
+                    self.actions.clear();
+                    scene_finished = true;
+                }
+                _ => {}
+            }
+        }
+        if scene_finished {
+            self.awaiting_continue = true;
+        }
+        SessionStep {
+            events,
+            started_scene_id: None,
+            scene_finished,
+            no_scene_available: false,
+            scene_start: 0,
+        }
+    }
+
+    fn consume_pending_scene_time(&mut self) {
+        let should_advance = self
+            .current_scene_time_anchor
+            .is_some_and(|anchor| anchor.matches_world(&self.world));
+        self.current_scene_time_anchor = None;
+        if should_advance {
+            undone_packs::advance_time_slot(&mut self.world, &self.registry);
+        }
+    }
+}
+
+fn required_composure_skill(registry: &PackRegistry) -> Result<SkillId, String> {
+    registry.composure_skill().map_err(|_| {
+        "Character creation contract error(s):\ncharacter creation requires skill 'COMPOSURE', but it is not registered".to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::load_runtime_content;
+    use crate::presets::{config_from_preset, preset_index};
+    use rand::SeedableRng;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn packs_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("packs")
+    }
+
+    fn robin_session() -> GameSession {
+        let content = load_runtime_content(&packs_dir()).unwrap();
+        let idx = preset_index(&content.registry, "Robin").expect("Robin preset");
+        let config = config_from_preset(&content.registry, idx);
+        GameSession::new_game(content, config, SmallRng::seed_from_u64(7)).unwrap()
+    }
+
+    fn play_first_actions_to_finish(session: &mut GameSession) {
+        for _ in 0..32 {
+            if session.awaiting_continue() {
+                return;
+            }
+            let action_id = session
+                .actions()
+                .iter()
+                .find(|action| !action.is_locked())
+                .expect("a running scene offers an action")
+                .id
+                .clone();
+            session.choose_action(&action_id).unwrap();
+        }
+        panic!("scene did not finish within 32 choices");
+    }
+
+    #[test]
+    fn first_continue_launches_the_route_opening_with_prose_and_actions() {
+        let mut session = robin_session();
+        let step = session.continue_flow().unwrap();
+
+        assert_eq!(
+            step.started_scene_id.as_deref(),
+            Some("base::workplace_arrival")
+        );
+        assert!(step
+            .events
+            .iter()
+            .any(|event| matches!(event, EngineEvent::ProseAdded(_))));
+        assert!(!session.actions().is_empty());
+        assert!(
+            session.continue_flow().is_err(),
+            "continue mid-scene must be refused"
+        );
+        assert!(
+            session.apply(&SessionCommand::Wait).is_err(),
+            "waiting mid-scene must be refused"
+        );
+    }
+
+    #[test]
+    fn finished_scene_waits_for_continue_then_picks_the_next() {
+        let mut session = robin_session();
+        session.continue_flow().unwrap();
+        play_first_actions_to_finish(&mut session);
+
+        assert!(session.awaiting_continue());
+        assert!(session.actions().is_empty());
+        let step = session.apply(&SessionCommand::Continue).unwrap();
+        assert_eq!(
+            step.started_scene_id.as_deref(),
+            Some("base::workplace_landlord")
+        );
+        assert!(!session.awaiting_continue());
+    }
+
+    #[test]
+    fn body_notices_come_before_the_next_scene_prose() {
+        let mut session = robin_session();
+        session
+            .world
+            .game_data
+            .body_notices
+            .push("Your hair brushes your collar now.".into());
+
+        let step = session.continue_flow().unwrap();
+        assert!(
+            matches!(step.events.first(), Some(EngineEvent::BodyChanged(text)) if text.contains("collar")),
+            "expected BodyChanged first, got {:?}",
+            step.events
+        );
+        assert!(session.world.game_data.body_notices.is_empty());
+    }
+
+    #[test]
+    fn a_requested_slot_starts_its_scene_after_the_actions_events() {
+        let mut session = robin_session();
+        session.jump_to_scene("base::plan_your_day").unwrap();
+
+        let step = session.choose_action("go_out").unwrap();
+        assert!(step.started_scene_id.is_some() || step.no_scene_available);
+        assert!(step.scene_start > 0 && step.scene_start <= step.events.len());
+        assert!(step.events[..step.scene_start]
+            .iter()
+            .any(|event| matches!(event, EngineEvent::SlotRequested(_))));
+        assert!(!step.events[step.scene_start..]
+            .iter()
+            .any(|event| matches!(event, EngineEvent::SlotRequested(_))));
+    }
+
+    #[test]
+    fn restart_replays_the_running_scene() {
+        let mut session = robin_session();
+        assert!(session.restart_scene().is_err());
+        session.jump_to_scene("base::plan_your_day").unwrap();
+
+        let step = session.restart_scene().unwrap();
+        assert_eq!(
+            step.started_scene_id.as_deref(),
+            Some("base::plan_your_day")
+        );
+        assert!(session.actions().iter().any(|action| action.id == "go_out"));
+    }
+
+    #[test]
+    fn load_save_resumes_from_the_persisted_world() {
+        let mut session = robin_session();
+        session.continue_flow().unwrap();
+        play_first_actions_to_finish(&mut session);
+
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let save_path = std::env::temp_dir().join(format!("undone_session_{unique}.json"));
+        session.save(&save_path).unwrap();
+
+        session.jump_to_scene("base::rain_shelter").unwrap();
+        let step = session.load_save(&save_path).unwrap();
+
+        assert_eq!(session.opening_scene, None);
+        assert_eq!(
+            step.started_scene_id.as_deref(),
+            Some("base::workplace_landlord")
+        );
+        assert!(session
+            .actions()
+            .iter()
+            .any(|action| action.id == "wait_him_out"));
+
+        std::fs::remove_file(save_path).unwrap();
+    }
+}
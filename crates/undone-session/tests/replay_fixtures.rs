@@ -0,0 +1,78 @@
+//! Every recorded run under `tests/replays/` must still play out the same:
+//! same scenes, same prose, same final world. Record a new fixture with
+//! `undone-cli --seed N --preset NAME --record crates/undone-session/tests/replays/NAME.json`.
+
+use std::path::PathBuf;
+
+use undone_session::{
+    config_from_preset, load_runtime_content, preset_index, run_replay, GameSession, Replay,
+    ReplayError, SessionCommand,
+};
+
+fn crate_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn packs_dir() -> PathBuf {
+    crate_dir()
+        .parent()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .join("packs")
+}
+
+#[test]
+fn recorded_runs_replay_unchanged() {
+    let mut fixtures: Vec<PathBuf> = std::fs::read_dir(crate_dir().join("tests/replays"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no replay fixtures found");
+
+    let failures: Vec<String> = fixtures
+        .iter()
+        .filter_map(|path| {
+            let content = load_runtime_content(&packs_dir()).unwrap();
+            let replay = Replay::read(path, &content.registry).unwrap();
+            run_replay(content, &replay)
+                .err()
+                .map(|error| format!("{}: {error}", path.display()))
+        })
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "replays diverged (re-record them if the change was intended):\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn replay_reports_the_first_step_whose_prose_changed() {
+    let content = load_runtime_content(&packs_dir()).unwrap();
+    let idx = preset_index(&content.registry, "Robin").unwrap();
+    let config = config_from_preset(&content.registry, idx);
+    let mut session = GameSession::new_recorded(content, config, 3).unwrap();
+    session.apply(&SessionCommand::Continue).unwrap();
+    let first_action = session.actions()[0].id.clone();
+    session
+        .apply(&SessionCommand::ChooseAction(first_action))
+        .unwrap();
+    assert!(
+        session.apply(&SessionCommand::Wait).is_err(),
+        "a refused command is not recorded"
+    );
+
+    let mut replay = session.replay().expect("recording session");
+    assert_eq!(replay.steps.len(), 2);
+    run_replay(load_runtime_content(&packs_dir()).unwrap(), &replay).unwrap();
+
+    replay.steps[1].prose_hash = "0000000000000000".into();
+    let error = run_replay(load_runtime_content(&packs_dir()).unwrap(), &replay).unwrap_err();
+    assert!(
+        matches!(error, ReplayError::ProseMismatch { step: 2, .. }),
+        "{error}"
+    );
+}
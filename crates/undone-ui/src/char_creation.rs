@@ -13,16 +13,15 @@ use crate::{AppSignals, PartialCharState};
 
 mod buttons;
 mod config;
-mod contracts;
 mod sections;
 mod signals;
 mod widgets;
 use buttons::*;
 use config::*;
 pub use config::{resolve_starting_traits, robin_quick_config};
-pub use contracts::*;
 use sections::*;
 use signals::*;
+pub use undone_session::contracts::*;
 use widgets::*;
 
 // ── public entry points ───────────────────────────────────────────────────────
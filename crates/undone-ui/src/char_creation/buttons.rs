@@ -10,13 +10,13 @@ use undone_domain::{
     PubicHairStyle, SkinTone, WaistSize, WetnessBaseline,
 };
 use undone_packs::{char_creation::CharCreationConfig, PresetData};
+use undone_session::contracts::*;
 
 use crate::game_state::{build_throwaway_game_state, start_game_checked, GameState, PreGameState};
 use crate::theme::{ThemeColors, UI_FONT_FAMILY};
 use crate::{AppPhase, AppSignals, PartialCharState};
 
 use super::config::*;
-use super::contracts::*;
 use super::signals::*;
 
 // ── Next button ───────────────────────────────────────────────────────────────
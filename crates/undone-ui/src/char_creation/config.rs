@@ -1,5 +1,5 @@
 //! Character-creation config building: presets, starting traits, defaults, bridge copy.
-use undone_domain::{Age, BeforeIdentity, BreastSize, PcOrigin, PlayerFigure, TraitId};
+use undone_domain::{Age, BreastSize, PcOrigin, PlayerFigure, TraitId};
 use undone_packs::{char_creation::CharCreationConfig, PackRegistry, PresetData};
 
 use crate::PartialCharState;
@@ -9,25 +9,7 @@ use crate::PartialCharState;
 // Presets are loaded from TOML files in packs/<pack>/data/presets/ and stored in
 // PackRegistry::presets(). The `PresetData` type is defined in undone-packs::preset.
 
-pub(crate) const CUSTOM_STARTING_TRAIT_IDS: &[&str] = &[
-    "SHY",
-    "CUTE",
-    "POSH",
-    "SULTRY",
-    "DOWN_TO_EARTH",
-    "BITCHY",
-    "REFINED",
-    "ROMANTIC",
-    "FLIRTY",
-    "AMBITIOUS",
-    "OUTGOING",
-    "OVERACTIVE_IMAGINATION",
-    "ANALYTICAL",
-    "CONFIDENT",
-    "SEXIST",
-    "HOMOPHOBIC",
-    "OBJECTIFYING",
-];
+pub(crate) use undone_session::config_from_preset;
 
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct FemFormDefaults {
@@ -180,64 +162,6 @@ pub fn robin_quick_config(registry: &PackRegistry) -> CharCreationConfig {
     config_from_preset(registry, idx)
 }
 
-/// Build a CharCreationConfig from a preset at the given index in `registry.presets()`.
-///
-/// Panics if the index is out of bounds.
-pub(crate) fn config_from_preset(registry: &PackRegistry, idx: usize) -> CharCreationConfig {
-    let p = &registry.presets()[idx];
-
-    let starting_traits: Vec<TraitId> = p
-        .trait_ids
-        .iter()
-        .filter_map(|trait_id| registry.resolve_trait(trait_id).ok())
-        .collect();
-
-    CharCreationConfig {
-        name_fem: p.name_fem.clone(),
-        name_masc: p.name_masc.clone(),
-        age: p.age,
-        race: p.race.clone(),
-        figure: p.figure,
-        breasts: p.breasts,
-        origin: p.origin,
-        before: Some(BeforeIdentity {
-            name: p.before_name.clone(),
-            age: p.before_age,
-            race: p.before_race.clone(),
-            sexuality: p.before_sexuality,
-            figure: p.before_figure,
-            height: p.before_height,
-            hair_colour: p.before_hair_colour,
-            eye_colour: p.before_eye_colour,
-            skin_tone: p.before_skin_tone,
-            penis_size: p.before_penis_size,
-            voice: p.before_voice,
-            traits: std::collections::HashSet::new(),
-        }),
-        starting_traits,
-        male_count: 6,
-        female_count: 3,
-        starting_flags: p.starting_flags.iter().cloned().collect(),
-        starting_arc_states: std::collections::HashMap::new(),
-        height: p.height,
-        butt: p.butt,
-        waist: p.waist,
-        lips: p.lips,
-        hair_colour: p.hair_colour,
-        hair_length: p.hair_length,
-        eye_colour: p.eye_colour,
-        skin_tone: p.skin_tone,
-        complexion: p.complexion,
-        appearance: p.appearance,
-        pubic_hair: p.pubic_hair,
-        natural_pubic_hair: p.natural_pubic_hair,
-        nipple_sensitivity: p.nipple_sensitivity,
-        clit_sensitivity: p.clit_sensitivity,
-        inner_labia: p.inner_labia,
-        wetness_baseline: p.wetness_baseline,
-    }
-}
-
 // ── PC origin helpers ─────────────────────────────────────────────────────────
 
 pub(crate) fn origin_from_idx(idx: u8) -> PcOrigin {
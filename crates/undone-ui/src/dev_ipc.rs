@@ -1,6 +1,8 @@
 use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use floem::action::exec_after;
@@ -8,9 +10,19 @@ use floem::kurbo::Size;
 use floem::prelude::SignalUpdate;
 use floem::WindowIdExt;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use undone_domain::{BoundedStat, SkillValue};
-
+use undone_scene::engine::EngineEvent;
+use undone_scene::script::api::ApiValue;
+use undone_session::{
+    console, reload_content, swap_content, GameSession, PackWatcher, PathLimits, PathSearch,
+    PathTarget,
+};
+
+use crate::dev_socket::{
+    address_file_path, listen_address, state_diff, DevRequest, DevSocket, INVALID_PARAMS,
+    METHOD_NOT_FOUND,
+};
 use crate::game_state::GameState;
 use crate::runtime_controller::RuntimeController;
 use crate::runtime_snapshot::{snapshot_runtime, RuntimeSnapshot};
@@ -28,10 +40,12 @@ pub enum DevCommand {
     GetRuntimeState,
     ChooseAction { action_id: String },
     ContinueScene,
+    FindPath(FindPathRequest),
     SetTab { tab: String },
     SetStat { stat: String, value: i32 },
     SetFlag { flag: String },
     RemoveFlag { flag: String },
+    ListFlags,
     AdvanceTime { weeks: u32 },
     SetWindowSize { width: f64, height: f64 },
     SetNpcLiking { npc_name: String, level: String },
@@ -41,6 +55,41 @@ pub enum DevCommand {
     SaveGame { name: String },
     LoadSave { name: String },
     ListSaves,
+    EvalExpression { expression: String },
+    RenderProse { template: String },
+    RunEffect { script: String },
+    CallWriteVerb(WriteVerbRequest),
+    GetWorld,
+    PatchWorld { patch: Value },
+    ReloadContent,
+    RestartScene,
+}
+
+/// Search from the live game for a way to reach a scene, a game flag or
+/// `ARC=STATE`, then fast-forward the game along it.
+#[derive(Debug, Deserialize)]
+pub struct FindPathRequest {
+    pub target: String,
+    /// Longest path to consider, in commands.
+    #[serde(default)]
+    pub max_steps: Option<usize>,
+    /// Keep only this many states per step instead of searching exhaustively.
+    #[serde(default)]
+    pub beam: Option<usize>,
+}
+
+/// One `REGISTRY` write verb call, e.g. `{"receiver": "npc", "npc":
+/// "base::jake", "method": "setRole", "args": ["ROLE_EX"]}`.
+#[derive(Debug, Deserialize)]
+pub struct WriteVerbRequest {
+    /// `w`, `gd`, `scene` or `npc`.
+    pub receiver: String,
+    pub method: String,
+    /// The `npc(ref)` ref for `npc` verbs: `m`, `f`, a role or a story id.
+    #[serde(default)]
+    pub npc: Option<String>,
+    #[serde(default)]
+    pub args: Vec<Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,6 +100,49 @@ pub struct DevCommandResponse {
     pub data: Option<serde_json::Value>,
 }
 
+/// One row of the dev panel's flag browser: a declared flag, a set one, or both.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlagBrowserEntry {
+    pub id: String,
+    pub description: Option<String>,
+    pub thread: Option<String>,
+    pub set: bool,
+    /// Who set the flag and when, e.g. `base::coffee_shop (week 1 day 2 Evening)`.
+    pub source: Option<String>,
+}
+
+/// Every declared game flag plus any set flag the packs don't declare,
+/// sorted by id.
+pub fn flag_browser(gs: &GameState) -> Vec<FlagBrowserEntry> {
+    let game_data = &gs.session.world.game_data;
+    let mut ids: Vec<String> = gs
+        .session
+        .registry
+        .flags()
+        .map(|def| def.id.clone())
+        .chain(game_data.flags.iter().cloned())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    ids.into_iter()
+        .map(|id| {
+            let def = gs.session.registry.get_flag(&id);
+            FlagBrowserEntry {
+                description: def.map(|def| def.description.clone()),
+                thread: def.and_then(|def| def.thread.clone()),
+                set: game_data.has_flag(&id),
+                source: game_data.flag_source(&id).map(|source| {
+                    format!(
+                        "{} (week {} day {} {:?})",
+                        source.source, source.at.week, source.at.day, source.at.slot
+                    )
+                }),
+                id,
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Serialize)]
 pub struct GameStateSnapshot {
     pub current_scene_id: Option<String>,
@@ -68,26 +160,227 @@ pub struct GameStateSnapshot {
     pub arc_states: Vec<(String, String)>,
 }
 
-pub fn start_polling(signals: AppSignals, gs: Rc<RefCell<GameState>>) {
+/// The running game's dev socket, once [`start_server`] has bound it.
+static SOCKET: OnceLock<DevSocket> = OnceLock::new();
+
+/// Listen for dev clients (see [`crate::dev_socket`]) and serve their
+/// commands from the UI thread. Does nothing outside dev mode.
+pub fn start_server(signals: AppSignals, gs: Rc<RefCell<GameState>>) {
     if !gs.borrow().dev_mode {
         return;
     }
-    schedule_poll(signals, gs);
+    if SOCKET.get().is_some() {
+        log::warn!("[dev-ipc] dev server already running");
+        return;
+    }
+    let (socket, requests) = match DevSocket::bind(&listen_address()) {
+        Ok(bound) => bound,
+        Err(err) => {
+            log::warn!("[dev-ipc] could not listen on {}: {err}", listen_address());
+            return;
+        }
+    };
+    let addr = socket.local_addr().to_string();
+    if let Err(err) = std::fs::write(address_file_path(), &addr) {
+        log::warn!(
+            "[dev-ipc] failed to write '{}': {err}",
+            address_file_path().display()
+        );
+    }
+    log::info!("[dev-ipc] listening on {addr}");
+    let _ = SOCKET.set(socket.clone());
+    schedule_serve(signals, gs, socket, Rc::new(requests), None);
+}
+
+/// The packs directory hot reload watches, once [`start_hot_reload`] has run.
+static PACKS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Watch `packs_dir` and reload it into the running game whenever a file
+/// under it changes. Does nothing outside dev mode.
+pub fn start_hot_reload(signals: AppSignals, gs: Rc<RefCell<GameState>>, packs_dir: PathBuf) {
+    if !gs.borrow().dev_mode {
+        return;
+    }
+    if PACKS_DIR.set(packs_dir.clone()).is_err() {
+        log::warn!("[hot-reload] already watching packs");
+        return;
+    }
+    log::info!("[hot-reload] watching '{}'", packs_dir.display());
+    schedule_hot_reload(signals, gs, PackWatcher::new(packs_dir));
+}
+
+/// Every 500 ms: reload the packs if anything under them changed, and tell
+/// subscribed dev clients how it went with a `reload` notification.
+fn schedule_hot_reload(signals: AppSignals, gs: Rc<RefCell<GameState>>, mut watcher: PackWatcher) {
+    exec_after(Duration::from_millis(500), move |_| {
+        if !gs.borrow().dev_mode {
+            return;
+        }
+        if watcher.poll() {
+            let response = reload_packs(&mut gs.borrow_mut(), signals, watcher.packs_dir());
+            if let Some(socket) = SOCKET.get() {
+                socket.notify(
+                    "reload",
+                    json!({ "success": response.success, "message": response.message }),
+                );
+            }
+            if response.success {
+                signals.dev_tick.update(|tick| *tick += 1);
+            }
+        }
+        schedule_hot_reload(signals, gs, watcher);
+    });
 }
 
-pub fn command_file_path() -> PathBuf {
-    std::env::temp_dir().join("undone-dev-cmd.json")
+/// Rebuild scenes, the schedule and pack data from `packs_dir` through the
+/// load-time gates and swap them into the running game. A rejected reload
+/// leaves the game as it was and shows its error in the dev panel. The
+/// running scene keeps its old definition until `restart_scene`.
+fn reload_packs(gs: &mut GameState, signals: AppSignals, packs_dir: &Path) -> DevCommandResponse {
+    let content = match reload_content(packs_dir, &gs.session.registry) {
+        Ok(content) => content,
+        Err(message) => {
+            log::warn!("[hot-reload] {message}");
+            signals.reload_error.set(Some(message.clone()));
+            return error_response(message);
+        }
+    };
+    let scene_count = content.scenes.len();
+    swap_content(
+        content,
+        &mut gs.session.registry,
+        &mut gs.session.engine,
+        &mut gs.session.scheduler,
+    );
+    signals.reload_error.set(None);
+    log::info!("[hot-reload] reloaded {scene_count} scenes");
+    success_runtime_response(
+        format!("Reloaded {scene_count} scenes"),
+        snapshot_runtime(signals, gs),
+    )
 }
 
-pub fn result_file_path() -> PathBuf {
-    std::env::temp_dir().join("undone-dev-result.json")
+/// The dev command a JSON-RPC request names: `method` is the command, and
+/// `params` its fields. Errs with a JSON-RPC error code and message.
+pub fn command_from_request(method: &str, params: Value) -> Result<DevCommand, (i64, String)> {
+    let mut fields = match params {
+        Value::Object(fields) => fields,
+        _ => return Err((INVALID_PARAMS, "params must be an object".to_string())),
+    };
+    fields.insert("command".to_string(), Value::String(method.to_string()));
+    serde_json::from_value(Value::Object(fields)).map_err(|err| {
+        let message = err.to_string();
+        if message.starts_with("unknown variant") {
+            (METHOD_NOT_FOUND, format!("unknown dev command '{method}'"))
+        } else {
+            (
+                INVALID_PARAMS,
+                format!("invalid params for '{method}': {message}"),
+            )
+        }
+    })
+}
+
+/// Send each engine event to subscribed dev clients as an `event`
+/// notification.
+pub fn publish_events(events: &[EngineEvent]) {
+    let Some(socket) = SOCKET.get() else {
+        return;
+    };
+    if !socket.has_subscribers() {
+        return;
+    }
+    for event in events {
+        match serde_json::to_value(event) {
+            Ok(params) => socket.notify("event", params),
+            Err(err) => log::warn!("[dev-ipc] failed to serialize event: {err}"),
+        }
+    }
+}
+
+/// Every 50 ms: run the requests that have arrived, then send subscribers the
+/// runtime state that changed since the last tick. `last_state` is `None`
+/// while nobody is subscribed, so a new subscriber's first `state_diff` is the
+/// whole snapshot.
+fn schedule_serve(
+    signals: AppSignals,
+    gs: Rc<RefCell<GameState>>,
+    socket: DevSocket,
+    requests: Rc<Receiver<DevRequest>>,
+    last_state: Option<Value>,
+) {
+    exec_after(Duration::from_millis(50), move |_| {
+        let (should_tick, last_state) = {
+            let mut gs = gs.borrow_mut();
+            if !gs.dev_mode {
+                return;
+            }
+            let mut should_tick = false;
+            while let Ok(request) = requests.try_recv() {
+                should_tick |= serve_request(&mut gs, signals, &socket, request);
+            }
+            let last_state = if socket.has_subscribers() {
+                publish_state_diff(&gs, signals, &socket, last_state)
+            } else {
+                None
+            };
+            (should_tick, last_state)
+        };
+        if should_tick {
+            signals.dev_tick.update(|tick| *tick += 1);
+        }
+        schedule_serve(signals, Rc::clone(&gs), socket, requests, last_state);
+    });
+}
+
+/// Run one request and answer it. Returns whether the command succeeded.
+fn serve_request(
+    gs: &mut GameState,
+    signals: AppSignals,
+    socket: &DevSocket,
+    request: DevRequest,
+) -> bool {
+    let command = match command_from_request(&request.method, request.params) {
+        Ok(command) => command,
+        Err((code, message)) => {
+            socket.respond_error(request.client, request.id, code, &message);
+            return false;
+        }
+    };
+    let response = execute_command(gs, signals, command);
+    let success = response.success;
+    match serde_json::to_value(&response) {
+        Ok(result) => socket.respond(request.client, request.id, result),
+        Err(err) => log::warn!("[dev-ipc] failed to serialize response: {err}"),
+    }
+    success
+}
+
+fn publish_state_diff(
+    gs: &GameState,
+    signals: AppSignals,
+    socket: &DevSocket,
+    last_state: Option<Value>,
+) -> Option<Value> {
+    let state = match serde_json::to_value(snapshot_runtime(signals, gs)) {
+        Ok(state) => state,
+        Err(err) => {
+            log::warn!("[dev-ipc] failed to serialize runtime state: {err}");
+            return last_state;
+        }
+    };
+    if let Some(diff) = state_diff(last_state.as_ref(), &state) {
+        socket.notify("state_diff", diff);
+    }
+    Some(state)
 }
 
 pub fn game_state_snapshot(gs: &GameState) -> GameStateSnapshot {
-    let mut flags: Vec<String> = gs.world.game_data.flags.iter().cloned().collect();
+    let mut flags: Vec<String> = gs.session.world.game_data.flags.iter().cloned().collect();
     flags.sort();
 
     let mut arc_states: Vec<(String, String)> = gs
+        .session
         .world
         .game_data
         .arc_states
@@ -96,21 +389,22 @@ pub fn game_state_snapshot(gs: &GameState) -> GameStateSnapshot {
         .collect();
     arc_states.sort_by(|a, b| a.0.cmp(&b.0));
 
-    let player = PlayerSnapshot::from_player(&gs.world, gs.femininity_id, gs.composure_id);
+    let player = PlayerSnapshot::from_player(&gs.session.world, gs.femininity_id, gs.composure_id);
 
     GameStateSnapshot {
-        current_scene_id: gs.engine.current_scene_id(),
-        week: gs.world.game_data.week,
-        day: gs.world.game_data.day,
-        time_slot: format!("{:?}", gs.world.game_data.time_slot),
+        current_scene_id: gs.session.engine.current_scene_id(),
+        week: gs.session.world.game_data.week,
+        day: gs.session.world.game_data.day,
+        time_slot: format!("{:?}", gs.session.world.game_data.time_slot),
         player_name: player.name,
         femininity: player.femininity,
         composure: gs
+            .session
             .registry
             .composure_skill()
-            .map(|id| gs.world.player.skill(id))
+            .map(|id| gs.session.world.player.skill(id))
             .unwrap_or(0),
-        desire: gs.world.game_data.desire(),
+        desire: gs.session.world.game_data.desire(),
         money: player.money,
         stress: player.stress,
         anxiety: player.anxiety,
@@ -148,10 +442,12 @@ pub fn execute_command(
         },
         DevCommand::ChooseAction { action_id } => choose_action(gs, signals, &action_id),
         DevCommand::ContinueScene => continue_scene(gs, signals),
+        DevCommand::FindPath(request) => find_path(gs, signals, request),
         DevCommand::SetTab { tab } => set_tab(gs, signals, &tab),
         DevCommand::SetStat { stat, value } => set_stat(gs, signals, &stat, value),
         DevCommand::SetFlag { flag } => set_flag(gs, signals, &flag),
         DevCommand::RemoveFlag { flag } => remove_flag(gs, signals, &flag),
+        DevCommand::ListFlags => list_flags(gs),
         DevCommand::AdvanceTime { weeks } => advance_time(gs, weeks),
         DevCommand::SetWindowSize { width, height } => set_window_size(gs, signals, width, height),
         DevCommand::SetNpcLiking { npc_name, level } => set_npc_liking(gs, &npc_name, &level),
@@ -161,111 +457,50 @@ pub fn execute_command(
         DevCommand::SaveGame { name } => save_game(gs, &name),
         DevCommand::LoadSave { name } => load_save(gs, signals, &name),
         DevCommand::ListSaves => list_saves(),
-    }
-}
-
-fn schedule_poll(signals: AppSignals, gs: Rc<RefCell<GameState>>) {
-    exec_after(Duration::from_millis(100), move |_| {
-        let should_tick = {
-            let mut gs = gs.borrow_mut();
-            if gs.dev_mode {
-                poll_once(&mut gs, signals)
-            } else {
-                return;
-            }
-        };
-        if should_tick {
-            signals.dev_tick.update(|tick| *tick += 1);
+        DevCommand::EvalExpression { expression } => eval_expression(gs, &expression),
+        DevCommand::RenderProse { template } => render_prose(gs, &template),
+        DevCommand::RunEffect { script } => {
+            let result = console::run_effect(
+                &script,
+                &mut gs.session.world,
+                &mut gs.session.engine,
+                &gs.session.registry,
+            );
+            world_edited(gs, signals, result.map(|()| "Ran effect".to_string()))
         }
-        schedule_poll(signals, Rc::clone(&gs));
-    });
-}
-
-fn poll_once(gs: &mut GameState, signals: AppSignals) -> bool {
-    let command_path = command_file_path();
-    let result_path = result_file_path();
-    poll_once_paths(gs, signals, &command_path, &result_path)
-}
-
-fn poll_once_paths(
-    gs: &mut GameState,
-    signals: AppSignals,
-    command_path: &Path,
-    result_path: &Path,
-) -> bool {
-    if !command_path.exists() {
-        return false;
-    }
-
-    let response = match std::fs::read_to_string(&command_path) {
-        Ok(raw) => match serde_json::from_str::<DevCommand>(&raw) {
-            Ok(command) => execute_command(gs, signals, command),
-            Err(err) => DevCommandResponse {
-                success: false,
-                message: format!("Invalid dev command: {err}"),
-                data: None,
+        DevCommand::CallWriteVerb(request) => {
+            let call = format!("{}.{}", request.receiver, request.method);
+            let result = console::call_write_verb(
+                &request.receiver,
+                &request.method,
+                request.npc.as_deref(),
+                &request.args,
+                &mut gs.session.world,
+                &mut gs.session.engine,
+                &gs.session.registry,
+            );
+            world_edited(gs, signals, result.map(|()| format!("Called {call}")))
+        }
+        DevCommand::GetWorld => match console::world_json(&gs.session.world) {
+            Ok(world) => DevCommandResponse {
+                success: true,
+                message: "World captured".to_string(),
+                data: Some(world),
             },
+            Err(message) => error_response(message),
         },
-        Err(err) => DevCommandResponse {
-            success: false,
-            message: format!("Failed to read dev command: {err}"),
-            data: None,
-        },
-    };
-
-    if let Err(err) = write_dev_result(result_path, &response) {
-        log::warn!(
-            "[dev-ipc] failed to write result '{}': {}",
-            result_path.display(),
-            err
-        );
-        return false;
-    }
-
-    if let Err(err) = std::fs::remove_file(command_path) {
-        log::warn!(
-            "[dev-ipc] failed to remove command '{}': {}",
-            command_path.display(),
-            err
-        );
-    }
-
-    response.success
-}
-
-fn write_dev_result(result_path: &Path, response: &DevCommandResponse) -> Result<(), String> {
-    let payload = serde_json::to_string(&response).unwrap_or_else(|err| {
-        format!(
-            r#"{{"success":false,"message":"Failed to serialize dev response: {}"}}"#,
-            err
-        )
-    });
-    let tmp_path = result_path.with_extension("tmp");
-    std::fs::write(&tmp_path, &payload)
-        .map_err(|err| format!("write temp '{}': {err}", tmp_path.display()))?;
-
-    match std::fs::rename(&tmp_path, result_path) {
-        Ok(()) => Ok(()),
-        Err(first_err) if result_path.exists() => {
-            std::fs::remove_file(result_path).map_err(|err| {
-                format!(
-                    "replace existing '{}' after rename failed ({first_err}): {err}",
-                    result_path.display()
-                )
-            })?;
-            std::fs::rename(&tmp_path, result_path).map_err(|err| {
-                format!(
-                    "rename temp '{}' to '{}' after removing old result (initial rename failed: {first_err}): {err}",
-                    tmp_path.display(),
-                    result_path.display()
-                )
-            })
+        DevCommand::PatchWorld { patch } => {
+            let result = console::patch_world(&mut gs.session.world, &patch);
+            world_edited(gs, signals, result.map(|()| "Patched world".to_string()))
+        }
+        DevCommand::ReloadContent => {
+            let packs_dir = PACKS_DIR
+                .get()
+                .cloned()
+                .unwrap_or_else(undone_session::resolve_packs_dir);
+            reload_packs(gs, signals, &packs_dir)
         }
-        Err(err) => Err(format!(
-            "rename temp '{}' to '{}': {err}",
-            tmp_path.display(),
-            result_path.display()
-        )),
+        DevCommand::RestartScene => restart_scene(gs, signals),
     }
 }
 
@@ -290,6 +525,20 @@ fn choose_action(gs: &mut GameState, signals: AppSignals, action_id: &str) -> De
     }
 }
 
+fn restart_scene(gs: &mut GameState, signals: AppSignals) -> DevCommandResponse {
+    let mut controller = RuntimeController::new(gs, signals);
+    match controller.restart_scene() {
+        Ok(outcome) => success_runtime_response(
+            format!(
+                "Restarted scene '{}'",
+                outcome.started_scene_id.unwrap_or_default()
+            ),
+            controller.snapshot(),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
 fn continue_scene(gs: &mut GameState, signals: AppSignals) -> DevCommandResponse {
     let mut controller = RuntimeController::new(gs, signals);
     match controller.continue_flow() {
@@ -298,6 +547,63 @@ fn continue_scene(gs: &mut GameState, signals: AppSignals) -> DevCommandResponse
     }
 }
 
+/// Search from the live runtime, mid-scene included, then play the path
+/// through the runtime controller so the story panel shows where it led.
+fn find_path(
+    gs: &mut GameState,
+    signals: AppSignals,
+    request: FindPathRequest,
+) -> DevCommandResponse {
+    let mut session = GameSession::from_state(
+        gs.session.registry.clone(),
+        gs.session.scheduler.clone(),
+        gs.session.state(),
+    );
+    let target = match PathTarget::parse(&request.target, &session.engine) {
+        Ok(target) => target,
+        Err(message) => return error_response(message),
+    };
+    let mut limits = PathLimits {
+        beam_width: request.beam,
+        ..PathLimits::default()
+    };
+    if let Some(max_steps) = request.max_steps {
+        limits.max_steps = max_steps;
+    }
+
+    let found = match undone_session::find_path(&mut session, &target, limits) {
+        Ok(PathSearch::Found(found)) => found,
+        Ok(PathSearch::NotFound(report)) => {
+            return DevCommandResponse {
+                success: false,
+                message: format!("{target}: {report}"),
+                data: serde_json::to_value(report).ok(),
+            };
+        }
+        Err(message) => return error_response(message),
+    };
+
+    let mut controller = RuntimeController::new(gs, signals);
+    for (number, step) in found.steps.iter().enumerate() {
+        if let Err(message) = controller.apply(&step.command) {
+            return error_response(format!(
+                "Path step {} ({step}) failed in the live game: {message}",
+                number + 1
+            ));
+        }
+    }
+
+    DevCommandResponse {
+        success: true,
+        message: format!("Reached {target} in {} steps", found.steps.len()),
+        data: Some(json!({
+            "path": found.steps,
+            "states_explored": found.states_explored,
+            "runtime": controller.snapshot(),
+        })),
+    }
+}
+
 fn set_tab(gs: &mut GameState, signals: AppSignals, tab: &str) -> DevCommandResponse {
     let normalized = tab.trim().to_lowercase();
     let target = match normalized.as_str() {
@@ -322,6 +628,60 @@ fn set_tab(gs: &mut GameState, signals: AppSignals, tab: &str) -> DevCommandResp
     )
 }
 
+fn eval_expression(gs: &GameState, expression: &str) -> DevCommandResponse {
+    match console::eval_expression(
+        expression,
+        &gs.session.world,
+        &gs.session.engine,
+        &gs.session.registry,
+    ) {
+        Ok(value) => DevCommandResponse {
+            success: true,
+            message: format!("{expression} evaluated"),
+            data: Some(match value {
+                ApiValue::Bool(b) => json!(b),
+                ApiValue::Int(i) => json!(i),
+                ApiValue::Str(s) => json!(s),
+            }),
+        },
+        Err(message) => error_response(message),
+    }
+}
+
+fn render_prose(gs: &GameState, template: &str) -> DevCommandResponse {
+    match console::render_template(
+        template,
+        &gs.session.world,
+        &gs.session.engine,
+        &gs.session.registry,
+    ) {
+        Ok(prose) => DevCommandResponse {
+            success: true,
+            message: "Prose rendered".to_string(),
+            data: Some(json!(prose)),
+        },
+        Err(message) => error_response(message),
+    }
+}
+
+/// Answer a world edit with the runtime state after it, refreshing the
+/// sidebar's player stats first.
+fn world_edited(
+    gs: &mut GameState,
+    signals: AppSignals,
+    result: Result<String, String>,
+) -> DevCommandResponse {
+    signals.player.set(PlayerSnapshot::from_player(
+        &gs.session.world,
+        gs.femininity_id,
+        gs.composure_id,
+    ));
+    match result {
+        Ok(message) => success_runtime_response(message, snapshot_runtime(signals, gs)),
+        Err(message) => error_response(message),
+    }
+}
+
 fn success_runtime_response(message: String, snapshot: RuntimeSnapshot) -> DevCommandResponse {
     DevCommandResponse {
         success: true,
@@ -345,21 +705,23 @@ fn set_stat(gs: &mut GameState, signals: AppSignals, stat: &str, value: i32) ->
     let normalized = stat.trim().to_lowercase();
     match normalized.as_str() {
         "money" => {
-            gs.world.player.money = value;
+            gs.session.world.player.money = value;
         }
         "stress" => {
-            gs.world.player.stress = BoundedStat::new(value);
+            gs.session.world.player.stress = BoundedStat::new(value);
         }
         "anxiety" => {
-            gs.world.player.anxiety = BoundedStat::new(value);
+            gs.session.world.player.anxiety = BoundedStat::new(value);
         }
         "femininity" => {
             let clamped = gs
+                .session
                 .registry
                 .get_skill_def(&gs.femininity_id)
                 .map(|def| value.clamp(def.min, def.max))
                 .unwrap_or(value);
-            gs.world
+            gs.session
+                .world
                 .player
                 .skills
                 .entry(gs.femininity_id)
@@ -381,7 +743,7 @@ fn set_stat(gs: &mut GameState, signals: AppSignals, stat: &str, value: i32) ->
     }
 
     signals.player.set(PlayerSnapshot::from_player(
-        &gs.world,
+        &gs.session.world,
         gs.femininity_id,
         gs.composure_id,
     ));
@@ -403,7 +765,7 @@ fn set_flag(gs: &mut GameState, _signals: AppSignals, flag: &str) -> DevCommandR
         };
     }
 
-    gs.world.game_data.set_flag(trimmed.to_string());
+    gs.session.world.game_data.set_flag_from(trimmed, "dev");
     DevCommandResponse {
         success: true,
         message: format!("Set flag '{trimmed}'"),
@@ -421,7 +783,7 @@ fn remove_flag(gs: &mut GameState, _signals: AppSignals, flag: &str) -> DevComma
         };
     }
 
-    gs.world.game_data.remove_flag(trimmed);
+    gs.session.world.game_data.remove_flag(trimmed);
     DevCommandResponse {
         success: true,
         message: format!("Removed flag '{trimmed}'"),
@@ -432,7 +794,7 @@ fn remove_flag(gs: &mut GameState, _signals: AppSignals, flag: &str) -> DevComma
 fn advance_time(gs: &mut GameState, weeks: u32) -> DevCommandResponse {
     let slots = weeks * 28; // 4 slots/day × 7 days/week
     for _ in 0..slots {
-        gs.world.game_data.advance_time_slot();
+        undone_packs::advance_time_slot(&mut gs.session.world, &gs.session.registry);
     }
     DevCommandResponse {
         success: true,
@@ -485,10 +847,10 @@ fn set_all_npc_liking(gs: &mut GameState, level: &str) -> DevCommandResponse {
         }
     };
 
-    for (_, npc) in gs.world.male_npcs.iter_mut() {
+    for (_, npc) in gs.session.world.male_npcs.iter_mut() {
         npc.core.npc_liking = liking;
     }
-    for (_, npc) in gs.world.female_npcs.iter_mut() {
+    for (_, npc) in gs.session.world.female_npcs.iter_mut() {
         npc.core.npc_liking = liking;
     }
 
@@ -499,8 +861,20 @@ fn set_all_npc_liking(gs: &mut GameState, level: &str) -> DevCommandResponse {
     }
 }
 
+fn list_flags(gs: &GameState) -> DevCommandResponse {
+    let entries = flag_browser(gs);
+    DevCommandResponse {
+        success: true,
+        message: format!("{} flags", entries.len()),
+        data: Some(
+            serde_json::to_value(entries)
+                .unwrap_or_else(|_| json!({"error": "failed to serialize flag list"})),
+        ),
+    }
+}
+
 fn list_scenes(gs: &GameState) -> DevCommandResponse {
-    let summaries = gs.engine.all_scene_summaries();
+    let summaries = gs.session.engine.all_scene_summaries();
     DevCommandResponse {
         success: true,
         message: format!("{} scenes loaded", summaries.len()),
@@ -512,7 +886,7 @@ fn list_scenes(gs: &GameState) -> DevCommandResponse {
 }
 
 fn get_scene_info(gs: &GameState, scene_id: &str) -> DevCommandResponse {
-    match gs.engine.scene_info(scene_id) {
+    match gs.session.engine.scene_info(scene_id) {
         Some(info) => DevCommandResponse {
             success: true,
             message: format!("Scene '{scene_id}' info"),
@@ -540,7 +914,7 @@ fn save_game(gs: &GameState, name: &str) -> DevCommandResponse {
     }
 
     let path = dir.join(format!("{trimmed}.json"));
-    match undone_save::save_game(&gs.world, &gs.registry, &path) {
+    match undone_save::save_game(&gs.session.world, &gs.session.registry, &path) {
         Ok(()) => DevCommandResponse {
             success: true,
             message: format!("Saved to '{}'", path.display()),
@@ -658,7 +1032,7 @@ fn set_npc_liking(gs: &mut GameState, npc_name: &str, level: &str) -> DevCommand
     let name_lower = npc_name.trim().to_lowercase();
     let mut found = false;
 
-    for (_, npc) in gs.world.male_npcs.iter_mut() {
+    for (_, npc) in gs.session.world.male_npcs.iter_mut() {
         if npc.core.name.to_lowercase() == name_lower {
             npc.core.npc_liking = liking;
             found = true;
@@ -666,7 +1040,7 @@ fn set_npc_liking(gs: &mut GameState, npc_name: &str, level: &str) -> DevCommand
         }
     }
     if !found {
-        for (_, npc) in gs.world.female_npcs.iter_mut() {
+        for (_, npc) in gs.session.world.female_npcs.iter_mut() {
             if npc.core.name.to_lowercase() == name_lower {
                 npc.core.npc_liking = liking;
                 found = true;
@@ -698,7 +1072,6 @@ mod tests {
     use crate::runtime_controller::RuntimeController;
     use floem::prelude::SignalGet;
     use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
 
     fn packs_dir() -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -719,66 +1092,72 @@ mod tests {
         start_game(pre, config, true)
     }
 
-    fn temp_dev_ipc_dir(name: &str) -> PathBuf {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        let dir = std::env::temp_dir().join(format!(
-            "undone_dev_ipc_{name}_{}_{}",
-            std::process::id(),
-            unique
-        ));
-        std::fs::create_dir_all(&dir).unwrap();
-        dir
-    }
-
     fn boot_runtime(gs: &mut GameState, signals: AppSignals) {
         let mut controller = RuntimeController::new(gs, signals);
         controller.continue_flow().unwrap();
     }
 
     #[test]
-    fn poll_once_writes_invalid_command_response_before_removing_command() {
-        let mut gs = test_game_state();
-        let signals = AppSignals::new();
-        let dir = temp_dev_ipc_dir("invalid_command");
-        let command_path = dir.join("cmd.json");
-        let result_path = dir.join("result.json");
-        std::fs::write(&command_path, r#"{"command":"not_real"}"#).unwrap();
-
-        let tick = poll_once_paths(&mut gs, signals, &command_path, &result_path);
-
-        assert!(!tick);
-        assert!(!command_path.exists());
-        let response: DevCommandResponse =
-            serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
-        assert!(!response.success);
-        assert!(response.message.contains("Invalid dev command"));
+    fn command_from_request_reads_the_method_as_the_command() {
+        let command =
+            command_from_request("set_stat", json!({"stat": "stress", "value": 3})).unwrap();
+        assert!(matches!(command, DevCommand::SetStat { ref stat, value: 3 } if stat == "stress"));
+        assert!(matches!(
+            command_from_request("get_state", json!({})),
+            Ok(DevCommand::GetState)
+        ));
 
-        let _ = std::fs::remove_dir_all(dir);
+        let (code, _) = command_from_request("not_real", json!({})).unwrap_err();
+        assert_eq!(code, METHOD_NOT_FOUND);
+        let (code, message) =
+            command_from_request("set_stat", json!({"stat": "stress"})).unwrap_err();
+        assert_eq!(code, INVALID_PARAMS);
+        assert!(message.contains("value"), "{message}");
     }
 
     #[test]
-    fn poll_once_keeps_command_when_result_cannot_be_written() {
+    fn execute_find_path_fast_forwards_to_the_target_scene() {
         let mut gs = test_game_state();
         let signals = AppSignals::new();
-        let dir = temp_dev_ipc_dir("result_failure");
-        let command_path = dir.join("cmd.json");
-        let result_path = dir.join("result.json");
-        std::fs::write(&command_path, r#"{"command":"get_state"}"#).unwrap();
-        std::fs::create_dir(&result_path).unwrap();
+        boot_runtime(&mut gs, signals);
 
-        let tick = poll_once_paths(&mut gs, signals, &command_path, &result_path);
+        let response = execute_command(
+            &mut gs,
+            signals,
+            DevCommand::FindPath(FindPathRequest {
+                target: "base::workplace_landlord".to_string(),
+                max_steps: None,
+                beam: None,
+            }),
+        );
 
-        assert!(!tick);
-        assert!(
-            command_path.exists(),
-            "command input should remain available for retry/debug when result persistence fails"
+        assert!(response.success, "{}", response.message);
+        assert_eq!(
+            gs.session.engine.current_scene_id().as_deref(),
+            Some("base::workplace_landlord")
+        );
+        let data = response.data.unwrap();
+        assert!(!data["path"].as_array().unwrap().is_empty());
+        assert_eq!(
+            data["runtime"]["current_scene_id"],
+            json!("base::workplace_landlord")
         );
-        assert!(result_path.is_dir());
 
-        let _ = std::fs::remove_dir_all(dir);
+        let response = execute_command(
+            &mut gs,
+            signals,
+            DevCommand::FindPath(FindPathRequest {
+                target: "NO_SCENE_EVER_SETS_THIS".to_string(),
+                max_steps: Some(2),
+                beam: None,
+            }),
+        );
+        assert!(!response.success);
+        assert!(
+            response.message.contains("not reachable"),
+            "{}",
+            response.message
+        );
     }
 
     #[test]
@@ -807,6 +1186,7 @@ mod tests {
             id: "stale".into(),
             label: "Stale".into(),
             detail: "stale detail".into(),
+            check: None,
         }]);
         signals.awaiting_continue.set(true);
         signals.scroll_gen.set(7);
@@ -844,7 +1224,82 @@ mod tests {
         );
 
         assert!(response.success);
-        assert_eq!(gs.world.player.stress.get(), 100);
+        assert_eq!(gs.session.world.player.stress.get(), 100);
+    }
+
+    #[test]
+    fn execute_world_edits_show_up_in_expressions() {
+        let mut gs = test_game_state();
+        let signals = AppSignals::new();
+
+        let response = execute_command(
+            &mut gs,
+            signals,
+            DevCommand::CallWriteVerb(WriteVerbRequest {
+                receiver: "w".to_string(),
+                method: "addTrait".to_string(),
+                npc: None,
+                args: vec![json!("SHY")],
+            }),
+        );
+        assert!(response.success, "{}", response.message);
+
+        let response = execute_command(
+            &mut gs,
+            signals,
+            DevCommand::PatchWorld {
+                patch: json!({ "player": { "money": 777 } }),
+            },
+        );
+        assert!(response.success, "{}", response.message);
+        assert_eq!(signals.player.get_untracked().money, 777);
+
+        let response = execute_command(
+            &mut gs,
+            signals,
+            DevCommand::EvalExpression {
+                expression: r#"w.hasTrait("SHY") && w.getMoney() == 777"#.to_string(),
+            },
+        );
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.data, Some(json!(true)));
+
+        let response = execute_command(
+            &mut gs,
+            signals,
+            DevCommand::RunEffect {
+                script: r#"w.addTrait("NOT_A_TRAIT")"#.to_string(),
+            },
+        );
+        assert!(!response.success);
+    }
+
+    #[test]
+    fn reload_packs_reports_errors_and_keeps_the_game_running() {
+        let mut gs = test_game_state();
+        let signals = AppSignals::new();
+        let response = execute_command(&mut gs, signals, DevCommand::RestartScene);
+        assert!(!response.success);
+
+        jump_to_scene(&mut gs, signals, "base::rain_shelter");
+
+        let response = reload_packs(&mut gs, signals, &packs_dir().join("missing"));
+        assert!(!response.success);
+        assert_eq!(signals.reload_error.get_untracked(), Some(response.message));
+        assert_eq!(
+            gs.session.engine.current_scene_id().as_deref(),
+            Some("base::rain_shelter")
+        );
+
+        let response = reload_packs(&mut gs, signals, &packs_dir());
+        assert!(response.success, "{}", response.message);
+        assert_eq!(signals.reload_error.get_untracked(), None);
+
+        signals.story.set(String::new());
+        let response = execute_command(&mut gs, signals, DevCommand::RestartScene);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.message, "Restarted scene 'base::rain_shelter'");
+        assert!(!signals.story.get_untracked().is_empty());
     }
 
     #[test]
@@ -863,6 +1318,37 @@ mod tests {
         assert!(data.get("game_flags").is_some());
     }
 
+    #[test]
+    fn flag_browser_lists_declared_flags_with_their_source() {
+        let mut gs = test_game_state();
+        let signals = AppSignals::new();
+        execute_command(
+            &mut gs,
+            signals,
+            DevCommand::SetFlag {
+                flag: "MET_JAKE".to_string(),
+            },
+        );
+
+        let entries = flag_browser(&gs);
+        let met_jake = entries.iter().find(|e| e.id == "MET_JAKE").unwrap();
+        assert!(met_jake.set);
+        assert!(met_jake.description.is_some());
+        assert_eq!(met_jake.thread.as_deref(), Some("Jake romance"));
+        assert!(met_jake.source.as_deref().unwrap().starts_with("dev "));
+
+        let unset = entries.iter().find(|e| e.id == "JAKE_KISSED").unwrap();
+        assert!(!unset.set);
+        assert_eq!(unset.source, None);
+
+        let response = execute_command(&mut gs, signals, DevCommand::ListFlags);
+        assert!(response.success);
+        assert_eq!(
+            response.data.unwrap().as_array().unwrap().len(),
+            entries.len()
+        );
+    }
+
     #[test]
     fn execute_get_runtime_state_returns_visible_story_and_actions() {
         let mut gs = test_game_state();
@@ -885,12 +1371,12 @@ mod tests {
     fn execute_advance_time_increments_week() {
         let mut gs = test_game_state();
         let signals = AppSignals::new();
-        let week_before = gs.world.game_data.week;
+        let week_before = gs.session.world.game_data.week;
 
         let response = execute_command(&mut gs, signals, DevCommand::AdvanceTime { weeks: 2 });
 
         assert!(response.success);
-        assert_eq!(gs.world.game_data.week, week_before + 2);
+        assert_eq!(gs.session.world.game_data.week, week_before + 2);
     }
 
     #[test]
@@ -1045,7 +1531,7 @@ mod tests {
         let signals = AppSignals::new();
 
         // Confirm precondition: 2+ NPCs spawned by robin_quick_config (6 male + 3 female).
-        let total_npcs = gs.world.male_npcs.len() + gs.world.female_npcs.len();
+        let total_npcs = gs.session.world.male_npcs.len() + gs.session.world.female_npcs.len();
         assert!(
             total_npcs >= 2,
             "expected 2+ NPCs from test_game_state, got {total_npcs}"
@@ -1060,7 +1546,7 @@ mod tests {
         );
 
         assert!(response.success);
-        for (_, npc) in gs.world.male_npcs.iter() {
+        for (_, npc) in gs.session.world.male_npcs.iter() {
             assert_eq!(
                 npc.core.npc_liking,
                 LikingLevel::Close,
@@ -1068,7 +1554,7 @@ mod tests {
                 npc.core.name
             );
         }
-        for (_, npc) in gs.world.female_npcs.iter() {
+        for (_, npc) in gs.session.world.female_npcs.iter() {
             assert_eq!(
                 npc.core.npc_liking,
                 LikingLevel::Close,
@@ -5,7 +5,10 @@ use floem::prelude::*;
 use floem::reactive::{create_effect, RwSignal};
 use floem::views::dyn_stack;
 
-use crate::dev_ipc::{execute_command, game_state_snapshot, runtime_state_snapshot, DevCommand};
+use crate::dev_ipc::{
+    execute_command, flag_browser, game_state_snapshot, runtime_state_snapshot, DevCommand,
+    FlagBrowserEntry,
+};
 use crate::game_state::GameState;
 use crate::layout::{DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH};
 use crate::runtime_snapshot::RuntimeSnapshot;
@@ -109,7 +112,7 @@ pub fn dev_panel(signals: AppSignals, gs: Rc<RefCell<GameState>>) -> impl View {
                             let _ = ctx.signals.dev_tick.get();
                             let ids = {
                                 let gs_ref = ctx.gs.borrow();
-                                gs_ref.engine.scene_ids()
+                                gs_ref.session.engine.scene_ids()
                             };
                             filter_scene_ids(ids, &get_or_default(filter))
                         }
@@ -166,7 +169,7 @@ pub fn dev_panel(signals: AppSignals, gs: Rc<RefCell<GameState>>) -> impl View {
         v_stack((
             h_stack((
                 text_input(flag_input)
-                    .placeholder("GAME_FLAG")
+                    .placeholder("Filter or type a GAME_FLAG")
                     .style(input_style(signals)),
                 action_button("Set", signals, {
                     let ctx = ctx.clone();
@@ -186,33 +189,65 @@ pub fn dev_panel(signals: AppSignals, gs: Rc<RefCell<GameState>>) -> impl View {
                 }),
             ))
             .style(|s| s.gap(8.0).items_center()),
-            dyn_stack(
-                {
-                    let ctx = ctx.clone();
-                    move || {
-                        let _ = ctx.signals.dev_tick.get();
-                        let gs_ref = ctx.gs.borrow();
-                        game_state_snapshot(&gs_ref).game_flags
-                    }
-                },
-                |flag: &String| flag.clone(),
-                move |flag: String| {
-                    label(move || flag.clone()).style(move |s| {
-                        let colors = ThemeColors::from_mode(signals.prefs.get().mode);
-                        s.padding_horiz(8.0)
-                            .padding_vert(4.0)
-                            .margin_right(6.0)
-                            .margin_bottom(6.0)
-                            .border(1.0)
-                            .border_color(colors.seam)
-                            .border_radius(4.0)
-                            .color(colors.ink_dim)
-                            .font_size(12.0)
-                            .font_family(UI_FONT_FAMILY.to_string())
-                    })
-                },
+            scroll(
+                dyn_stack(
+                    {
+                        let ctx = ctx.clone();
+                        move || {
+                            let _ = ctx.signals.dev_tick.get();
+                            let entries = {
+                                let gs_ref = ctx.gs.borrow();
+                                flag_browser(&gs_ref)
+                            };
+                            filter_flag_entries(entries, &get_or_default(flag_input))
+                        }
+                    },
+                    |entry: &FlagBrowserEntry| (entry.id.clone(), entry.set),
+                    {
+                        let ctx = ctx.clone();
+                        move |entry: FlagBrowserEntry| flag_row(entry, ctx.clone())
+                    },
+                )
+                .style(|s| s.width_full().flex_col()),
             )
-            .style(|s| s.flex_row().flex_wrap(floem::style::FlexWrap::Wrap)),
+            .scroll_style(|s| s.shrink_to_fit())
+            .style(|s| s.width_full().max_height(320.0)),
+        )),
+        signals,
+    );
+
+    let reload_section = section_card(
+        "Hot Reload",
+        v_stack((
+            label(move || {
+                signals.reload_error.get().map_or_else(
+                    || "Watching packs/ — saved edits load on their own".to_string(),
+                    |error| format!("Reload failed; still running the last good content.\n{error}"),
+                )
+            })
+            .style(move |s| {
+                let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+                let failed = signals.reload_error.get().is_some();
+                s.width_full()
+                    .color(if failed { colors.lamp } else { colors.ink_dim })
+                    .font_size(13.0)
+                    .font_family(if failed {
+                        "Consolas, Menlo, monospace".to_string()
+                    } else {
+                        UI_FONT_FAMILY.to_string()
+                    })
+            }),
+            h_stack((
+                action_button("Reload Now", signals, {
+                    let ctx = ctx.clone();
+                    move || ctx.run(DevCommand::ReloadContent)
+                }),
+                action_button("Restart Scene", signals, {
+                    let ctx = ctx.clone();
+                    move || ctx.run(DevCommand::RestartScene)
+                }),
+            ))
+            .style(|s| s.gap(8.0).flex_wrap(floem::style::FlexWrap::Wrap)),
         )),
         signals,
     );
@@ -349,6 +384,7 @@ pub fn dev_panel(signals: AppSignals, gs: Rc<RefCell<GameState>>) -> impl View {
                 .color(colors.ink_dim)
                 .font_family(UI_FONT_FAMILY.to_string())
         }),
+        reload_section,
         scene_section,
         stats_section,
         flag_section,
@@ -378,6 +414,78 @@ fn filter_scene_ids(scene_ids: Vec<String>, filter_text: &str) -> Vec<String> {
         .collect()
 }
 
+/// One flag browser row: a set/unset marker, the id, then what it means and
+/// where it came from. Clicking the row toggles the flag.
+fn flag_row(entry: FlagBrowserEntry, ctx: DevContext) -> impl View {
+    let signals = ctx.signals;
+    let heading = format!("{} {}", if entry.set { "●" } else { "○" }, entry.id);
+    let detail = [
+        entry.description.clone(),
+        entry.thread.clone(),
+        entry
+            .source
+            .clone()
+            .map(|source| format!("set by {source}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+    let set = entry.set;
+    let flag = entry.id;
+    v_stack((
+        label(move || heading.clone()).style(move |s| {
+            let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+            s.color(if set { colors.ink } else { colors.ink_dim })
+                .font_family(UI_FONT_FAMILY.to_string())
+        }),
+        label(move || detail.clone()).style(move |s| {
+            let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+            s.color(colors.ink_dim)
+                .font_size(12.0)
+                .font_family(UI_FONT_FAMILY.to_string())
+        }),
+    ))
+    .on_click_stop(move |_| {
+        let flag = flag.clone();
+        ctx.run(if set {
+            DevCommand::RemoveFlag { flag }
+        } else {
+            DevCommand::SetFlag { flag }
+        });
+    })
+    .style(move |s| {
+        let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+        s.width_full()
+            .padding_horiz(12.0)
+            .padding_vert(6.0)
+            .border_bottom(1.0)
+            .border_color(colors.seam)
+            .hover(|s| s.background(colors.lamp_glow))
+    })
+}
+
+fn filter_flag_entries(entries: Vec<FlagBrowserEntry>, filter_text: &str) -> Vec<FlagBrowserEntry> {
+    if filter_text.trim().is_empty() {
+        return entries;
+    }
+
+    let needle = filter_text.trim().to_lowercase();
+    entries
+        .into_iter()
+        .filter(|entry| {
+            [
+                Some(&entry.id),
+                entry.description.as_ref(),
+                entry.thread.as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|text| text.to_lowercase().contains(&needle))
+        })
+        .collect()
+}
+
 fn format_runtime_snapshot_json(snapshot: &RuntimeSnapshot) -> String {
     serde_json::to_string_pretty(snapshot).unwrap_or_else(|err| format!("{{\"error\":\"{err}\"}}"))
 }
@@ -515,8 +623,8 @@ fn action_button_owned(
 #[cfg(test)]
 mod tests {
     use super::{
-        default_window_size, filter_scene_ids, format_runtime_snapshot_json,
-        parse_window_size_inputs, WINDOW_SIZE_PRESETS,
+        default_window_size, filter_flag_entries, filter_scene_ids, format_runtime_snapshot_json,
+        parse_window_size_inputs, FlagBrowserEntry, WINDOW_SIZE_PRESETS,
     };
     use crate::layout::{DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH};
     use crate::runtime_snapshot::{
@@ -537,6 +645,36 @@ mod tests {
         assert_eq!(filtered, vec!["base::coffee_shop".to_string()]);
     }
 
+    #[test]
+    fn filter_flag_entries_matches_ids_descriptions_and_threads() {
+        let entry = |id: &str, description: &str, thread: &str| FlagBrowserEntry {
+            id: id.into(),
+            description: Some(description.into()),
+            thread: Some(thread.into()),
+            set: false,
+            source: None,
+        };
+        let entries = vec![
+            entry(
+                "MET_JAKE",
+                "She met Jake at the coffee shop.",
+                "Jake romance",
+            ),
+            entry("MET_THEO", "She met Theo in the library.", "Theo / campus"),
+        ];
+
+        let ids = |filter: &str| {
+            filter_flag_entries(entries.clone(), filter)
+                .into_iter()
+                .map(|e| e.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("jake"), vec!["MET_JAKE"]);
+        assert_eq!(ids("library"), vec!["MET_THEO"]);
+        assert_eq!(ids("campus"), vec!["MET_THEO"]);
+        assert_eq!(ids(" "), vec!["MET_JAKE", "MET_THEO"]);
+    }
+
     #[test]
     fn format_runtime_snapshot_json_includes_runtime_fields() {
         let formatted = format_runtime_snapshot_json(&RuntimeSnapshot {
@@ -551,6 +689,8 @@ mod tests {
                 id: "wait".into(),
                 label: "Wait".into(),
                 detail: "Stay".into(),
+                odds_phrase: None,
+                locked: false,
             }],
             active_npc: Some(ActiveNpcSnapshot {
                 name: "Jake".into(),
@@ -0,0 +1,503 @@
+//! The dev IPC transport: JSON-RPC 2.0 over a localhost TCP socket, one JSON
+//! message per line.
+//!
+//! Any number of clients may connect at once. Each request carries its own
+//! `id`, and its response goes back to the client that sent it with the same
+//! `id`. Requests reach the UI thread through a channel and run there one at a
+//! time, in arrival order. `subscribe` / `unsubscribe` are answered here: a
+//! subscribed client also receives notifications — `event` for each
+//! `EngineEvent` the UI processes and `state_diff` for the runtime snapshot
+//! fields that changed.
+//!
+//! Nothing here writes to a socket while holding the client list: each client
+//! has an outbound queue drained by its own writer thread, so a client that
+//! stops reading never stalls the UI thread or the other clients.
+//!
+//! The game listens on `UNDONE_DEV_IPC_ADDR` when set, else on a free port on
+//! 127.0.0.1, and writes the address it bound to `undone-dev-ipc.addr` in the
+//! temp dir for clients to find. Only loopback addresses are accepted.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Map, Value};
+
+/// Environment variable naming the address to listen on, e.g. `127.0.0.1:7600`.
+pub const ADDR_ENV: &str = "UNDONE_DEV_IPC_ADDR";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// How long a client's writer thread waits on one write. A client that stops
+/// reading is dropped once a write times out.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Messages queued for one client before it counts as stalled and is dropped.
+const OUTBOX_LIMIT: usize = 256;
+
+/// Where a running game writes the address its dev socket listens on.
+pub fn address_file_path() -> PathBuf {
+    std::env::temp_dir().join("undone-dev-ipc.addr")
+}
+
+/// The address to listen on: `UNDONE_DEV_IPC_ADDR`, else any free local port.
+pub fn listen_address() -> String {
+    std::env::var(ADDR_ENV).unwrap_or_else(|_| "127.0.0.1:0".to_string())
+}
+
+pub type ClientId = u64;
+
+/// A request for the UI thread to run and answer with [`DevSocket::respond`].
+#[derive(Debug)]
+pub struct DevRequest {
+    pub client: ClientId,
+    /// `None` for a JSON-RPC notification, which gets no response.
+    pub id: Option<Value>,
+    pub method: String,
+    pub params: Value,
+}
+
+struct Client {
+    /// Lines for the client's writer thread.
+    outbox: SyncSender<String>,
+    /// Kept to shut the connection down when the client is dropped.
+    stream: TcpStream,
+    subscribed: bool,
+}
+
+impl Client {
+    /// Queue one message line without blocking. When the queue is full or the
+    /// writer has stopped, the connection is shut down, which also ends its
+    /// reader and writer threads, and the caller drops it.
+    fn enqueue(&self, line: &str) -> bool {
+        match self.outbox.try_send(line.to_string()) {
+            Ok(()) => true,
+            Err(err) => {
+                log::warn!("[dev-ipc] dropping client: {err}");
+                let _ = self.stream.shutdown(Shutdown::Both);
+                false
+            }
+        }
+    }
+}
+
+/// Drain `outbox` into `stream` until the client goes away or a write fails.
+fn write_client(mut stream: TcpStream, outbox: Receiver<String>) {
+    for line in outbox {
+        if let Err(err) = stream.write_all(line.as_bytes()) {
+            log::warn!("[dev-ipc] client write failed: {err}");
+            break;
+        }
+    }
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+#[derive(Default)]
+struct Clients {
+    next_id: ClientId,
+    connected: HashMap<ClientId, Client>,
+}
+
+/// The socket's sending side, shared between the UI thread and the
+/// connection threads.
+#[derive(Clone)]
+pub struct DevSocket {
+    addr: SocketAddr,
+    clients: Arc<Mutex<Clients>>,
+}
+
+impl DevSocket {
+    /// Listen on `addr` and accept clients on a background thread. Requests
+    /// arrive on the returned channel. An address that is not loopback is
+    /// refused: the socket can drive the game and is unauthenticated.
+    pub fn bind(addr: &str) -> io::Result<(DevSocket, Receiver<DevRequest>)> {
+        let addrs: Vec<SocketAddr> = addr.to_socket_addrs()?.collect();
+        if let Some(open) = addrs.iter().find(|addr| !addr.ip().is_loopback()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{open} is not a loopback address"),
+            ));
+        }
+        let listener = TcpListener::bind(&addrs[..])?;
+        let socket = DevSocket {
+            addr: listener.local_addr()?,
+            clients: Arc::default(),
+        };
+        let (requests, received) = mpsc::channel();
+        let accepting = socket.clone();
+        std::thread::Builder::new()
+            .name("dev-ipc-accept".into())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    match stream {
+                        Ok(stream) => accepting.accept(stream, requests.clone()),
+                        Err(err) => log::warn!("[dev-ipc] accept failed: {err}"),
+                    }
+                }
+            })?;
+        Ok((socket, received))
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    fn accept(&self, stream: TcpStream, requests: Sender<DevRequest>) {
+        let streams = stream
+            .set_write_timeout(Some(WRITE_TIMEOUT))
+            .and_then(|()| Ok((stream.try_clone()?, stream.try_clone()?)));
+        let (writer, handle) = match streams {
+            Ok(streams) => streams,
+            Err(err) => {
+                log::warn!("[dev-ipc] could not set up client stream: {err}");
+                return;
+            }
+        };
+        let (outbox, queued) = mpsc::sync_channel(OUTBOX_LIMIT);
+        let client = {
+            let mut clients = self.lock();
+            clients.next_id += 1;
+            let id = clients.next_id;
+            clients.connected.insert(
+                id,
+                Client {
+                    outbox,
+                    stream: handle,
+                    subscribed: false,
+                },
+            );
+            id
+        };
+        let writing = std::thread::Builder::new()
+            .name(format!("dev-ipc-write-{client}"))
+            .spawn(move || write_client(writer, queued));
+        let socket = self.clone();
+        let reading = writing.and_then(|_| {
+            std::thread::Builder::new()
+                .name(format!("dev-ipc-client-{client}"))
+                .spawn(move || {
+                    socket.read_client(client, stream, &requests);
+                    socket.lock().connected.remove(&client);
+                })
+        });
+        if let Err(err) = reading {
+            log::warn!("[dev-ipc] could not start client thread: {err}");
+            self.lock().connected.remove(&client);
+        }
+    }
+
+    fn read_client(&self, client: ClientId, stream: TcpStream, requests: &Sender<DevRequest>) {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let request = match parse_request(client, &line) {
+                Ok(request) => request,
+                Err((id, code, message)) => {
+                    self.respond_error(client, Some(id), code, &message);
+                    continue;
+                }
+            };
+            match request.method.as_str() {
+                "subscribe" | "unsubscribe" => {
+                    let subscribed = request.method == "subscribe";
+                    if let Some(entry) = self.lock().connected.get_mut(&client) {
+                        entry.subscribed = subscribed;
+                    }
+                    self.respond(client, request.id, json!({ "subscribed": subscribed }));
+                }
+                _ => {
+                    if requests.send(request).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Answer request `id` from `client`. A notification (`id` of `None`) is
+    /// not answered.
+    pub fn respond(&self, client: ClientId, id: Option<Value>, result: Value) {
+        if let Some(id) = id {
+            self.send_to(
+                client,
+                &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            );
+        }
+    }
+
+    pub fn respond_error(&self, client: ClientId, id: Option<Value>, code: i64, message: &str) {
+        if let Some(id) = id {
+            self.send_to(
+                client,
+                &json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": code, "message": message },
+                }),
+            );
+        }
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        self.lock()
+            .connected
+            .values()
+            .any(|client| client.subscribed)
+    }
+
+    /// Queue a notification for every subscribed client.
+    pub fn notify(&self, method: &str, params: Value) {
+        let line = message_line(&json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+        let mut clients = self.lock();
+        clients
+            .connected
+            .retain(|_, client| !client.subscribed || client.enqueue(&line));
+    }
+
+    fn send_to(&self, client: ClientId, message: &Value) {
+        let line = message_line(message);
+        let mut clients = self.lock();
+        let failed = match clients.connected.get(&client) {
+            Some(entry) => !entry.enqueue(&line),
+            None => false,
+        };
+        if failed {
+            clients.connected.remove(&client);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Clients> {
+        self.clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn message_line(message: &Value) -> String {
+    let mut line = message.to_string();
+    line.push('\n');
+    line
+}
+
+/// A JSON-RPC request, or the id, error code and message to answer it with.
+fn parse_request(client: ClientId, line: &str) -> Result<DevRequest, (Value, i64, String)> {
+    let value: Value = serde_json::from_str(line)
+        .map_err(|err| (Value::Null, PARSE_ERROR, format!("invalid JSON: {err}")))?;
+    let Value::Object(mut message) = value else {
+        return Err((Value::Null, INVALID_REQUEST, "expected an object".into()));
+    };
+    let id = message.remove("id");
+    let error_id = id.clone().unwrap_or(Value::Null);
+    let Some(Value::String(method)) = message.remove("method") else {
+        return Err((error_id, INVALID_REQUEST, "missing 'method'".into()));
+    };
+    let params = match message.remove("params") {
+        None | Some(Value::Null) => Value::Object(Map::new()),
+        Some(params @ Value::Object(_)) => params,
+        Some(_) => {
+            return Err((
+                error_id,
+                INVALID_PARAMS,
+                "'params' must be an object".into(),
+            ));
+        }
+    };
+    Ok(DevRequest {
+        client,
+        id,
+        method,
+        params,
+    })
+}
+
+/// The top-level fields of `after` that differ from `before`, with their new
+/// values; every field when there is no `before`. `None` when nothing changed.
+pub fn state_diff(before: Option<&Value>, after: &Value) -> Option<Value> {
+    let Value::Object(after) = after else {
+        return None;
+    };
+    let before = before.and_then(Value::as_object);
+    let changed: Map<String, Value> = after
+        .iter()
+        .filter(|(key, value)| before.and_then(|before| before.get(*key)) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    (!changed.is_empty()).then_some(Value::Object(changed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect(socket: &DevSocket) -> (TcpStream, BufReader<TcpStream>) {
+        let stream = TcpStream::connect(socket.local_addr()).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        (stream, reader)
+    }
+
+    fn send(stream: &mut TcpStream, message: Value) {
+        stream.write_all(message_line(&message).as_bytes()).unwrap();
+    }
+
+    fn read(reader: &mut BufReader<TcpStream>) -> Value {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[test]
+    fn responses_go_back_to_the_client_that_asked_with_its_id() {
+        let (socket, requests) = DevSocket::bind("127.0.0.1:0").unwrap();
+        let (mut first, mut first_reader) = connect(&socket);
+        let (mut second, mut second_reader) = connect(&socket);
+
+        send(
+            &mut first,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "get_state"}),
+        );
+        send(
+            &mut second,
+            json!({"jsonrpc": "2.0", "id": "b", "method": "jump_to_scene", "params": {"scene_id": "x"}}),
+        );
+        for _ in 0..2 {
+            let request = requests.recv_timeout(Duration::from_secs(5)).unwrap();
+            let echo = json!({ "method": request.method, "params": request.params });
+            socket.respond(request.client, request.id, echo);
+        }
+
+        let answer = read(&mut first_reader);
+        assert_eq!(answer["id"], json!(1));
+        assert_eq!(answer["result"]["method"], json!("get_state"));
+        let answer = read(&mut second_reader);
+        assert_eq!(answer["id"], json!("b"));
+        assert_eq!(answer["result"]["params"]["scene_id"], json!("x"));
+    }
+
+    #[test]
+    fn malformed_requests_get_json_rpc_errors() {
+        let (socket, _requests) = DevSocket::bind("127.0.0.1:0").unwrap();
+        let (mut stream, mut reader) = connect(&socket);
+
+        stream.write_all(b"{not json\n").unwrap();
+        assert_eq!(read(&mut reader)["error"]["code"], json!(PARSE_ERROR));
+
+        send(
+            &mut stream,
+            json!({"jsonrpc": "2.0", "id": 7, "params": {}}),
+        );
+        let answer = read(&mut reader);
+        assert_eq!(answer["id"], json!(7));
+        assert_eq!(answer["error"]["code"], json!(INVALID_REQUEST));
+    }
+
+    #[test]
+    fn only_subscribed_clients_receive_notifications() {
+        let (socket, _requests) = DevSocket::bind("127.0.0.1:0").unwrap();
+        let (mut subscriber, mut subscriber_reader) = connect(&socket);
+        let (_other, _other_reader) = connect(&socket);
+
+        send(
+            &mut subscriber,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "subscribe"}),
+        );
+        assert_eq!(
+            read(&mut subscriber_reader)["result"]["subscribed"],
+            json!(true)
+        );
+        assert!(socket.has_subscribers());
+
+        socket.notify("event", json!({"event": "scene_finished"}));
+        let notification = read(&mut subscriber_reader);
+        assert_eq!(notification["method"], json!("event"));
+        assert!(notification.get("id").is_none());
+    }
+
+    #[test]
+    fn a_subscriber_that_stops_reading_is_dropped() {
+        let (socket, _requests) = DevSocket::bind("127.0.0.1:0").unwrap();
+        let (mut stalled, mut stalled_reader) = connect(&socket);
+        send(
+            &mut stalled,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "subscribe"}),
+        );
+        read(&mut stalled_reader);
+
+        let payload = "x".repeat(1 << 20);
+        for _ in 0..256 {
+            if !socket.has_subscribers() {
+                break;
+            }
+            socket.notify("event", json!({ "payload": payload }));
+        }
+        assert!(!socket.has_subscribers());
+    }
+
+    #[test]
+    fn a_stalled_subscriber_does_not_hold_up_the_others() {
+        let (socket, _requests) = DevSocket::bind("127.0.0.1:0").unwrap();
+        let mut stalled = Vec::new();
+        for _ in 0..3 {
+            let (mut stream, mut reader) = connect(&socket);
+            send(
+                &mut stream,
+                json!({"jsonrpc": "2.0", "id": 1, "method": "subscribe"}),
+            );
+            read(&mut reader);
+            stalled.push((stream, reader));
+        }
+
+        let payload = "x".repeat(1 << 14);
+        for _ in 0..(OUTBOX_LIMIT * 8) {
+            let started = std::time::Instant::now();
+            socket.notify("event", json!({ "payload": payload }));
+            assert!(
+                started.elapsed() < WRITE_TIMEOUT,
+                "notify must only queue, took {:?}",
+                started.elapsed()
+            );
+        }
+        assert!(!socket.has_subscribers());
+
+        let (mut live, mut live_reader) = connect(&socket);
+        send(
+            &mut live,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "subscribe"}),
+        );
+        assert_eq!(read(&mut live_reader)["id"], json!(2));
+        socket.notify("event", json!({"event": "scene_finished"}));
+        assert_eq!(read(&mut live_reader)["method"], json!("event"));
+    }
+
+    #[test]
+    fn non_loopback_addresses_are_refused() {
+        let err = DevSocket::bind("0.0.0.0:0").err().expect("must refuse");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(DevSocket::bind("localhost:0").is_ok());
+    }
+
+    #[test]
+    fn state_diff_keeps_only_changed_fields() {
+        let before = json!({"week": 1, "current_scene_id": "a", "story_paragraphs": ["x"]});
+        let after = json!({"week": 1, "current_scene_id": "b", "story_paragraphs": ["x"]});
+        assert_eq!(
+            state_diff(Some(&before), &after),
+            Some(json!({"current_scene_id": "b"}))
+        );
+        assert_eq!(state_diff(Some(&after), &after), None);
+        assert_eq!(state_diff(None, &after), Some(after.clone()));
+    }
+}
@@ -1,17 +1,18 @@
 use rand::{rngs::SmallRng, SeedableRng};
 use std::collections::HashMap;
 use std::path::Path;
-use std::path::PathBuf;
-use undone_domain::{SkillId, SkillValue, TimeSlot};
+use undone_domain::SkillId;
 
 use undone_packs::{
     char_creation::{new_game, CharCreationConfig},
-    load_packs, PackRegistry,
+    PackRegistry,
 };
 use undone_scene::engine::{EngineEvent, SceneEngine};
-use undone_scene::loader::load_scenes;
-use undone_scene::scheduler::{load_schedule, validate_entry_scene_references, Scheduler};
+use undone_scene::scheduler::Scheduler;
 use undone_scene::types::SceneDefinition;
+use undone_session::resolve_packs_dir;
+pub use undone_session::{load_runtime_content, LoadedRuntimeContent};
+use undone_session::{GameSession, SessionState};
 use undone_world::World;
 
 /// State available before a character has been created.
@@ -25,48 +26,15 @@ pub struct PreGameState {
     pub init_error: Option<String>,
 }
 
-pub struct LoadedRuntimeContent {
-    pub registry: PackRegistry,
-    pub scenes: HashMap<String, std::sync::Arc<SceneDefinition>>,
-    pub scheduler: Scheduler,
-}
-
 pub struct GameState {
-    pub world: World,
-    pub registry: PackRegistry,
-    pub engine: SceneEngine,
-    pub scheduler: Scheduler,
-    pub rng: SmallRng,
+    /// The world, scene engine and scheduler, and the rules the runtime
+    /// follows between scenes. `RuntimeController` drives it.
+    pub session: GameSession,
     pub dev_mode: bool,
     /// Set when pack loading fails; checked by app_view to surface the error.
     pub init_error: Option<String>,
-    pub opening_scene: Option<String>,
     pub femininity_id: SkillId,
     pub composure_id: SkillId,
-    pub current_scene_time_anchor: Option<SceneTimeAnchor>,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SceneTimeAnchor {
-    pub week: u32,
-    pub day: u8,
-    pub time_slot: TimeSlot,
-}
-
-impl SceneTimeAnchor {
-    pub fn capture(world: &World) -> Self {
-        Self {
-            week: world.game_data.week,
-            day: world.game_data.day,
-            time_slot: world.game_data.time_slot,
-        }
-    }
-
-    pub fn matches_world(&self, world: &World) -> bool {
-        self.week == world.game_data.week
-            && self.day == world.game_data.day
-            && self.time_slot == world.game_data.time_slot
-    }
 }
 
 pub struct ResumeGameResult {
@@ -74,21 +42,6 @@ pub struct ResumeGameResult {
     pub started_scene_id: Option<String>,
 }
 
-/// Resolve the packs directory. Tries:
-/// 1. `<exe_dir>/packs` (distribution layout)
-/// 2. `./packs` (cargo run from workspace root)
-fn resolve_packs_dir() -> PathBuf {
-    if let Ok(exe) = std::env::current_exe() {
-        if let Some(dir) = exe.parent() {
-            let candidate = dir.join("packs");
-            if candidate.is_dir() {
-                return candidate;
-            }
-        }
-    }
-    PathBuf::from("packs")
-}
-
 /// Build a failed `PreGameState` carrying an error message. Logs to stderr.
 fn failed_pre(
     registry: PackRegistry,
@@ -152,64 +105,6 @@ pub(crate) fn test_pre_state_from_dir(packs_dir: &Path) -> PreGameState {
     }
 }
 
-pub fn load_runtime_content(packs_dir: &Path) -> Result<LoadedRuntimeContent, String> {
-    let (registry, metas) =
-        load_packs(packs_dir).map_err(|e| format!("Failed to load packs: {e}"))?;
-
-    let conflict_errors = registry.validate_trait_conflicts();
-    if !conflict_errors.is_empty() {
-        return Err(format!(
-            "Trait conflict errors:\n{}",
-            conflict_errors.join("\n")
-        ));
-    }
-
-    let mut scenes: HashMap<String, std::sync::Arc<SceneDefinition>> = HashMap::new();
-    let mut scene_sources: HashMap<String, String> = HashMap::new();
-    for meta in &metas {
-        let scene_dir = meta.pack_dir.join(&meta.manifest.content.scenes_dir);
-        let pack_scenes = load_scenes(&scene_dir, &registry)
-            .map_err(|e| format!("Scene load error in pack '{}': {e}", meta.manifest.pack.id))?;
-        extend_scenes_checked(
-            &mut scenes,
-            &mut scene_sources,
-            pack_scenes,
-            &meta.manifest.pack.id,
-        )
-        .map_err(|e| format!("Scene load error in pack '{}': {e}", meta.manifest.pack.id))?;
-    }
-
-    undone_scene::loader::validate_cross_references(&scenes)
-        .map_err(|e| format!("Scene validation error: {e}"))?;
-
-    let scheduler =
-        load_schedule(&metas, &registry).map_err(|e| format!("Schedule load error: {e}"))?;
-    scheduler
-        .validate_scene_references(&scenes)
-        .map_err(|e| format!("Schedule validation error: {e}"))?;
-    validate_entry_scene_references(
-        &scenes,
-        registry.opening_scene(),
-        registry.transformation_scene(),
-    )
-    .map_err(|e| format!("Entry scene validation error: {e}"))?;
-
-    let char_creation_errors =
-        crate::char_creation::validate_runtime_contract(&registry, &scheduler);
-    if !char_creation_errors.is_empty() {
-        return Err(format!(
-            "Character creation contract error(s):\n{}",
-            char_creation_errors.join("\n")
-        ));
-    }
-
-    Ok(LoadedRuntimeContent {
-        registry,
-        scenes,
-        scheduler,
-    })
-}
-
 /// Create a world from character creation config and build the full `GameState`.
 ///
 /// # Panics
@@ -247,17 +142,11 @@ pub fn start_game_checked(
     let composure_id = required_composure_skill(&registry)?;
     let world = new_game(config, &mut registry, &mut rng);
     Ok(GameState {
-        world,
-        registry,
-        engine: SceneEngine::new(scenes),
-        scheduler,
-        rng,
+        session: fresh_session(registry, scenes, scheduler, world, rng, opening_scene),
         dev_mode,
         init_error,
-        opening_scene,
         femininity_id,
         composure_id,
-        current_scene_time_anchor: None,
     })
 }
 
@@ -280,37 +169,21 @@ pub fn build_throwaway_game_state(
     let composure_id = required_composure_skill(&pre.registry)?;
     let world = new_game(config, &mut pre.registry, &mut pre.rng);
     Ok(GameState {
-        world,
-        registry: pre.registry.clone(),
-        engine: SceneEngine::new(pre.scenes.clone()),
-        scheduler: pre.scheduler.clone(),
-        rng: SmallRng::from_entropy(),
+        session: fresh_session(
+            pre.registry.clone(),
+            pre.scenes.clone(),
+            pre.scheduler.clone(),
+            world,
+            SmallRng::from_entropy(),
+            opening_scene,
+        ),
         dev_mode,
         init_error: pre.init_error.clone(),
-        opening_scene,
         femininity_id,
         composure_id,
-        current_scene_time_anchor: None,
     })
 }
 
-fn extend_scenes_checked(
-    scenes: &mut HashMap<String, std::sync::Arc<SceneDefinition>>,
-    scene_sources: &mut HashMap<String, String>,
-    incoming: HashMap<String, std::sync::Arc<SceneDefinition>>,
-    source: &str,
-) -> Result<(), String> {
-    for (scene_id, scene) in incoming {
-        if let Some(first_source) = scene_sources.insert(scene_id.clone(), source.to_string()) {
-            return Err(format!(
-                "duplicate scene id '{scene_id}': '{source}' conflicts with already-loaded '{first_source}'"
-            ));
-        }
-        scenes.insert(scene_id, scene);
-    }
-    Ok(())
-}
-
 /// Build `GameState` from a loaded save world, using already-loaded pack content.
 ///
 /// `opening_scene` is intentionally `None` so resuming from save does not replay
@@ -325,7 +198,7 @@ pub fn start_loaded_game_checked(
     dev_mode: bool,
 ) -> Result<GameState, String> {
     let PreGameState {
-        registry,
+        mut registry,
         scenes,
         scheduler,
         rng,
@@ -334,41 +207,40 @@ pub fn start_loaded_game_checked(
     let femininity_id = required_femininity_skill(&registry)?;
     let composure_id = required_composure_skill(&registry)?;
 
-    // Structural-skill backfill for saves written before a structural skill
-    // existed. COMPOSURE was promoted to a structural skill in the looping-adult
-    // layer (save v7). A save predating it has no COMPOSURE entry, which would
-    // read as composure 0 (maximum loss-of-control) rather than the intended
-    // starting value — silently unlocking the reckless/spiral content gates. The
-    // value lives in one place (`char_creation::STARTING_COMPOSURE`). New games
-    // seed COMPOSURE during character creation and never reach this path.
     let mut world = world;
-    if !world.player.skills.contains_key(&composure_id) {
-        log::info!("backfilling missing COMPOSURE skill into loaded save");
-        world.player.skills.insert(
-            composure_id,
-            SkillValue {
-                value: undone_packs::char_creation::STARTING_COMPOSURE,
-                modifier: 0,
-            },
-        );
-    }
+    undone_session::backfill_composure(&mut world, composure_id);
+    undone_session::backfill_story_npcs(&mut world, &mut registry);
 
-    let engine = SceneEngine::new(scenes);
     Ok(GameState {
-        world,
-        registry,
-        engine,
-        scheduler,
-        rng,
+        session: fresh_session(registry, scenes, scheduler, world, rng, None),
         dev_mode,
         init_error,
-        opening_scene: None,
         femininity_id,
         composure_id,
-        current_scene_time_anchor: None,
     })
 }
 
+/// A session over `world` with no scene started yet.
+pub(crate) fn fresh_session(
+    registry: PackRegistry,
+    scenes: HashMap<String, std::sync::Arc<SceneDefinition>>,
+    scheduler: Scheduler,
+    world: World,
+    rng: SmallRng,
+    opening_scene: Option<String>,
+) -> GameSession {
+    let state = SessionState {
+        world,
+        engine: SceneEngine::new(scenes),
+        rng,
+        opening_scene,
+        current_scene_time_anchor: None,
+        actions: Vec::new(),
+        awaiting_continue: false,
+    };
+    GameSession::from_state(registry, scheduler, state)
+}
+
 /// Validate and load a save file into a full `GameState`.
 pub fn load_game_state_from_save(
     mut pre: PreGameState,
@@ -397,43 +269,21 @@ fn required_composure_skill(registry: &PackRegistry) -> Result<SkillId, String>
 /// This is the authoritative resume path for loading a save into an existing
 /// `GameState`. It guarantees that stale scene frames and queued events do not
 /// survive across the load boundary.
-pub fn resume_current_world(gs: &mut GameState) -> ResumeGameResult {
-    gs.engine.reset_runtime();
-    gs.opening_scene = None;
-    gs.current_scene_time_anchor = None;
-
-    let mut started_scene_id = None;
-    if let Some(result) = gs.scheduler.pick_next(&gs.world, &gs.registry, &mut gs.rng) {
-        if result.once_only {
-            gs.world
-                .game_data
-                .set_flag(format!("ONCE_{}", result.scene_id));
-        }
-        started_scene_id = Some(result.scene_id.clone());
-        gs.current_scene_time_anchor = result
-            .consumes_time
-            .then(|| SceneTimeAnchor::capture(&gs.world));
-        crate::start_scene(
-            &mut gs.engine,
-            &gs.world,
-            &gs.registry,
-            result.scene_id,
-            result.npc_role.as_deref(),
-        );
-    }
-
-    ResumeGameResult {
-        events: gs.engine.drain(),
-        started_scene_id,
-    }
+pub fn resume_current_world(gs: &mut GameState) -> Result<ResumeGameResult, String> {
+    let step = gs.session.resume()?;
+    Ok(ResumeGameResult {
+        events: step.events,
+        started_scene_id: step.started_scene_id,
+    })
 }
 
 pub fn load_world_from_save(gs: &mut GameState, save_path: &Path) -> Result<(), String> {
-    let loaded_world = undone_save::load_game(save_path, &mut gs.registry)
+    let session = &mut gs.session;
+    let loaded_world = undone_save::load_game(save_path, &mut session.registry)
         .map_err(|e| format!("Load failed: {e}"))?;
-    gs.world = loaded_world;
-    gs.opening_scene = None;
-    gs.current_scene_time_anchor = None;
+    session.world = loaded_world;
+    session.opening_scene = None;
+    session.current_scene_time_anchor = None;
     Ok(())
 }
 
@@ -443,7 +293,7 @@ pub fn reload_current_game_from_save(
     save_path: &Path,
 ) -> Result<ResumeGameResult, String> {
     load_world_from_save(gs, save_path)?;
-    Ok(resume_current_world(gs))
+    resume_current_world(gs)
 }
 
 #[cfg(test)]
@@ -455,11 +305,12 @@ mod tests {
     use floem::prelude::SignalUpdate;
     use rand::SeedableRng;
     use std::collections::{HashMap, HashSet};
+    use std::path::PathBuf;
     use std::time::{SystemTime, UNIX_EPOCH};
     use undone_domain::{
         Age, Appearance, BeforeIdentity, BeforeSexuality, BeforeVoice, BreastSize, ButtSize,
         ClitSensitivity, Complexion, EyeColour, HairColour, HairLength, Height, InnerLabiaSize,
-        LipShape, MaleFigure, NaturalPubicHair, NippleSensitivity, PcOrigin, PenisSize,
+        LipShape, MaleFigure, NaturalPubicHair, NippleSensitivity, NpcKey, PcOrigin, PenisSize,
         PlayerFigure, PubicHairStyle, SkinTone, WaistSize, WetnessBaseline,
     };
     use undone_packs::char_creation::CharCreationConfig;
@@ -486,15 +337,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn load_runtime_content_returns_validated_registry_scenes_and_scheduler() {
-        let packs_dir = packs_dir();
-        let loaded = load_runtime_content(&packs_dir).expect("shared loader should succeed");
-
-        assert!(!loaded.scenes.is_empty());
-        assert!(loaded.registry.opening_scene().is_some());
-    }
-
     fn workplace_config() -> CharCreationConfig {
         CharCreationConfig {
             name_fem: "Robin".into(),
@@ -550,7 +392,7 @@ mod tests {
 
     fn play_scene_to_finish(gs: &mut GameState) {
         loop {
-            let events = gs.engine.drain();
+            let events = gs.session.engine.drain();
             if events
                 .iter()
                 .any(|event| matches!(event, EngineEvent::SceneFinished))
@@ -573,10 +415,10 @@ mod tests {
                 .expect("scene should expose a selectable action")
                 .id
                 .clone();
-            gs.engine.send(
+            gs.session.engine.send(
                 EngineCommand::ChooseAction(action_id),
-                &mut gs.world,
-                &gs.registry,
+                &mut gs.session.world,
+                &gs.session.registry,
             );
         }
     }
@@ -589,12 +431,6 @@ mod tests {
         std::env::temp_dir().join(format!("undone_{name}_{unique}.json"))
     }
 
-    #[test]
-    fn resolve_packs_dir_returns_path_ending_in_packs() {
-        let dir = resolve_packs_dir();
-        assert_eq!(dir.file_name().unwrap(), "packs");
-    }
-
     #[test]
     fn start_game_reports_missing_femininity_skill_as_init_error() {
         let pre = malformed_pre_state_without_femininity();
@@ -607,7 +443,7 @@ mod tests {
     fn start_loaded_game_reports_missing_femininity_skill_as_error() {
         let source = start_game(test_pre_state(), workplace_config(), false);
         let pre = malformed_pre_state_without_femininity();
-        let result = start_loaded_game_checked(pre, source.world.clone(), false);
+        let result = start_loaded_game_checked(pre, source.session.world.clone(), false);
 
         assert!(matches!(result, Err(message) if message.contains("FEMININITY")));
     }
@@ -618,73 +454,124 @@ mod tests {
         // (pre-v7) loads with composure 0 instead of the starting value, silently
         // unlocking the low-composure "reckless" content gates.
         let mut source = start_game(test_pre_state(), workplace_config(), false);
-        let composure_id = source.registry.composure_skill().unwrap();
-        source.world.player.skills.remove(&composure_id);
+        let composure_id = source.session.registry.composure_skill().unwrap();
+        source.session.world.player.skills.remove(&composure_id);
         assert!(
-            !source.world.player.skills.contains_key(&composure_id),
+            !source
+                .session
+                .world
+                .player
+                .skills
+                .contains_key(&composure_id),
             "precondition: COMPOSURE removed to simulate a pre-v7 save"
         );
 
-        let gs = start_loaded_game_checked(test_pre_state(), source.world.clone(), false)
+        let gs = start_loaded_game_checked(test_pre_state(), source.session.world.clone(), false)
             .expect("loading a pre-COMPOSURE save should succeed");
 
-        let composure_id = gs.registry.composure_skill().unwrap();
+        let composure_id = gs.session.registry.composure_skill().unwrap();
         assert_eq!(
-            gs.world.player.skills.get(&composure_id).map(|s| s.value),
+            gs.session
+                .world
+                .player
+                .skills
+                .get(&composure_id)
+                .map(|s| s.value),
             Some(undone_packs::char_creation::STARTING_COMPOSURE),
             "missing COMPOSURE must be backfilled to the starting value, not left at 0"
         );
     }
 
+    #[test]
+    fn loaded_save_missing_story_characters_is_backfilled() {
+        // BREAKS IF: a save written before story characters existed fails with
+        // a bad NPC reference the first time a scene calls npc("base::jake").
+        let mut source = start_game(test_pre_state(), workplace_config(), false);
+        let world = &mut source.session.world;
+        let Some(NpcKey::Male(jake)) = world.story_npc("base::jake") else {
+            panic!("precondition: new game spawns Jake");
+        };
+        world.male_npcs[jake].core.story_id = None;
+        let Some(NpcKey::Male(marcus)) = world.story_npc("base::marcus") else {
+            panic!("precondition: new game spawns Marcus");
+        };
+        world.male_npcs.remove(marcus);
+
+        let gs = start_loaded_game_checked(test_pre_state(), source.session.world.clone(), false)
+            .expect("loading a pre-story-character save should succeed");
+
+        let world = &gs.session.world;
+        assert_eq!(
+            world.story_npc("base::jake"),
+            Some(NpcKey::Male(jake)),
+            "the NPC already holding ROLE_JAKE must be adopted as Jake"
+        );
+        let Some(NpcKey::Male(marcus)) = world.story_npc("base::marcus") else {
+            panic!("a missing story character must be spawned");
+        };
+        assert!(world.male_npcs[marcus].core.roles.contains("ROLE_MARCUS"));
+        let story_npcs = world
+            .male_npcs
+            .values()
+            .filter(|npc| npc.core.story_id.is_some())
+            .count();
+        assert_eq!(
+            story_npcs,
+            gs.session.registry.characters().len(),
+            "backfill must not duplicate story characters"
+        );
+    }
+
     #[test]
     fn reload_current_game_from_save_resets_runtime_and_resumes_from_persisted_world() {
         let pre = test_pre_state();
         let mut gs = start_game(pre, workplace_config(), false);
 
         assert_eq!(
-            gs.opening_scene.as_deref(),
+            gs.session.opening_scene.as_deref(),
             Some("base::rain_shelter"),
             "new game should retain the new-game opening scene until first launch"
         );
 
         let first_pick = gs
+            .session
             .scheduler
-            .pick_next(&gs.world, &gs.registry, &mut gs.rng)
+            .pick_next(&gs.session.world, &gs.session.registry, &mut gs.session.rng)
             .expect("workplace route should schedule arrival");
         assert_eq!(first_pick.scene_id, "base::workplace_arrival");
-        if first_pick.once_only {
-            gs.world
-                .game_data
-                .set_flag(format!("ONCE_{}", first_pick.scene_id));
-        }
+        first_pick.mark_started(&mut gs.session.world);
         crate::start_scene(
-            &mut gs.engine,
-            &gs.world,
-            &gs.registry,
+            &mut gs.session.engine,
+            &mut gs.session.world,
+            &gs.session.registry,
             first_pick.scene_id,
             first_pick.npc_role.as_deref(),
+            first_pick.npc,
         );
         play_scene_to_finish(&mut gs);
 
         assert_eq!(
-            gs.world.game_data.arc_state("base::workplace_opening"),
+            gs.session
+                .world
+                .game_data
+                .arc_state("base::workplace_opening"),
             Some("arrived"),
             "workplace arrival should advance the persisted arc state"
         );
 
         let save_path = temp_save_path("resume_runtime_reset");
-        undone_save::save_game(&gs.world, &gs.registry, &save_path).unwrap();
+        undone_save::save_game(&gs.session.world, &gs.session.registry, &save_path).unwrap();
 
-        gs.engine.send(
+        gs.session.engine.send(
             EngineCommand::StartScene("base::rain_shelter".into()),
-            &mut gs.world,
-            &gs.registry,
+            &mut gs.session.world,
+            &gs.session.registry,
         );
 
         let resume = reload_current_game_from_save(&mut gs, &save_path).unwrap();
 
         assert_eq!(
-            gs.opening_scene, None,
+            gs.session.opening_scene, None,
             "loaded saves must not replay opening scene"
         );
         assert_eq!(
@@ -732,26 +619,28 @@ mod tests {
         let mut source = start_game(pre, workplace_config(), false);
 
         let first_pick = source
+            .session
             .scheduler
-            .pick_next(&source.world, &source.registry, &mut source.rng)
+            .pick_next(
+                &source.session.world,
+                &source.session.registry,
+                &mut source.session.rng,
+            )
             .expect("workplace route should schedule arrival");
-        if first_pick.once_only {
-            source
-                .world
-                .game_data
-                .set_flag(format!("ONCE_{}", first_pick.scene_id));
-        }
+        first_pick.mark_started(&mut source.session.world);
         crate::start_scene(
-            &mut source.engine,
-            &source.world,
-            &source.registry,
+            &mut source.session.engine,
+            &mut source.session.world,
+            &source.session.registry,
             first_pick.scene_id,
             first_pick.npc_role.as_deref(),
+            first_pick.npc,
         );
         play_scene_to_finish(&mut source);
 
         let save_path = temp_save_path("load_state_through_controller");
-        undone_save::save_game(&source.world, &source.registry, &save_path).unwrap();
+        undone_save::save_game(&source.session.world, &source.session.registry, &save_path)
+            .unwrap();
 
         let pre = test_pre_state();
         let mut loaded = load_game_state_from_save(pre, &save_path, false).unwrap();
@@ -760,7 +649,7 @@ mod tests {
         let outcome = controller.continue_flow().unwrap();
         let snapshot = controller.snapshot();
 
-        assert_eq!(controller.gs.opening_scene, None);
+        assert_eq!(controller.gs.session.opening_scene, None);
         assert_eq!(
             outcome.started_scene_id.as_deref(),
             Some("base::workplace_landlord")
@@ -779,30 +668,28 @@ mod tests {
         let mut gs = start_game(pre, workplace_config(), false);
 
         let first_pick = gs
+            .session
             .scheduler
-            .pick_next(&gs.world, &gs.registry, &mut gs.rng)
+            .pick_next(&gs.session.world, &gs.session.registry, &mut gs.session.rng)
             .expect("workplace route should schedule arrival");
-        if first_pick.once_only {
-            gs.world
-                .game_data
-                .set_flag(format!("ONCE_{}", first_pick.scene_id));
-        }
+        first_pick.mark_started(&mut gs.session.world);
         crate::start_scene(
-            &mut gs.engine,
-            &gs.world,
-            &gs.registry,
+            &mut gs.session.engine,
+            &mut gs.session.world,
+            &gs.session.registry,
             first_pick.scene_id,
             first_pick.npc_role.as_deref(),
+            first_pick.npc,
         );
         play_scene_to_finish(&mut gs);
 
         let save_path = temp_save_path("resume_snapshot_reset");
-        undone_save::save_game(&gs.world, &gs.registry, &save_path).unwrap();
+        undone_save::save_game(&gs.session.world, &gs.session.registry, &save_path).unwrap();
 
-        gs.engine.send(
+        gs.session.engine.send(
             EngineCommand::StartScene("base::rain_shelter".into()),
-            &mut gs.world,
-            &gs.registry,
+            &mut gs.session.world,
+            &gs.session.registry,
         );
 
         let signals = AppSignals::new();
@@ -811,6 +698,7 @@ mod tests {
             id: "stale".into(),
             label: "Stale".into(),
             detail: "stale".into(),
+            check: None,
         }]);
         signals.active_npc.set(Some(NpcSnapshot {
             name: "Stale".into(),
@@ -217,6 +217,19 @@ fn continue_to_next_scene(state: &Rc<RefCell<GameState>>, signals: AppSignals) {
     let _ = controller.continue_flow();
 }
 
+/// Shown under a spent red check in place of its odds.
+const SPENT_CHECK_TEXT: &str = "That moment has passed.";
+
+/// The narrator-voice line under a check action's label; empty for plain
+/// choices and for checks with no pack phrasing.
+fn check_odds_text(action: &ActionView) -> String {
+    match &action.check {
+        Some(check) if check.spent => SPENT_CHECK_TEXT.to_string(),
+        Some(check) => check.phrase.clone().unwrap_or_default(),
+        None => String::new(),
+    }
+}
+
 fn action_feedback_reset_generation(
     scene_epoch: u64,
     actions: &[ActionView],
@@ -537,10 +550,16 @@ fn choices_bar(
             let label_text = action.label.clone();
             let detail_text = action.detail.clone();
             let detail_text_enter = detail_text.clone();
+            let locked = action.is_locked();
+            let odds_text = check_odds_text(&action);
+            let has_odds = !odds_text.is_empty();
             let state_clone = Rc::clone(&state);
             let signals_clone = signals;
 
             let exec_action = move || {
+                if locked {
+                    return;
+                }
                 dispatch_action(action_id.clone(), &state_clone, signals_clone);
             };
 
@@ -562,12 +581,27 @@ fn choices_bar(
                         .font_size(15.0)
                         .font_family(UI_FONT_FAMILY.to_string())
                 }),
-                label(move || label_text.clone()).style(move |s| {
-                    let colors = ThemeColors::from_mode(signals.prefs.get().mode);
-                    s.color(colors.ink)
-                        .font_size(15.0)
-                        .font_family(UI_FONT_FAMILY.to_string())
-                }),
+                v_stack((
+                    label(move || label_text.clone()).style(move |s| {
+                        let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+                        s.color(if locked { colors.ink_ghost } else { colors.ink })
+                            .font_size(15.0)
+                            .font_family(UI_FONT_FAMILY.to_string())
+                    }),
+                    label(move || odds_text.clone()).style(move |s| {
+                        let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+                        let s = s
+                            .padding_top(2.0)
+                            .color(colors.ink_dim)
+                            .font_size(12.0)
+                            .font_family(UI_FONT_FAMILY.to_string());
+                        if has_odds {
+                            s
+                        } else {
+                            s.display(floem::style::Display::None)
+                        }
+                    }),
+                )),
             ))
             .keyboard_navigable()
             .on_click_stop(move |_| {
@@ -651,7 +685,7 @@ fn choices_bar(
 mod tests {
     use super::{
         action_bar_side_padding, action_feedback_reset_generation, centered_action_hitbox_contains,
-        markdown_to_text_layout,
+        check_odds_text, markdown_to_text_layout, SPENT_CHECK_TEXT,
     };
     use crate::layout::{
         action_button_columns_for_window, action_button_rows_for_window, sidebar_width_for_window,
@@ -792,6 +826,7 @@ mod tests {
             id: "wait".into(),
             label: "Wait".into(),
             detail: "Hold steady.".into(),
+            check: None,
         }];
 
         assert_ne!(
@@ -806,11 +841,13 @@ mod tests {
             id: "wait".into(),
             label: "Wait".into(),
             detail: "Hold steady.".into(),
+            check: None,
         }];
         let second_actions = vec![ActionView {
             id: "leave".into(),
             label: "Leave".into(),
             detail: "Move on.".into(),
+            check: None,
         }];
 
         assert_ne!(
@@ -818,4 +855,26 @@ mod tests {
             action_feedback_reset_generation(3, &second_actions)
         );
     }
+
+    #[test]
+    fn check_odds_text_shows_the_phrase_or_the_spent_line() {
+        let check = |spent: bool| undone_scene::engine::CheckView {
+            skill: "COMPOSURE".into(),
+            odds_pass: 0.5,
+            check_type: undone_scene::CheckType::Red,
+            spent,
+            phrase: (!spent).then(|| "Maybe.".to_string()),
+        };
+        let mut action = ActionView {
+            id: "hold".into(),
+            label: "Hold still".into(),
+            detail: String::new(),
+            check: None,
+        };
+        assert_eq!(check_odds_text(&action), "");
+        action.check = Some(check(false));
+        assert_eq!(check_odds_text(&action), "Maybe.");
+        action.check = Some(check(true));
+        assert_eq!(check_odds_text(&action), SPENT_CHECK_TEXT);
+    }
 }
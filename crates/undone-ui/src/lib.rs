@@ -1,6 +1,7 @@
 pub mod char_creation;
 pub mod dev_ipc;
 pub mod dev_panel;
+pub mod dev_socket;
 pub mod game_state;
 pub mod landing_page;
 pub mod layout;
@@ -96,6 +97,9 @@ pub struct AppSignals {
     /// When true, the player has finished a scene and should see a "Continue"
     /// button instead of action choices. Clicking Continue loads the next scene.
     pub awaiting_continue: RwSignal<bool>,
+    /// Why the last dev-mode hot reload of `packs/` was rejected; cleared by the
+    /// next one that succeeds.
+    pub reload_error: RwSignal<Option<String>>,
 }
 
 impl Default for AppSignals {
@@ -125,6 +129,7 @@ impl AppSignals {
             window_height: RwSignal::new(DEFAULT_WINDOW_HEIGHT),
             window_id,
             awaiting_continue: RwSignal::new(false),
+            reload_error: RwSignal::new(None),
         }
     }
 }
@@ -141,6 +146,15 @@ pub struct PlayerSnapshot {
     pub anxiety: i32,
     pub arousal: String, // e.g. "Comfort", "Enjoy"
     pub alcohol: String, // e.g. "Sober", "Tipsy"
+    pub buffs: Vec<BuffSnapshot>,
+}
+
+/// A buff on the player, as the stats sidebar shows it.
+#[derive(Clone, Default, PartialEq)]
+pub struct BuffSnapshot {
+    pub name: String,
+    pub stacks: u32,
+    pub source: String, // scene id, or the granting trait/item/need
 }
 
 impl PlayerSnapshot {
@@ -158,6 +172,15 @@ impl PlayerSnapshot {
             anxiety: p.anxiety.get(),
             arousal: format!("{}", p.arousal),
             alcohol: format!("{}", p.alcohol),
+            buffs: p
+                .buffs
+                .iter()
+                .map(|buff| BuffSnapshot {
+                    name: buff.name.clone(),
+                    stacks: buff.stacks,
+                    source: buff.source.clone(),
+                })
+                .collect(),
         }
     }
 }
@@ -306,8 +329,11 @@ pub fn app_view(window_id: WindowId, dev_mode: bool, quick_start: bool) -> impl
                 {
                     let mut gs_opt = gs_ref.borrow_mut();
                     if let Some(ref mut gs) = *gs_opt {
-                        let transformation_scene =
-                            gs.registry.transformation_scene().map(str::to_owned);
+                        let transformation_scene = gs
+                            .session
+                            .registry
+                            .transformation_scene()
+                            .map(str::to_owned);
                         if let Some(scene_id) = transformation_scene {
                             let mut controller = RuntimeController::new(gs, signals);
                             let _ = controller.start_scene(scene_id);
@@ -389,7 +415,12 @@ pub fn app_view(window_id: WindowId, dev_mode: bool, quick_start: bool) -> impl
                     }
                 };
                 let gs_cell: Rc<RefCell<GameState>> = Rc::new(RefCell::new(inner_gs));
-                crate::dev_ipc::start_polling(signals, Rc::clone(&gs_cell));
+                crate::dev_ipc::start_server(signals, Rc::clone(&gs_cell));
+                crate::dev_ipc::start_hot_reload(
+                    signals,
+                    Rc::clone(&gs_cell),
+                    undone_session::resolve_packs_dir(),
+                );
 
                 dyn_container(move || signals.tab.get(), {
                     let gs_cell = Rc::clone(&gs_cell);
@@ -506,39 +537,7 @@ pub fn app_view(window_id: WindowId, dev_mode: bool, quick_start: bool) -> impl
         .style(|s| s.size_full())
 }
 
-/// Start a scene and wire in the active NPCs so effects like `set_npc_role` and
-/// `add_npc_liking` can resolve their `npc = "m"` / `npc = "f"` references.
-///
-/// When `npc_role` is provided (from the schedule event), the NPC with that role
-/// is preferred as the active male/female. Falls back to the first NPC in the
-/// slotmap when no role is specified or no NPC has the requested role.
-pub fn start_scene(
-    engine: &mut undone_scene::engine::SceneEngine,
-    world: &World,
-    registry: &undone_packs::PackRegistry,
-    scene_id: String,
-    npc_role: Option<&str>,
-) {
-    let active_male = npc_role
-        .and_then(|role| {
-            world
-                .male_npcs
-                .iter()
-                .find(|(_, npc)| npc.core.roles.contains(role))
-                .map(|(key, _)| key)
-        })
-        .or_else(|| world.male_npcs.iter().next().map(|(key, _)| key));
-    let active_female = npc_role
-        .and_then(|role| {
-            world
-                .female_npcs
-                .iter()
-                .find(|(_, npc)| npc.core.roles.contains(role))
-                .map(|(key, _)| key)
-        })
-        .or_else(|| world.female_npcs.iter().next().map(|(key, _)| key));
-    engine.start_scene_with_bindings(scene_id, active_male, active_female, world, registry);
-}
+pub use undone_session::start_scene;
 
 pub fn reset_scene_ui_state(signals: AppSignals) {
     signals.story.set(String::new());
@@ -605,6 +604,7 @@ pub fn process_events(
     femininity_id: SkillId,
     composure_id: SkillId,
 ) -> bool {
+    crate::dev_ipc::publish_events(&events);
     let mut scene_finished = false;
     for event in events {
         match event {
@@ -659,6 +659,15 @@ pub fn process_events(
                     schedule_scroll_to_bottom(signals);
                 }
             }
+            EngineEvent::BodyChanged(text) => {
+                let should_scroll = !signals.story.get_untracked().is_empty();
+                signals.story.update(|s| {
+                    append_story_paragraph(s, &format!("*{}*", text));
+                });
+                if should_scroll {
+                    schedule_scroll_to_bottom(signals);
+                }
+            }
             EngineEvent::SlotRequested(_slot) => {
                 // Slot routing is handled by the caller (left_panel dispatch);
                 // the UI event processor ignores it here.
@@ -711,6 +720,9 @@ mod tests {
             core: NpcCore {
                 name: "Jake".into(),
                 display_name: None,
+                story_id: None,
+                agenda: Default::default(),
+                memory: Default::default(),
                 age: Age::MidLateTwenties,
                 race: "white".into(),
                 eye_colour: "blue".into(),
@@ -808,6 +820,7 @@ mod tests {
             id: "stale".into(),
             label: "Stale".into(),
             detail: "old".into(),
+            check: None,
         }]);
         signals.active_npc.set(Some(NpcSnapshot {
             name: "Jake".into(),
@@ -962,6 +975,7 @@ mod tests {
                 id: "go".into(),
                 label: "Go".into(),
                 detail: "do it".into(),
+                check: None,
             }])],
             signals,
             &world,
@@ -980,6 +994,7 @@ mod tests {
             id: "stale".into(),
             label: "Stale".into(),
             detail: String::new(),
+            check: None,
         }]);
         let world = test_world();
         let finished = process_events(
@@ -1118,6 +1133,8 @@ mod tests {
             id: "test::npc_binding".into(),
             pack: "test".into(),
             intro_prose: "Intro.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -1140,8 +1157,11 @@ mod tests {
                     goto: None,
                     slot: None,
                     finish: true,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         };
@@ -1157,10 +1177,11 @@ mod tests {
 
         start_scene(
             &mut engine,
-            &world,
+            &mut world,
             &registry,
             "test::npc_binding".into(),
             None,
+            None,
         );
         engine.drain();
 
@@ -1187,6 +1208,8 @@ mod tests {
             id: "test::intro_time_npc".into(),
             pack: "test".into(),
             intro_prose: "{{ m.getLiking() }}".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![],
@@ -1204,10 +1227,11 @@ mod tests {
 
         start_scene(
             &mut engine,
-            &world,
+            &mut world,
             &registry,
             "test::intro_time_npc".into(),
             None,
+            None,
         );
         let events = engine.drain();
 
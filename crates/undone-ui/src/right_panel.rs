@@ -1,6 +1,6 @@
 use crate::layout::sidebar_width_for_window;
 use crate::theme::{ThemeColors, ThemeMode, UI_FONT_FAMILY};
-use crate::{AppSignals, NpcSnapshot, PlayerSnapshot};
+use crate::{AppSignals, BuffSnapshot, NpcSnapshot, PlayerSnapshot};
 use floem::peniko::Color;
 use floem::prelude::*;
 use floem::reactive::RwSignal;
@@ -75,10 +75,39 @@ fn stats_panel(player: RwSignal<PlayerSnapshot>, signals: AppSignals) -> impl Vi
         stat_row("Arousal", move || player.get().arousal.clone(), signals),
         stat_row("Desire", move || player.get().desire.to_string(), signals),
         stat_row("Alcohol", move || player.get().alcohol.clone(), signals),
+        label(move || buffs_text(&player.get().buffs)).style(move |s| {
+            let colors = ThemeColors::from_mode(signals.prefs.get().mode);
+            let s = s
+                .margin_top(8.0)
+                .color(colors.ink_dim)
+                .font_size(12.0)
+                .font_family(UI_FONT_FAMILY.to_string());
+            if player.get().buffs.is_empty() {
+                s.display(floem::style::Display::None)
+            } else {
+                s
+            }
+        }),
     ))
     .style(|s| s.padding(16.0))
 }
 
+/// One line per buff: name, stack count when stacked, and where it came from.
+fn buffs_text(buffs: &[BuffSnapshot]) -> String {
+    buffs
+        .iter()
+        .map(|buff| {
+            let stacks = if buff.stacks > 1 {
+                format!(" x{}", buff.stacks)
+            } else {
+                String::new()
+            };
+            format!("{}{} ({})", buff.name, stacks, buff.source)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn people_panel(active_npc: RwSignal<Option<NpcSnapshot>>, signals: AppSignals) -> impl View {
     let title = label(|| "People Here".to_string()).style(move |s| {
         let colors = ThemeColors::from_mode(signals.prefs.get().mode);
@@ -242,6 +271,26 @@ fn mode_toggle(signals: AppSignals) -> impl View {
 mod tests {
     use super::*;
 
+    #[test]
+    fn buffs_text_lists_name_stacks_and_source() {
+        let buffs = vec![
+            BuffSnapshot {
+                name: "Hungover".into(),
+                stacks: 2,
+                source: "base::bar_night".into(),
+            },
+            BuffSnapshot {
+                name: "Poised".into(),
+                stacks: 1,
+                source: "Confident".into(),
+            },
+        ];
+        assert_eq!(
+            buffs_text(&buffs),
+            "Hungover x2 (base::bar_night)\nPoised (Confident)"
+        );
+    }
+
     #[test]
     fn liking_band_mapping_is_player_readable() {
         assert_eq!(liking_band(LikingLevel::Neutral), "Neutral");
@@ -1,12 +1,10 @@
 use floem::prelude::{SignalGet, SignalUpdate};
 
-use crate::game_state::{GameState, SceneTimeAnchor};
+use crate::game_state::GameState;
 use crate::runtime_snapshot::{snapshot_runtime, RuntimeSnapshot};
-use crate::{
-    process_events, reset_scene_ui_state, start_scene, AppPhase, AppSignals, AppTab, PlayerSnapshot,
-};
+use crate::{process_events, reset_scene_ui_state, AppPhase, AppSignals, AppTab, PlayerSnapshot};
 use undone_scene::engine::EngineEvent;
-use undone_scene::scheduler::PickResult;
+use undone_session::{SessionCommand, SessionResult, SessionStep};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuntimeCommandOutcome {
@@ -17,6 +15,8 @@ pub struct RuntimeCommandOutcome {
 
 pub type RuntimeCommandResult = Result<RuntimeCommandOutcome, String>;
 
+/// Runs player commands through the game's `GameSession` and renders what each
+/// one produced into the UI signals.
 pub struct RuntimeController<'a> {
     pub gs: &'a mut GameState,
     pub signals: AppSignals,
@@ -27,71 +27,46 @@ impl<'a> RuntimeController<'a> {
         Self { gs, signals }
     }
 
-    pub fn start_scene(&mut self, scene_id: impl Into<String>) -> RuntimeCommandResult {
-        let scene_id = scene_id.into();
-        if !self.gs.engine.has_scene(&scene_id) {
-            return Err(format!("Unknown scene '{scene_id}'"));
+    /// Run one session command, as a path found over a `GameSession` or a
+    /// replay lists them.
+    pub fn apply(&mut self, command: &SessionCommand) -> RuntimeCommandResult {
+        match command {
+            SessionCommand::Continue => self.continue_flow(),
+            SessionCommand::ChooseAction(action_id) => self.choose_action(action_id),
+            SessionCommand::JumpToScene(scene_id) => self.jump_to_scene(scene_id),
+            SessionCommand::Wait => self.wait(),
         }
+    }
 
-        self.start_scene_internal(scene_id, None, None)
+    pub fn start_scene(&mut self, scene_id: impl Into<String>) -> RuntimeCommandResult {
+        let step = self.gs.session.jump_to_scene(&scene_id.into());
+        self.show(step)
     }
 
     pub fn choose_action(&mut self, action_id: &str) -> RuntimeCommandResult {
-        let chosen = self
-            .signals
-            .actions
-            .get_untracked()
-            .into_iter()
+        let label = self
+            .gs
+            .session
+            .actions()
+            .iter()
             .find(|action| action.id == action_id)
-            .ok_or_else(|| format!("Action '{action_id}' is not currently visible"))?;
-
-        self.echo_choice(&chosen.label);
-
-        let events =
-            self.gs
-                .engine
-                .advance_with_action(action_id, &mut self.gs.world, &self.gs.registry);
-        let requested_slot = events.iter().find_map(|event| {
-            if let EngineEvent::SlotRequested(slot) = event {
-                Some(slot.clone())
-            } else {
-                None
-            }
-        });
-        let scene_finished = process_events(
-            events,
-            self.signals,
-            &self.gs.world,
-            self.gs.femininity_id,
-            self.gs.composure_id,
-        );
-
-        if let Some(slot_name) = requested_slot {
-            return self.start_requested_slot(&slot_name);
+            .map(|action| action.label.clone());
+        let step = self.gs.session.choose_action(action_id)?;
+        if let Some(label) = label {
+            self.echo_choice(&label);
         }
-
-        if scene_finished {
-            if self.signals.phase.get_untracked() == AppPhase::TransformationIntro {
-                self.signals.phase.set(AppPhase::FemCreation);
-            } else {
-                self.signals.awaiting_continue.set(true);
-            }
-        }
-
-        Ok(self.outcome(None, scene_finished))
+        self.show(Ok(step))
     }
 
     pub fn continue_flow(&mut self) -> RuntimeCommandResult {
-        let can_launch_initial = self.gs.engine.current_scene_id().is_none();
-        if !self.signals.awaiting_continue.get_untracked() && !can_launch_initial {
-            return Err("Runtime is not awaiting continue".to_string());
-        }
-
-        if self.signals.awaiting_continue.get_untracked() {
-            self.consume_pending_scene_time();
-        }
+        let step = self.gs.session.continue_flow();
+        self.show(step)
+    }
 
-        self.start_next_scene(true)
+    /// Let one time slot pass with no scene running, then pick the next scene.
+    pub fn wait(&mut self) -> RuntimeCommandResult {
+        let step = self.gs.session.wait();
+        self.show(step)
     }
 
     pub fn jump_to_scene(&mut self, scene_id: &str) -> RuntimeCommandResult {
@@ -100,141 +75,82 @@ impl<'a> RuntimeController<'a> {
         Ok(outcome)
     }
 
+    /// Replay the running scene from its current definition — after a hot
+    /// reload, say — with the NPCs, roles and params it was started with.
+    pub fn restart_scene(&mut self) -> RuntimeCommandResult {
+        let step = self.gs.session.restart_scene();
+        self.show(step)
+    }
+
     pub fn resume_from_current_world(&mut self) -> RuntimeCommandResult {
-        self.gs.engine.reset_runtime();
-        self.gs.opening_scene = None;
-        self.gs.current_scene_time_anchor = None;
-        self.start_next_scene(false)
+        let step = self.gs.session.resume();
+        self.show(step)
     }
 
     pub fn snapshot(&self) -> RuntimeSnapshot {
         snapshot_runtime(self.signals, self.gs)
     }
 
-    fn start_scene_internal(
-        &mut self,
-        scene_id: String,
-        scene_time_anchor: Option<SceneTimeAnchor>,
-        npc_role: Option<&str>,
-    ) -> RuntimeCommandResult {
-        if !self.gs.engine.has_scene(&scene_id) {
-            return Err(format!("Unknown scene '{scene_id}'"));
-        }
-
-        self.gs.current_scene_time_anchor = scene_time_anchor;
-        reset_scene_ui_state(self.signals);
-        start_scene(
-            &mut self.gs.engine,
-            &self.gs.world,
-            &self.gs.registry,
-            scene_id.clone(),
-            npc_role,
-        );
-        let events = self.gs.engine.drain();
-        let scene_finished = process_events(
-            events,
-            self.signals,
-            &self.gs.world,
-            self.gs.femininity_id,
-            self.gs.composure_id,
-        );
-        if scene_finished {
-            self.signals.awaiting_continue.set(true);
+    /// Render a session step: events that closed out the previous scene,
+    /// then a fresh story panel for whatever the step started.
+    fn show(&mut self, step: SessionResult) -> RuntimeCommandResult {
+        let SessionStep {
+            mut events,
+            started_scene_id,
+            no_scene_available,
+            scene_start,
+            ..
+        } = step?;
+        let opened_scene = started_scene_id.is_some() || no_scene_available;
+        if opened_scene {
+            let started = events.split_off(scene_start);
+            if !events.is_empty() {
+                self.process(events);
+            }
+            reset_scene_ui_state(self.signals);
+            events = started;
         }
 
-        Ok(self.outcome(Some(scene_id), scene_finished))
-    }
-
-    fn start_next_scene(&mut self, allow_opening_scene: bool) -> RuntimeCommandResult {
-        if let Some(result) =
-            self.gs
-                .scheduler
-                .pick_next(&self.gs.world, &self.gs.registry, &mut self.gs.rng)
-        {
-            let _ = self.gs.opening_scene.take();
-            return self.start_scheduled_scene(result);
+        let scene_finished = self.process(events);
+        if no_scene_available {
+            self.show_no_scene_available();
         }
-
-        if allow_opening_scene {
-            if let Some(scene_id) = self.gs.opening_scene.take() {
-                return self.start_scene_internal(scene_id, None, None);
+        if scene_finished {
+            if !opened_scene && self.signals.phase.get_untracked() == AppPhase::TransformationIntro
+            {
+                self.signals.phase.set(AppPhase::FemCreation);
+            } else {
+                self.signals.awaiting_continue.set(true);
             }
         }
 
-        Ok(self.show_no_scene_available())
-    }
-
-    fn start_requested_slot(&mut self, slot_name: &str) -> RuntimeCommandResult {
-        if let Some(result) = self.gs.scheduler.pick(
-            slot_name,
-            &self.gs.world,
-            &self.gs.registry,
-            &mut self.gs.rng,
-        ) {
-            return self.start_scheduled_scene(result);
-        }
-
-        Ok(self.show_no_scene_available())
+        Ok(RuntimeCommandOutcome {
+            started_scene_id,
+            current_scene_id: self.gs.session.current_scene_id(),
+            scene_finished,
+        })
     }
 
-    fn start_scheduled_scene(&mut self, result: PickResult) -> RuntimeCommandResult {
-        if !self.gs.engine.has_scene(&result.scene_id) {
-            return Err(format!("Unknown scene '{}'", result.scene_id));
-        }
-
-        if result.once_only {
-            self.gs
-                .world
-                .game_data
-                .set_flag(format!("ONCE_{}", result.scene_id));
-        }
-        let scene_time_anchor = result
-            .consumes_time
-            .then(|| SceneTimeAnchor::capture(&self.gs.world));
-        self.start_scene_internal(
-            result.scene_id,
-            scene_time_anchor,
-            result.npc_role.as_deref(),
+    fn process(&self, events: Vec<EngineEvent>) -> bool {
+        process_events(
+            events,
+            self.signals,
+            &self.gs.session.world,
+            self.gs.femininity_id,
+            self.gs.composure_id,
         )
     }
 
-    fn show_no_scene_available(&mut self) -> RuntimeCommandOutcome {
-        self.gs.current_scene_time_anchor = None;
-        reset_scene_ui_state(self.signals);
+    fn show_no_scene_available(&self) {
         self.signals
             .story
-            .set("[No eligible scene is currently available.]".to_string());
+            .set(undone_session::NO_SCENE_AVAILABLE.to_string());
         self.signals.actions.set(vec![]);
         self.signals.player.set(PlayerSnapshot::from_player(
-            &self.gs.world,
+            &self.gs.session.world,
             self.gs.femininity_id,
             self.gs.composure_id,
         ));
-
-        self.outcome(None, false)
-    }
-
-    fn outcome(
-        &self,
-        started_scene_id: Option<String>,
-        scene_finished: bool,
-    ) -> RuntimeCommandOutcome {
-        RuntimeCommandOutcome {
-            started_scene_id,
-            current_scene_id: self.gs.engine.current_scene_id(),
-            scene_finished,
-        }
-    }
-
-    fn consume_pending_scene_time(&mut self) {
-        let should_advance = self
-            .gs
-            .current_scene_time_anchor
-            .is_some_and(|anchor| anchor.matches_world(&self.gs.world));
-        self.gs.current_scene_time_anchor = None;
-        if should_advance {
-            self.gs.world.game_data.advance_time_slot();
-        }
     }
 
     fn echo_choice(&self, label: &str) {
@@ -246,7 +162,7 @@ impl<'a> RuntimeController<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game_state::{start_game, GameState, PreGameState};
+    use crate::game_state::{fresh_session, start_game, GameState, PreGameState};
     use crate::NpcSnapshot;
     use lasso::Key;
     use rand::{rngs::SmallRng, SeedableRng};
@@ -256,7 +172,7 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
     use undone_domain::{AttractionLevel, LikingLevel, RelationshipStatus, SkillId};
     use undone_packs::{LoadedPackMeta, PackContent, PackManifest, PackMeta, PackRegistry};
-    use undone_scene::engine::{ActionView, SceneEngine};
+    use undone_scene::engine::ActionView;
     use undone_scene::scheduler::{load_schedule, Scheduler};
     use undone_scene::types::{Action, NextBranch, SceneDefinition};
     use undone_world::test_helpers::{make_test_male_npc, make_test_world as test_world};
@@ -308,17 +224,18 @@ mod tests {
         world.male_npcs.insert(make_test_male_npc(personality));
 
         GameState {
-            world,
-            registry,
-            engine: SceneEngine::new(scenes),
-            scheduler: Scheduler::empty(),
-            rng: SmallRng::seed_from_u64(7),
+            session: fresh_session(
+                registry,
+                scenes,
+                Scheduler::empty(),
+                world,
+                SmallRng::seed_from_u64(7),
+                None,
+            ),
             dev_mode: true,
             init_error: None,
-            opening_scene: None,
             femininity_id: SkillId::from_spur(lasso::Spur::try_from_usize(0).unwrap()),
             composure_id: SkillId::from_spur(lasso::Spur::try_from_usize(1).unwrap()),
-            current_scene_time_anchor: None,
         }
     }
 
@@ -363,6 +280,13 @@ mod tests {
                     races_file: None,
                     categories_file: None,
                     arcs_file: None,
+                    characters_file: None,
+                    routines_file: None,
+                    body_file: None,
+                    odds_file: None,
+                    buffs_file: None,
+                    flags_file: None,
+                    prose_lint_file: None,
                 },
             },
         };
@@ -370,29 +294,43 @@ mod tests {
     }
 
     fn settle_workplace_route(gs: &mut GameState) {
-        gs.world.game_data.set_flag("ROUTE_WORKPLACE");
-        gs.world
+        gs.session.world.game_data.set_flag("ROUTE_WORKPLACE");
+        gs.session
+            .world
             .game_data
             .advance_arc("base::workplace_opening", "settled");
-        gs.world.game_data.set_flag("MET_LANDLORD");
-        gs.world.game_data.set_flag("FIRST_MEETING_DONE");
-        gs.world.game_data.set_flag("ONCE_base::workplace_arrival");
-        gs.world.game_data.set_flag("ONCE_base::workplace_landlord");
-        gs.world
+        gs.session.world.game_data.set_flag("MET_LANDLORD");
+        gs.session.world.game_data.set_flag("FIRST_MEETING_DONE");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_arrival");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_landlord");
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_night");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_clothes");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_day");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_work_meeting");
-        gs.world.game_data.set_flag("ONCE_base::workplace_evening");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_evening");
         for _ in 0..28 {
-            gs.world.game_data.advance_time_slot();
+            gs.session.world.game_data.advance_time_slot();
         }
     }
 
@@ -418,6 +356,8 @@ mod tests {
             id: "test::clear_state".into(),
             pack: "test".into(),
             intro_prose: "Fresh intro.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -433,8 +373,11 @@ mod tests {
                     goto: None,
                     slot: None,
                     finish: false,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         };
@@ -446,6 +389,7 @@ mod tests {
             id: "stale".into(),
             label: "Stale".into(),
             detail: "old".into(),
+            check: None,
         }]);
         signals.active_npc.set(Some(NpcSnapshot {
             name: "Old".into(),
@@ -478,6 +422,8 @@ mod tests {
             id: "test::intro_time_npc".into(),
             pack: "test".into(),
             intro_prose: "{{ m.getLiking() }}".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![],
@@ -501,16 +447,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn runtime_controller_wait_passes_one_slot_through_the_session() {
+        let mut gs = test_game_state();
+        let signals = AppSignals::new();
+        let before = gs.session.world.game_data.absolute_slot();
+
+        let mut controller = RuntimeController::new(&mut gs, signals);
+        let outcome = controller.apply(&SessionCommand::Wait).unwrap();
+
+        assert_eq!(
+            controller.gs.session.world.game_data.absolute_slot(),
+            before + 1
+        );
+        assert_eq!(
+            outcome.current_scene_id,
+            controller.gs.session.current_scene_id()
+        );
+        if outcome.current_scene_id.is_some() {
+            assert!(controller.apply(&SessionCommand::Wait).is_err());
+        } else {
+            assert_eq!(
+                signals.story.get_untracked(),
+                undone_session::NO_SCENE_AVAILABLE
+            );
+        }
+    }
+
     #[test]
     fn runtime_controller_continue_flow_starts_next_scene_and_applies_once_only() {
         let mut gs = test_game_state();
         let signals = AppSignals::new();
         signals.awaiting_continue.set(true);
 
-        let mut probe_rng = gs.rng.clone();
+        let mut probe_rng = gs.session.rng.clone();
         let expected = gs
+            .session
             .scheduler
-            .pick_next(&gs.world, &gs.registry, &mut probe_rng)
+            .pick_next(&gs.session.world, &gs.session.registry, &mut probe_rng)
             .expect("test game should have an eligible scene");
 
         let mut controller = RuntimeController::new(&mut gs, signals);
@@ -521,12 +495,13 @@ mod tests {
             Some(expected.scene_id.as_str())
         );
         assert_eq!(
-            controller.gs.engine.current_scene_id().as_deref(),
+            controller.gs.session.engine.current_scene_id().as_deref(),
             Some(expected.scene_id.as_str())
         );
         if expected.once_only {
             assert!(controller
                 .gs
+                .session
                 .world
                 .game_data
                 .has_flag(&format!("ONCE_{}", expected.scene_id)));
@@ -539,6 +514,8 @@ mod tests {
             id: "test::jump_target".into(),
             pack: "test".into(),
             intro_prose: "{{ m.getLiking() }}".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![],
@@ -578,7 +555,7 @@ mod tests {
         let mut controller = RuntimeController::new(&mut gs, signals);
         controller.start_scene("base::plan_your_day").unwrap();
         for _ in 0..28 {
-            controller.gs.world.game_data.advance_time_slot();
+            controller.gs.session.world.game_data.advance_time_slot();
         }
 
         let before = controller.snapshot();
@@ -591,14 +568,15 @@ mod tests {
             vec!["go_out", "run_errands", "stay_in"]
         );
 
-        let mut probe_rng = controller.gs.rng.clone();
+        let mut probe_rng = controller.gs.session.rng.clone();
         let expected = controller
             .gs
+            .session
             .scheduler
             .pick(
                 "free_time",
-                &controller.gs.world,
-                &controller.gs.registry,
+                &controller.gs.session.world,
+                &controller.gs.session.registry,
                 &mut probe_rng,
             )
             .expect("free_time slot should have at least one eligible scene");
@@ -635,6 +613,8 @@ mod tests {
             id: "test::hub".into(),
             pack: "test".into(),
             intro_prose: "Hub.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -650,13 +630,16 @@ mod tests {
                     goto: None,
                     slot: Some("test_slot".into()),
                     finish: false,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         };
         let mut gs = custom_game_state(scene);
-        gs.scheduler = scheduler_with_event("test_slot", "test::missing_scene");
+        gs.session.scheduler = scheduler_with_event("test_slot", "test::missing_scene");
         let signals = AppSignals::new();
 
         let mut controller = RuntimeController::new(&mut gs, signals);
@@ -680,11 +663,11 @@ mod tests {
         controller.choose_action("go_out").unwrap();
 
         assert!(
-            controller.gs.current_scene_time_anchor.is_some(),
+            controller.gs.session.current_scene_time_anchor.is_some(),
             "scheduled free_time scenes should remember their cadence anchor"
         );
 
-        let mut expected = controller.gs.world.game_data.clone();
+        let mut expected = controller.gs.session.world.game_data.clone();
         expected.advance_time_slot();
 
         let paused = play_first_visible_action_until_pause(&mut controller);
@@ -697,9 +680,9 @@ mod tests {
 
         assert_eq!(
             (
-                controller.gs.world.game_data.week,
-                controller.gs.world.game_data.day,
-                format!("{:?}", controller.gs.world.game_data.time_slot),
+                controller.gs.session.world.game_data.week,
+                controller.gs.session.world.game_data.day,
+                format!("{:?}", controller.gs.session.world.game_data.time_slot),
             ),
             (
                 expected.week,
@@ -713,23 +696,34 @@ mod tests {
     #[test]
     fn runtime_controller_opening_arc_scene_does_not_double_advance_time() {
         let mut gs = test_game_state();
-        gs.world.game_data.set_flag("ROUTE_WORKPLACE");
-        gs.world
+        gs.session.world.game_data.set_flag("ROUTE_WORKPLACE");
+        gs.session
+            .world
             .game_data
             .advance_arc("base::workplace_opening", "working");
-        gs.world.game_data.set_flag("FIRST_MEETING_DONE");
-        gs.world.game_data.set_flag("ONCE_base::workplace_arrival");
-        gs.world.game_data.set_flag("ONCE_base::workplace_landlord");
-        gs.world
+        gs.session.world.game_data.set_flag("FIRST_MEETING_DONE");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_arrival");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_landlord");
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_night");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_clothes");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_day");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_work_meeting");
         let signals = AppSignals::new();
@@ -743,20 +737,20 @@ mod tests {
             Some("base::workplace_evening")
         );
         assert!(
-            controller.gs.current_scene_time_anchor.is_none(),
+            controller.gs.session.current_scene_time_anchor.is_none(),
             "opening-arc scenes should not be treated as cadence-consuming slot scenes"
         );
 
         let _ = play_first_visible_action_until_pause(&mut controller);
-        let after_scene = controller.gs.world.game_data.clone();
+        let after_scene = controller.gs.session.world.game_data.clone();
 
         controller.continue_flow().unwrap();
 
         assert_eq!(
             (
-                controller.gs.world.game_data.week,
-                controller.gs.world.game_data.day,
-                controller.gs.world.game_data.time_slot,
+                controller.gs.session.world.game_data.week,
+                controller.gs.session.world.game_data.day,
+                controller.gs.session.world.game_data.time_slot,
             ),
             (after_scene.week, after_scene.day, after_scene.time_slot),
             "continue_flow must not add an extra time step after explicit advance_time scenes"
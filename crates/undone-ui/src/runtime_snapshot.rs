@@ -27,6 +27,12 @@ pub struct VisibleActionSnapshot {
     pub id: String,
     pub label: String,
     pub detail: String,
+    /// Check actions only: the narrator-voice odds phrase shown on the button.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub odds_phrase: Option<String>,
+    /// A spent red check, shown but not choosable.
+    #[serde(default)]
+    pub locked: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -76,10 +82,11 @@ pub struct ArcStateSnapshot {
 }
 
 pub fn snapshot_runtime(signals: AppSignals, gs: &GameState) -> RuntimeSnapshot {
-    let mut game_flags: Vec<String> = gs.world.game_data.flags.iter().cloned().collect();
+    let mut game_flags: Vec<String> = gs.session.world.game_data.flags.iter().cloned().collect();
     game_flags.sort();
 
     let mut arc_states: Vec<ArcStateSnapshot> = gs
+        .session
         .world
         .game_data
         .arc_states
@@ -91,10 +98,11 @@ pub fn snapshot_runtime(signals: AppSignals, gs: &GameState) -> RuntimeSnapshot
         .collect();
     arc_states.sort_by(|left, right| left.id.cmp(&right.id));
 
-    let player = PlayerSnapshot::from_player(&gs.world, gs.femininity_id, gs.composure_id);
+    let player = PlayerSnapshot::from_player(&gs.session.world, gs.femininity_id, gs.composure_id);
     let active_npcs = gs
+        .session
         .engine
-        .current_bound_npcs(&gs.world, &gs.registry)
+        .current_bound_npcs(&gs.session.world, &gs.session.registry)
         .into_iter()
         .map(bound_npc_snapshot)
         .collect();
@@ -104,7 +112,7 @@ pub fn snapshot_runtime(signals: AppSignals, gs: &GameState) -> RuntimeSnapshot
         tab: tab_name(signals.tab.get_untracked()).to_string(),
         window_width: signals.window_width.get_untracked(),
         window_height: signals.window_height.get_untracked(),
-        current_scene_id: gs.engine.current_scene_id(),
+        current_scene_id: gs.session.engine.current_scene_id(),
         awaiting_continue: signals.awaiting_continue.get_untracked(),
         story_paragraphs: story_paragraphs(&signals.story.get_untracked()),
         visible_actions: signals
@@ -112,6 +120,8 @@ pub fn snapshot_runtime(signals: AppSignals, gs: &GameState) -> RuntimeSnapshot
             .get_untracked()
             .into_iter()
             .map(|action| VisibleActionSnapshot {
+                locked: action.is_locked(),
+                odds_phrase: action.check.and_then(|check| check.phrase),
                 id: action.id,
                 label: action.label,
                 detail: action.detail,
@@ -123,11 +133,12 @@ pub fn snapshot_runtime(signals: AppSignals, gs: &GameState) -> RuntimeSnapshot
             name: player.name,
             femininity: player.femininity,
             composure: gs
+                .session
                 .registry
                 .composure_skill()
-                .map(|id| gs.world.player.skill(id))
+                .map(|id| gs.session.world.player.skill(id))
                 .unwrap_or(0),
-            desire: gs.world.game_data.desire(),
+            desire: gs.session.world.game_data.desire(),
             money: player.money,
             stress: player.stress,
             anxiety: player.anxiety,
@@ -135,9 +146,9 @@ pub fn snapshot_runtime(signals: AppSignals, gs: &GameState) -> RuntimeSnapshot
             alcohol: player.alcohol,
         },
         world: WorldSummarySnapshot {
-            week: gs.world.game_data.week,
-            day: gs.world.game_data.day,
-            time_slot: format!("{:?}", gs.world.game_data.time_slot),
+            week: gs.session.world.game_data.week,
+            day: gs.session.world.game_data.day,
+            time_slot: format!("{:?}", gs.session.world.game_data.time_slot),
             game_flags,
             arc_states,
         },
@@ -295,6 +306,7 @@ mod tests {
             id: "wait".into(),
             label: "Wait".into(),
             detail: "Stay put.".into(),
+            check: None,
         }]);
         signals.active_npc.set(Some(NpcSnapshot {
             name: "Jake".into(),
@@ -304,16 +316,17 @@ mod tests {
             pc_liking: LikingLevel::Like,
             pc_attraction: AttractionLevel::Attracted,
         }));
-        gs.world.game_data.set_flag("FLAG_ALPHA");
-        gs.world
+        gs.session.world.game_data.set_flag("FLAG_ALPHA");
+        gs.session
+            .world
             .game_data
             .advance_arc("base::workplace_opening", "arrived");
-        gs.engine.send(
+        gs.session.engine.send(
             EngineCommand::StartScene("base::rain_shelter".into()),
-            &mut gs.world,
-            &gs.registry,
+            &mut gs.session.world,
+            &gs.session.registry,
         );
-        gs.engine.drain();
+        gs.session.engine.drain();
 
         let snapshot = snapshot_runtime(signals, &gs);
 
@@ -337,6 +350,8 @@ mod tests {
                 id: "wait".into(),
                 label: "Wait".into(),
                 detail: "Stay put.".into(),
+                odds_phrase: None,
+                locked: false,
             }]
         );
         assert_eq!(
@@ -370,59 +385,66 @@ mod tests {
     fn runtime_snapshot_lists_multiple_active_npcs() {
         let mut gs = test_game_state();
         let signals = AppSignals::new();
-        let romantic = gs.registry.intern_personality("ROMANTIC");
-        let calm = gs.registry.intern_personality("CALM");
-        let male_key = gs.world.male_npcs.insert(test_male_npc(romantic));
-        let female_key = gs.world.female_npcs.insert(undone_domain::FemaleNpc {
-            core: NpcCore {
-                name: "Mia".into(),
-                display_name: None,
-                age: Age::MidLateTwenties,
-                race: "white".into(),
-                eye_colour: "green".into(),
-                hair_colour: "black".into(),
-                personality: calm,
-                traits: HashSet::new(),
-                relationship: RelationshipStatus::Friend,
-                pc_liking: LikingLevel::Like,
-                npc_liking: LikingLevel::Neutral,
-                pc_love: LoveLevel::None,
-                npc_love: LoveLevel::None,
-                pc_attraction: AttractionLevel::Unattracted,
-                npc_attraction: AttractionLevel::Unattracted,
-                behaviour: Behaviour::Neutral,
-                relationship_flags: HashSet::new(),
-                sexual_activities: HashSet::new(),
-                custom_flags: HashMap::new(),
-                custom_ints: HashMap::new(),
-                knowledge: 0,
-                contactable: true,
-                arousal: undone_domain::ArousalLevel::Comfort,
-                alcohol: undone_domain::AlcoholLevel::Sober,
-                roles: HashSet::new(),
-            },
-            char_type: undone_domain::CharTypeId::from_spur(
-                lasso::Spur::try_from_usize(0).unwrap(),
-            ),
-            figure: undone_domain::PlayerFigure::Slim,
-            breasts: undone_domain::BreastSize::Average,
-            clothing: undone_domain::FemaleClothing::default(),
-            pregnancy: None,
-            virgin: true,
-        });
+        let romantic = gs.session.registry.intern_personality("ROMANTIC");
+        let calm = gs.session.registry.intern_personality("CALM");
+        let male_key = gs.session.world.male_npcs.insert(test_male_npc(romantic));
+        let female_key = gs
+            .session
+            .world
+            .female_npcs
+            .insert(undone_domain::FemaleNpc {
+                core: NpcCore {
+                    name: "Mia".into(),
+                    display_name: None,
+                    story_id: None,
+                    agenda: Default::default(),
+                    memory: Default::default(),
+                    age: Age::MidLateTwenties,
+                    race: "white".into(),
+                    eye_colour: "green".into(),
+                    hair_colour: "black".into(),
+                    personality: calm,
+                    traits: HashSet::new(),
+                    relationship: RelationshipStatus::Friend,
+                    pc_liking: LikingLevel::Like,
+                    npc_liking: LikingLevel::Neutral,
+                    pc_love: LoveLevel::None,
+                    npc_love: LoveLevel::None,
+                    pc_attraction: AttractionLevel::Unattracted,
+                    npc_attraction: AttractionLevel::Unattracted,
+                    behaviour: Behaviour::Neutral,
+                    relationship_flags: HashSet::new(),
+                    sexual_activities: HashSet::new(),
+                    custom_flags: HashMap::new(),
+                    custom_ints: HashMap::new(),
+                    knowledge: 0,
+                    contactable: true,
+                    arousal: undone_domain::ArousalLevel::Comfort,
+                    alcohol: undone_domain::AlcoholLevel::Sober,
+                    roles: HashSet::new(),
+                },
+                char_type: undone_domain::CharTypeId::from_spur(
+                    lasso::Spur::try_from_usize(0).unwrap(),
+                ),
+                figure: undone_domain::PlayerFigure::Slim,
+                breasts: undone_domain::BreastSize::Average,
+                clothing: undone_domain::FemaleClothing::default(),
+                pregnancy: None,
+                virgin: true,
+            });
         let mut role_bindings = HashMap::new();
         role_bindings.insert("ROLE_TEAM_LEAD".to_string(), SceneNpcRef::Male(male_key));
         role_bindings.insert("ROLE_DESIGNER".to_string(), SceneNpcRef::Female(female_key));
 
-        gs.engine.start_scene_with_role_bindings(
+        gs.session.engine.start_scene_with_role_bindings(
             "base::rain_shelter".into(),
             None,
             None,
             role_bindings,
-            &gs.world,
-            &gs.registry,
+            &gs.session.world,
+            &gs.session.registry,
         );
-        gs.engine.drain();
+        gs.session.engine.drain();
 
         let snapshot = snapshot_runtime(signals, &gs);
         assert_eq!(
@@ -460,46 +482,53 @@ mod tests {
     fn multi_npc_scene_contract_supports_prose_effects_and_snapshot() {
         let mut gs = test_game_state();
         let signals = AppSignals::new();
-        let romantic = gs.registry.intern_personality("ROMANTIC");
-        let calm = gs.registry.intern_personality("CALM");
-        let male_key = gs.world.male_npcs.insert(test_male_npc(romantic));
-        let female_key = gs.world.female_npcs.insert(undone_domain::FemaleNpc {
-            core: NpcCore {
-                name: "Mia".into(),
-                display_name: None,
-                age: Age::MidLateTwenties,
-                race: "white".into(),
-                eye_colour: "green".into(),
-                hair_colour: "black".into(),
-                personality: calm,
-                traits: HashSet::new(),
-                relationship: RelationshipStatus::Friend,
-                pc_liking: LikingLevel::Like,
-                npc_liking: LikingLevel::Neutral,
-                pc_love: LoveLevel::None,
-                npc_love: LoveLevel::None,
-                pc_attraction: AttractionLevel::Unattracted,
-                npc_attraction: AttractionLevel::Unattracted,
-                behaviour: Behaviour::Neutral,
-                relationship_flags: HashSet::new(),
-                sexual_activities: HashSet::new(),
-                custom_flags: HashMap::new(),
-                custom_ints: HashMap::new(),
-                knowledge: 0,
-                contactable: true,
-                arousal: undone_domain::ArousalLevel::Comfort,
-                alcohol: undone_domain::AlcoholLevel::Sober,
-                roles: HashSet::new(),
-            },
-            char_type: undone_domain::CharTypeId::from_spur(
-                lasso::Spur::try_from_usize(0).unwrap(),
-            ),
-            figure: undone_domain::PlayerFigure::Slim,
-            breasts: undone_domain::BreastSize::Average,
-            clothing: undone_domain::FemaleClothing::default(),
-            pregnancy: None,
-            virgin: true,
-        });
+        let romantic = gs.session.registry.intern_personality("ROMANTIC");
+        let calm = gs.session.registry.intern_personality("CALM");
+        let male_key = gs.session.world.male_npcs.insert(test_male_npc(romantic));
+        let female_key = gs
+            .session
+            .world
+            .female_npcs
+            .insert(undone_domain::FemaleNpc {
+                core: NpcCore {
+                    name: "Mia".into(),
+                    display_name: None,
+                    story_id: None,
+                    agenda: Default::default(),
+                    memory: Default::default(),
+                    age: Age::MidLateTwenties,
+                    race: "white".into(),
+                    eye_colour: "green".into(),
+                    hair_colour: "black".into(),
+                    personality: calm,
+                    traits: HashSet::new(),
+                    relationship: RelationshipStatus::Friend,
+                    pc_liking: LikingLevel::Like,
+                    npc_liking: LikingLevel::Neutral,
+                    pc_love: LoveLevel::None,
+                    npc_love: LoveLevel::None,
+                    pc_attraction: AttractionLevel::Unattracted,
+                    npc_attraction: AttractionLevel::Unattracted,
+                    behaviour: Behaviour::Neutral,
+                    relationship_flags: HashSet::new(),
+                    sexual_activities: HashSet::new(),
+                    custom_flags: HashMap::new(),
+                    custom_ints: HashMap::new(),
+                    knowledge: 0,
+                    contactable: true,
+                    arousal: undone_domain::ArousalLevel::Comfort,
+                    alcohol: undone_domain::AlcoholLevel::Sober,
+                    roles: HashSet::new(),
+                },
+                char_type: undone_domain::CharTypeId::from_spur(
+                    lasso::Spur::try_from_usize(0).unwrap(),
+                ),
+                figure: undone_domain::PlayerFigure::Slim,
+                breasts: undone_domain::BreastSize::Average,
+                clothing: undone_domain::FemaleClothing::default(),
+                pregnancy: None,
+                virgin: true,
+            });
         let mut scenes = HashMap::new();
         scenes.insert(
             "test::multi_npc".to_string(),
@@ -509,6 +538,8 @@ mod tests {
                 intro_prose:
                     r#"{{ role.getName("ROLE_TEAM_LEAD") }} and {{ role.getName("ROLE_DESIGNER") }}"#
                         .into(),
+                params: Default::default(),
+                vars: Default::default(),
                 intro_variants: vec![],
                 intro_thoughts: vec![],
                 actions: vec![Action {
@@ -528,24 +559,25 @@ mod tests {
                     ),
                     next: vec![],
                     thoughts: vec![],
+                    check: None,
                 }],
                 npc_actions: Vec::<NpcAction>::new(),
             }),
         );
-        gs.engine = SceneEngine::new(scenes);
+        gs.session.engine = SceneEngine::new(scenes);
 
         let mut role_bindings = HashMap::new();
         role_bindings.insert("ROLE_TEAM_LEAD".to_string(), SceneNpcRef::Male(male_key));
         role_bindings.insert("ROLE_DESIGNER".to_string(), SceneNpcRef::Female(female_key));
-        gs.engine.start_scene_with_role_bindings(
+        gs.session.engine.start_scene_with_role_bindings(
             "test::multi_npc".into(),
             None,
             None,
             role_bindings,
-            &gs.world,
-            &gs.registry,
+            &gs.session.world,
+            &gs.session.registry,
         );
-        let events = gs.engine.drain();
+        let events = gs.session.engine.drain();
         let intro = events
             .iter()
             .find_map(|event| match event {
@@ -556,10 +588,13 @@ mod tests {
         assert!(intro.contains("Jake"));
         assert!(intro.contains("Mia"));
 
-        gs.engine
-            .advance_with_action("approve", &mut gs.world, &gs.registry);
+        gs.session.engine.advance_with_action(
+            "approve",
+            &mut gs.session.world,
+            &gs.session.registry,
+        );
         assert_eq!(
-            gs.world.male_npcs[male_key].core.pc_liking,
+            gs.session.world.male_npcs[male_key].core.pc_liking,
             LikingLevel::Close
         );
 
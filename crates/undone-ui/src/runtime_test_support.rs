@@ -151,29 +151,43 @@ mod tests {
     }
 
     fn settle_workplace_route(gs: &mut GameState) {
-        gs.world.game_data.set_flag("ROUTE_WORKPLACE");
-        gs.world
+        gs.session.world.game_data.set_flag("ROUTE_WORKPLACE");
+        gs.session
+            .world
             .game_data
             .advance_arc("base::workplace_opening", "settled");
-        gs.world.game_data.set_flag("MET_LANDLORD");
-        gs.world.game_data.set_flag("FIRST_MEETING_DONE");
-        gs.world.game_data.set_flag("ONCE_base::workplace_arrival");
-        gs.world.game_data.set_flag("ONCE_base::workplace_landlord");
-        gs.world
+        gs.session.world.game_data.set_flag("MET_LANDLORD");
+        gs.session.world.game_data.set_flag("FIRST_MEETING_DONE");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_arrival");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_landlord");
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_night");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_clothes");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_first_day");
-        gs.world
+        gs.session
+            .world
             .game_data
             .set_flag("ONCE_base::workplace_work_meeting");
-        gs.world.game_data.set_flag("ONCE_base::workplace_evening");
+        gs.session
+            .world
+            .game_data
+            .set_flag("ONCE_base::workplace_evening");
         for _ in 0..28 {
-            gs.world.game_data.advance_time_slot();
+            gs.session.world.game_data.advance_time_slot();
         }
     }
 
@@ -432,6 +446,7 @@ mod tests {
         assert!(
             harness
                 .gs
+                .session
                 .world
                 .game_data
                 .has_flag(&format!("ONCE_{first_scene}")),
@@ -462,7 +477,12 @@ mod tests {
         let _ = play_until_continue(&mut harness);
 
         let save_path = temp_save_path("resume");
-        undone_save::save_game(&harness.gs.world, &harness.gs.registry, &save_path).unwrap();
+        undone_save::save_game(
+            &harness.gs.session.world,
+            &harness.gs.session.registry,
+            &save_path,
+        )
+        .unwrap();
 
         harness.signals.story.set("stale prose".into());
         harness
@@ -472,6 +492,7 @@ mod tests {
                 id: "stale".into(),
                 label: "Stale".into(),
                 detail: "stale".into(),
+                check: None,
             }]);
         load_world_from_save(&mut harness.gs, &save_path).unwrap();
         {
@@ -508,6 +529,8 @@ mod tests {
             id: "test::acceptance_error".into(),
             pack: "test".into(),
             intro_prose: "{{ m.undefinedMethod() }}".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![],
@@ -521,21 +544,22 @@ mod tests {
         let mut world = undone_world::test_helpers::make_test_world();
         world.male_npcs.insert(make_test_male_npc(personality));
         let mut gs = GameState {
-            world,
-            registry,
-            engine: undone_scene::engine::SceneEngine::new(scenes),
-            scheduler: undone_scene::scheduler::Scheduler::empty(),
-            rng: SmallRng::seed_from_u64(7),
+            session: crate::game_state::fresh_session(
+                registry,
+                scenes,
+                undone_scene::scheduler::Scheduler::empty(),
+                world,
+                SmallRng::seed_from_u64(7),
+                None,
+            ),
             dev_mode: true,
             init_error: None,
-            opening_scene: None,
             femininity_id: undone_domain::SkillId::from_spur(
                 lasso::Spur::try_from_usize(0).unwrap(),
             ),
             composure_id: undone_domain::SkillId::from_spur(
                 lasso::Spur::try_from_usize(1).unwrap(),
             ),
-            current_scene_time_anchor: None,
         };
         let signals = make_test_signals();
 
@@ -632,7 +656,7 @@ mod tests {
             "slot request should start a scheduled free_time scene"
         );
 
-        let mut expected = harness.gs.world.game_data.clone();
+        let mut expected = harness.gs.session.world.game_data.clone();
         expected.advance_time_slot();
         let expected_time_slot = format!("{:?}", expected.time_slot);
 
@@ -756,6 +780,7 @@ mod tests {
         let mut desk_harness = make_harness();
         desk_harness
             .gs
+            .session
             .world
             .game_data
             .set_flag("FIRST_DAY_LUNCH_DESK");
@@ -768,6 +793,7 @@ mod tests {
         let mut group_harness = make_harness();
         group_harness
             .gs
+            .session
             .world
             .game_data
             .set_flag("FIRST_DAY_LUNCH_GROUP");
@@ -129,12 +129,12 @@ pub fn saves_panel(signals: AppSignals, state: Rc<RefCell<GameState>>) -> impl V
             }
 
             let gs = save_state.borrow();
-            let fem_name = gs.world.player.name_fem.replace(' ', "_");
+            let fem_name = gs.session.world.player.name_fem.replace(' ', "_");
             let ts = system_time_to_secs(SystemTime::now());
             let filename = format!("{fem_name}_{ts}.json");
             let path = dir.join(&filename);
 
-            match undone_save::save_game(&gs.world, &gs.registry, &path) {
+            match undone_save::save_game(&gs.session.world, &gs.session.registry, &path) {
                 Ok(()) => {
                     status_msg.set(format!("Saved: {}", filename.trim_end_matches(".json")));
                     save_list.set(list_saves());
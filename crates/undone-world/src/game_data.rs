@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use undone_domain::{BoundedStat, StatId, TimeSlot};
+use undone_domain::{BodyAttribute, BoundedStat, MemoryStamp, NpcKey, StatId, TimeSlot, VarValue};
 
 /// Desire accrued each time a time slot is consumed without sexual release.
 /// The body's wanting builds passively over idle days; release scenes discharge
@@ -8,9 +8,22 @@ use undone_domain::{BoundedStat, StatId, TimeSlot};
 /// so the looping-adult pacing has a single dial.
 pub const DESIRE_GAIN_PER_SLOT: i32 = 8;
 
+/// Time slots in one day (morning through night).
+pub const SLOTS_PER_DAY: u32 = 4;
+/// Time slots in one week.
+pub const SLOTS_PER_WEEK: u32 = SLOTS_PER_DAY * 7;
+/// Slots a queued NPC outreach waits to be played before it lapses. Outreach
+/// whose event never becomes eligible would otherwise sit in the queue for
+/// good, and its rule could never queue again.
+pub const OUTREACH_EXPIRY_SLOTS: u32 = SLOTS_PER_WEEK;
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameData {
     pub flags: HashSet<String>,
+    /// Where each flag in `flags` came from, for flags set through
+    /// `set_flag_from`. Older saves load with none recorded.
+    #[serde(default)]
+    pub flag_sources: HashMap<String, FlagSource>,
     pub stats: HashMap<StatId, i32>,
     pub job_title: String,
     pub allow_anal: bool,
@@ -23,15 +36,55 @@ pub struct GameData {
     /// Added with serde(default) for backward-compat with v3 saves.
     #[serde(default)]
     pub arc_states: HashMap<String, String>,
-    /// Red-check permanent failure registry: "scene_id::skill_id".
-    /// Once a red check fails it is blocked for the rest of the game.
-    #[serde(default)]
+    /// Red-check permanent failure registry. Keys name their kind, so an
+    /// action id can't collide with a skill id: check actions record
+    /// "action:scene_id::action_id", `checkSkillRed` conditions
+    /// "skill:scene_id::skill_id". Once a red check fails it is blocked for the
+    /// rest of the game.
+    #[serde(default, deserialize_with = "deserialize_red_check_failures")]
     pub red_check_failures: HashSet<String>,
     /// Sexual desire need-state, 0–100. Builds passively as time passes (see
     /// `advance_time_slot`) and biases the scheduler toward `desire_scaled`
     /// scenes. Discharged by release scenes. `serde(default)` → old saves load at 0.
     #[serde(default)]
     pub desire: BoundedStat,
+    /// Outreach queued by NPC routines — an NPC wants to reach the player via a
+    /// scene. Played by the scheduler's `outreach = true` events, oldest first;
+    /// lapses after `OUTREACH_EXPIRY_SLOTS` unplayed.
+    #[serde(default)]
+    pub npc_outreach: Vec<NpcOutreach>,
+    /// Week each body attribute last changed, by a body-change rule or a scene
+    /// write. Rules measure their intervals from here, so a haircut restarts
+    /// the hair-growth clock.
+    #[serde(default)]
+    pub body_changed: HashMap<BodyAttribute, u32>,
+    /// Body-change messages not yet shown to the player. The scene engine
+    /// drains these into `BodyChanged` events.
+    #[serde(default)]
+    pub body_notices: Vec<String>,
+    /// Persistent typed variables declared as `[[var]]` in packs' `flags.toml`,
+    /// by id. Unset ones read as their declared default.
+    #[serde(default)]
+    pub vars: HashMap<String, VarValue>,
+}
+
+/// One queued NPC outreach: routine `rule` wants `scene` played with `npc` bound.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NpcOutreach {
+    pub rule: String,
+    pub scene: String,
+    pub npc: NpcKey,
+    /// `absolute_slot` when it was queued. Older saves load as queued at 0.
+    #[serde(default)]
+    pub queued_at: u32,
+}
+
+/// Who set a game flag, and when: a scene id, `"scheduler"`, `"new game"` or
+/// `"dev"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlagSource {
+    pub source: String,
+    pub at: MemoryStamp,
 }
 
 fn default_time_slot() -> TimeSlot {
@@ -47,8 +100,27 @@ impl GameData {
         self.flags.insert(flag.into());
     }
 
+    /// Set `flag`, recording `source` and the current time as its provenance.
+    pub fn set_flag_from(&mut self, flag: impl Into<String>, source: impl Into<String>) {
+        let flag = flag.into();
+        let at = self.memory_stamp();
+        self.flag_sources.insert(
+            flag.clone(),
+            FlagSource {
+                source: source.into(),
+                at,
+            },
+        );
+        self.flags.insert(flag);
+    }
+
+    pub fn flag_source(&self, flag: &str) -> Option<&FlagSource> {
+        self.flag_sources.get(flag)
+    }
+
     pub fn remove_flag(&mut self, flag: &str) {
         self.flags.remove(flag);
+        self.flag_sources.remove(flag);
     }
 
     pub fn get_stat(&self, id: StatId) -> i32 {
@@ -80,9 +152,13 @@ impl GameData {
     }
 
     /// Advance to the next time slot. Returns true if the week rolled over.
-    /// Each consumed slot accrues desire (the body's passive wanting).
+    /// Each consumed slot accrues desire (the body's passive wanting) and
+    /// lapses outreach that has waited `OUTREACH_EXPIRY_SLOTS`.
     pub fn advance_time_slot(&mut self) -> bool {
         self.desire.apply_delta(DESIRE_GAIN_PER_SLOT);
+        let now = self.absolute_slot() + 1;
+        self.npc_outreach
+            .retain(|o| now.saturating_sub(o.queued_at) < OUTREACH_EXPIRY_SLOTS);
         match self.time_slot.next() {
             Some(next) => {
                 self.time_slot = next;
@@ -102,6 +178,49 @@ impl GameData {
         }
     }
 
+    /// Slots elapsed since week 0, Monday morning. Monotonic across
+    /// `advance_time_slot`; used to measure gaps between NPC interactions.
+    pub fn absolute_slot(&self) -> u32 {
+        self.week * SLOTS_PER_WEEK + self.day as u32 * SLOTS_PER_DAY + self.time_slot as u32
+    }
+
+    /// The current time, for stamping memories.
+    pub fn memory_stamp(&self) -> MemoryStamp {
+        MemoryStamp {
+            week: self.week,
+            day: self.day,
+            slot: self.time_slot,
+        }
+    }
+
+    /// Weeks since `attribute` last changed (since game start if never).
+    pub fn weeks_since_body_change(&self, attribute: BodyAttribute) -> u32 {
+        self.week
+            .saturating_sub(self.body_changed.get(&attribute).copied().unwrap_or(0))
+    }
+
+    /// Queue an outreach unless the same rule already has one pending for `npc`.
+    pub fn queue_outreach(&mut self, outreach: NpcOutreach) {
+        let pending = self
+            .npc_outreach
+            .iter()
+            .any(|o| o.rule == outreach.rule && o.npc == outreach.npc);
+        if !pending {
+            self.npc_outreach.push(outreach);
+        }
+    }
+
+    /// The oldest queued outreach for `scene`, if any.
+    pub fn pending_outreach(&self, scene: &str) -> Option<&NpcOutreach> {
+        self.npc_outreach.iter().find(|o| o.scene == scene)
+    }
+
+    /// Drop the queued outreach for `scene` from `npc` once it has been played.
+    pub fn clear_outreach(&mut self, scene: &str, npc: NpcKey) {
+        self.npc_outreach
+            .retain(|o| !(o.scene == scene && o.npc == npc));
+    }
+
     pub fn is_weekday(&self) -> bool {
         self.day <= 4 // 0=Mon through 4=Fri
     }
@@ -110,16 +229,29 @@ impl GameData {
         self.day >= 5 // 5=Sat, 6=Sun
     }
 
-    /// Record a permanent red-check failure for this scene+skill combination.
+    /// Record a permanent red-check failure under a stable check id. Check
+    /// actions use `<scene id>::<action id>`.
+    pub fn fail_check(&mut self, check_id: &str) {
+        self.red_check_failures.insert(format!("action:{check_id}"));
+    }
+
+    /// Returns true if the red check `check_id` has been permanently failed.
+    pub fn has_failed_check(&self, check_id: &str) -> bool {
+        self.red_check_failures
+            .contains(&format!("action:{check_id}"))
+    }
+
+    /// Record a permanent red-check failure for a condition-style
+    /// `checkSkillRed`, keyed by scene+skill.
     pub fn fail_red_check(&mut self, scene_id: &str, skill_id: &str) {
         self.red_check_failures
-            .insert(format!("{scene_id}::{skill_id}"));
+            .insert(format!("skill:{scene_id}::{skill_id}"));
     }
 
     /// Returns true if a red check for this scene+skill has been permanently failed.
     pub fn has_failed_red_check(&self, scene_id: &str, skill_id: &str) -> bool {
         self.red_check_failures
-            .contains(&format!("{scene_id}::{skill_id}"))
+            .contains(&format!("skill:{scene_id}::{skill_id}"))
     }
 
     /// Returns the current state name for an arc, if the arc has been started.
@@ -131,12 +263,57 @@ impl GameData {
     pub fn advance_arc(&mut self, arc_id: impl Into<String>, state: impl Into<String>) {
         self.arc_states.insert(arc_id.into(), state.into());
     }
+
+    /// The value a scene last stored in game variable `id`, if any.
+    pub fn var(&self, id: &str) -> Option<&VarValue> {
+        self.vars.get(id)
+    }
+
+    pub fn set_var(&mut self, id: impl Into<String>, value: VarValue) {
+        self.vars.insert(id.into(), value);
+    }
+}
+
+/// Saves written before red-check keys named their kind hold bare
+/// "scene_id::skill_id" keys from `checkSkillRed`; read those as skill keys.
+fn deserialize_red_check_failures<'de, D>(deserializer: D) -> Result<HashSet<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let keys = HashSet::<String>::deserialize(deserializer)?;
+    Ok(keys
+        .into_iter()
+        .map(|key| {
+            if key.starts_with("action:") || key.starts_with("skill:") {
+                key
+            } else {
+                format!("skill:{key}")
+            }
+        })
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn flag_sources_follow_the_flag() {
+        let mut gd = GameData {
+            week: 2,
+            day: 3,
+            ..GameData::default()
+        };
+        gd.set_flag_from("MET_JAKE", "base::coffee_shop");
+        let source = gd.flag_source("MET_JAKE").unwrap();
+        assert_eq!(source.source, "base::coffee_shop");
+        assert_eq!((source.at.week, source.at.day), (2, 3));
+
+        gd.remove_flag("MET_JAKE");
+        assert!(!gd.has_flag("MET_JAKE"));
+        assert!(gd.flag_source("MET_JAKE").is_none());
+    }
+
     #[test]
     fn advance_time_slot_morning_to_afternoon() {
         let mut gd = GameData::default();
@@ -171,6 +348,17 @@ mod tests {
         assert_eq!(gd.week, 1);
     }
 
+    #[test]
+    fn absolute_slot_counts_every_advance() {
+        let mut gd = GameData::default();
+        assert_eq!(gd.absolute_slot(), 0);
+        for expected in 1..=30 {
+            gd.advance_time_slot();
+            assert_eq!(gd.absolute_slot(), expected);
+        }
+        assert_eq!(gd.week, 1);
+    }
+
     #[test]
     fn red_check_absent_initially() {
         let gd = GameData::default();
@@ -194,6 +382,15 @@ mod tests {
         assert!(!gd.has_failed_red_check("base::other_scene", "CHARM"));
     }
 
+    #[test]
+    fn action_and_skill_red_checks_do_not_collide() {
+        let mut gd = GameData::default();
+        gd.fail_check("base::some_scene::CHARM");
+        assert!(!gd.has_failed_red_check("base::some_scene", "CHARM"));
+        gd.fail_red_check("base::other_scene", "hold");
+        assert!(!gd.has_failed_check("base::other_scene::hold"));
+    }
+
     #[test]
     fn arc_state_absent_initially() {
         let gd = GameData::default();
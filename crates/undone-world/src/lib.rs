@@ -1,9 +1,13 @@
 pub mod game_data;
-pub use game_data::GameData;
+pub mod relations;
+pub use game_data::{
+    FlagSource, GameData, NpcOutreach, OUTREACH_EXPIRY_SLOTS, SLOTS_PER_DAY, SLOTS_PER_WEEK,
+};
+pub use relations::{NpcRelation, NpcRelations};
 
 use serde::{Deserialize, Serialize};
 use slotmap::SlotMap;
-use undone_domain::{FemaleNpc, FemaleNpcKey, MaleNpc, MaleNpcKey, Player};
+use undone_domain::{FemaleNpc, FemaleNpcKey, MaleNpc, MaleNpcKey, NpcCore, NpcKey, Player};
 
 #[cfg(any(test, feature = "test-helpers"))]
 pub mod test_helpers;
@@ -14,6 +18,9 @@ pub struct World {
     pub male_npcs: SlotMap<MaleNpcKey, MaleNpc>,
     pub female_npcs: SlotMap<FemaleNpcKey, FemaleNpc>,
     pub game_data: GameData,
+    /// NPC-to-NPC relationship graph. `serde(default)` → older saves load empty.
+    #[serde(default)]
+    pub npc_relations: NpcRelations,
 }
 
 impl World {
@@ -32,4 +39,33 @@ impl World {
     pub fn female_npc_mut(&mut self, key: FemaleNpcKey) -> Option<&mut FemaleNpc> {
         self.female_npcs.get_mut(key)
     }
+
+    pub fn npc_core(&self, key: NpcKey) -> Option<&NpcCore> {
+        match key {
+            NpcKey::Male(key) => self.male_npcs.get(key).map(|n| &n.core),
+            NpcKey::Female(key) => self.female_npcs.get(key).map(|n| &n.core),
+        }
+    }
+
+    pub fn npc_core_mut(&mut self, key: NpcKey) -> Option<&mut NpcCore> {
+        match key {
+            NpcKey::Male(key) => self.male_npcs.get_mut(key).map(|n| &mut n.core),
+            NpcKey::Female(key) => self.female_npcs.get_mut(key).map(|n| &mut n.core),
+        }
+    }
+
+    /// Find the NPC instantiated from the story character `story_id`
+    /// (`base::jake`). `None` if no such character exists in this world.
+    pub fn story_npc(&self, story_id: &str) -> Option<NpcKey> {
+        self.male_npcs
+            .iter()
+            .find(|(_, npc)| npc.core.story_id.as_deref() == Some(story_id))
+            .map(|(key, _)| NpcKey::Male(key))
+            .or_else(|| {
+                self.female_npcs
+                    .iter()
+                    .find(|(_, npc)| npc.core.story_id.as_deref() == Some(story_id))
+                    .map(|(key, _)| NpcKey::Female(key))
+            })
+    }
 }
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use undone_domain::{LikingLevel, NpcKey, NpcRelationKind};
+
+/// One undirected edge in the NPC relationship graph: `a` and `b` know each
+/// other as `kind`, with `closeness` measuring how warm that is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NpcRelation {
+    pub a: NpcKey,
+    pub b: NpcKey,
+    pub kind: NpcRelationKind,
+    pub closeness: LikingLevel,
+}
+
+impl NpcRelation {
+    fn joins(&self, x: NpcKey, y: NpcKey) -> bool {
+        (self.a == x && self.b == y) || (self.a == y && self.b == x)
+    }
+
+    /// The NPC on the other end of this edge from `npc`, if `npc` is on it.
+    pub fn other(&self, npc: NpcKey) -> Option<NpcKey> {
+        if self.a == npc {
+            Some(self.b)
+        } else if self.b == npc {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+}
+
+/// NPC-to-NPC relationships, at most one edge per pair. Stored as a flat list
+/// (the graph is small and sparse) so it serializes as a plain JSON array.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NpcRelations {
+    edges: Vec<NpcRelation>,
+}
+
+impl NpcRelations {
+    pub fn get(&self, x: NpcKey, y: NpcKey) -> Option<&NpcRelation> {
+        self.edges.iter().find(|e| e.joins(x, y))
+    }
+
+    pub fn get_mut(&mut self, x: NpcKey, y: NpcKey) -> Option<&mut NpcRelation> {
+        self.edges.iter_mut().find(|e| e.joins(x, y))
+    }
+
+    /// Create or replace the edge between `x` and `y`. Self-edges are ignored.
+    pub fn set(&mut self, x: NpcKey, y: NpcKey, kind: NpcRelationKind, closeness: LikingLevel) {
+        if x == y {
+            return;
+        }
+        match self.get_mut(x, y) {
+            Some(edge) => {
+                edge.kind = kind;
+                edge.closeness = closeness;
+            }
+            None => self.edges.push(NpcRelation {
+                a: x,
+                b: y,
+                kind,
+                closeness,
+            }),
+        }
+    }
+
+    pub fn remove(&mut self, x: NpcKey, y: NpcKey) {
+        self.edges.retain(|e| !e.joins(x, y));
+    }
+
+    /// Every edge touching `npc`, paired with the NPC at the other end.
+    pub fn of(&self, npc: NpcKey) -> impl Iterator<Item = (NpcKey, &NpcRelation)> {
+        self.edges
+            .iter()
+            .filter_map(move |e| e.other(npc).map(|other| (other, e)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &NpcRelation> {
+        self.edges.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slotmap::SlotMap;
+    use std::collections::HashSet;
+    use undone_domain::{FemaleNpcKey, MaleNpcKey};
+
+    fn keys() -> (NpcKey, NpcKey, NpcKey) {
+        let mut males: SlotMap<MaleNpcKey, ()> = SlotMap::with_key();
+        let mut females: SlotMap<FemaleNpcKey, ()> = SlotMap::with_key();
+        (
+            NpcKey::Male(males.insert(())),
+            NpcKey::Male(males.insert(())),
+            NpcKey::Female(females.insert(())),
+        )
+    }
+
+    #[test]
+    fn edges_are_undirected_and_unique_per_pair() {
+        let (jake, marcus, ana) = keys();
+        let mut rel = NpcRelations::default();
+        rel.set(jake, marcus, NpcRelationKind::Coworker, LikingLevel::Ok);
+        rel.set(marcus, jake, NpcRelationKind::Friend, LikingLevel::Like);
+        rel.set(ana, jake, NpcRelationKind::Rival, LikingLevel::Neutral);
+
+        assert_eq!(rel.len(), 2);
+        let edge = rel.get(jake, marcus).unwrap();
+        assert_eq!(edge.kind, NpcRelationKind::Friend);
+        assert_eq!(edge.closeness, LikingLevel::Like);
+        assert!(rel.get(marcus, ana).is_none());
+
+        let of_jake: HashSet<_> = rel.of(jake).map(|(other, _)| other).collect();
+        assert_eq!(of_jake, HashSet::from([marcus, ana]));
+
+        rel.remove(jake, ana);
+        assert!(rel.get(ana, jake).is_none());
+    }
+
+    #[test]
+    fn self_edges_are_ignored() {
+        let (jake, _, _) = keys();
+        let mut rel = NpcRelations::default();
+        rel.set(jake, jake, NpcRelationKind::Friend, LikingLevel::Close);
+        assert!(rel.is_empty());
+    }
+}
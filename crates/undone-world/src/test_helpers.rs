@@ -9,7 +9,7 @@ use undone_domain::{
     WaistSize, WetnessBaseline,
 };
 
-use crate::{GameData, World};
+use crate::{GameData, NpcRelations, World};
 
 /// Canonical test World: CisMaleTransformed player with sensible defaults.
 /// Tests that need specific field values should mutate the returned world.
@@ -70,10 +70,14 @@ pub fn make_test_world() -> World {
             custom_flags: HashMap::new(),
             custom_ints: HashMap::new(),
             origin: PcOrigin::CisMaleTransformed,
+            memory: Default::default(),
+            buffs: Vec::new(),
+            practice: HashMap::new(),
         },
         male_npcs: SlotMap::with_key(),
         female_npcs: SlotMap::with_key(),
         game_data: GameData::default(),
+        npc_relations: NpcRelations::default(),
     }
 }
 
@@ -84,6 +88,9 @@ pub fn make_test_male_npc(personality: PersonalityId) -> MaleNpc {
         core: NpcCore {
             name: "Jake".into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: Age::MidLateTwenties,
             race: "white".into(),
             eye_colour: "blue".into(),
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::process;
 
 fn main() {
@@ -15,6 +16,16 @@ fn main() {
         .and_then(|index| args.get(index + 1))
         .and_then(|value| value.parse().ok())
         .unwrap_or(1000);
+    let coverage_json: Option<PathBuf> = args
+        .iter()
+        .position(|arg| arg == "--coverage-json")
+        .and_then(|index| args.get(index + 1))
+        .map(PathBuf::from);
+    let trajectories_json: Option<PathBuf> = args
+        .iter()
+        .position(|arg| arg == "--trajectories-json")
+        .and_then(|index| args.get(index + 1))
+        .map(PathBuf::from);
 
     let packs_dir = undone::validate_pack::default_packs_dir();
     println!("Loading packs from {:?}", packs_dir);
@@ -60,13 +71,14 @@ fn main() {
     }
 
     println!("\nRunning runtime-driven distribution simulation ({weeks} weeks x {runs} runs)...\n");
-    let result = match undone::validate_pack::run_simulation_from_dir(&packs_dir, weeks, runs) {
-        Ok(result) => result,
-        Err(error) => {
-            eprintln!("{error}");
-            process::exit(1);
-        }
-    };
+    let (result, coverage) =
+        match undone::validate_pack::run_simulation_with_coverage(&packs_dir, weeks, runs) {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        };
 
     println!("Runtime Scene Distribution ({weeks} weeks x {runs} runs):");
     for stat in result.stats() {
@@ -80,4 +92,88 @@ fn main() {
             stat.scene_id, stat.percentage, stat.avg_per_run, warning
         );
     }
+
+    let skill_stats = result.skill_stats();
+    if !skill_stats.is_empty() {
+        println!("\nSkill Trajectories (start -> end, avg over runs):");
+        for stat in skill_stats {
+            println!(
+                "  {:<40} - {:>5.1} -> {:>5.1} (end {}..{})",
+                stat.skill_id, stat.avg_start, stat.avg_end, stat.min_end, stat.max_end
+            );
+        }
+    }
+
+    println!("\nBranch Coverage (never reached in {runs} runs):");
+    let (mut covered, mut total) = (0, 0);
+    for scene in &coverage {
+        covered += scene.covered;
+        total += scene.total;
+        if scene.starts == 0 {
+            println!(
+                "  {:<40} - never started ({} points)",
+                scene.scene_id, scene.total
+            );
+            continue;
+        }
+        if scene.covered == scene.total {
+            continue;
+        }
+        println!(
+            "  {:<40} - {}/{} covered ({} starts)",
+            scene.scene_id, scene.covered, scene.total, scene.starts
+        );
+        for point in scene.dead() {
+            println!("      {}", point.point);
+        }
+    }
+    println!(
+        "  {covered}/{total} points covered across {} scenes.",
+        coverage.len()
+    );
+
+    if let Some(path) = coverage_json {
+        let scenes: Vec<serde_json::Value> = coverage
+            .iter()
+            .map(|scene| {
+                serde_json::json!({
+                    "scene_id": scene.scene_id,
+                    "starts": scene.starts,
+                    "covered": scene.covered,
+                    "total": scene.total,
+                    "dead": scene.dead().map(|point| &point.point).collect::<Vec<_>>(),
+                    "points": scene.points,
+                })
+            })
+            .collect();
+        let document = serde_json::json!({
+            "weeks": weeks,
+            "runs": runs,
+            "scenes": scenes,
+        });
+        let text = serde_json::to_string_pretty(&document).expect("coverage serializes");
+        if let Err(error) = std::fs::write(&path, text) {
+            eprintln!("Could not write {}: {error}", path.display());
+            process::exit(1);
+        }
+        println!("\nCoverage JSON written to {}", path.display());
+    }
+
+    if let Some(path) = trajectories_json {
+        let runs_json: Vec<serde_json::Value> = result
+            .skill_trajectories
+            .iter()
+            .map(|trajectory| serde_json::json!({ "weeks": trajectory.weeks }))
+            .collect();
+        let document = serde_json::json!({
+            "weeks": weeks,
+            "runs": runs_json,
+        });
+        let text = serde_json::to_string_pretty(&document).expect("trajectories serialize");
+        if let Err(error) = std::fs::write(&path, text) {
+            eprintln!("Could not write {}: {error}", path.display());
+            process::exit(1);
+        }
+        println!("\nSkill trajectories JSON written to {}", path.display());
+    }
 }
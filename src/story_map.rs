@@ -16,7 +16,8 @@ use undone_packs::load_packs;
 use undone_scene::reachability::{arc_state_eqs, required_game_flags};
 use undone_scene::scheduler::SceneBinding;
 use undone_scene::script::validate::{source_advance_arcs, source_set_game_flags};
-use undone_scene::types::SceneDefinition;
+use undone_scene::script::{script_library, CompiledScript, ScriptLibrary, ScriptSource};
+use undone_scene::types::{NextBranch, SceneDefinition};
 use undone_scene::{load_scenes, load_schedule};
 
 /// The full reconciled map. Serializes to the JSON sidecar.
@@ -161,8 +162,12 @@ pub fn build_story_map(packs_dir: &Path) -> Result<StoryMap, String> {
         }
     }
 
+    // Pack helpers, so gates written as helper calls still yield their signals.
+    let library = script_library(&registry).map_err(|e| format!("pack script load failed: {e}"))?;
+
     let facts = collect_scene_facts(
         &scenes,
+        &library,
         &HashMap::new(),
         &bindings,
         &entry_scenes,
@@ -220,46 +225,46 @@ pub(crate) struct SceneFacts {
 /// conditions, npc-action conditions and their next branches, and intro
 /// variant/thought conditions. These are *consumers* of signals — a flag read
 /// here is genuinely consumed even though it does not gate scene entry.
-fn scene_internal_condition_srcs(scene: &SceneDefinition) -> Vec<String> {
-    let mut out: Vec<String> = Vec::new();
+fn scene_internal_conditions(scene: &SceneDefinition) -> Vec<&CompiledScript> {
+    let mut out: Vec<&CompiledScript> = Vec::new();
     for a in &scene.actions {
         if let Some(c) = &a.condition {
-            out.push(c.source.clone());
+            out.push(c);
         }
-        for nb in &a.next {
+        for nb in a.next_branches() {
             if let Some(c) = &nb.condition {
-                out.push(c.source.clone());
+                out.push(c);
             }
         }
-        for t in &a.thoughts {
+        for t in a.all_thoughts() {
             if let Some(c) = &t.condition {
-                out.push(c.source.clone());
+                out.push(c);
             }
         }
     }
     for na in &scene.npc_actions {
         if let Some(c) = &na.condition {
-            out.push(c.source.clone());
+            out.push(c);
         }
-        for nb in &na.next {
+        for nb in na.next.iter().flat_map(NextBranch::flatten) {
             if let Some(c) = &nb.condition {
-                out.push(c.source.clone());
+                out.push(c);
             }
         }
     }
     for v in &scene.intro_variants {
-        out.push(v.condition.source.clone());
+        out.push(&v.condition);
     }
     for t in &scene.intro_thoughts {
         if let Some(c) = &t.condition {
-            out.push(c.source.clone());
+            out.push(c);
         }
     }
     out
 }
 
-/// Turn an effect source into the signal tokens it produces (flags + ARC=STATE).
-fn produced_signals(effect_src: &str) -> Vec<String> {
+/// Turn an effect into the signal tokens it produces (flags + ARC=STATE).
+fn produced_signals(effect_src: &(impl ScriptSource + ?Sized)) -> Vec<String> {
     let mut out = source_set_game_flags(effect_src);
     for (arc, state) in source_advance_arcs(effect_src) {
         out.push(format!("{arc}={state}"));
@@ -267,9 +272,10 @@ fn produced_signals(effect_src: &str) -> Vec<String> {
     out
 }
 
-/// Turn a condition source into the POSITIVE signal tokens it requires.
-/// Negated flag checks (`!hasGameFlag`) are anti-requirements and excluded.
-fn required_signals(cond_src: &str) -> Vec<String> {
+/// Turn a condition into the POSITIVE signal tokens it requires, pack helper
+/// bodies included. Negated flag checks (`!hasGameFlag`) are anti-requirements
+/// and excluded.
+fn required_signals(cond_src: &(impl ScriptSource + ?Sized)) -> Vec<String> {
     let mut out: Vec<String> = required_game_flags(cond_src)
         .into_iter()
         .filter(|(_, negated)| !negated)
@@ -283,6 +289,7 @@ fn required_signals(cond_src: &str) -> Vec<String> {
 
 /// Collect derived facts for every scene.
 ///
+/// - `library`: the pack helpers gate sources may call.
 /// - `gate_sources`: test-only override of per-scene gate condition strings. In
 ///   production pass an empty map; gates come from `bindings`.
 /// - `bindings`: schedule bindings (slot/once_only/gate sources) from `Scheduler::bindings`.
@@ -292,6 +299,7 @@ fn required_signals(cond_src: &str) -> Vec<String> {
 ///   reachability check) — folded into the producible set for the status pass.
 pub(crate) fn collect_scene_facts(
     scenes: &HashMap<String, Arc<SceneDefinition>>,
+    library: &ScriptLibrary,
     gate_sources: &HashMap<String, Vec<String>>,
     bindings: &[SceneBinding],
     entry_scenes: &HashSet<String>,
@@ -317,14 +325,14 @@ pub(crate) fn collect_scene_facts(
             .into_iter()
             .flatten()
         {
-            entry.extend(required_signals(src));
+            entry.extend(required_signals(&library.with_source(src)));
         }
     }
     // Merge the test-only gate override.
     for (scene, srcs) in gate_sources {
         let entry = gate_for.entry(scene.clone()).or_default();
         for src in srcs {
-            entry.extend(required_signals(src));
+            entry.extend(required_signals(&library.with_source(src)));
         }
     }
 
@@ -336,14 +344,13 @@ pub(crate) fn collect_scene_facts(
     for (id, scene) in scenes {
         let mut f = SceneFacts::default();
 
-        let effect_srcs = scene
+        let effects = scene
             .actions
             .iter()
-            .filter_map(|a| a.effect.as_ref())
-            .chain(scene.npc_actions.iter().filter_map(|a| a.effect.as_ref()))
-            .map(|s| s.source.as_str());
-        for src in effect_srcs {
-            f.produces.extend(produced_signals(src));
+            .flat_map(|a| a.effects())
+            .chain(scene.npc_actions.iter().filter_map(|a| a.effect.as_ref()));
+        for effect in effects {
+            f.produces.extend(produced_signals(effect));
         }
 
         // Requirements that gate the SCENE come from the schedule binding.
@@ -355,25 +362,25 @@ pub(crate) fn collect_scene_facts(
         // condition, so a flag read only inside an action is still counted as
         // consumed (otherwise it would mis-report as dangling).
         f.consumes.extend(f.requires.iter().cloned());
-        for src in scene_internal_condition_srcs(scene) {
-            f.consumes.extend(required_signals(&src));
+        for condition in scene_internal_conditions(scene) {
+            f.consumes.extend(required_signals(condition));
         }
 
-        // Goto targets are matched as-is against the full `pack::id` scene keys,
-        // mirroring the engine (`SceneEngine::start_scene` looks up the raw goto
-        // string in its scene map). Bare intra-scene targets therefore correctly
-        // resolve to nothing and never mark a scene reachable.
+        // Goto and call targets are matched as-is against the full `pack::id`
+        // scene keys, mirroring the engine (`SceneEngine::start_scene` looks up
+        // the raw target string in its scene map). Bare intra-scene targets
+        // therefore correctly resolve to nothing and never mark a scene reachable.
         for action in &scene.actions {
-            for nb in &action.next {
-                if let Some(goto) = &nb.goto {
-                    f.goto_targets.push(goto.clone());
+            for nb in action.next_branches() {
+                if let Some(target) = nb.target_scene() {
+                    f.goto_targets.push(target.to_string());
                 }
             }
         }
         for npc_action in &scene.npc_actions {
-            for nb in &npc_action.next {
-                if let Some(goto) = &nb.goto {
-                    f.goto_targets.push(goto.clone());
+            for nb in npc_action.next.iter().flat_map(NextBranch::flatten) {
+                if let Some(target) = nb.target_scene() {
+                    f.goto_targets.push(target.to_string());
                 }
             }
         }
@@ -875,6 +882,8 @@ mod tests {
             id: id.to_string(),
             pack: "base".into(),
             intro_prose: "Intro.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -890,8 +899,11 @@ mod tests {
                     goto: None,
                     slot: None,
                     finish: true,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         })
@@ -902,6 +914,8 @@ mod tests {
             id: id.to_string(),
             pack: "base".into(),
             intro_prose: "Intro.".into(),
+            params: Default::default(),
+            vars: Default::default(),
             intro_variants: vec![],
             intro_thoughts: vec![],
             actions: vec![Action {
@@ -917,8 +931,11 @@ mod tests {
                     goto: None,
                     slot: None,
                     finish: true,
+                    call: None,
+                    returns: None,
                 }],
                 thoughts: vec![],
+                check: None,
             }],
             npc_actions: vec![],
         })
@@ -937,6 +954,7 @@ mod tests {
         );
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -964,11 +982,13 @@ mod tests {
             once_only: false,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
             condition_source: None,
             trigger_source: Some(r#"gd.hasGameFlag("NEVER_SET")"#.into()),
         }];
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &gates,
             &bindings,
             &Default::default(),
@@ -1013,6 +1033,7 @@ note = "Recurring affair."
         );
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -1058,6 +1079,7 @@ flag_prefix = "MARCUS_"
         );
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -1082,6 +1104,7 @@ flag_prefix = "JAKE_"
         let scenes: HashMap<String, Arc<SceneDefinition>> = HashMap::new();
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -1117,6 +1140,7 @@ planned = ["gym_regular_first"]
         );
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -1171,6 +1195,7 @@ planned = ["gym_regular_first"]
         );
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -1216,12 +1241,14 @@ planned = ["gym_regular_first"]
             once_only: false,
             npc_role: None,
             desire_scaled: false,
+            outreach: false,
             condition_source: None,
             trigger_source: Some(r#"gd.hasGameFlag("ROUTE_X")"#.into()),
         }];
         let starting: HashSet<String> = ["ROUTE_X".to_string()].into_iter().collect();
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &bindings,
             &Default::default(),
@@ -1251,6 +1278,7 @@ planned = ["gym_regular_first"]
         );
         let facts = collect_scene_facts(
             &scenes,
+            &ScriptLibrary::default(),
             &HashMap::new(),
             &[],
             &Default::default(),
@@ -6,6 +6,8 @@ use std::sync::Arc;
 use rand::{rngs::SmallRng, SeedableRng};
 use toml::Value;
 use undone_packs::{load_packs, LoadedPackMeta, PackRegistry};
+use undone_scene::coverage::{coverage_report, SceneCoverage};
+use undone_scene::prose_lint::{LintSeverity, ProseLintRules};
 use undone_scene::scheduler::Scheduler;
 use undone_scene::simulator::{SimulationConfig, SimulationResult};
 use undone_scene::types::SceneDefinition;
@@ -81,6 +83,16 @@ pub fn run_simulation_from_dir(
     weeks: u32,
     runs: u32,
 ) -> Result<SimulationResult, String> {
+    run_simulation_with_coverage(packs_dir, weeks, runs).map(|(result, _)| result)
+}
+
+/// Run the simulation and report, per scene, which actions, variants,
+/// thoughts, npc_actions and prose branches it reached.
+pub fn run_simulation_with_coverage(
+    packs_dir: impl AsRef<Path>,
+    weeks: u32,
+    runs: u32,
+) -> Result<(SimulationResult, Vec<SceneCoverage>), String> {
     let (report, context) = collect_validation(packs_dir.as_ref())?;
     if report.has_errors() {
         return Err(report.errors.join("\n"));
@@ -89,7 +101,7 @@ pub fn run_simulation_from_dir(
     let context =
         context.ok_or_else(|| "Simulation skipped: scheduler not available.".to_string())?;
     let world = build_simulation_world(&context.registry)?;
-    Ok(undone_scene::simulator::simulate(
+    let result = undone_scene::simulator::simulate(
         context
             .scheduler
             .as_ref()
@@ -102,7 +114,9 @@ pub fn run_simulation_from_dir(
             runs,
             seed: 42,
         },
-    ))
+    );
+    let coverage = coverage_report(&result.coverage, &context.scenes);
+    Ok((result, coverage))
 }
 
 pub fn default_packs_dir() -> PathBuf {
@@ -367,8 +381,9 @@ fn collect_validation(
             }
             Err(error) => {
                 report.errors.push(format!(
-                    "ERROR loading scenes for '{}': {error}",
-                    meta.manifest.pack.id
+                    "ERROR loading scenes for '{}': {}",
+                    meta.manifest.pack.id,
+                    error.render()
                 ));
             }
         }
@@ -427,7 +442,8 @@ fn collect_validation(
         );
     }
 
-    report.prose_findings = collect_prose_findings(&report.packs_dir, &pack_metas);
+    let prose_lint = load_prose_lint_rules(&pack_metas, &mut report);
+    collect_prose_findings(&mut report, &pack_metas, &prose_lint);
 
     let context = Some(LoadedValidationContext {
         registry,
@@ -481,8 +497,31 @@ fn build_simulation_world(registry: &PackRegistry) -> Result<World, String> {
     ))
 }
 
-fn collect_prose_findings(packs_dir: &Path, pack_metas: &[LoadedPackMeta]) -> Vec<ProseFinding> {
-    let mut findings = Vec::new();
+fn load_prose_lint_rules(
+    pack_metas: &[LoadedPackMeta],
+    report: &mut ValidationReport,
+) -> ProseLintRules {
+    let mut rules = ProseLintRules::default();
+    for meta in pack_metas {
+        let Some(rules_file) = &meta.manifest.content.prose_lint_file else {
+            continue;
+        };
+        match ProseLintRules::load(&meta.pack_dir.join(rules_file)) {
+            Ok(pack_rules) => rules.extend(pack_rules),
+            Err(error) => report.errors.push(format!(
+                "ERROR loading prose-lint rules for '{}': {error}",
+                meta.manifest.pack.id
+            )),
+        }
+    }
+    rules
+}
+
+fn collect_prose_findings(
+    report: &mut ValidationReport,
+    pack_metas: &[LoadedPackMeta],
+    prose_lint: &ProseLintRules,
+) {
     for meta in pack_metas {
         let scene_dir = meta.pack_dir.join(&meta.manifest.content.scenes_dir);
         let mut scene_files = Vec::new();
@@ -492,14 +531,53 @@ fn collect_prose_findings(packs_dir: &Path, pack_metas: &[LoadedPackMeta]) -> Ve
                 continue;
             };
             let relative_path = scene_file
-                .strip_prefix(packs_dir)
+                .strip_prefix(&report.packs_dir)
                 .ok()
                 .map(normalize_pack_relative_path)
                 .unwrap_or_else(|| scene_file.to_string_lossy().replace('\\', "/"));
-            findings.extend(audit_scene_text(&relative_path, &scene_text));
+            report
+                .prose_findings
+                .extend(audit_scene_text(&relative_path, &scene_text));
+            lint_scene_prose(report, prose_lint, &relative_path, &scene_text);
+        }
+    }
+}
+
+/// Run the packs' prose-lint rules over one scene file: `error` rule matches
+/// fail validation, `warning` ones become prose findings.
+fn lint_scene_prose(
+    report: &mut ValidationReport,
+    prose_lint: &ProseLintRules,
+    file_path: &str,
+    scene_text: &str,
+) {
+    if prose_lint.is_empty() {
+        return;
+    }
+    // A scene file that does not parse is already a scene load error.
+    let Ok(findings) = prose_lint.lint_scene_file(Path::new(file_path), scene_text) else {
+        return;
+    };
+    for finding in findings {
+        let message = format!(
+            "{}: \"{}\" in {}",
+            finding.message,
+            finding.matched,
+            finding.location()
+        );
+        match finding.severity {
+            LintSeverity::Error => report.errors.push(format!(
+                "ERROR [prose-lint:{}] {file_path}:{}: {message}",
+                finding.rule_id, finding.span.line
+            )),
+            LintSeverity::Warning => report.prose_findings.push(ProseFinding {
+                file_path: file_path.to_string(),
+                kind: finding.rule_id,
+                line: Some(finding.span.line),
+                message,
+            }),
         }
     }
-    findings
 }
 
 fn collect_scene_files(scene_dir: &Path, scene_files: &mut Vec<PathBuf>) {
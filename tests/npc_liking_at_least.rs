@@ -38,6 +38,9 @@ fn male_npc_with_role_and_liking(
         core: undone_domain::NpcCore {
             name: name.into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: undone_domain::Age::Thirties,
             race: "white".into(),
             eye_colour: "grey".into(),
@@ -81,6 +84,9 @@ fn female_npc_with_role_and_liking(
         core: undone_domain::NpcCore {
             name: name.into(),
             display_name: None,
+            story_id: None,
+            agenda: Default::default(),
+            memory: Default::default(),
             age: undone_domain::Age::EarlyTwenties,
             race: "white".into(),
             eye_colour: "green".into(),
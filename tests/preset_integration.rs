@@ -731,15 +731,23 @@ fn new_game_from_robin_preset_spawns_npcs() {
     let mut rng = rand::rngs::SmallRng::seed_from_u64(105);
     let world = undone_packs::new_game(config, &mut registry, &mut rng);
 
+    let random_males = world
+        .male_npcs
+        .values()
+        .filter(|npc| npc.core.story_id.is_none())
+        .count();
+    let random_females = world
+        .female_npcs
+        .values()
+        .filter(|npc| npc.core.story_id.is_none())
+        .count();
     assert_eq!(
-        world.male_npcs.len(),
-        6,
-        "robin_quick_config should spawn 6 male NPCs"
+        random_males, 6,
+        "robin_quick_config should spawn 6 random male NPCs"
     );
     assert_eq!(
-        world.female_npcs.len(),
-        3,
-        "robin_quick_config should spawn 3 female NPCs"
+        random_females, 3,
+        "robin_quick_config should spawn 3 random female NPCs"
     );
 }
 
@@ -20,6 +20,15 @@ fn copy_dir_recursive(src: &Path, dst: &Path) {
 }
 
 fn invalid_prose_pack_dir() -> PathBuf {
+    edited_weekend_morning_pack_dir(
+        "You stretch beneath the blankets for another minute, eyes closed.",
+        "You check your phone and wait for something to happen.",
+    )
+}
+
+/// A scratch copy of the repo packs with `from` replaced by `to` in
+/// `weekend_morning.toml`.
+fn edited_weekend_morning_pack_dir(from: &str, to: &str) -> PathBuf {
     let unique = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
@@ -33,14 +42,7 @@ fn invalid_prose_pack_dir() -> PathBuf {
         .join("scenes")
         .join("weekend_morning.toml");
     let scene = fs::read_to_string(&scene_path).unwrap();
-    fs::write(
-        &scene_path,
-        scene.replace(
-            "You stretch beneath the blankets for another minute, eyes closed.",
-            "You check your phone and wait for something to happen.",
-        ),
-    )
-    .unwrap();
+    fs::write(&scene_path, scene.replace(from, to)).unwrap();
 
     fixture_packs_dir
 }
@@ -251,6 +253,37 @@ fn validate_pack_report_includes_prose_findings() {
     fs::remove_dir_all(fixture_packs_dir.parent().unwrap()).unwrap();
 }
 
+#[test]
+fn validate_pack_fails_on_pack_prose_lint_errors() {
+    let fixture_packs_dir = edited_weekend_morning_pack_dir(
+        "A stretch. The morning takes its time arriving.",
+        "A stretch. You bit your lip. The morning takes its time arriving.",
+    );
+    let report = undone::validate_pack::validate_pack_dir(&fixture_packs_dir).expect("report");
+
+    assert!(
+        report.errors.iter().any(|error| error
+            .contains("[prose-lint:erotic_cliche] packs/base/scenes/weekend_morning.toml:")
+            && error.contains("\"bit your lip\" in intro prose branch `{% else %}`")),
+        "{:?}",
+        report.errors
+    );
+
+    fs::remove_dir_all(fixture_packs_dir.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn repo_scenes_pass_pack_prose_lint_rules() {
+    let report = undone::validate_pack::validate_repo_scenes_for_tests().expect("audit");
+    let errors: Vec<_> = report
+        .errors
+        .iter()
+        .filter(|error| error.contains("[prose-lint:"))
+        .collect();
+
+    assert!(errors.is_empty(), "{errors:#?}");
+}
+
 #[test]
 fn campus_cluster_has_no_third_person_or_unnecessary_guard_findings() {
     let report = undone::validate_pack::validate_repo_scenes_for_tests().expect("audit");
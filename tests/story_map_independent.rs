@@ -121,7 +121,9 @@ fn flag_read_only_by_action_condition_is_not_dangling() {
         .map(|w| w.detail.as_str())
         .collect();
     assert!(
-        !write_next_dangling.iter().any(|d| d.contains("STARTED_JOB")),
+        !write_next_dangling
+            .iter()
+            .any(|d| d.contains("STARTED_JOB")),
         "STARTED_JOB must not appear as a dangling write-next item; got {write_next_dangling:?}"
     );
 }
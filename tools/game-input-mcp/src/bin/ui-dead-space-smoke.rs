@@ -126,8 +126,7 @@ fn main() -> anyhow::Result<()> {
     }
 
     let root = std::env::current_dir().context("failed to read current directory")?;
-    let _ = std::fs::remove_file(std::env::temp_dir().join("undone-dev-cmd.json"));
-    let _ = std::fs::remove_file(std::env::temp_dir().join("undone-dev-result.json"));
+    let _ = std::fs::remove_file(game_input_mcp::dev_client::address_file_path());
 
     if input::find_process("undone.exe")?.is_some() {
         bail!("undone.exe is already running; close it before running ui-dead-space-smoke");
@@ -1,7 +1,14 @@
-use anyhow::{anyhow, Context, Result};
+//! Client for the game's dev IPC socket: newline-delimited JSON-RPC 2.0 over
+//! TCP. A game launched with `--dev` writes the address it listens on to
+//! `undone-dev-ipc.addr` in the temp dir; `UNDONE_DEV_IPC_ADDR` overrides it.
+
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Map, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -11,35 +18,286 @@ pub struct DevCommandResponse {
     pub data: Option<Value>,
 }
 
-pub fn command_file_path() -> PathBuf {
-    std::env::temp_dir().join("undone-dev-cmd.json")
+/// A notification from a subscription: `event` (an engine event),
+/// `state_diff` (the runtime snapshot fields that changed) or `reload` (how a
+/// hot reload of `packs/` went).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DevNotification {
+    pub method: String,
+    pub params: Value,
 }
 
-pub fn result_file_path() -> PathBuf {
-    std::env::temp_dir().join("undone-dev-result.json")
+pub fn address_file_path() -> PathBuf {
+    std::env::temp_dir().join("undone-dev-ipc.addr")
 }
 
-pub fn send_command(command_json: &str, timeout: Duration) -> Result<DevCommandResponse> {
-    let command_path = command_file_path();
-    let result_path = result_file_path();
+/// The running game's dev socket address.
+pub fn game_address() -> Result<SocketAddr> {
+    let addr = match std::env::var("UNDONE_DEV_IPC_ADDR") {
+        Ok(addr) => addr,
+        Err(_) => std::fs::read_to_string(address_file_path())
+            .context("no dev IPC address file. Is the game running with --dev?")?,
+    };
+    addr.trim()
+        .to_socket_addrs()
+        .with_context(|| format!("bad dev IPC address '{}'", addr.trim()))?
+        .next()
+        .ok_or_else(|| anyhow!("bad dev IPC address '{}'", addr.trim()))
+}
 
-    let _ = std::fs::remove_file(&result_path);
-    let tmp_path = command_path.with_extension("tmp");
-    std::fs::write(&tmp_path, command_json).context("write command failed")?;
-    std::fs::rename(&tmp_path, &command_path).context("rename command failed")?;
+/// Split a `{"command": "...", ...}` payload into a JSON-RPC method and
+/// params.
+pub fn request_parts(command_json: &str) -> Result<(String, Value)> {
+    let value: Value = serde_json::from_str(command_json).context("command is not valid JSON")?;
+    let Value::Object(mut params) = value else {
+        bail!("command must be a JSON object");
+    };
+    let Some(Value::String(method)) = params.remove("command") else {
+        bail!("command is missing a string 'command' field");
+    };
+    Ok((method, Value::Object(params)))
+}
 
-    let deadline = Instant::now() + timeout;
+/// Send a `{"command": "...", ...}` payload and wait for the game's response.
+pub fn send_command(command_json: &str, timeout: Duration) -> Result<DevCommandResponse> {
+    let (method, params) = request_parts(command_json)?;
+    let result = call(game_address()?, &method, params, timeout)?;
+    serde_json::from_value(result).context("parse result failed")
+}
+
+/// Make one JSON-RPC call and return its `result`.
+pub fn call(addr: SocketAddr, method: &str, params: Value, timeout: Duration) -> Result<Value> {
+    let mut connection = Connection::open(addr, timeout)?;
+    let id = connection.send(method, params)?;
     loop {
-        if result_path.exists() {
-            let result = std::fs::read_to_string(&result_path).context("read result failed")?;
-            let _ = std::fs::remove_file(&result_path);
-            return serde_json::from_str(&result).context("parse result failed");
+        let message = connection.read()?;
+        if message.get("id") == Some(&json!(id)) {
+            return rpc_result(message);
+        }
+    }
+}
+
+/// A connection that has subscribed to the game's event stream.
+pub struct Subscription {
+    connection: Connection,
+}
+
+impl Subscription {
+    pub fn open(addr: SocketAddr, timeout: Duration) -> Result<Self> {
+        let mut connection = Connection::open(addr, timeout)?;
+        let id = connection.send("subscribe", Value::Object(Map::new()))?;
+        loop {
+            let message = connection.read()?;
+            if message.get("id") == Some(&json!(id)) {
+                rpc_result(message)?;
+                return Ok(Subscription { connection });
+            }
+        }
+    }
+
+    /// Every notification that arrives within `duration`.
+    pub fn collect_for(&mut self, duration: Duration) -> Result<Vec<DevNotification>> {
+        let deadline = Instant::now() + duration;
+        let mut notifications = Vec::new();
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if remaining.is_zero() {
+                break;
+            }
+            self.connection.set_timeout(remaining)?;
+            match self.connection.read() {
+                Ok(mut message) => {
+                    if let Some(Value::String(method)) = message.get_mut("method").map(Value::take)
+                    {
+                        notifications.push(DevNotification {
+                            method,
+                            params: message
+                                .get_mut("params")
+                                .map(Value::take)
+                                .unwrap_or_default(),
+                        });
+                    }
+                }
+                Err(err) if is_timeout(&err) => break,
+                Err(err) => return Err(err),
+            }
         }
-        if Instant::now() > deadline {
-            return Err(anyhow!(
-                "timeout waiting for game response. Is the game running with --dev?"
-            ));
+        Ok(notifications)
+    }
+}
+
+struct Connection {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Connection {
+    fn open(addr: SocketAddr, timeout: Duration) -> Result<Self> {
+        let stream = TcpStream::connect_timeout(&addr, timeout).with_context(|| {
+            format!("could not connect to the game at {addr}. Is it running with --dev?")
+        })?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let connection = Connection { stream, reader };
+        connection.set_timeout(timeout)?;
+        Ok(connection)
+    }
+
+    fn set_timeout(&self, timeout: Duration) -> Result<()> {
+        self.stream.set_read_timeout(Some(timeout))?;
+        Ok(())
+    }
+
+    fn send(&mut self, method: &str, params: Value) -> Result<u64> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut line =
+            json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string();
+        line.push('\n');
+        self.stream
+            .write_all(line.as_bytes())
+            .context("send command failed")?;
+        Ok(id)
+    }
+
+    fn read(&mut self) -> Result<Value> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => bail!("the game closed the dev connection"),
+            Ok(_) => serde_json::from_str(&line).context("parse response failed"),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                Err(anyhow::Error::new(err)
+                    .context("timeout waiting for game response. Is the game running with --dev?"))
+            }
+            Err(err) => Err(anyhow::Error::new(err).context("read response failed")),
         }
-        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn rpc_result(mut message: Value) -> Result<Value> {
+    if let Some(error) = message.get("error") {
+        bail!(
+            "{} (code {})",
+            error["message"].as_str().unwrap_or("dev command failed"),
+            error["code"]
+        );
+    }
+    Ok(message
+        .get_mut("result")
+        .map(Value::take)
+        .unwrap_or_default())
+}
+
+fn is_timeout(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<std::io::Error>().is_some_and(|err| {
+        matches!(
+            err.kind(),
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// A one-connection stand-in for the game: answers each request with its
+    /// method and params, or with an error for `fail`, and sends an `event`
+    /// notification after `subscribe`.
+    fn fake_game() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            for line in BufReader::new(stream).lines() {
+                let request: Value = serde_json::from_str(&line.unwrap()).unwrap();
+                let id = request["id"].clone();
+                let mut replies = vec![match request["method"].as_str() {
+                    Some("fail") => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": -32601, "message": "unknown dev command 'fail'" },
+                    }),
+                    Some("subscribe") => json!({
+                        "jsonrpc": "2.0", "id": id, "result": { "subscribed": true },
+                    }),
+                    _ => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": {
+                            "success": true,
+                            "message": request["method"],
+                            "data": request["params"],
+                        },
+                    }),
+                }];
+                if request["method"] == "subscribe" {
+                    replies.push(json!({
+                        "jsonrpc": "2.0",
+                        "method": "event",
+                        "params": { "event": "scene_finished" },
+                    }));
+                }
+                for reply in replies {
+                    writeln!(writer, "{reply}").unwrap();
+                }
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn request_parts_splits_out_the_command() {
+        let (method, params) =
+            request_parts(r#"{"command": "jump_to_scene", "scene_id": "base::rain_shelter"}"#)
+                .unwrap();
+        assert_eq!(method, "jump_to_scene");
+        assert_eq!(params, json!({ "scene_id": "base::rain_shelter" }));
+        assert!(request_parts(r#"{"scene_id": "x"}"#).is_err());
+        assert!(request_parts("[]").is_err());
+    }
+
+    #[test]
+    fn call_returns_the_matching_result() {
+        let addr = fake_game();
+        let result = call(
+            addr,
+            "set_flag",
+            json!({ "flag": "MET" }),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        let response: DevCommandResponse = serde_json::from_value(result).unwrap();
+        assert!(response.success);
+        assert_eq!(response.message, "set_flag");
+        assert_eq!(response.data, Some(json!({ "flag": "MET" })));
+    }
+
+    #[test]
+    fn call_surfaces_json_rpc_errors() {
+        let addr = fake_game();
+        let err = call(addr, "fail", json!({}), Duration::from_secs(5)).unwrap_err();
+        assert!(err.to_string().contains("unknown dev command"), "{err}");
+    }
+
+    #[test]
+    fn subscription_collects_notifications() {
+        let addr = fake_game();
+        let mut subscription = Subscription::open(addr, Duration::from_secs(5)).unwrap();
+        let notifications = subscription
+            .collect_for(Duration::from_millis(300))
+            .unwrap();
+        assert_eq!(
+            notifications,
+            vec![DevNotification {
+                method: "event".into(),
+                params: json!({ "event": "scene_finished" }),
+            }]
+        );
     }
 }
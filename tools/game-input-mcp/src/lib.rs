@@ -1,4 +1,3 @@
-#[cfg(target_os = "windows")]
 pub mod dev_client;
 #[cfg(target_os = "windows")]
 pub mod input;
@@ -7,7 +7,7 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde_json::json;
 
-use crate::input;
+use crate::{dev_client, input};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct StartGameInput {
@@ -73,12 +73,18 @@ pub struct HoverInput {
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct DevCommandInput {
-    /// Raw JSON command payload written to the game's dev IPC command file.
+    /// Raw JSON command payload, `{"command": "...", ...}`, sent to the game's dev IPC socket.
     pub command_json: String,
     /// How long to wait for the game to respond before returning a timeout.
     pub timeout_ms: Option<u64>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WatchDevEventsInput {
+    /// How long to listen, in milliseconds. Defaults to 1000.
+    pub duration_ms: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetGameStateInput {}
 
@@ -123,6 +129,52 @@ pub struct SetAllNpcLikingInput {
     pub level: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EvalExpressionInput {
+    /// Read expression, e.g. `w.getSkill("FEMININITY") > 10` or `gd.arcState("base::jake")`.
+    pub expression: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RenderProseInput {
+    /// Prose template, e.g. `{% if w.hasTrait("SHY") %}shy{% else %}bold{% endif %}`.
+    pub template: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RunEffectInput {
+    /// Effect script, e.g. `w.addTrait("SHY"); gd.setDesire(40)`.
+    pub script: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CallWriteVerbInput {
+    /// Receiver of the write verb: `w`, `gd`, `scene` or `npc`.
+    pub receiver: String,
+    /// Write verb name, e.g. `addTrait`, `setDesire`, `setRole`, `setRelationship`.
+    pub method: String,
+    /// For `npc` verbs: `m`, `f`, a bound role, or a story id such as `base::jake`.
+    pub npc: Option<String>,
+    /// The verb's arguments as JSON strings, ints and bools.
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetWorldInput {}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PatchWorldInput {
+    /// JSON merge patch (RFC 7396) for the world, in the shape get_world returns.
+    pub patch: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReloadContentInput {}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RestartSceneInput {}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ChooseActionInput {
     /// Stable visible action id returned by get_runtime_state.
@@ -132,6 +184,16 @@ pub struct ChooseActionInput {
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ContinueSceneInput {}
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FindPathInput {
+    /// A scene id, a game flag, or `ARC=STATE`.
+    pub target: String,
+    /// Longest path to consider, in commands (default 80).
+    pub max_steps: Option<usize>,
+    /// Keep only this many states per step, for targets too deep to search exhaustively.
+    pub beam: Option<usize>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SetTabInput {
     /// Supported tabs: game, saves, settings, dev.
@@ -197,6 +259,16 @@ fn continue_scene_payload() -> String {
     .to_string()
 }
 
+fn find_path_payload(target: &str, max_steps: Option<usize>, beam: Option<usize>) -> String {
+    json!({
+        "command": "find_path",
+        "target": target,
+        "max_steps": max_steps,
+        "beam": beam,
+    })
+    .to_string()
+}
+
 fn set_tab_payload(tab: &str) -> String {
     json!({
         "command": "set_tab",
@@ -396,35 +468,44 @@ impl GameInputServer {
         &self,
         params: Parameters<DevCommandInput>,
     ) -> Result<CallToolResult, McpError> {
-        let timeout_ms = params.0.timeout_ms.unwrap_or(2000);
-        let command_path = std::env::temp_dir().join("undone-dev-cmd.json");
-        let result_path = std::env::temp_dir().join("undone-dev-result.json");
-
-        let _ = std::fs::remove_file(&result_path);
-        let tmp_path = command_path.with_extension("tmp");
-        std::fs::write(&tmp_path, &params.0.command_json)
-            .map_err(|e| McpError::internal_error(format!("write command failed: {e}"), None))?;
-        std::fs::rename(&tmp_path, &command_path).map_err(|e| {
-            let _ = std::fs::remove_file(&tmp_path);
-            McpError::internal_error(format!("rename command failed: {e}"), None)
-        })?;
-
-        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(timeout_ms);
-        loop {
-            if result_path.exists() {
-                let result = std::fs::read_to_string(&result_path).map_err(|e| {
-                    McpError::internal_error(format!("read result failed: {e}"), None)
-                })?;
-                let _ = std::fs::remove_file(&result_path);
-                return Ok(CallToolResult::success(vec![Content::text(result)]));
-            }
-            if std::time::Instant::now() > deadline {
-                return Ok(CallToolResult::success(vec![Content::text(
-                    r#"{"success": false, "message": "Timeout waiting for game response. Is the game running with --dev?"}"#,
-                )]));
-            }
-            std::thread::sleep(std::time::Duration::from_millis(50));
-        }
+        let timeout = std::time::Duration::from_millis(params.0.timeout_ms.unwrap_or(2000));
+        let command_json = params.0.command_json;
+        let result = tokio::task::spawn_blocking(move || {
+            let (method, params) = dev_client::request_parts(&command_json)?;
+            dev_client::call(dev_client::game_address()?, &method, params, timeout)
+        })
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let text = match result {
+            Ok(result) => result.to_string(),
+            Err(err) => json!({ "success": false, "message": format!("{err:#}") }).to_string(),
+        };
+        Ok(CallToolResult::success(vec![Content::text(text)]))
+    }
+
+    #[tool(
+        description = "Subscribe to a running Undone game in dev mode and return what it streams for duration_ms: `event` notifications (engine events such as prose_added, actions_available, scene_finished) `state_diff` notifications (runtime state fields that changed) and `reload` notifications (the outcome of a hot reload of packs/). The first state_diff is the full runtime state."
+    )]
+    async fn watch_dev_events(
+        &self,
+        params: Parameters<WatchDevEventsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let duration = std::time::Duration::from_millis(params.0.duration_ms.unwrap_or(1000));
+        let result = tokio::task::spawn_blocking(move || {
+            let timeout = std::time::Duration::from_secs(2);
+            let mut subscription =
+                dev_client::Subscription::open(dev_client::game_address()?, timeout)?;
+            subscription.collect_for(duration)
+        })
+        .await
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let text = match result {
+            Ok(notifications) => json!(notifications).to_string(),
+            Err(err) => json!({ "success": false, "message": format!("{err:#}") }).to_string(),
+        };
+        Ok(CallToolResult::success(vec![Content::text(text)]))
     }
 
     #[tool(description = "Get the current game state from a running Undone game in dev mode.")]
@@ -500,6 +581,21 @@ impl GameInputServer {
         .await
     }
 
+    #[tool(
+        description = "Search for the shortest run of choices from the current point of a running Undone game in dev mode to a scene id, game flag, or ARC=STATE, then fast-forward the game along it. Returns the path and the runtime state at its end."
+    )]
+    async fn find_path(
+        &self,
+        params: Parameters<FindPathInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let input = params.0;
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: find_path_payload(&input.target, input.max_steps, input.beam),
+            timeout_ms: Some(60000),
+        }))
+        .await
+    }
+
     #[tool(description = "Switch the active app tab in a running Undone game in dev mode.")]
     async fn set_tab(&self, params: Parameters<SetTabInput>) -> Result<CallToolResult, McpError> {
         self.dev_command(Parameters(DevCommandInput {
@@ -705,6 +801,150 @@ impl GameInputServer {
         .await
     }
 
+    #[tool(
+        description = "Evaluate a read expression (anything a scene condition may contain) against the live world of a running Undone game in dev mode. Returns its bool, int or string value."
+    )]
+    async fn eval_expression(
+        &self,
+        params: Parameters<EvalExpressionInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "eval_expression",
+                "expression": params.0.expression,
+            })
+            .to_string(),
+            timeout_ms: Some(2000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Render a prose template against the live world of a running Undone game in dev mode, in the running scene's context."
+    )]
+    async fn render_prose(
+        &self,
+        params: Parameters<RenderProseInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "render_prose",
+                "template": params.0.template,
+            })
+            .to_string(),
+            timeout_ms: Some(2000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Run an effect script against the live world of a running Undone game in dev mode. Returns the runtime state after it."
+    )]
+    async fn run_effect(
+        &self,
+        params: Parameters<RunEffectInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "run_effect",
+                "script": params.0.script,
+            })
+            .to_string(),
+            timeout_ms: Some(2000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Call one scripting write verb (traits, skills, arc states, desire, money, player attributes, NPC roles, relationships, ...) in a running Undone game in dev mode. An unknown verb lists the verbs the receiver has."
+    )]
+    async fn call_write_verb(
+        &self,
+        params: Parameters<CallWriteVerbInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "call_write_verb",
+                "receiver": params.0.receiver,
+                "method": params.0.method,
+                "npc": params.0.npc,
+                "args": params.0.args,
+            })
+            .to_string(),
+            timeout_ms: Some(2000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Reload scenes, the schedule and pack data from packs/ into a running Undone game in dev mode, through the same validation as startup. The game also does this on its own when a pack file changes. A rejected reload leaves the game running its last good content and returns the load error."
+    )]
+    async fn reload_content(
+        &self,
+        _params: Parameters<ReloadContentInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "reload_content"
+            })
+            .to_string(),
+            timeout_ms: Some(10000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Restart the running scene of an Undone game in dev mode from its current (reloaded) definition, keeping its NPCs, role bindings and params. Returns the runtime state."
+    )]
+    async fn restart_scene(
+        &self,
+        _params: Parameters<RestartSceneInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "restart_scene"
+            })
+            .to_string(),
+            timeout_ms: Some(2000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Dump the full World of a running Undone game in dev mode as JSON, in its save-file shape."
+    )]
+    async fn get_world(
+        &self,
+        _params: Parameters<GetWorldInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "get_world"
+            })
+            .to_string(),
+            timeout_ms: Some(5000),
+        }))
+        .await
+    }
+
+    #[tool(
+        description = "Apply a JSON merge patch to the World of a running Undone game in dev mode. Objects merge key by key and null removes a key; an invalid result leaves the world unchanged."
+    )]
+    async fn patch_world(
+        &self,
+        params: Parameters<PatchWorldInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.dev_command(Parameters(DevCommandInput {
+            command_json: json!({
+                "command": "patch_world",
+                "patch": params.0.patch,
+            })
+            .to_string(),
+            timeout_ms: Some(5000),
+        }))
+        .await
+    }
+
     #[tool(
         description = "Stop the game process by killing it. Finds the process by exe name and terminates it."
     )]
@@ -755,13 +995,19 @@ impl ServerHandler for GameInputServer {
                  stop_game(exe_name) to kill the process, is_game_running(exe_name) to check \
                  if it's running and get the PID, and dev-mode IPC helpers such as \
                  get_game_state(), get_runtime_state(), jump_to_scene(scene_id), \
-                 choose_action(action_id), continue_scene(), set_tab(tab), \
+                 choose_action(action_id), continue_scene(), find_path(target), set_tab(tab), \
                  set_window_size(width, height), \
                  set_game_stat(stat, value), \
                  set_game_flag(flag), remove_game_flag(flag), advance_time(weeks), \
                  set_npc_liking(npc_name, level), set_all_npc_liking(level), \
                  list_scenes(), get_scene_info(scene_id), save_game(name), \
-                 load_save(name), and list_saves()."
+                 load_save(name), and list_saves(). eval_expression(expression), \
+                 render_prose(template), run_effect(script), \
+                 call_write_verb(receiver, method, npc, args), get_world() and \
+                 patch_world(patch) read and edit the live world. \
+                 reload_content() and restart_scene() pick up edits to packs/ without a \
+                 restart. watch_dev_events(duration_ms) streams engine events, runtime state \
+                 changes and hot reload results."
                     .into(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
@@ -773,8 +1019,8 @@ impl ServerHandler for GameInputServer {
 #[cfg(test)]
 mod tests {
     use super::{
-        choose_action_payload, continue_scene_payload, runtime_state_payload, set_tab_payload,
-        set_window_size_payload,
+        choose_action_payload, continue_scene_payload, find_path_payload, runtime_state_payload,
+        set_tab_payload, set_window_size_payload,
     };
     use serde_json::json;
 
@@ -802,6 +1048,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_path_payload_passes_target_and_limits() {
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&find_path_payload(
+                "base::coffee_shop",
+                None,
+                Some(8)
+            ))
+            .unwrap(),
+            json!({
+                "command": "find_path",
+                "target": "base::coffee_shop",
+                "max_steps": null,
+                "beam": 8,
+            })
+        );
+    }
+
     #[test]
     fn set_tab_payload_includes_requested_tab() {
         assert_eq!(
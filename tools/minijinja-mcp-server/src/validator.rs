@@ -108,7 +108,7 @@ pub fn validate_prose(source: &str, registry: &undone_packs::PackRegistry) -> Ve
     match undone_scene::script::api::prose_validate::validate_prose(source, registry, "mcp") {
         Ok(()) => vec![],
         Err(e) => vec![TemplateError {
-            line: None,
+            line: e.position().line().map(|l| l as u32),
             message: e.to_string(),
         }],
     }
@@ -173,6 +173,7 @@ mod tests {
             description: String::new(),
             min: 0,
             max: 100,
+            progression: None,
         }]);
         r
     }
@@ -184,6 +185,8 @@ mod tests {
             !validate_prose("{{ w.notAReal() }}", &r).is_empty(),
             "unknown prose method should be reported"
         );
+        let errors = validate_prose("It begins.\n{{ w.notAReal() }}", &r);
+        assert_eq!(errors[0].line, Some(2));
     }
 
     #[test]
@@ -6,6 +6,10 @@
  * Runs regex-based checks against DeepSeek draft output. No LLM judgment.
  * Returns structured JSON with findings grouped by severity.
  *
+ * The phrase and pattern rules are loaded from the pack data in
+ * packs/base/data/prose_lint.toml, the same file validate-pack enforces on
+ * every scene. Edit rules there; the checks below are the draft-only extras.
+ *
  * Usage:
  *   node tools/prose-lint.mjs <file>
  *   node tools/prose-lint.mjs --stdin
@@ -13,90 +17,108 @@
  */
 
 import fs from "node:fs/promises";
+import path from "node:path";
 import process from "node:process";
+import { fileURLToPath } from "node:url";
+
+const scriptDir = path.dirname(fileURLToPath(import.meta.url));
+const RULES_FILE = path.resolve(scriptDir, "..", "packs/base/data/prose_lint.toml");
+
+// ─── Pack rules ──────────────────────────────────────────────────────────────
+// `[[rules]]` from prose_lint.toml. `error` rules are critical, `warning`
+// rules important. Phrases match case-insensitively on whole words; patterns
+// carry their own `(?i)` when they want it.
+
+// Just enough TOML for the rules file: `[[rules]]` tables whose values are
+// strings or arrays of strings, and `#` comments.
+function parseRulesToml(text) {
+  const rules = [];
+  let i = 0;
+
+  const skipSpace = () => {
+    while (i < text.length) {
+      if (/\s/.test(text[i])) {
+        i++;
+      } else if (text[i] === "#") {
+        while (i < text.length && text[i] !== "\n") i++;
+      } else {
+        break;
+      }
+    }
+  };
 
-// ─── Banned phrases (Critical severity) ──────────────────────────────────────
-
-const BANNED_PHRASES = [
-  { pattern: /none of this was conscious/gi, message: "Narrator analyzing body/transformation" },
-  { pattern: /your body is making (calculations|them)/gi, message: "Narrator analyzing body" },
-  { pattern: /you used to do this/gi, message: "Banned transformation commentary" },
-  { pattern: /you used to (\w+) this/gi, message: "Banned transformation commentary" },
-  { pattern: /you know what he'?s (doing|thinking)/gi, message: "Banned omniscient claim" },
-  { pattern: /you recognize the calculation/gi, message: "Banned transformation commentary" },
-  { pattern: /without you deciding/gi, message: "Narrator analyzing body" },
-  { pattern: /the armor went up/gi, message: "Narrator analyzing body" },
-  { pattern: /which is what you came here for/gi, message: "Narrator explaining motivation" },
-  { pattern: /because your hands need something to do/gi, message: "Narrator explaining motivation" },
-  { pattern: /your body is making them/gi, message: "Narrator analyzing body" },
-  { pattern: /you'?re just watching it work/gi, message: "Narrator analyzing body" },
-  { pattern: /none of this is conscious/gi, message: "Narrator analyzing body/transformation" },
-  // Recurring interiority tells caught in the looping-adult review (2026-06):
-  // naming an internal faculty then reporting it suppressed, or the body as a
-  // deliberating agent. Show the body; never narrate the mental process.
-  { pattern: /the part of you that (takes notes|takes stock|might object|could object|keeps a hand|usually keeps)/gi, message: "Naming an internal faculty then reporting it suppressed (interiority)" },
-  { pattern: /the (?:part|version) of you that (?:takes notes|is not available)/gi, message: "Naming an internal faculty (interiority)" },
-  { pattern: /the body (?:has been |is |had been )?(?:making (?:a|its) case|running the argument|making an argument|making its case)/gi, message: "Narrator analyzing body ('the body makes a case')" },
-  { pattern: /(?:cognitive )?dissonance is noted(?: and filed)?/gi, message: "Narrator analyzing a mental state ('noted and filed')" },
-  { pattern: /noted and filed/gi, message: "Narrator analyzing a mental state ('noted and filed')" },
-];
+  const parseString = () => {
+    const quote = text[i++];
+    let out = "";
+    while (i < text.length && text[i] !== quote) {
+      if (quote === '"' && text[i] === "\\") {
+        const escape = text[i + 1];
+        if (escape === "u") {
+          out += String.fromCodePoint(parseInt(text.slice(i + 2, i + 6), 16));
+          i += 6;
+          continue;
+        }
+        out += { n: "\n", t: "\t", '"': '"', "\\": "\\" }[escape] ?? escape;
+        i += 2;
+        continue;
+      }
+      out += text[i++];
+    }
+    i++;
+    return out;
+  };
 
-// ─── Orgasm spelling: "cum" not "come" (Important) ───────────────────────────
-// House style: the climax verb is "cum"/"cums"/"cumming". Past tense stays
-// "came". Only the person/body-climaxing sense — motion ("come in", "comes
-// out") and the orgasm/it ARRIVING ("the orgasm comes") stay "come".
-
-const ORGASM_SPELLING = [
-  { pattern: /\b(?:make|makes|making|let|lets|letting)\s+(?:you|her|him|me)\s+come\b/gi },
-  { pattern: /\b(?:going to|about to|gonna)\s+come\b/gi },
-  { pattern: /\bcome\s+(?:hard|fast|first|again|twice|quietly|loud(?:ly)?|already|on (?:his|her|your)|against (?:his|her|your)|around (?:his|her|your))/gi },
-  { pattern: /\b(?:you|he|she)\s+comes?\s+(?:hard|fast|first|again|twice|quietly|loud(?:ly)?|on (?:his|her|your)|against (?:his|her|your)|around (?:his|her|your)|before (?:he|she|you)|with a (?:grunt|groan|moan))/gi },
-  { pattern: /\b(?:you'?re|he'?s|she'?s|i'?m)\s+coming\b(?!\s+(?:over|in|out|up|down|back|to|home|with|along|through|around|here|apart|undone|for|after|toward|with you))/gi },
-];
+  const parseValue = () => {
+    skipSpace();
+    if (text[i] !== "[") return parseString();
+    i++;
+    const items = [];
+    for (skipSpace(); text[i] !== "]"; skipSpace()) {
+      items.push(parseString());
+      skipSpace();
+      if (text[i] === ",") i++;
+    }
+    i++;
+    return items;
+  };
 
-// ─── AI erotic clichés (Critical) ────────────────────────────────────────────
-
-const EROTIC_CLICHES = [
-  { pattern: /bit (her|your) lip/gi, message: "AI erotic cliché" },
-  { pattern: /heat building inside/gi, message: "AI erotic cliché" },
-  { pattern: /couldn'?t help (her|your)self/gi, message: "AI erotic cliché" },
-  { pattern: /explored (her|your) body/gi, message: "AI erotic cliché" },
-  { pattern: /\bthrobbing\b/gi, message: "AI erotic cliché" },
-  { pattern: /feminine core/gi, message: "AI erotic euphemism" },
-  { pattern: /desire building/gi, message: "AI erotic cliché" },
-  { pattern: /growing need/gi, message: "AI erotic cliché" },
-];
+  for (skipSpace(); i < text.length; skipSpace()) {
+    if (text.startsWith("[[rules]]", i)) {
+      rules.push({});
+      i += "[[rules]]".length;
+      continue;
+    }
+    const key = /^[\w-]+/.exec(text.slice(i))[0];
+    i += key.length;
+    skipSpace();
+    if (text[i] !== "=") throw new Error(`${RULES_FILE}: expected '=' after ${key}`);
+    i++;
+    rules[rules.length - 1][key] = parseValue();
+  }
+  return rules;
+}
 
-// ─── AI prose tells (Critical) ───────────────────────────────────────────────
+function escapeRegex(text) {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
 
-const AI_PROSE_TELLS = [
-  // Em-dash reveals: "Not X, exactly — more like Y"
-  {
-    pattern: /[Nn]ot \w+,? exactly\s*[—–-]\s*more like/g,
-    message: "Em-dash reveal pattern",
-  },
-  // "something — not quite X"
-  {
-    pattern: /something\s*[—–-]\s*not quite/g,
-    message: "Em-dash reveal pattern",
-  },
-  // Over-naming: "the universal X nod/look/smile"
-  {
-    pattern: /the (?:universal|unspoken|familiar|inevitable|particular) [\w-]+ (?:nod|look|smile|gesture|shrug|pause|silence)/gi,
-    message: "Over-naming — label the experience instead of showing it",
-  },
-  // Heart/pulse clichés
-  { pattern: /heart skip/gi, message: "Heart/pulse cliché" },
-  { pattern: /pulse quicken/gi, message: "Heart/pulse cliché" },
-  { pattern: /shiver.{0,15}spine/gi, message: "Heart/pulse cliché" },
-  { pattern: /heart pound/gi, message: "Heart/pulse cliché" },
-  { pattern: /breath catch/gi, message: "Heart/pulse cliché (breath catch)" },
-  // Emotion announcements
-  {
-    pattern: /[Yy]ou feel (nervous|anxious|confident|happy|sad|scared|excited|uncomfortable|uneasy|relieved|grateful|hopeful)/g,
-    message: "Emotion announcement — show physical evidence instead",
-  },
-];
+// A Rust `regex` pattern as a global JS RegExp; a leading `(?i)` becomes the
+// `i` flag.
+function toRegExp(pattern) {
+  return pattern.startsWith("(?i)")
+    ? new RegExp(pattern.slice(4), "gi")
+    : new RegExp(pattern, "g");
+}
+
+const PACK_RULES = parseRulesToml(await fs.readFile(RULES_FILE, "utf8")).map((rule) => ({
+  id: rule.id,
+  severity: rule.severity === "error" ? "critical" : "important",
+  message: rule.message,
+  patterns: [
+    ...(rule.phrases ?? []).map((phrase) => new RegExp(`\\b${escapeRegex(phrase)}\\b`, "gi")),
+    ...(rule.patterns ?? []).map(toRegExp),
+  ],
+}));
 
 // ─── Overused words (Important — flag at 3+) ─────────────────────────────────
 
@@ -276,43 +298,18 @@ function lint(text) {
     findings.push({ severity, rule, line, text: matchText.trim(), message });
   }
 
-  // 1. Banned phrases
-  for (const { pattern, message } of BANNED_PHRASES) {
-    pattern.lastIndex = 0;
-    let m;
-    while ((m = pattern.exec(cleanText)) !== null) {
-      add("critical", "banned_phrase", lineForOffset(cleanText, m.index), m[0], message);
-    }
-  }
-
-  // 2. AI erotic clichés
-  for (const { pattern, message } of EROTIC_CLICHES) {
-    pattern.lastIndex = 0;
-    let m;
-    while ((m = pattern.exec(cleanText)) !== null) {
-      add("critical", "erotic_cliche", lineForOffset(cleanText, m.index), m[0], message);
-    }
-  }
-
-  // 3. AI prose tells
-  for (const { pattern, message } of AI_PROSE_TELLS) {
-    pattern.lastIndex = 0;
-    let m;
-    while ((m = pattern.exec(cleanText)) !== null) {
-      add("critical", "ai_prose_tell", lineForOffset(cleanText, m.index), m[0], message);
-    }
-  }
-
-  // 3b. Orgasm spelling — climax verb should be "cum", not "come"
-  for (const { pattern } of ORGASM_SPELLING) {
-    pattern.lastIndex = 0;
-    let m;
-    while ((m = pattern.exec(cleanText)) !== null) {
-      add("important", "orgasm_spelling", lineForOffset(cleanText, m.index), m[0], "Use 'cum'/'cums'/'cumming' for the orgasm sense (not 'come'); 'came' stays");
+  // 1. Pack rules
+  for (const { id, severity, message, patterns } of PACK_RULES) {
+    for (const pattern of patterns) {
+      pattern.lastIndex = 0;
+      let m;
+      while ((m = pattern.exec(cleanText)) !== null) {
+        add(severity, id, lineForOffset(cleanText, m.index), m[0], message);
+      }
     }
   }
 
-  // 4. Full thoughts (italicized sentences > 25 chars)
+  // 2. Full thoughts (italicized sentences > 25 chars)
   {
     FULL_THOUGHT_PATTERN.lastIndex = 0;
     let m;
@@ -325,7 +322,7 @@ function lint(text) {
     }
   }
 
-  // 5. Player acts in intro
+  // 3. Player acts in intro
   for (const section of sections) {
     if (section.type !== "INTRO") continue;
     const introText = section.lines.map((l) => l.text).join("\n");
@@ -360,7 +357,7 @@ function lint(text) {
     }
   }
 
-  // 6. POV violations
+  // 4. POV violations
   {
     SHE_SUBJECT_PATTERN.lastIndex = 0;
     let m;
@@ -369,7 +366,7 @@ function lint(text) {
     }
   }
 
-  // 7. Anaphoric repetition
+  // 5. Anaphoric repetition
   {
     ANAPHORIC_PATTERN.lastIndex = 0;
     let m;
@@ -378,7 +375,7 @@ function lint(text) {
     }
   }
 
-  // 8. Staccato closers
+  // 6. Staccato closers
   {
     const closers = findStaccatoClosers(cleanText);
     for (const closer of closers) {
@@ -389,7 +386,7 @@ function lint(text) {
     }
   }
 
-  // 9. Overused words
+  // 7. Overused words
   const overusedStats = {};
   for (const { pattern, label } of OVERUSED_WORDS) {
     pattern.lastIndex = 0;
@@ -404,7 +401,7 @@ function lint(text) {
     add("important", "overused_word", 0, `${label} (${count}x)`, `Overused: "${label}" appears ${count} times (threshold: ${OVERUSED_THRESHOLD})`);
   }
 
-  // 10. Structural depth checks (only on complete scene drafts)
+  // 8. Structural depth checks (only on complete scene drafts)
   const hasIntro = sections.some((s) => s.type === "INTRO");
   const hasAction = sections.some((s) => s.type === "ACTION");
   const isCompleteDraft = hasIntro && hasAction;
@@ -466,7 +463,7 @@ function lint(text) {
     }
   }
 
-  // 11. Section presence checks (always run if multiple sections detected)
+  // 9. Section presence checks (always run if multiple sections detected)
   if (sections.length >= 2) {
     const introSections = sections.filter((s) => s.type === "INTRO");
     const actionSections = sections.filter((s) => s.type === "ACTION");
@@ -479,7 +476,7 @@ function lint(text) {
     }
   }
 
-  // 12. Branch density checks (only on complete drafts)
+  // 10. Branch density checks (only on complete drafts)
   if (isCompleteDraft) {
     const actionSections = sections.filter((s) => s.type === "ACTION");
 
@@ -528,7 +525,7 @@ function selfTest() {
       name: "banned phrase caught",
       input: `INTRO:\nNone of this was conscious. Your body is making calculations.\n`,
       expectPass: false,
-      expectRule: "banned_phrase",
+      expectRule: "narrator_analysis",
     },
     {
       name: "erotic cliché caught",
@@ -551,7 +548,7 @@ function selfTest() {
       name: "emotion announcement caught",
       input: `INTRO:\nYou feel nervous as you walk in.\n`,
       expectPass: false,
-      expectRule: "ai_prose_tell",
+      expectRule: "emotion_announcement",
     },
     {
       name: "full thought caught",
@@ -580,13 +577,13 @@ function selfTest() {
       name: "interiority 'noted and filed' flagged",
       input: `ACTION: x\nThe cognitive dissonance is noted and filed.\n`,
       expectPass: false,
-      expectRule: "banned_phrase",
+      expectRule: "narrator_analysis",
     },
     {
       name: "em-dash reveal caught",
       input: `ACTION: look\nNot danger, exactly — more like being placed.\n`,
       expectPass: false,
-      expectRule: "ai_prose_tell",
+      expectRule: "em_dash_reveal",
     },
     {
       name: "overused words flagged",
@@ -92,9 +92,10 @@ pub fn validate_game_effect(
 }
 
 fn script_error_to_diagnostic(e: &undone_scene::script::ScriptError) -> Diagnostic {
+    let pos = e.position();
     Diagnostic {
-        line: None,
-        column: None,
+        line: pos.line().map(|l| l as u16),
+        column: pos.position().map(|c| c as u16),
         message: e.to_string(),
         severity: DiagnosticSeverity::Error,
     }
@@ -174,6 +175,7 @@ mod tests {
             hidden: false,
             group: None,
             conflicts: vec![],
+            composure_penalty: 0,
         }]);
         reg
     }
@@ -184,6 +186,7 @@ mod tests {
         let diags = validate_game_condition(r#"w.hasTrait("NOPE")"#, &reg);
         assert!(!diags.is_empty(), "unknown trait id must be rejected");
         assert!(diags[0].message.contains("NOPE"));
+        assert_eq!((diags[0].line, diags[0].column), (Some(1), Some(3)));
     }
 
     #[test]